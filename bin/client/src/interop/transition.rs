@@ -23,7 +23,7 @@ use kona_proof_interop::{BootInfo, INVALID_TRANSITION_HASH, OptimisticBlock, Pre
 use op_alloy_consensus::OpTxEnvelope;
 use op_revm::OpSpecId;
 use revm::context::BlockEnv;
-use tracing::{error, info, warn};
+use tracing::{Instrument, error, info, info_span, warn};
 
 /// Executes a sub-transition of the interop proof with the given [PreimageOracleClient] and
 /// [HintWriterClient].
@@ -137,7 +137,11 @@ where
 
     // Run the derivation pipeline until we are able to produce the output root of the claimed
     // L2 block.
-    match driver.advance_to_target(rollup_config.as_ref(), Some(disputed_l2_block_number)).await {
+    match driver
+        .advance_to_target(rollup_config.as_ref(), Some(disputed_l2_block_number))
+        .instrument(info_span!(target: "interop_client", "driver"))
+        .await
+    {
         Ok((safe_head, output_root)) => {
             let optimistic_block = OptimisticBlock::new(safe_head.block_info.hash, output_root);
             transition_and_check(
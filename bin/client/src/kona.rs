@@ -32,7 +32,7 @@ fn main() -> Result<(), String> {
     {
         use kona_std_fpvm::tracing::FpvmTracingSubscriber;
 
-        let subscriber = FpvmTracingSubscriber::new(tracing::Level::INFO);
+        let subscriber = FpvmTracingSubscriber::new(tracing::Level::INFO).with_span_trace();
         tracing::subscriber::set_global_default(subscriber)
             .expect("Failed to set tracing subscriber");
     }
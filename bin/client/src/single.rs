@@ -18,7 +18,7 @@ use kona_proof::{
     sync::new_oracle_pipeline_cursor,
 };
 use thiserror::Error;
-use tracing::{error, info};
+use tracing::{Instrument, error, info, info_span};
 
 /// An error that can occur when running the fault proof program.
 #[derive(Error, Debug)]
@@ -155,6 +155,7 @@ where
     // L2 block.
     let (safe_head, output_root) = driver
         .advance_to_target(rollup_config.as_ref(), Some(boot.claimed_l2_block_number))
+        .instrument(info_span!(target: "client", "driver"))
         .await?;
 
     ////////////////////////////////////////////////////////////////
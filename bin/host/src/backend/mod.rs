@@ -6,4 +6,7 @@ pub use offline::OfflineHostBackend;
 mod online;
 pub use online::{HintHandler, OnlineHostBackend, OnlineHostBackendCfg};
 
+mod prefetch;
+pub use prefetch::PrefetchPolicy;
+
 pub(crate) mod util;
@@ -1,13 +1,14 @@
 //! Contains the implementations of the [HintRouter] and [PreimageFetcher] traits.
 
-use crate::kv::KeyValueStore;
+use crate::{ProfileSession, kv::KeyValueStore};
 use async_trait::async_trait;
 use kona_preimage::{
     HintRouter, PreimageFetcher, PreimageKey,
     errors::{PreimageOracleError, PreimageOracleResult},
 };
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 use tokio::sync::RwLock;
+use tracing::warn;
 
 /// A [KeyValueStore]-backed implementation of the [PreimageFetcher] trait.
 #[derive(Debug)]
@@ -16,6 +17,10 @@ where
     KV: KeyValueStore + ?Sized,
 {
     inner: Arc<RwLock<KV>>,
+    /// The profiler session that hints and preimages are recorded to, if profiling is enabled.
+    profile: Option<ProfileSession>,
+    /// The keys requested by the client that were not present in the key-value store.
+    missing: Mutex<Vec<PreimageKey>>,
 }
 
 impl<KV> OfflineHostBackend<KV>
@@ -24,7 +29,19 @@ where
 {
     /// Create a new [OfflineHostBackend] from the given [KeyValueStore].
     pub const fn new(kv_store: Arc<RwLock<KV>>) -> Self {
-        Self { inner: kv_store }
+        Self { inner: kv_store, profile: None, missing: Mutex::new(Vec::new()) }
+    }
+
+    /// Records the hints and preimages served by the [OfflineHostBackend] to the given
+    /// [ProfileSession].
+    pub fn with_profile(mut self, profile: ProfileSession) -> Self {
+        self.profile = Some(profile);
+        self
+    }
+
+    /// Returns the keys requested by the client that were not present in the key-value store.
+    pub fn missing_keys(&self) -> Vec<PreimageKey> {
+        self.missing.lock().unwrap_or_else(|e| e.into_inner()).clone()
     }
 }
 
@@ -35,7 +52,15 @@ where
 {
     async fn get_preimage(&self, key: PreimageKey) -> PreimageOracleResult<Vec<u8>> {
         let kv_store = self.inner.read().await;
-        kv_store.get(key.into()).ok_or(PreimageOracleError::KeyNotFound)
+        let Some(preimage) = kv_store.get(key.into()) else {
+            warn!(target: "host_backend", "Preimage not found in the key-value store: {key}");
+            self.missing.lock().unwrap_or_else(|e| e.into_inner()).push(key);
+            return Err(PreimageOracleError::KeyNotFound);
+        };
+        if let Some(profile) = self.profile.as_ref() {
+            profile.record_preimage(key.key_type(), preimage.len(), true);
+        }
+        Ok(preimage)
     }
 }
 
@@ -44,7 +69,10 @@ impl<KV> HintRouter for OfflineHostBackend<KV>
 where
     KV: KeyValueStore + Send + Sync + ?Sized,
 {
-    async fn route_hint(&self, _hint: String) -> PreimageOracleResult<()> {
+    async fn route_hint(&self, hint: String) -> PreimageOracleResult<()> {
+        if let Some(profile) = self.profile.as_ref() {
+            profile.record_raw_hint(&hint);
+        }
         Ok(())
     }
 }
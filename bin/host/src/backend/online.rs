@@ -1,6 +1,7 @@
 //! Contains the [OnlineHostBackend] definition.
 
-use crate::SharedKeyValueStore;
+use super::prefetch::{PrefetchPolicy, Prefetcher};
+use crate::{ProfileSession, SharedKeyValueStore};
 use anyhow::Result;
 use async_trait::async_trait;
 use kona_preimage::{
@@ -8,7 +9,14 @@ use kona_preimage::{
     errors::{PreimageOracleError, PreimageOracleResult},
 };
 use kona_proof::{Hint, errors::HintParsingError};
-use std::{collections::HashSet, hash::Hash, str::FromStr, sync::Arc, time::Duration};
+use std::{
+    collections::HashSet,
+    fmt::Display,
+    hash::Hash,
+    str::FromStr,
+    sync::Arc,
+    time::{Duration, Instant},
+};
 use tokio::{sync::RwLock, time::timeout};
 use tracing::{debug, error, trace, warn};
 
@@ -30,7 +38,14 @@ fn preimage_backoff_delay(attempt: usize) -> Duration {
 /// [OnlineHostBackend].
 pub trait OnlineHostBackendCfg {
     /// The hint type describing the range of hints that can be received.
-    type HintType: FromStr<Err = HintParsingError> + Hash + Eq + PartialEq + Clone + Send + Sync;
+    type HintType: FromStr<Err = HintParsingError>
+        + Display
+        + Hash
+        + Eq
+        + PartialEq
+        + Clone
+        + Send
+        + Sync;
 
     /// The providers that are used to fetch data in response to hints.
     type Providers: Send + Sync;
@@ -63,15 +78,19 @@ where
     H: HintHandler,
 {
     /// The configuration that is used to route hints.
-    cfg: C,
+    cfg: Arc<C>,
     /// The key-value store that is used to store preimages.
     kv: SharedKeyValueStore,
     /// The providers that are used to fetch data in response to hints.
-    providers: C::Providers,
+    providers: Arc<C::Providers>,
     /// Hints that should be immediately executed by the host.
     proactive_hints: HashSet<C::HintType>,
     /// The last hint that was received.
     last_hint: Arc<RwLock<Option<Hint<C::HintType>>>>,
+    /// The profiler session that hints and preimages are recorded to, if profiling is enabled.
+    profile: Option<ProfileSession>,
+    /// The prefetcher that speculatively fetches follow-up hints, if prefetching is enabled.
+    prefetcher: Option<Arc<Prefetcher<C::HintType>>>,
     /// Phantom marker for the [HintHandler].
     _hint_handler: std::marker::PhantomData<H>,
 }
@@ -85,11 +104,13 @@ where
     /// external configuration.
     pub fn new(cfg: C, kv: SharedKeyValueStore, providers: C::Providers, _: H) -> Self {
         Self {
-            cfg,
+            cfg: Arc::new(cfg),
             kv,
-            providers,
+            providers: Arc::new(providers),
             proactive_hints: HashSet::default(),
             last_hint: Arc::new(RwLock::new(None)),
+            profile: None,
+            prefetcher: None,
             _hint_handler: std::marker::PhantomData,
         }
     }
@@ -99,13 +120,100 @@ where
         self.proactive_hints.insert(hint_type);
         self
     }
+
+    /// Records the hints and preimages served by the [OnlineHostBackend] to the given
+    /// [ProfileSession].
+    pub fn with_profile(mut self, profile: ProfileSession) -> Self {
+        self.profile = Some(profile);
+        self
+    }
+
+    /// Enables speculative prefetching of the follow-up hints produced by the given
+    /// [PrefetchPolicy]. Follow-ups are fetched in the background once the data for the hint that
+    /// produced them has been fetched, with at most `max_concurrency` fetches in flight at once.
+    pub fn with_prefetch_policy<P>(mut self, policy: P, max_concurrency: usize) -> Self
+    where
+        P: PrefetchPolicy<HintType = C::HintType> + 'static,
+    {
+        self.prefetcher = Some(Arc::new(Prefetcher::new(policy, max_concurrency)));
+        self
+    }
+}
+
+impl<C, H> OnlineHostBackend<C, H>
+where
+    C: OnlineHostBackendCfg + Send + Sync + 'static,
+    H: HintHandler<Cfg = C> + Send + Sync + 'static,
+{
+    /// Fetches the data for a hint, recording the latency of the fetch to the profiler and
+    /// scheduling the prefetch of its follow-up hints.
+    async fn fetch_hint(&self, hint: Hint<C::HintType>) -> Result<()> {
+        let hint_type = self.profile.as_ref().map(|_| hint.ty.to_string());
+        let trigger = self.prefetcher.as_ref().map(|_| hint.clone());
+
+        let start = Instant::now();
+        let result = H::fetch_hint(hint, &self.cfg, &self.providers, self.kv.clone()).await;
+        if let (Some(profile), Some(hint_type)) = (self.profile.as_ref(), hint_type) {
+            profile.record_fetch(&hint_type, start.elapsed(), result.is_ok());
+        }
+
+        if let (Ok(()), Some(trigger)) = (&result, trigger) {
+            self.prefetch_follow_ups(trigger);
+        }
+        result
+    }
+
+    /// Fetches the follow-up hints of `hint` produced by the [PrefetchPolicy] in the background.
+    fn prefetch_follow_ups(&self, hint: Hint<C::HintType>) {
+        let Some(prefetcher) = self.prefetcher.clone() else {
+            return;
+        };
+        let cfg = self.cfg.clone();
+        let providers = self.providers.clone();
+        let kv = self.kv.clone();
+        let profile = self.profile.clone();
+        let in_flight = prefetcher.track();
+
+        tokio::spawn(async move {
+            let _in_flight = in_flight;
+            for follow_up in prefetcher.policy.follow_ups(&hint, &kv).await {
+                if !prefetcher.schedule(&follow_up) {
+                    continue;
+                }
+
+                // Only spawn a fetch once a permit is available, so that at most
+                // `max_concurrency` prefetch tasks exist at once.
+                let Ok(permit) = prefetcher.permits.clone().acquire_owned().await else {
+                    return;
+                };
+                let in_flight = prefetcher.track();
+                let (cfg, providers, kv, profile) =
+                    (cfg.clone(), providers.clone(), kv.clone(), profile.clone());
+                tokio::spawn(async move {
+                    let (_permit, _in_flight) = (permit, in_flight);
+
+                    let hint_type = follow_up.ty.to_string();
+                    trace!(target: "host_backend", "Prefetching hint: {}", follow_up.encode());
+
+                    let start = Instant::now();
+                    let result = H::fetch_hint(follow_up, &cfg, &providers, kv).await;
+                    if let Some(profile) = profile {
+                        profile.record_fetch(&hint_type, start.elapsed(), result.is_ok());
+                    }
+                    if let Err(e) = result {
+                        debug!(target: "host_backend", "Failed to prefetch {hint_type} hint: {e}");
+                    }
+                });
+            }
+        });
+    }
 }
 
 #[async_trait]
 impl<C, H> HintRouter for OnlineHostBackend<C, H>
 where
-    C: OnlineHostBackendCfg + Send + Sync,
-    H: HintHandler<Cfg = C> + Send + Sync,
+    C: OnlineHostBackendCfg + Send + Sync + 'static,
+    H: HintHandler<Cfg = C> + Send + Sync + 'static,
 {
     /// Set the last hint to be received.
     async fn route_hint(&self, hint: String) -> PreimageOracleResult<()> {
@@ -114,9 +222,13 @@ where
         let parsed_hint = hint
             .parse::<Hint<C::HintType>>()
             .map_err(|e| PreimageOracleError::HintParseFailed(e.to_string()))?;
+        if let Some(profile) = self.profile.as_ref() {
+            profile.record_hint(&parsed_hint.ty.to_string(), &parsed_hint.data);
+        }
+
         if self.proactive_hints.contains(&parsed_hint.ty) {
             debug!(target: "host_backend", "Proactive hint received; Immediately fetching {hint}");
-            H::fetch_hint(parsed_hint, &self.cfg, &self.providers, self.kv.clone())
+            self.fetch_hint(parsed_hint)
                 .await
                 .map_err(|e| PreimageOracleError::Other(e.to_string()))?;
         } else {
@@ -131,8 +243,8 @@ where
 #[async_trait]
 impl<C, H> PreimageFetcher for OnlineHostBackend<C, H>
 where
-    C: OnlineHostBackendCfg + Send + Sync,
-    H: HintHandler<Cfg = C> + Send + Sync,
+    C: OnlineHostBackendCfg + Send + Sync + 'static,
+    H: HintHandler<Cfg = C> + Send + Sync + 'static,
 {
     /// Get the preimage for the given key.
     async fn get_preimage(&self, key: PreimageKey) -> PreimageOracleResult<Vec<u8>> {
@@ -144,8 +256,11 @@ where
         drop(kv_lock);
 
         // If preimage already exists, return it immediately
-        if preimage.is_some() {
-            return preimage.ok_or(PreimageOracleError::KeyNotFound);
+        if let Some(preimage) = preimage {
+            if let Some(profile) = self.profile.as_ref() {
+                profile.record_preimage(key.key_type(), preimage.len(), true);
+            }
+            return Ok(preimage);
         }
 
         // Retry loop with timeout and exponential backoff protection
@@ -169,9 +284,7 @@ where
 
                 // Try to fetch hint if available
                 if let Some(hint) = self.last_hint.read().await.as_ref() {
-                    match H::fetch_hint(hint.clone(), &self.cfg, &self.providers, self.kv.clone())
-                        .await
-                    {
+                    match self.fetch_hint(hint.clone()).await {
                         Ok(_) => {
                             // Check if the key is now available
                             let kv_lock = self.kv.read().await;
@@ -215,7 +328,11 @@ where
             PreimageOracleError::Timeout
         })?;
 
-        result?.ok_or(PreimageOracleError::KeyNotFound)
+        let preimage = result?.ok_or(PreimageOracleError::KeyNotFound)?;
+        if let Some(profile) = self.profile.as_ref() {
+            profile.record_preimage(key.key_type(), preimage.len(), false);
+        }
+        Ok(preimage)
     }
 }
 
@@ -463,6 +580,70 @@ mod tests {
         assert!(backend.route_hint(hint2.to_string()).await.is_ok());
     }
 
+    #[tokio::test]
+    async fn test_profile_records_hints_and_preimages() {
+        let (backend, kv, providers) = create_test_backend();
+        let profiler = Arc::new(crate::HostProfiler::new(false));
+        let backend = backend.with_profile(profiler.session());
+
+        let cached = PreimageKey::new_keccak256([2u8; 32]);
+        kv.write().await.set(cached.into(), vec![0u8; 4]).unwrap();
+        let fetched = PreimageKey::new_keccak256([1u8; 32]);
+        providers.set_store_key(fetched.into()).await;
+
+        let hint_str =
+            "l1-block-header 0102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f20";
+        backend.route_hint(hint_str.to_string()).await.unwrap();
+        backend.get_preimage(cached).await.unwrap();
+        backend.get_preimage(fetched).await.unwrap();
+
+        let report = profiler.report();
+        let hint = &report.totals.hints["l1-block-header"];
+        assert_eq!((hint.count, hint.fetches, hint.fetch_errors), (1, 1, 0));
+
+        let preimages = &report.totals.preimages["Keccak256"];
+        assert_eq!(preimages.requests, 2);
+        assert_eq!(preimages.bytes, 4 + b"test_preimage_data".len() as u64);
+        assert_eq!((preimages.cache_hits, preimages.cache_misses), (1, 1));
+    }
+
+    #[tokio::test]
+    async fn test_prefetch_follow_ups() {
+        struct ReceiptsPolicy;
+
+        #[async_trait]
+        impl PrefetchPolicy for ReceiptsPolicy {
+            type HintType = HintType;
+
+            async fn follow_ups(
+                &self,
+                hint: &Hint<HintType>,
+                _: &SharedKeyValueStore,
+            ) -> Vec<Hint<HintType>> {
+                match hint.ty {
+                    HintType::L1BlockHeader => {
+                        vec![HintType::L1Receipts.with_data(&[hint.data.as_ref()])]
+                    }
+                    _ => Vec::new(),
+                }
+            }
+        }
+
+        let (backend, _, providers) = create_test_backend();
+        let backend = backend
+            .with_proactive_hint(HintType::L1BlockHeader)
+            .with_prefetch_policy(ReceiptsPolicy, 2);
+
+        let hint_str =
+            "l1-block-header 0102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f20";
+        backend.route_hint(hint_str.to_string()).await.unwrap();
+        backend.route_hint(hint_str.to_string()).await.unwrap();
+
+        // The follow-up is fetched once in the background, despite being produced twice.
+        backend.prefetcher.as_ref().unwrap().idle().await;
+        assert_eq!(providers.get_fetch_count(), 3);
+    }
+
     #[test]
     fn test_preimage_backoff_delay() {
         // Test backoff delay calculation
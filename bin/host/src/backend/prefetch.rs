@@ -0,0 +1,133 @@
+//! Contains the [PrefetchPolicy] trait, used by the [OnlineHostBackend] to speculatively fetch
+//! the data for hints that the client program is likely to send next.
+//!
+//! [OnlineHostBackend]: crate::OnlineHostBackend
+
+use crate::SharedKeyValueStore;
+use async_trait::async_trait;
+use kona_proof::Hint;
+use lru::LruCache;
+use std::{
+    fmt::Display,
+    num::NonZeroUsize,
+    sync::{Arc, Mutex},
+};
+use tokio::sync::{Semaphore, watch};
+
+/// The number of recently prefetched hints remembered to avoid fetching the same hint twice.
+const PREFETCH_HISTORY_SIZE: usize = 1024;
+
+/// A [PrefetchPolicy] predicts the hints that the client program is likely to send after a given
+/// hint, so that the host can fetch their data in the background before it is requested.
+#[async_trait]
+pub trait PrefetchPolicy: Send + Sync {
+    /// The hint type that the policy operates on.
+    type HintType: Send + Sync;
+
+    /// Returns the hints to prefetch after the data for `hint` has been fetched. The data fetched
+    /// for `hint` is available in `kv` when this is called.
+    async fn follow_ups(
+        &self,
+        hint: &Hint<Self::HintType>,
+        kv: &SharedKeyValueStore,
+    ) -> Vec<Hint<Self::HintType>>;
+}
+
+/// Schedules the follow-up hints produced by a [PrefetchPolicy], bounding the number of
+/// concurrent fetches and skipping hints that were prefetched recently.
+pub(crate) struct Prefetcher<HT> {
+    /// The policy that produces follow-up hints.
+    pub(crate) policy: Box<dyn PrefetchPolicy<HintType = HT>>,
+    /// The permits bounding the number of concurrent prefetches.
+    pub(crate) permits: Arc<Semaphore>,
+    /// The encoded hints that were recently scheduled for prefetching.
+    history: Mutex<LruCache<String, ()>>,
+    /// The number of prefetch tasks in flight.
+    in_flight: Arc<watch::Sender<usize>>,
+}
+
+impl<HT> std::fmt::Debug for Prefetcher<HT> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Prefetcher").field("permits", &self.permits).finish_non_exhaustive()
+    }
+}
+
+impl<HT: Display> Prefetcher<HT> {
+    /// Creates a new [Prefetcher] that runs at most `max_concurrency` fetches at once.
+    pub(crate) fn new<P>(policy: P, max_concurrency: usize) -> Self
+    where
+        P: PrefetchPolicy<HintType = HT> + 'static,
+    {
+        Self {
+            policy: Box::new(policy),
+            permits: Arc::new(Semaphore::new(max_concurrency.max(1))),
+            history: Mutex::new(LruCache::new(
+                NonZeroUsize::new(PREFETCH_HISTORY_SIZE).expect("non-zero"),
+            )),
+            in_flight: Arc::new(watch::Sender::new(0)),
+        }
+    }
+
+    /// Registers a prefetch task, which is in flight until the returned [InFlight] guard is
+    /// dropped.
+    pub(crate) fn track(&self) -> InFlight {
+        self.in_flight.send_modify(|n| *n += 1);
+        InFlight(self.in_flight.clone())
+    }
+
+    /// Waits until no prefetch tasks are in flight.
+    #[cfg(test)]
+    pub(crate) async fn idle(&self) {
+        let mut in_flight = self.in_flight.subscribe();
+        let _ = in_flight.wait_for(|n| *n == 0).await;
+    }
+
+    /// Records that `hint` is being prefetched, returning `false` if it was prefetched recently.
+    pub(crate) fn schedule(&self, hint: &Hint<HT>) -> bool {
+        let mut history = self.history.lock().unwrap_or_else(|e| e.into_inner());
+        history.put(hint.encode(), ()).is_none()
+    }
+}
+
+/// A guard marking a prefetch task as in flight, see [Prefetcher::track].
+#[derive(Debug)]
+pub(crate) struct InFlight(Arc<watch::Sender<usize>>);
+
+impl Drop for InFlight {
+    fn drop(&mut self) {
+        self.0.send_modify(|n| *n -= 1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kona_proof::HintType;
+
+    struct NoopPolicy;
+
+    #[async_trait]
+    impl PrefetchPolicy for NoopPolicy {
+        type HintType = HintType;
+
+        async fn follow_ups(
+            &self,
+            _: &Hint<HintType>,
+            _: &SharedKeyValueStore,
+        ) -> Vec<Hint<HintType>> {
+            Vec::new()
+        }
+    }
+
+    #[test]
+    fn test_schedule_skips_recent_hints() {
+        let prefetcher = Prefetcher::new(NoopPolicy, 0);
+        let a = HintType::L1BlockHeader.with_data(&[&[1u8; 32]]);
+        let b = HintType::L1Receipts.with_data(&[&[1u8; 32]]);
+
+        assert!(prefetcher.schedule(&a));
+        assert!(!prefetcher.schedule(&a));
+        assert!(prefetcher.schedule(&b));
+        assert_eq!(prefetcher.permits.available_permits(), 1);
+    }
+}
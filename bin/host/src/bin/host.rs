@@ -41,6 +41,16 @@ pub enum HostMode {
     /// Run the host in super-chain (interop) mode.
     #[cfg(feature = "interop")]
     Super(kona_host::interop::InteropHost),
+    /// Check the inputs of a single-chain proof against the L1 and L2 chains, without running
+    /// the proof.
+    #[cfg(feature = "single")]
+    VerifyInputs(kona_host::single::VerifyInputsCommand),
+    /// Replay an oracle transcript recorded by the single-chain host to the client program.
+    #[cfg(feature = "single")]
+    Replay(kona_host::single::ReplayCommand),
+    /// Run the single-chain client program over a witness, verifying every preimage it reads.
+    #[cfg(feature = "single")]
+    VerifyWitness(kona_host::single::VerifyWitnessCommand),
 }
 
 #[tokio::main(flavor = "multi_thread")]
@@ -57,6 +67,18 @@ async fn main() -> Result<()> {
         HostMode::Super(cfg) => {
             cfg.start().await?;
         }
+        #[cfg(feature = "single")]
+        HostMode::VerifyInputs(cmd) => {
+            cmd.run().await?;
+        }
+        #[cfg(feature = "single")]
+        HostMode::Replay(cmd) => {
+            cmd.run().await?;
+        }
+        #[cfg(feature = "single")]
+        HostMode::VerifyWitness(cmd) => {
+            cmd.run().await?;
+        }
     }
 
     info!(target: "host", "Exiting host program.");
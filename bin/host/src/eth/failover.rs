@@ -0,0 +1,385 @@
+//! Multi-endpoint failover and rate limiting for the host's upstream providers.
+//!
+//! Shared public RPC endpoints frequently rate-limit or drop requests in the middle of a proof.
+//! The [FailoverTransport] and [FailoverBeaconClient] spread requests over a list of endpoints,
+//! moving on to the next healthy endpoint when one fails and throttling each endpoint to a
+//! configured request rate.
+
+use alloy_eips::eip4844::IndexedBlobHash;
+use alloy_json_rpc::{RequestPacket, ResponsePacket, ResponsePayload};
+use alloy_transport::{TransportError, TransportFut};
+use alloy_transport_http::Http;
+use async_trait::async_trait;
+use kona_providers_alloy::{
+    APIConfigResponse, APIGenesisResponse, BeaconClient, BoxedBlobWithIndex, OnlineBeaconClient,
+};
+use reqwest::Client;
+use std::{
+    fmt::Display,
+    sync::{Arc, Mutex},
+    task::{Context, Poll},
+    time::Duration,
+};
+use tokio::time::Instant;
+use tower::Service;
+use tracing::warn;
+
+/// The cooldown applied to an endpoint after its first consecutive failure. The cooldown doubles
+/// with every further consecutive failure, up to [MAX_COOLDOWN].
+const BASE_COOLDOWN: Duration = Duration::from_secs(1);
+
+/// The maximum cooldown applied to a failing endpoint.
+const MAX_COOLDOWN: Duration = Duration::from_secs(60);
+
+/// JSON-RPC error codes that public endpoints use to signal rate limiting.
+const RATE_LIMIT_ERROR_CODES: [i64; 3] = [429, -32005, -32016];
+
+/// Splits a comma-separated list of endpoint URLs, ignoring surrounding whitespace and empty
+/// entries.
+pub fn parse_endpoints(urls: &str) -> Vec<String> {
+    urls.split(',').map(str::trim).filter(|url| !url.is_empty()).map(String::from).collect()
+}
+
+/// Limits the rate of requests sent to a single endpoint by spacing them at a fixed interval.
+#[derive(Debug)]
+struct RateLimiter {
+    /// The minimum interval between two requests.
+    interval: Duration,
+    /// The earliest instant at which the next request may be sent.
+    next_slot: Mutex<Instant>,
+}
+
+impl RateLimiter {
+    /// Creates a new [RateLimiter] allowing `requests_per_second` requests per second.
+    fn new(requests_per_second: u32) -> Self {
+        Self {
+            interval: Duration::from_secs(1) / requests_per_second.max(1),
+            next_slot: Mutex::new(Instant::now()),
+        }
+    }
+
+    /// Waits until the next request may be sent, reserving its slot.
+    async fn acquire(&self) {
+        let slot = {
+            let mut next_slot = self.next_slot.lock().unwrap_or_else(|e| e.into_inner());
+            let slot = (*next_slot).max(Instant::now());
+            *next_slot = slot + self.interval;
+            slot
+        };
+        tokio::time::sleep_until(slot).await;
+    }
+}
+
+/// The health of a single endpoint.
+#[derive(Debug, Default, Clone, Copy)]
+struct Health {
+    /// The number of consecutive failed requests.
+    consecutive_failures: u32,
+    /// The instant until which the endpoint is considered unhealthy.
+    unhealthy_until: Option<Instant>,
+}
+
+/// A single upstream endpoint in an [EndpointPool].
+#[derive(Debug)]
+struct Endpoint<T> {
+    /// The URL of the endpoint.
+    url: String,
+    /// The client used to send requests to the endpoint.
+    client: T,
+    /// The rate limiter of the endpoint, if rate limiting is enabled.
+    limiter: Option<RateLimiter>,
+    /// The health of the endpoint.
+    health: Mutex<Health>,
+}
+
+impl<T> Endpoint<T> {
+    /// Returns the instant until which the endpoint is unhealthy, if it is unhealthy at `now`.
+    fn unhealthy_until(&self, now: Instant) -> Option<Instant> {
+        let health = self.health.lock().unwrap_or_else(|e| e.into_inner());
+        health.unhealthy_until.filter(|until| *until > now)
+    }
+
+    /// Marks a successful request, restoring the endpoint's health.
+    fn mark_success(&self) {
+        *self.health.lock().unwrap_or_else(|e| e.into_inner()) = Health::default();
+    }
+
+    /// Marks a failed request, putting the endpoint into an exponentially growing cooldown.
+    fn mark_failure(&self) -> Duration {
+        let mut health = self.health.lock().unwrap_or_else(|e| e.into_inner());
+        health.consecutive_failures = health.consecutive_failures.saturating_add(1);
+        let cooldown = BASE_COOLDOWN
+            .saturating_mul(1 << (health.consecutive_failures - 1).min(6))
+            .min(MAX_COOLDOWN);
+        health.unhealthy_until = Some(Instant::now() + cooldown);
+        cooldown
+    }
+}
+
+/// A pool of interchangeable upstream endpoints with health-aware failover and per-endpoint rate
+/// limiting.
+#[derive(Debug)]
+pub struct EndpointPool<T> {
+    /// The endpoints, in order of preference.
+    endpoints: Vec<Endpoint<T>>,
+}
+
+impl<T> EndpointPool<T> {
+    /// Creates a new [EndpointPool] from `(url, client)` pairs, in order of preference. If
+    /// `rate_limit` is set, each endpoint is limited to that many requests per second.
+    pub fn new(clients: impl IntoIterator<Item = (String, T)>, rate_limit: Option<u32>) -> Self {
+        let endpoints = clients
+            .into_iter()
+            .map(|(url, client)| Endpoint {
+                url,
+                client,
+                limiter: rate_limit.map(RateLimiter::new),
+                health: Mutex::new(Health::default()),
+            })
+            .collect();
+        Self { endpoints }
+    }
+
+    /// Returns the endpoint indices in the order they should be tried: healthy endpoints in order
+    /// of preference, followed by unhealthy endpoints in the order they recover.
+    fn order(&self) -> Vec<usize> {
+        let now = Instant::now();
+        let mut order = (0..self.endpoints.len())
+            .map(|i| (self.endpoints[i].unhealthy_until(now), i))
+            .collect::<Vec<_>>();
+        order.sort_by_key(|(until, _)| *until);
+        order.into_iter().map(|(_, i)| i).collect()
+    }
+
+    /// Sends a request to the endpoints of the pool, failing over to the next endpoint while
+    /// `should_failover` holds for the result. The result of the last endpoint tried is returned.
+    pub async fn call<'a, F, Fut, R, E>(
+        &'a self,
+        mut request: F,
+        should_failover: impl Fn(&Result<R, E>) -> bool,
+    ) -> Result<R, E>
+    where
+        F: FnMut(&'a T) -> Fut,
+        Fut: Future<Output = Result<R, E>>,
+        E: Display,
+    {
+        let order = self.order();
+        let mut last_result = None;
+
+        for (attempt, index) in order.iter().enumerate() {
+            let endpoint = &self.endpoints[*index];
+            if let Some(limiter) = endpoint.limiter.as_ref() {
+                limiter.acquire().await;
+            }
+
+            let result = request(&endpoint.client).await;
+            if !should_failover(&result) {
+                endpoint.mark_success();
+                return result;
+            }
+
+            let cooldown = endpoint.mark_failure();
+            let remaining = order.len() - attempt - 1;
+            match result.as_ref() {
+                Err(e) => warn!(
+                    target: "host_backend",
+                    "Request to {} failed ({e}); cooling down for {cooldown:?}, {remaining} endpoint(s) left",
+                    endpoint.url
+                ),
+                Ok(_) => warn!(
+                    target: "host_backend",
+                    "Request to {} was rate limited; cooling down for {cooldown:?}, {remaining} endpoint(s) left",
+                    endpoint.url
+                ),
+            }
+            last_result = Some(result);
+        }
+
+        last_result.expect("endpoint pool must not be empty")
+    }
+}
+
+/// A JSON-RPC transport that fails over between multiple HTTP endpoints and rate limits each of
+/// them.
+#[derive(Debug, Clone)]
+pub struct FailoverTransport {
+    /// The HTTP endpoints.
+    pool: Arc<EndpointPool<Http<Client>>>,
+}
+
+impl FailoverTransport {
+    /// Creates a new [FailoverTransport] over the given endpoint URLs, in order of preference.
+    pub fn new(urls: &[String], rate_limit: Option<u32>) -> Result<Self, url::ParseError> {
+        let clients = urls
+            .iter()
+            .map(|url| Ok((url.clone(), Http::<Client>::new(url.parse()?))))
+            .collect::<Result<Vec<_>, url::ParseError>>()?;
+        Ok(Self { pool: Arc::new(EndpointPool::new(clients, rate_limit)) })
+    }
+
+    /// Returns `true` if a request should be retried on another endpoint.
+    fn should_failover(result: &Result<ResponsePacket, TransportError>) -> bool {
+        let Ok(response) = result else {
+            return true;
+        };
+
+        let responses = match response {
+            ResponsePacket::Single(response) => std::slice::from_ref(response),
+            ResponsePacket::Batch(responses) => responses.as_slice(),
+        };
+        responses.iter().any(|response| match &response.payload {
+            ResponsePayload::Failure(error) => {
+                RATE_LIMIT_ERROR_CODES.contains(&error.code) ||
+                    error.message.to_lowercase().contains("rate limit")
+            }
+            ResponsePayload::Success(_) => false,
+        })
+    }
+}
+
+impl Service<RequestPacket> for FailoverTransport {
+    type Response = ResponsePacket;
+    type Error = TransportError;
+    type Future = TransportFut<'static>;
+
+    fn poll_ready(&mut self, _: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: RequestPacket) -> Self::Future {
+        let pool = Arc::clone(&self.pool);
+        Box::pin(async move {
+            pool.call(
+                |http| {
+                    let mut http = http.clone();
+                    let request = request.clone();
+                    async move { http.call(request).await }
+                },
+                Self::should_failover,
+            )
+            .await
+        })
+    }
+}
+
+/// A [BeaconClient] that fails over between multiple beacon API endpoints and rate limits each of
+/// them.
+#[derive(Debug, Clone)]
+pub struct FailoverBeaconClient {
+    /// The beacon API endpoints.
+    pool: Arc<EndpointPool<OnlineBeaconClient>>,
+}
+
+impl FailoverBeaconClient {
+    /// Creates a new [FailoverBeaconClient] over the given beacon API URLs, in order of
+    /// preference.
+    pub fn new(urls: &[String], rate_limit: Option<u32>) -> Self {
+        let clients =
+            urls.iter().map(|url| (url.clone(), OnlineBeaconClient::new_http(url.clone())));
+        Self { pool: Arc::new(EndpointPool::new(clients, rate_limit)) }
+    }
+}
+
+#[async_trait]
+impl BeaconClient for FailoverBeaconClient {
+    type Error = reqwest::Error;
+
+    async fn config_spec(&self) -> Result<APIConfigResponse, Self::Error> {
+        self.pool.call(|client| client.config_spec(), Result::is_err).await
+    }
+
+    async fn beacon_genesis(&self) -> Result<APIGenesisResponse, Self::Error> {
+        self.pool.call(|client| client.beacon_genesis(), Result::is_err).await
+    }
+
+    async fn filtered_beacon_blobs(
+        &self,
+        slot: u64,
+        blob_hashes: &[IndexedBlobHash],
+    ) -> Result<Vec<BoxedBlobWithIndex>, Self::Error> {
+        self.pool
+            .call(
+                |client| BeaconClient::filtered_beacon_blobs(client, slot, blob_hashes),
+                Result::is_err,
+            )
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    fn pool(n: usize, rate_limit: Option<u32>) -> EndpointPool<usize> {
+        EndpointPool::new((0..n).map(|i| (format!("endpoint-{i}"), i)), rate_limit)
+    }
+
+    #[test]
+    fn test_parse_endpoints() {
+        assert_eq!(
+            parse_endpoints("http://a:8545, http://b:8545,,"),
+            ["http://a:8545", "http://b:8545"]
+        );
+        assert_eq!(parse_endpoints("http://a:8545"), ["http://a:8545"]);
+        assert!(parse_endpoints(" , ").is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_failover_to_next_endpoint() {
+        let pool = pool(3, None);
+        let calls = AtomicUsize::new(0);
+
+        let result = pool
+            .call(
+                |i| {
+                    calls.fetch_add(1, Ordering::Relaxed);
+                    let i = *i;
+                    async move { if i < 2 { Err("unavailable") } else { Ok(i) } }
+                },
+                Result::is_err,
+            )
+            .await;
+        assert_eq!(result, Ok(2));
+        assert_eq!(calls.load(Ordering::Relaxed), 3);
+
+        // The failed endpoints are cooling down, so the healthy endpoint is tried first.
+        assert_eq!(pool.order(), [2, 0, 1]);
+        let result = pool.call(|i| std::future::ready(Ok::<_, &str>(*i)), Result::is_err).await;
+        assert_eq!(result, Ok(2));
+    }
+
+    #[tokio::test]
+    async fn test_all_endpoints_fail() {
+        let pool = pool(2, None);
+        let result = pool
+            .call(|i| std::future::ready(Err::<(), _>(format!("failed {i}"))), Result::is_err)
+            .await;
+        assert_eq!(result, Err("failed 1".to_string()));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_cooldown_backoff_and_recovery() {
+        let pool = pool(2, None);
+        assert_eq!(pool.endpoints[0].mark_failure(), BASE_COOLDOWN);
+        assert_eq!(pool.endpoints[0].mark_failure(), BASE_COOLDOWN * 2);
+        assert_eq!(pool.order(), [1, 0]);
+
+        tokio::time::advance(BASE_COOLDOWN * 2).await;
+        assert_eq!(pool.order(), [0, 1]);
+
+        pool.endpoints[0].mark_success();
+        assert_eq!(pool.endpoints[0].mark_failure(), BASE_COOLDOWN);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_rate_limit() {
+        let pool = pool(1, Some(10));
+        let start = Instant::now();
+        for _ in 0..5 {
+            pool.call(|i| std::future::ready(Ok::<_, &str>(*i)), Result::is_err).await.unwrap();
+        }
+        let elapsed = start.elapsed();
+        assert!(elapsed >= Duration::from_millis(400) && elapsed < Duration::from_millis(500));
+    }
+}
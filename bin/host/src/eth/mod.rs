@@ -5,6 +5,9 @@ use alloy_rpc_client::RpcClient;
 use alloy_transport_http::Http;
 use reqwest::Client;
 
+mod failover;
+pub use failover::{EndpointPool, FailoverBeaconClient, FailoverTransport, parse_endpoints};
+
 mod precompiles;
 pub(crate) use precompiles::execute;
 
@@ -14,3 +17,13 @@ pub fn http_provider<N: Network>(url: &str) -> RootProvider<N> {
     let http = Http::<Client>::new(url);
     RootProvider::new(RpcClient::new(http, true))
 }
+
+/// Returns a provider that fails over between the given endpoint URLs, in order of preference.
+/// If `rate_limit` is set, each endpoint is limited to that many requests per second.
+pub fn failover_provider<N: Network>(
+    urls: &[String],
+    rate_limit: Option<u32>,
+) -> Result<RootProvider<N>, url::ParseError> {
+    let transport = FailoverTransport::new(urls, rate_limit)?;
+    Ok(RootProvider::new(RpcClient::new(transport, true)))
+}
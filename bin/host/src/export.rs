@@ -0,0 +1,156 @@
+//! This module contains the [PreimageExporter], which copies the preimages a client program
+//! requested into a standalone key-value store.
+
+use crate::SharedKeyValueStore;
+use async_trait::async_trait;
+use kona_preimage::{
+    HintRouter, PreimageFetcher, PreimageKey, PreimageKeyType,
+    errors::{PreimageOracleError, PreimageOracleResult},
+};
+use std::{
+    collections::HashSet,
+    sync::{Arc, Mutex},
+};
+
+/// A [PreimageFetcher] and [HintRouter] that copies every preimage served by the wrapped backend
+/// into an export key-value store.
+///
+/// Local preimages are not exported, as they are served from the host's inputs. The export holds
+/// exactly the preimages the client program requested, so that it can be served offline as a
+/// standalone data directory.
+#[derive(Debug)]
+pub struct PreimageExporter<B> {
+    /// The backend serving the client.
+    inner: B,
+    /// The key-value store that preimages are exported to.
+    export: SharedKeyValueStore,
+    /// The keys exported so far.
+    exported: Mutex<HashSet<PreimageKey>>,
+}
+
+impl<B> PreimageExporter<B> {
+    /// Creates a new [PreimageExporter] that exports the preimages served by `inner`.
+    pub fn new(inner: B, export: SharedKeyValueStore) -> Self {
+        Self { inner, export, exported: Default::default() }
+    }
+
+    /// Returns the number of preimages exported so far.
+    pub fn exported(&self) -> usize {
+        self.exported.lock().unwrap_or_else(|e| e.into_inner()).len()
+    }
+}
+
+#[async_trait]
+impl<B> PreimageFetcher for PreimageExporter<B>
+where
+    B: PreimageFetcher + Send + Sync,
+{
+    async fn get_preimage(&self, key: PreimageKey) -> PreimageOracleResult<Vec<u8>> {
+        let preimage = self.inner.get_preimage(key).await?;
+        if key.key_type() != PreimageKeyType::Local &&
+            self.exported.lock().unwrap_or_else(|e| e.into_inner()).insert(key)
+        {
+            self.export
+                .write()
+                .await
+                .set(key.into(), preimage.clone())
+                .map_err(|e| PreimageOracleError::Other(e.to_string()))?;
+        }
+        Ok(preimage)
+    }
+}
+
+#[async_trait]
+impl<B> HintRouter for PreimageExporter<B>
+where
+    B: HintRouter + Send + Sync,
+{
+    async fn route_hint(&self, hint: String) -> PreimageOracleResult<()> {
+        self.inner.route_hint(hint).await
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        DiskKeyValueStore, KeyValueStore, MemoryKeyValueStore, OfflineHostBackend, PreimageServer,
+    };
+    use alloy_primitives::keccak256;
+    use kona_preimage::{
+        BidirectionalChannel, HintReader, HintWriter, HintWriterClient, OracleReader, OracleServer,
+        PreimageOracleClient,
+    };
+    use tokio::{sync::RwLock, task};
+
+    /// Serves the backend to a client requesting the given keys, returning the preimages served.
+    async fn serve<B>(backend: Arc<B>, keys: &[PreimageKey]) -> Vec<Option<Vec<u8>>>
+    where
+        B: PreimageFetcher + HintRouter + Send + Sync + 'static,
+    {
+        let hint = BidirectionalChannel::new().unwrap();
+        let preimage = BidirectionalChannel::new().unwrap();
+        let server = task::spawn(
+            PreimageServer::new(
+                OracleServer::new(preimage.host),
+                HintReader::new(hint.host),
+                backend,
+            )
+            .start(),
+        );
+
+        let (oracle, hints) = (OracleReader::new(preimage.client), HintWriter::new(hint.client));
+        let mut served = Vec::new();
+        for key in keys {
+            hints.write(&format!("keccak256-preimage {key}")).await.unwrap();
+            served.push(oracle.get(*key).await.ok());
+        }
+        drop((oracle, hints));
+        let _ = server.await;
+        served
+    }
+
+    #[tokio::test]
+    async fn test_export_and_replay_offline() {
+        let preimages: Vec<_> = (0u8..4).map(|i| vec![i; 32]).collect();
+        let keys: Vec<_> =
+            preimages.iter().map(|p| PreimageKey::new_keccak256(*keccak256(p))).collect();
+        let mut source = MemoryKeyValueStore::new();
+        for (key, preimage) in keys.iter().zip(&preimages) {
+            source.set((*key).into(), preimage.clone()).unwrap();
+        }
+        let local_key = PreimageKey::new_local(1);
+        source.set(local_key.into(), vec![0xff; 32]).unwrap();
+
+        // Only the first two preimages and the local input are requested.
+        let export_dir = tempfile::tempdir().unwrap();
+        let export: SharedKeyValueStore =
+            Arc::new(RwLock::new(DiskKeyValueStore::new(export_dir.path().to_path_buf())));
+        let exporter = Arc::new(PreimageExporter::new(
+            OfflineHostBackend::new(Arc::new(RwLock::new(source))),
+            export.clone(),
+        ));
+        let requested = [keys[0], keys[1], keys[0], local_key];
+        let served = serve(exporter.clone(), &requested).await;
+        assert!(served.iter().all(Option::is_some));
+        assert_eq!(exporter.exported(), 2);
+
+        // The export is closed once the server tasks have observed the closed channels.
+        while Arc::strong_count(&exporter) > 1 {
+            task::yield_now().await;
+        }
+        drop((exporter, export));
+
+        // The export replays the requested preimages offline, and holds nothing else.
+        let replay = Arc::new(OfflineHostBackend::new(Arc::new(RwLock::new(
+            DiskKeyValueStore::new(export_dir.path().to_path_buf()),
+        ))));
+        let replayed = serve(replay.clone(), &keys[..2]).await;
+        assert_eq!(replayed, vec![Some(preimages[0].clone()), Some(preimages[1].clone())]);
+        assert!(replay.missing_keys().is_empty());
+
+        assert_eq!(serve(replay.clone(), &[keys[2]]).await, vec![None]);
+        assert_eq!(serve(replay.clone(), &[local_key]).await, vec![None]);
+        assert_eq!(replay.missing_keys(), vec![keys[2], local_key]);
+    }
+}
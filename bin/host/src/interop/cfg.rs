@@ -2,9 +2,11 @@
 
 use super::{InteropHintHandler, InteropLocalInputs};
 use crate::{
-    DiskKeyValueStore, MemoryKeyValueStore, OfflineHostBackend, OnlineHostBackend,
-    OnlineHostBackendCfg, PreimageServer, SharedKeyValueStore, SplitKeyValueStore,
-    eth::http_provider, server::PreimageServerError,
+    DiskKeyValueStore, HostProfiler, MemoryKeyValueStore, OfflineHostBackend, OnlineHostBackend,
+    OnlineHostBackendCfg, PreimageExporter, PreimageServer, SharedKeyValueStore,
+    SplitKeyValueStore,
+    eth::{FailoverBeaconClient, failover_provider, parse_endpoints},
+    server::PreimageServerError,
 };
 use alloy_primitives::{B256, Bytes};
 use alloy_provider::{Provider, RootProvider};
@@ -12,10 +14,11 @@ use clap::Parser;
 use kona_cli::cli_styles;
 use kona_genesis::{L1ChainConfig, RollupConfig};
 use kona_preimage::{
-    BidirectionalChannel, Channel, HintReader, HintWriter, OracleReader, OracleServer,
+    BidirectionalChannel, Channel, HintReader, HintWriter, OracleReader, OracleServer, PreimageKey,
+    PreimageServerBackend,
 };
 use kona_proof_interop::HintType;
-use kona_providers_alloy::{OnlineBeaconClient, OnlineBlobProvider};
+use kona_providers_alloy::OnlineBlobProvider;
 use kona_std_fpvm::{FileChannel, FileDescriptor};
 use op_alloy_network::Optimism;
 use serde::Serialize;
@@ -24,6 +27,7 @@ use tokio::{
     sync::RwLock,
     task::{self, JoinHandle},
 };
+use tracing::{info, warn};
 
 /// The interop host application.
 #[derive(Default, Parser, Serialize, Clone, Debug)]
@@ -44,7 +48,8 @@ pub struct InteropHost {
     /// Claimed L2 timestamp, corresponding to the L2 post-state.
     #[arg(long, visible_alias = "l2-timestamp", env)]
     pub claimed_l2_timestamp: u64,
-    /// Addresses of L2 JSON-RPC endpoints to use (eth and debug namespace required).
+    /// Addresses of L2 JSON-RPC endpoints to use (eth and debug namespace required). Endpoints
+    /// that report the same chain ID are failed over between in order of preference.
     #[arg(
         long,
         visible_alias = "l2s",
@@ -54,7 +59,8 @@ pub struct InteropHost {
         env
     )]
     pub l2_node_addresses: Option<Vec<String>>,
-    /// Address of L1 JSON-RPC endpoint to use (eth and debug namespace required)
+    /// Address of L1 JSON-RPC endpoint to use (eth and debug namespace required). Accepts a
+    /// comma-separated list of endpoints, which are failed over between in order of preference.
     #[arg(
         long,
         visible_alias = "l1",
@@ -63,7 +69,8 @@ pub struct InteropHost {
         env
     )]
     pub l1_node_address: Option<String>,
-    /// Address of the L1 Beacon API endpoint to use.
+    /// Address of the L1 Beacon API endpoint to use. Accepts a comma-separated list of endpoints,
+    /// which are failed over between in order of preference.
     #[arg(
         long,
         visible_alias = "beacon",
@@ -81,13 +88,40 @@ pub struct InteropHost {
         env
     )]
     pub data_dir: Option<PathBuf>,
+    /// The maximum number of requests per second sent to each L1, L2, and beacon API endpoint.
+    /// Unlimited if not set.
+    #[arg(long = "rpc.rate-limit", value_name = "RPS", requires = "l1_node_address", env)]
+    pub rpc_rate_limit: Option<u32>,
     /// Run the client program natively.
-    #[arg(long, conflicts_with = "server", required_unless_present = "server")]
+    #[arg(
+        long,
+        conflicts_with_all = ["server", "validate"],
+        required_unless_present_any = ["server", "validate"]
+    )]
     pub native: bool,
     /// Run in pre-image server mode without executing any client program. If not provided, the
     /// host will run the client program in the host process.
-    #[arg(long, conflicts_with = "native", required_unless_present = "native")]
+    #[arg(
+        long,
+        conflicts_with_all = ["native", "validate"],
+        required_unless_present_any = ["native", "validate"]
+    )]
     pub server: bool,
+    /// Validate that `--data-dir` contains every preimage the client program needs, by running
+    /// the client program natively against the data directory alone. Logs a per-chain summary of
+    /// the preimages served, and fails with the first missing preimage key.
+    #[arg(
+        long,
+        requires = "data_dir",
+        conflicts_with_all = [
+            "native",
+            "server",
+            "l1_node_address",
+            "l2_node_addresses",
+            "l1_beacon_address",
+        ]
+    )]
+    pub validate: bool,
     /// Path to rollup configs. If provided, the host will use this config instead of attempting to
     /// look up the configs in the superchain registry.
     /// The rollup configs should be stored as serde-JSON serialized files.
@@ -98,6 +132,19 @@ pub struct InteropHost {
     /// The l1 configs should be stored as serde-JSON serialized files.
     #[arg(long, alias = "l1-cfgs", value_delimiter = ',', env)]
     pub l1_config_paths: Option<Vec<PathBuf>>,
+    /// Path to write a JSON report of the cost of serving the client to on exit. The report
+    /// aggregates requests, bytes served, key-value store hit ratio, and upstream fetch latency
+    /// per hint type and preimage key type, both in total and per L2 chain.
+    #[arg(long = "profile.report", value_name = "PATH", env)]
+    pub profile_report: Option<PathBuf>,
+    /// Include a per-L2-block breakdown in the `--profile.report` output.
+    #[arg(long = "profile.per-block", requires = "profile_report", env)]
+    pub profile_per_block: bool,
+    /// Export exactly the preimages that the client program requested during a `--native` run
+    /// into a standalone data directory at the given path. The export can be served offline with
+    /// `--data-dir`.
+    #[arg(long = "export", value_name = "DIR", requires = "native", env)]
+    pub export_dir: Option<PathBuf>,
 }
 
 /// An error that can occur when handling interop hosts
@@ -124,6 +171,15 @@ pub enum InteropHostError {
     /// An error when no provider found for chain ID.
     #[error("No provider found for chain ID: {0}")]
     RootProviderError(u64),
+    /// An invalid provider endpoint URL.
+    #[error("Invalid endpoint URL: {0}")]
+    InvalidEndpoint(#[from] url::ParseError),
+    /// A preimage needed by the client program is missing from the data directory.
+    #[error("Preimage missing from the data directory: {0}")]
+    MissingPreimage(PreimageKey),
+    /// The client program failed.
+    #[error("Client program failed: {0}")]
+    ClientError(#[from] kona_client::interop::FaultProofProgramError),
     /// Any other error.
     #[error("Error: {0}")]
     Other(&'static str),
@@ -132,22 +188,33 @@ pub enum InteropHostError {
 impl InteropHost {
     /// Starts the [InteropHost] application.
     pub async fn start(self) -> Result<(), InteropHostError> {
+        let profiler = self.create_profiler();
+
         if self.server {
             let hint = FileChannel::new(FileDescriptor::HintRead, FileDescriptor::HintWrite);
             let preimage =
                 FileChannel::new(FileDescriptor::PreimageRead, FileDescriptor::PreimageWrite);
 
-            self.start_server(hint, preimage).await?.await?
+            let result = self.start_server(hint, preimage, profiler.as_ref(), None).await?.await?;
+            self.write_profile_report(profiler.as_deref())?;
+            result
+        } else if self.validate {
+            let profiler =
+                profiler.unwrap_or_else(|| Arc::new(HostProfiler::new(false).with_per_chain()));
+            self.validate_data_dir(&profiler).await
         } else {
-            self.start_native().await
+            self.start_native(profiler.as_ref()).await
         }
     }
 
-    /// Starts the preimage server, communicating with the client over the provided channels.
+    /// Starts the preimage server, communicating with the client over the provided channels. If
+    /// an export store is given, the preimages served are exported to it.
     async fn start_server<C>(
         &self,
         hint: C,
         preimage: C,
+        profiler: Option<&Arc<HostProfiler>>,
+        export: Option<&SharedKeyValueStore>,
     ) -> Result<JoinHandle<Result<(), InteropHostError>>, InteropHostError>
     where
         C: Channel + Send + Sync + 'static,
@@ -155,59 +222,162 @@ impl InteropHost {
         let kv_store = self.create_key_value_store()?;
 
         let task_handle = if self.is_offline() {
-            task::spawn(async {
-                PreimageServer::new(
-                    OracleServer::new(preimage),
-                    HintReader::new(hint),
-                    Arc::new(OfflineHostBackend::new(kv_store)),
-                )
-                .start()
-                .await
-                .map_err(InteropHostError::from)
-            })
+            let mut backend = OfflineHostBackend::new(kv_store);
+            if let Some(profiler) = profiler {
+                backend = backend.with_profile(profiler.session());
+            }
+            Self::spawn_server(hint, preimage, backend, export)
         } else {
             let providers = self.create_providers().await?;
-            let backend = OnlineHostBackend::new(
+            let mut backend = OnlineHostBackend::new(
                 self.clone(),
                 kv_store.clone(),
                 providers,
                 InteropHintHandler,
             )
             .with_proactive_hint(HintType::L2BlockData);
-
-            task::spawn(async {
-                PreimageServer::new(
-                    OracleServer::new(preimage),
-                    HintReader::new(hint),
-                    Arc::new(backend),
-                )
-                .start()
-                .await
-                .map_err(InteropHostError::from)
-            })
+            if let Some(profiler) = profiler {
+                backend = backend.with_profile(profiler.session());
+            }
+            Self::spawn_server(hint, preimage, backend, export)
         };
 
         Ok(task_handle)
     }
 
+    /// Spawns a [PreimageServer] serving the given backend, exporting the preimages served to the
+    /// export store if one is given.
+    fn spawn_server<C, B>(
+        hint: C,
+        preimage: C,
+        backend: B,
+        export: Option<&SharedKeyValueStore>,
+    ) -> JoinHandle<Result<(), InteropHostError>>
+    where
+        C: Channel + Send + Sync + 'static,
+        B: PreimageServerBackend + Send + Sync + 'static,
+    {
+        let (oracle_server, hint_reader) = (OracleServer::new(preimage), HintReader::new(hint));
+        match export {
+            Some(export) => {
+                let backend = Arc::new(PreimageExporter::new(backend, export.clone()));
+                task::spawn(async move {
+                    let result = PreimageServer::new(oracle_server, hint_reader, backend.clone())
+                        .start()
+                        .await;
+                    info!(target: "host", exported = backend.exported(), "Exported preimages");
+                    result.map_err(InteropHostError::from)
+                })
+            }
+            None => task::spawn(async {
+                PreimageServer::new(oracle_server, hint_reader, Arc::new(backend))
+                    .start()
+                    .await
+                    .map_err(InteropHostError::from)
+            }),
+        }
+    }
+
     /// Starts the host in native mode, running both the client and preimage server in the same
     /// process.
-    async fn start_native(&self) -> Result<(), InteropHostError> {
+    async fn start_native(
+        &self,
+        profiler: Option<&Arc<HostProfiler>>,
+    ) -> Result<(), InteropHostError> {
         let hint = BidirectionalChannel::new()?;
         let preimage = BidirectionalChannel::new()?;
 
-        let server_task = self.start_server(hint.host, preimage.host).await?;
+        let export = self.create_export_store();
+        let server_task =
+            self.start_server(hint.host, preimage.host, profiler, export.as_ref()).await?;
         let client_task = task::spawn(kona_client::interop::run(
             OracleReader::new(preimage.client),
             HintWriter::new(hint.client),
         ));
 
         let (_, client_result) = tokio::try_join!(server_task, client_task)?;
+        self.write_profile_report(profiler.map(Arc::as_ref))?;
+        if let Some(export_dir) = self.export_dir.as_ref() {
+            info!(target: "host", "Exported the client's preimages to {}", export_dir.display());
+        }
 
         // Bubble up the exit status of the client program if execution completes.
         std::process::exit(client_result.is_err() as i32)
     }
 
+    /// Runs the client program natively against the data directory alone, failing with the first
+    /// preimage that the client program needs but the data directory does not contain.
+    async fn validate_data_dir(
+        &self,
+        profiler: &Arc<HostProfiler>,
+    ) -> Result<(), InteropHostError> {
+        if !self.is_offline() {
+            return Err(InteropHostError::Other("Validation requires an offline data directory"));
+        }
+
+        let hint = BidirectionalChannel::new()?;
+        let preimage = BidirectionalChannel::new()?;
+        let backend = Arc::new(
+            OfflineHostBackend::new(self.create_key_value_store()?)
+                .with_profile(profiler.session()),
+        );
+
+        let server_task = task::spawn(
+            PreimageServer::new(
+                OracleServer::new(preimage.host),
+                HintReader::new(hint.host),
+                backend.clone(),
+            )
+            .start(),
+        );
+        let client_task = task::spawn(kona_client::interop::run(
+            OracleReader::new(preimage.client),
+            HintWriter::new(hint.client),
+        ));
+        let (_, client_result) = tokio::try_join!(server_task, client_task)?;
+
+        self.log_chain_summary(profiler);
+        self.write_profile_report(Some(profiler))?;
+
+        if let Some(key) = backend.missing_keys().first() {
+            return Err(InteropHostError::MissingPreimage(*key));
+        }
+        client_result?;
+
+        info!(target: "host", "Data directory contains every preimage needed by the client program");
+        Ok(())
+    }
+
+    /// Logs the number of hints and preimages served for each L2 chain.
+    fn log_chain_summary(&self, profiler: &HostProfiler) {
+        for (chain_id, stats) in profiler.report().chains {
+            let hints: u64 = stats.hints.values().map(|h| h.count).sum();
+            let preimages: u64 = stats.preimages.values().map(|p| p.requests).sum();
+            let bytes: u64 = stats.preimages.values().map(|p| p.bytes).sum();
+            info!(target: "host", chain_id, hints, preimages, bytes, "Served preimages for chain");
+        }
+    }
+
+    /// Creates the [HostProfiler] if a profile report was requested.
+    pub fn create_profiler(&self) -> Option<Arc<HostProfiler>> {
+        self.profile_report
+            .as_ref()
+            .map(|_| Arc::new(HostProfiler::new(self.profile_per_block).with_per_chain()))
+    }
+
+    /// Writes the report of the [HostProfiler] to the `--profile.report` path, if profiling is
+    /// enabled.
+    fn write_profile_report(
+        &self,
+        profiler: Option<&HostProfiler>,
+    ) -> Result<(), InteropHostError> {
+        if let (Some(profiler), Some(path)) = (profiler, self.profile_report.as_ref()) {
+            profiler.write_report(path)?;
+            info!(target: "host", "Wrote proof cost profile to {}", path.display());
+        }
+        Ok(())
+    }
+
     /// Returns `true` if the host is running in offline mode.
     pub const fn is_offline(&self) -> bool {
         self.l1_node_address.is_none() &&
@@ -269,29 +439,60 @@ impl InteropHost {
         Ok(kv_store)
     }
 
+    /// Creates the key-value store that the preimages requested by the client are exported to, if
+    /// an export was requested.
+    fn create_export_store(&self) -> Option<SharedKeyValueStore> {
+        let export_dir = self.export_dir.as_ref()?;
+        Some(Arc::new(RwLock::new(DiskKeyValueStore::new(export_dir.clone()))))
+    }
+
     /// Creates the providers required for the preimage server backend.
     async fn create_providers(&self) -> Result<InteropProviders, InteropHostError> {
-        let l1_provider = http_provider(
-            self.l1_node_address.as_ref().ok_or(InteropHostError::Other("Provider must be set"))?,
-        );
+        let endpoints = |address: Option<&String>, missing: &'static str| {
+            let endpoints = address.map(|a| parse_endpoints(a)).unwrap_or_default();
+            if endpoints.is_empty() {
+                return Err(InteropHostError::Other(missing));
+            }
+            Ok(endpoints)
+        };
 
-        let blob_provider = OnlineBlobProvider::init(OnlineBeaconClient::new_http(
-            self.l1_beacon_address
-                .clone()
-                .ok_or(InteropHostError::Other("Beacon API URL must be set"))?,
+        let l1_provider = failover_provider(
+            &endpoints(self.l1_node_address.as_ref(), "Provider must be set")?,
+            self.rpc_rate_limit,
+        )?;
+        let blob_provider = OnlineBlobProvider::init(FailoverBeaconClient::new(
+            &endpoints(self.l1_beacon_address.as_ref(), "Beacon API URL must be set")?,
+            self.rpc_rate_limit,
         ))
         .await;
 
-        // Resolve all chain IDs to their corresponding providers.
+        // Group the L2 endpoints by the chain ID they serve, so that each chain fails over
+        // between its own endpoints.
         let l2_node_addresses = self
             .l2_node_addresses
             .as_ref()
             .ok_or(InteropHostError::Other("L2 node addresses must be set"))?;
-        let mut l2_providers = HashMap::default();
+        let mut l2_endpoints = HashMap::<u64, Vec<String>>::default();
         for l2_node_address in l2_node_addresses {
-            let l2_provider = http_provider::<Optimism>(l2_node_address);
-            let chain_id = l2_provider.get_chain_id().await?;
-            l2_providers.insert(chain_id, l2_provider);
+            let provider = failover_provider::<Optimism>(&[l2_node_address.clone()], None)?;
+            match provider.get_chain_id().await {
+                Ok(chain_id) => {
+                    l2_endpoints.entry(chain_id).or_default().push(l2_node_address.clone())
+                }
+                Err(e) => {
+                    warn!(target: "host", "Skipping unreachable L2 endpoint {l2_node_address}: {e}")
+                }
+            }
+        }
+        if l2_endpoints.is_empty() {
+            return Err(InteropHostError::Other("No reachable L2 node addresses"));
+        }
+
+        let mut l2_providers = HashMap::default();
+        for (chain_id, endpoints) in l2_endpoints {
+            info!(target: "host", chain_id, endpoints = endpoints.len(), "Resolved L2 endpoints");
+            l2_providers
+                .insert(chain_id, failover_provider::<Optimism>(&endpoints, self.rpc_rate_limit)?);
         }
 
         Ok(InteropProviders { l1: l1_provider, blobs: blob_provider, l2s: l2_providers })
@@ -309,7 +510,7 @@ pub struct InteropProviders {
     /// The L1 EL provider.
     pub l1: RootProvider,
     /// The L1 beacon node provider.
-    pub blobs: OnlineBlobProvider<OnlineBeaconClient>,
+    pub blobs: OnlineBlobProvider<FailoverBeaconClient>,
     /// The L2 EL providers, keyed by chain ID.
     pub l2s: HashMap<u64, RootProvider<Optimism>>,
 }
@@ -381,4 +582,85 @@ mod tests {
         assert_eq!(host.claimed_l2_timestamp, 0);
         assert!(host.native);
     }
+
+    #[test]
+    fn test_parse_interop_validate() {
+        let base = [
+            "interop-host",
+            "--l1-head",
+            "ffd7db0f9d5cdeb49c4c9eba649d4dc6d852d64671e65488e57f58584992ac68",
+            "--l2-pre-state",
+            "ff",
+            "--claimed-l2-post-state",
+            "ffd7db0f9d5cdeb49c4c9eba649d4dc6d852d64671e65488e57f58584992ac68",
+            "--claimed-l2-timestamp",
+            "0",
+        ];
+
+        let host =
+            InteropHost::parse_from(base.iter().chain(&["--validate", "--data-dir", "dummy"]));
+        assert!(host.validate && host.is_offline());
+        assert!(host.create_profiler().is_none());
+
+        // Validation requires a data directory, and only runs offline.
+        assert!(InteropHost::try_parse_from(base.iter().chain(&["--validate"])).is_err());
+        assert!(
+            InteropHost::try_parse_from(base.iter().chain(&[
+                "--validate",
+                "--data-dir",
+                "dummy",
+                "--native"
+            ]))
+            .is_err()
+        );
+        assert!(
+            InteropHost::try_parse_from(base.iter().chain(&[
+                "--validate",
+                "--data-dir",
+                "dummy",
+                "--l1-node-address",
+                "http://localhost:8546"
+            ]))
+            .is_err()
+        );
+
+        let host = InteropHost::parse_from(base.iter().chain(&[
+            "--native",
+            "--data-dir",
+            "dummy",
+            "--profile.report",
+            "report.json",
+        ]));
+        assert!(host.create_profiler().is_some());
+    }
+
+    #[test]
+    fn test_parse_interop_export() {
+        let base = [
+            "interop-host",
+            "--l1-head",
+            "ffd7db0f9d5cdeb49c4c9eba649d4dc6d852d64671e65488e57f58584992ac68",
+            "--l2-pre-state",
+            "ff",
+            "--claimed-l2-post-state",
+            "ffd7db0f9d5cdeb49c4c9eba649d4dc6d852d64671e65488e57f58584992ac68",
+            "--claimed-l2-timestamp",
+            "0",
+            "--data-dir",
+            "dummy",
+        ];
+
+        let host = InteropHost::parse_from(base.iter().chain(&["--native", "--export", "export"]));
+        assert_eq!(host.export_dir, Some(PathBuf::from("export")));
+
+        // Exports are only written by native runs.
+        assert!(
+            InteropHost::try_parse_from(base.iter().chain(&["--server", "--export", "export"]))
+                .is_err()
+        );
+        assert!(
+            InteropHost::try_parse_from(base.iter().chain(&["--validate", "--export", "export"]))
+                .is_err()
+        );
+    }
 }
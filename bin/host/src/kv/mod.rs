@@ -17,6 +17,12 @@ pub use split::SplitKeyValueStore;
 /// A type alias for a shared key-value store.
 pub type SharedKeyValueStore = Arc<RwLock<dyn KeyValueStore + Send + Sync>>;
 
+/// A type alias for a key-value store shared between the stores of several client sessions.
+///
+/// Unlike a [SharedKeyValueStore], it is locked synchronously, so that it can back the remote half
+/// of each session's [SplitKeyValueStore].
+pub type SyncKeyValueStore = Arc<std::sync::RwLock<dyn KeyValueStore + Send + Sync>>;
+
 /// Describes the interface of a simple, synchronous key-value store.
 pub trait KeyValueStore {
     /// Get the value associated with the given key.
@@ -25,3 +31,13 @@ pub trait KeyValueStore {
     /// Set the value associated with the given key.
     fn set(&mut self, key: B256, value: Vec<u8>) -> Result<()>;
 }
+
+impl<T: KeyValueStore + ?Sized> KeyValueStore for Arc<std::sync::RwLock<T>> {
+    fn get(&self, key: B256) -> Option<Vec<u8>> {
+        self.read().unwrap_or_else(|e| e.into_inner()).get(key)
+    }
+
+    fn set(&mut self, key: B256, value: Vec<u8>) -> Result<()> {
+        self.write().unwrap_or_else(|e| e.into_inner()).set(key, value)
+    }
+}
@@ -4,13 +4,28 @@
 mod server;
 pub use server::{PreimageServer, PreimageServerError};
 
+mod profiler;
+pub use profiler::{
+    BlockProfile, HintStats, HostProfiler, PreimageStats, ProfileReport, ProfileSession,
+    ProfileStats,
+};
+
+mod transcript;
+pub use transcript::{TranscriptEntry, TranscriptRecorder, TranscriptReplayer, TranscriptWriter};
+
+mod export;
+pub use export::PreimageExporter;
+
 mod kv;
 pub use kv::{
     DiskKeyValueStore, KeyValueStore, MemoryKeyValueStore, SharedKeyValueStore, SplitKeyValueStore,
+    SyncKeyValueStore,
 };
 
 mod backend;
-pub use backend::{HintHandler, OfflineHostBackend, OnlineHostBackend, OnlineHostBackendCfg};
+pub use backend::{
+    HintHandler, OfflineHostBackend, OnlineHostBackend, OnlineHostBackendCfg, PrefetchPolicy,
+};
 
 pub mod eth;
 
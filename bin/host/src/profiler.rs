@@ -0,0 +1,417 @@
+//! This module contains the [HostProfiler], which aggregates the cost of serving preimages to the
+//! client program.
+
+use alloy_primitives::{B256, hex};
+use kona_genesis::RollupConfig;
+use kona_preimage::PreimageKeyType;
+use kona_proof::HintType;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{BTreeMap, HashMap},
+    path::Path,
+    sync::{Arc, Mutex, MutexGuard},
+    time::Duration,
+};
+
+/// Aggregated statistics for a single hint type.
+#[derive(Debug, Default, Clone, PartialEq, Serialize)]
+pub struct HintStats {
+    /// The number of hints received.
+    pub count: u64,
+    /// The number of upstream fetches performed in response to the hint.
+    pub fetches: u64,
+    /// The number of upstream fetches that failed.
+    pub fetch_errors: u64,
+    /// The total latency of upstream fetches, in milliseconds.
+    pub fetch_latency_ms_total: f64,
+    /// The maximum latency of a single upstream fetch, in milliseconds.
+    pub fetch_latency_ms_max: f64,
+}
+
+/// Aggregated statistics for a single preimage key type.
+#[derive(Debug, Default, Clone, PartialEq, Serialize)]
+pub struct PreimageStats {
+    /// The number of preimages served.
+    pub requests: u64,
+    /// The total number of preimage bytes served.
+    pub bytes: u64,
+    /// The number of preimages that were already present in the key-value store when requested.
+    pub cache_hits: u64,
+    /// The number of preimages that had to be fetched from upstream before being served.
+    pub cache_misses: u64,
+    /// The ratio of `cache_hits` to `requests`.
+    pub cache_hit_ratio: f64,
+}
+
+/// Statistics keyed by hint type and preimage key type.
+#[derive(Debug, Default, Clone, PartialEq, Serialize)]
+pub struct ProfileStats {
+    /// Statistics per hint type.
+    pub hints: BTreeMap<String, HintStats>,
+    /// Statistics per preimage key type.
+    pub preimages: BTreeMap<String, PreimageStats>,
+}
+
+impl ProfileStats {
+    fn record_hint(&mut self, hint_type: &str) {
+        self.hints.entry(hint_type.to_string()).or_default().count += 1;
+    }
+
+    fn record_fetch(&mut self, hint_type: &str, latency: Duration, success: bool) {
+        let stats = self.hints.entry(hint_type.to_string()).or_default();
+        let latency_ms = latency.as_secs_f64() * 1000.0;
+        stats.fetches += 1;
+        stats.fetch_errors += !success as u64;
+        stats.fetch_latency_ms_total += latency_ms;
+        stats.fetch_latency_ms_max = stats.fetch_latency_ms_max.max(latency_ms);
+    }
+
+    fn record_preimage(&mut self, key_type: PreimageKeyType, bytes: usize, cache_hit: bool) {
+        let stats = self.preimages.entry(format!("{key_type:?}")).or_default();
+        stats.requests += 1;
+        stats.bytes += bytes as u64;
+        if cache_hit {
+            stats.cache_hits += 1;
+        } else {
+            stats.cache_misses += 1;
+        }
+        stats.cache_hit_ratio = stats.cache_hits as f64 / stats.requests as f64;
+    }
+}
+
+/// The statistics attributed to a single L2 block.
+///
+/// A block begins when the client sends the `l2-payload-witness` hint for it, and every hint and
+/// preimage served until the next block begins is attributed to it.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct BlockProfile {
+    /// The hash of the block's parent.
+    pub parent_hash: B256,
+    /// The timestamp of the block.
+    pub timestamp: u64,
+    /// The number of the block, if the rollup config is known.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub number: Option<u64>,
+    /// The statistics attributed to the block.
+    #[serde(flatten)]
+    pub stats: ProfileStats,
+}
+
+/// The report emitted by the [HostProfiler].
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ProfileReport {
+    /// Statistics aggregated over the whole run.
+    pub totals: ProfileStats,
+    /// Statistics per L2 block, in the order the blocks were first seen. Only populated if the
+    /// per-block breakdown is enabled.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub blocks: Vec<BlockProfile>,
+    /// Statistics per L2 chain ID. Only populated if the per-chain breakdown is enabled.
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub chains: BTreeMap<u64, ProfileStats>,
+}
+
+/// The [HostProfiler] aggregates the number of requests, bytes served, key-value store hit ratio,
+/// and upstream fetch latency of the host, keyed by hint type and preimage key type.
+#[derive(Debug, Default)]
+pub struct HostProfiler {
+    /// Whether to attribute statistics to individual L2 blocks.
+    per_block: bool,
+    /// Whether to attribute statistics to individual L2 chains.
+    per_chain: bool,
+    /// The rollup config, used to derive block numbers from timestamps.
+    rollup_config: Option<RollupConfig>,
+    /// The aggregated statistics.
+    state: Mutex<ProfilerState>,
+}
+
+/// The mutable state of the [HostProfiler].
+#[derive(Debug, Default)]
+struct ProfilerState {
+    /// Statistics aggregated over the whole run.
+    totals: ProfileStats,
+    /// Statistics per L2 block.
+    blocks: Vec<BlockProfile>,
+    /// The index into `blocks` of each block, keyed by parent hash.
+    block_indices: HashMap<B256, usize>,
+    /// Statistics per L2 chain ID.
+    chains: BTreeMap<u64, ProfileStats>,
+}
+
+impl HostProfiler {
+    /// Creates a new [HostProfiler]. If `per_block` is set, statistics are also attributed to the
+    /// L2 block being executed by the client.
+    pub fn new(per_block: bool) -> Self {
+        Self { per_block, ..Default::default() }
+    }
+
+    /// Sets the [RollupConfig] used to number the blocks in the per-block breakdown.
+    pub fn with_rollup_config(mut self, rollup_config: RollupConfig) -> Self {
+        self.rollup_config = Some(rollup_config);
+        self
+    }
+
+    /// Attributes statistics to the L2 chain that the client last sent an L2 hint for. Used by
+    /// the interop host, whose L2 hints are suffixed with a chain ID.
+    pub const fn with_per_chain(mut self) -> Self {
+        self.per_chain = true;
+        self
+    }
+
+    /// Starts a new [ProfileSession] for a single client.
+    pub fn session(self: &Arc<Self>) -> ProfileSession {
+        ProfileSession {
+            profiler: self.clone(),
+            block: Arc::new(Mutex::new(None)),
+            chain: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    /// Returns a [ProfileReport] of the statistics collected so far.
+    pub fn report(&self) -> ProfileReport {
+        let state = self.state();
+        ProfileReport {
+            totals: state.totals.clone(),
+            blocks: state.blocks.clone(),
+            chains: state.chains.clone(),
+        }
+    }
+
+    /// Writes the [ProfileReport] to the given path as JSON.
+    pub fn write_report(&self, path: &Path) -> std::io::Result<()> {
+        std::fs::write(path, serde_json::to_vec_pretty(&self.report())?)
+    }
+
+    /// Applies `f` to the run totals, and to the statistics of the given block and chain.
+    fn update(&self, block: Option<usize>, chain: Option<u64>, f: impl Fn(&mut ProfileStats)) {
+        let mut state = self.state();
+        f(&mut state.totals);
+        if let Some(block) = block.and_then(|i| state.blocks.get_mut(i)) {
+            f(&mut block.stats);
+        }
+        if let Some(chain) = chain {
+            f(state.chains.entry(chain).or_default());
+        }
+    }
+
+    /// Returns the index of the block with the given parent hash, inserting it if unseen.
+    fn begin_block(&self, parent_hash: B256, timestamp: u64) -> usize {
+        let number = self
+            .rollup_config
+            .as_ref()
+            .map(|cfg| cfg.genesis.l2.number + cfg.block_number_from_timestamp(timestamp));
+
+        let mut state = self.state();
+        if let Some(index) = state.block_indices.get(&parent_hash) {
+            return *index;
+        }
+        let index = state.blocks.len();
+        state.blocks.push(BlockProfile {
+            parent_hash,
+            timestamp,
+            number,
+            stats: ProfileStats::default(),
+        });
+        state.block_indices.insert(parent_hash, index);
+        index
+    }
+
+    fn state(&self) -> MutexGuard<'_, ProfilerState> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// A handle to the [HostProfiler] for a single client session.
+///
+/// Each session tracks the L2 block its client is currently executing, so that concurrent client
+/// sessions served by the same host are attributed to the correct blocks.
+#[derive(Debug, Clone)]
+pub struct ProfileSession {
+    /// The shared profiler.
+    profiler: Arc<HostProfiler>,
+    /// The index of the block currently being executed by the client.
+    block: Arc<Mutex<Option<usize>>>,
+    /// The L2 chain ID that the client last sent an L2 hint for.
+    chain: Arc<Mutex<Option<u64>>>,
+}
+
+impl ProfileSession {
+    /// Records a hint of the given type, sent by the client.
+    pub fn record_hint(&self, hint_type: &str, data: &[u8]) {
+        if self.profiler.per_block && hint_type == <&str>::from(HintType::L2PayloadWitness) {
+            if let Some((parent_hash, timestamp)) = decode_payload_witness(data) {
+                let index = self.profiler.begin_block(parent_hash, timestamp);
+                *self.block.lock().unwrap_or_else(|e| e.into_inner()) = Some(index);
+            }
+        }
+        if self.profiler.per_chain && hint_type.starts_with("l2-") {
+            if let Some(chain_id) = data.last_chunk::<8>() {
+                *self.chain.lock().unwrap_or_else(|e| e.into_inner()) =
+                    Some(u64::from_be_bytes(*chain_id));
+            }
+        }
+        self.update(|stats| stats.record_hint(hint_type));
+    }
+
+    /// Records a raw hint string, in the `<type> <hex data>` format sent by the client.
+    pub fn record_raw_hint(&self, hint: &str) {
+        let (hint_type, data) = hint.split_once(' ').unwrap_or((hint, ""));
+        self.record_hint(hint_type, &hex::decode(data).unwrap_or_default());
+    }
+
+    /// Records an upstream fetch performed in response to a hint of the given type.
+    pub fn record_fetch(&self, hint_type: &str, latency: Duration, success: bool) {
+        self.update(|stats| stats.record_fetch(hint_type, latency, success));
+    }
+
+    /// Records a preimage of the given key type served to the client. `cache_hit` indicates
+    /// whether the preimage was present in the key-value store when it was requested.
+    pub fn record_preimage(&self, key_type: PreimageKeyType, bytes: usize, cache_hit: bool) {
+        self.update(|stats| stats.record_preimage(key_type, bytes, cache_hit));
+    }
+
+    /// Applies `f` to the statistics of the current block and chain of the session.
+    fn update(&self, f: impl Fn(&mut ProfileStats)) {
+        let block = *self.block.lock().unwrap_or_else(|e| e.into_inner());
+        let chain = *self.chain.lock().unwrap_or_else(|e| e.into_inner());
+        self.profiler.update(block, chain, f);
+    }
+}
+
+/// Decodes the parent hash and block timestamp from the data of an `l2-payload-witness` hint.
+///
+/// The hint data is the parent hash followed by the JSON-encoded payload attributes, which may be
+/// followed by a chain ID.
+fn decode_payload_witness(data: &[u8]) -> Option<(B256, u64)> {
+    #[derive(Deserialize)]
+    struct Attributes {
+        #[serde(with = "alloy_serde::quantity")]
+        timestamp: u64,
+    }
+
+    let parent_hash = B256::try_from(data.get(..32)?).ok()?;
+    let attributes =
+        serde_json::Deserializer::from_slice(&data[32..]).into_iter::<Attributes>().next()?.ok()?;
+    Some((parent_hash, attributes.timestamp))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn witness_hint_data(parent_hash: B256, timestamp: u64) -> Vec<u8> {
+        let attributes = format!(r#"{{"timestamp":"{timestamp:#x}","gasLimit":"0x1"}}"#);
+        [parent_hash.as_slice(), attributes.as_bytes(), 5000u64.to_be_bytes().as_slice()].concat()
+    }
+
+    #[test]
+    fn test_aggregates_totals() {
+        let profiler = Arc::new(HostProfiler::new(false));
+        let session = profiler.session();
+
+        session.record_raw_hint("l1-block-header 0x0102");
+        session.record_hint("l1-block-header", &[]);
+        session.record_fetch("l1-block-header", Duration::from_millis(10), true);
+        session.record_fetch("l1-block-header", Duration::from_millis(30), false);
+        session.record_preimage(PreimageKeyType::Keccak256, 100, true);
+        session.record_preimage(PreimageKeyType::Keccak256, 50, false);
+        session.record_preimage(PreimageKeyType::Keccak256, 50, true);
+        session.record_preimage(PreimageKeyType::Local, 8, true);
+
+        let report = profiler.report();
+        assert!(report.blocks.is_empty());
+
+        let hint = &report.totals.hints["l1-block-header"];
+        assert_eq!(hint.count, 2);
+        assert_eq!(hint.fetches, 2);
+        assert_eq!(hint.fetch_errors, 1);
+        assert!((hint.fetch_latency_ms_total - 40.0).abs() < 1e-6);
+        assert!((hint.fetch_latency_ms_max - 30.0).abs() < 1e-6);
+
+        let keccak = &report.totals.preimages["Keccak256"];
+        assert_eq!(keccak.requests, 3);
+        assert_eq!(keccak.bytes, 200);
+        assert_eq!((keccak.cache_hits, keccak.cache_misses), (2, 1));
+        assert!((keccak.cache_hit_ratio - 2.0 / 3.0).abs() < 1e-6);
+        assert_eq!(report.totals.preimages["Local"].requests, 1);
+    }
+
+    #[test]
+    fn test_per_block_breakdown() {
+        let mut rollup_config = RollupConfig { block_time: 2, ..Default::default() };
+        rollup_config.genesis.l2.number = 100;
+        rollup_config.genesis.l2_time = 1_000;
+        let profiler = Arc::new(HostProfiler::new(true).with_rollup_config(rollup_config));
+        let (a, b) = (profiler.session(), profiler.session());
+
+        // Preimages served before the first block are only counted in the totals.
+        a.record_preimage(PreimageKeyType::Local, 32, true);
+
+        a.record_hint("l2-payload-witness", &witness_hint_data(B256::with_last_byte(1), 1_010));
+        b.record_hint("l2-payload-witness", &witness_hint_data(B256::with_last_byte(2), 1_012));
+        a.record_preimage(PreimageKeyType::Keccak256, 10, false);
+        b.record_preimage(PreimageKeyType::Keccak256, 20, true);
+        b.record_preimage(PreimageKeyType::Keccak256, 20, true);
+
+        // Re-entering a block resumes attribution to the existing entry.
+        b.record_hint("l2-payload-witness", &witness_hint_data(B256::with_last_byte(1), 1_010));
+        b.record_preimage(PreimageKeyType::Keccak256, 5, true);
+
+        let report = profiler.report();
+        assert_eq!(report.totals.preimages["Keccak256"].requests, 4);
+        assert_eq!(report.totals.preimages["Local"].requests, 1);
+        assert_eq!(report.blocks.len(), 2);
+
+        let (first, second) = (&report.blocks[0], &report.blocks[1]);
+        assert_eq!(
+            (first.parent_hash, first.timestamp, first.number),
+            (B256::with_last_byte(1), 1_010, Some(105))
+        );
+        assert_eq!(first.stats.hints["l2-payload-witness"].count, 2);
+        assert_eq!(first.stats.preimages["Keccak256"].bytes, 15);
+        assert_eq!(second.number, Some(106));
+        assert_eq!(second.stats.preimages["Keccak256"].bytes, 40);
+        assert!(!first.stats.preimages.contains_key("Local"));
+
+        let json = serde_json::to_value(&report).unwrap();
+        assert_eq!(json["blocks"][1]["preimages"]["Keccak256"]["cache_hits"], 2);
+    }
+
+    #[test]
+    fn test_per_chain_breakdown() {
+        let profiler = Arc::new(HostProfiler::new(false).with_per_chain());
+        let session = profiler.session();
+
+        session.record_hint("agreed-pre-state", &[0u8; 64]);
+        session.record_preimage(PreimageKeyType::Local, 32, true);
+
+        let header_hint = |chain_id: u64| [[0u8; 32].as_slice(), &chain_id.to_be_bytes()].concat();
+        session.record_hint("l2-block-header", &header_hint(10));
+        session.record_preimage(PreimageKeyType::Keccak256, 100, true);
+        session.record_hint("l1-block-header", &[0u8; 32]);
+        session.record_preimage(PreimageKeyType::Keccak256, 50, true);
+        session.record_hint("l2-block-header", &header_hint(20));
+        session.record_preimage(PreimageKeyType::Keccak256, 10, true);
+
+        let report = profiler.report();
+        assert_eq!(report.totals.preimages["Keccak256"].bytes, 160);
+        assert_eq!(report.chains.len(), 2);
+        assert_eq!(report.chains[&10].preimages["Keccak256"].bytes, 150);
+        assert_eq!(report.chains[&10].hints["l1-block-header"].count, 1);
+        assert_eq!(report.chains[&20].preimages["Keccak256"].requests, 1);
+
+        let json = serde_json::to_value(&report).unwrap();
+        assert_eq!(json["chains"]["20"]["hints"]["l2-block-header"]["count"], 1);
+    }
+
+    #[test]
+    fn test_decode_payload_witness() {
+        let parent_hash = B256::repeat_byte(0xaa);
+        assert_eq!(
+            decode_payload_witness(&witness_hint_data(parent_hash, 0x1234)),
+            Some((parent_hash, 0x1234))
+        );
+        assert_eq!(decode_payload_witness(&[0u8; 16]), None);
+        assert_eq!(decode_payload_witness(&[0u8; 40]), None);
+    }
+}
@@ -1,10 +1,15 @@
 //! This module contains all CLI-specific code for the single chain entrypoint.
 
-use super::{SingleChainHintHandler, SingleChainLocalInputs};
+use super::{
+    SessionInputs, SingleChainHintHandler, SingleChainLocalInputs, SingleChainPrefetchPolicy,
+    handler::ensure_l1_canonical,
+};
 use crate::{
-    DiskKeyValueStore, MemoryKeyValueStore, OfflineHostBackend, OnlineHostBackend,
-    OnlineHostBackendCfg, PreimageServer, SharedKeyValueStore, SplitKeyValueStore,
-    eth::http_provider, server::PreimageServerError,
+    DiskKeyValueStore, HostProfiler, KeyValueStore, MemoryKeyValueStore, OfflineHostBackend,
+    OnlineHostBackend, OnlineHostBackendCfg, PreimageServer, SharedKeyValueStore,
+    SplitKeyValueStore, SyncKeyValueStore, TranscriptRecorder, TranscriptWriter,
+    eth::{FailoverBeaconClient, failover_provider, parse_endpoints},
+    server::PreimageServerError,
 };
 use alloy_primitives::B256;
 use alloy_provider::RootProvider;
@@ -12,10 +17,12 @@ use clap::Parser;
 use kona_cli::cli_styles;
 use kona_genesis::{L1ChainConfig, RollupConfig};
 use kona_preimage::{
-    BidirectionalChannel, Channel, HintReader, HintWriter, OracleReader, OracleServer,
+    BidirectionalChannel, Channel, ChannelAddr, HintReader, HintWriter, OracleReader, OracleServer,
+    PreimageServerBackend, SocketChannels, SocketListener, errors::ChannelError,
 };
 use kona_proof::HintType;
-use kona_providers_alloy::{OnlineBeaconClient, OnlineBlobProvider};
+use kona_providers_alloy::{LocalBlobArchive, OnlineBlobArchiver, OnlineBlobProvider};
+use kona_registry::ROLLUP_CONFIGS;
 use kona_std_fpvm::{FileChannel, FileDescriptor};
 use op_alloy_network::Optimism;
 use serde::Serialize;
@@ -24,6 +31,7 @@ use tokio::{
     sync::RwLock,
     task::{self, JoinHandle},
 };
+use tracing::{debug, info, warn};
 
 /// The host binary CLI application arguments.
 #[derive(Default, Parser, Serialize, Clone, Debug)]
@@ -44,7 +52,8 @@ pub struct SingleChainHost {
     /// Number of the L2 block that the claimed output root commits to.
     #[arg(long, visible_alias = "l2-block-number", env)]
     pub claimed_l2_block_number: u64,
-    /// Address of L2 JSON-RPC endpoint to use (eth and debug namespace required).
+    /// Address of L2 JSON-RPC endpoint to use (eth and debug namespace required). Accepts a
+    /// comma-separated list of endpoints, which are failed over between in order of preference.
     #[arg(
         long,
         visible_alias = "l2",
@@ -53,7 +62,8 @@ pub struct SingleChainHost {
         env
     )]
     pub l2_node_address: Option<String>,
-    /// Address of L1 JSON-RPC endpoint to use (eth and debug namespace required)
+    /// Address of L1 JSON-RPC endpoint to use (eth and debug namespace required). Accepts a
+    /// comma-separated list of endpoints, which are failed over between in order of preference.
     #[arg(
         long,
         visible_alias = "l1",
@@ -62,7 +72,8 @@ pub struct SingleChainHost {
         env
     )]
     pub l1_node_address: Option<String>,
-    /// Address of the L1 Beacon API endpoint to use.
+    /// Address of the L1 Beacon API endpoint to use. Accepts a comma-separated list of endpoints,
+    /// which are failed over between in order of preference.
     #[arg(
         long,
         visible_alias = "beacon",
@@ -71,28 +82,74 @@ pub struct SingleChainHost {
         env
     )]
     pub l1_beacon_address: Option<String>,
+    /// Address of a blob-archiver API to fetch blobs from when they have expired from the L1
+    /// Beacon API endpoint.
+    #[arg(long, visible_alias = "beacon-archiver", requires = "l1_beacon_address", env)]
+    pub l1_beacon_archiver_address: Option<String>,
+    /// Path to a local directory of blob sidecars, named by versioned hash, to fetch blobs from
+    /// when they are unavailable from the L1 Beacon API endpoint and blob archiver.
+    #[arg(long, requires = "l1_beacon_address", env)]
+    pub l1_blob_dir: Option<PathBuf>,
+    /// The maximum number of requests per second sent to each L1, L2, and beacon API endpoint.
+    /// Unlimited if not set.
+    #[arg(long = "rpc.rate-limit", value_name = "RPS", requires = "l1_node_address", env)]
+    pub rpc_rate_limit: Option<u32>,
+    /// Address of a second L1 JSON-RPC endpoint to cross-check the canonical L1 chain against. If
+    /// set, the L1 head must be canonical on both `--l1-node-address` and this endpoint, and every
+    /// L1 block whose header, transactions or receipts are fetched must be canonical on this
+    /// endpoint. Fetched headers must also match their hash.
+    #[arg(long = "l1.cross-check-address", value_name = "URL", requires = "l1_node_address", env)]
+    pub l1_cross_check_address: Option<String>,
     /// The Data Directory for preimage data storage. Optional if running in online mode,
     /// required if running in offline mode.
     #[arg(
         long,
         visible_alias = "db",
         required_unless_present_all = ["l2_node_address", "l1_node_address", "l1_beacon_address"],
+        required_unless_present = "client_connect",
         env
     )]
     pub data_dir: Option<PathBuf>,
     /// Run the client program natively.
-    #[arg(long, conflicts_with = "server", required_unless_present = "server")]
+    #[arg(
+        long,
+        conflicts_with_all = ["server", "server_listen", "client_connect"],
+        required_unless_present_any = ["server", "server_listen", "client_connect"]
+    )]
     pub native: bool,
     /// Run in pre-image server mode without executing any client program. If not provided, the
     /// host will run the client program in the host process.
-    #[arg(long, conflicts_with = "native", required_unless_present = "native")]
+    #[arg(
+        long,
+        conflicts_with_all = ["native", "server_listen", "client_connect"],
+        required_unless_present_any = ["native", "server_listen", "client_connect"]
+    )]
     pub server: bool,
+    /// Run in pre-image server mode, serving clients that connect to the given socket address
+    /// (`unix:<path>` or `tcp:<host>:<port>`). Every client session proves the claim it sends on
+    /// connect, and shares the same key-value store and providers.
+    #[arg(
+        long = "server.listen",
+        value_name = "ADDR",
+        conflicts_with_all = ["native", "server", "client_connect"],
+        env
+    )]
+    pub server_listen: Option<ChannelAddr>,
+    /// Run the client program natively, connecting to a pre-image server listening at the given
+    /// socket address (`unix:<path>` or `tcp:<host>:<port>`).
+    #[arg(
+        long = "client.connect",
+        value_name = "ADDR",
+        conflicts_with_all = ["native", "server", "server_listen"],
+        env
+    )]
+    pub client_connect: Option<ChannelAddr>,
     /// The L2 chain ID of a supported chain. If provided, the host will look for the corresponding
     /// rollup config in the superchain registry.
     #[arg(
         long,
         conflicts_with = "rollup_config_path",
-        required_unless_present = "rollup_config_path",
+        required_unless_present_any = ["rollup_config_path", "client_connect"],
         env
     )]
     pub l2_chain_id: Option<u64>,
@@ -102,7 +159,7 @@ pub struct SingleChainHost {
         long,
         alias = "rollup-cfg",
         conflicts_with = "l2_chain_id",
-        required_unless_present = "l2_chain_id",
+        required_unless_present_any = ["l2_chain_id", "client_connect"],
         env
     )]
     pub rollup_config_path: Option<PathBuf>,
@@ -114,6 +171,29 @@ pub struct SingleChainHost {
     /// the execution layer.
     #[arg(long, env)]
     pub enable_experimental_witness_endpoint: bool,
+    /// Path to write a JSON report of the cost of serving the client to on exit. The report
+    /// aggregates requests, bytes served, key-value store hit ratio, and upstream fetch latency
+    /// per hint type and preimage key type.
+    #[arg(long = "profile.report", value_name = "PATH", conflicts_with = "client_connect", env)]
+    pub profile_report: Option<PathBuf>,
+    /// Include a per-L2-block breakdown in the `--profile.report` output.
+    #[arg(long = "profile.per-block", requires = "profile_report", env)]
+    pub profile_per_block: bool,
+    /// The maximum number of follow-up hints to prefetch concurrently in the background, such as
+    /// the transactions, receipts, and parent of a block whose header was requested. Prefetching
+    /// is disabled if set to 0.
+    #[arg(long = "prefetch.concurrency", value_name = "N", default_value_t = 0, env)]
+    pub prefetch_concurrency: usize,
+    /// Path to record the ordered transcript of the hints and preimage requests issued by the
+    /// client to, as JSON lines. The transcript can be served back to the client program with
+    /// `kona-host replay`.
+    #[arg(
+        long = "transcript.record",
+        value_name = "PATH",
+        conflicts_with_all = ["server_listen", "client_connect"],
+        env
+    )]
+    pub transcript_record: Option<PathBuf>,
 }
 
 /// An error that can occur when handling single chain hosts
@@ -137,6 +217,15 @@ pub enum SingleChainHostError {
     /// No l1 config found.
     #[error("No l1 config found")]
     NoL1Config,
+    /// An invalid provider endpoint URL.
+    #[error("Invalid endpoint URL: {0}")]
+    InvalidEndpoint(#[from] url::ParseError),
+    /// An error on the session channel of a socket client session.
+    #[error("Session channel error: {0}")]
+    Session(#[from] ChannelError),
+    /// The L1 providers disagree on the canonical L1 chain.
+    #[error("L1 cross-check failed: {0}")]
+    CrossCheck(anyhow::Error),
     /// Any other error.
     #[error("Error: {0}")]
     Other(&'static str),
@@ -145,22 +234,40 @@ pub enum SingleChainHostError {
 impl SingleChainHost {
     /// Starts the [SingleChainHost] application.
     pub async fn start(self) -> Result<(), SingleChainHostError> {
+        let profiler = self.create_profiler();
+        let transcript = self.create_transcript()?;
+
         if self.server {
             let hint = FileChannel::new(FileDescriptor::HintRead, FileDescriptor::HintWrite);
             let preimage =
                 FileChannel::new(FileDescriptor::PreimageRead, FileDescriptor::PreimageWrite);
 
-            self.start_server(hint, preimage).await?.await?
+            let result = self
+                .start_server(hint, preimage, profiler.as_ref(), transcript.as_ref())
+                .await?
+                .await?;
+            self.write_profile_report(profiler.as_deref())?;
+            if let Some(transcript) = transcript {
+                transcript.flush()?;
+            }
+            result
+        } else if let Some(addr) = self.server_listen.as_ref() {
+            self.start_listener(addr, profiler.as_ref()).await
+        } else if let Some(addr) = self.client_connect.as_ref() {
+            self.start_client(addr).await
         } else {
-            self.start_native().await
+            self.start_native(profiler.as_ref(), transcript.as_ref()).await
         }
     }
 
-    /// Starts the preimage server, communicating with the client over the provided channels.
+    /// Starts the preimage server, communicating with the client over the provided channels. If a
+    /// [TranscriptWriter] is given, the hints and preimage requests served are recorded to it.
     pub async fn start_server<C>(
         &self,
         hint: C,
         preimage: C,
+        profiler: Option<&Arc<HostProfiler>>,
+        transcript: Option<&Arc<TranscriptWriter>>,
     ) -> Result<JoinHandle<Result<(), SingleChainHostError>>, SingleChainHostError>
     where
         C: Channel + Send + Sync + 'static,
@@ -168,54 +275,184 @@ impl SingleChainHost {
         let kv_store = self.create_key_value_store()?;
 
         let task_handle = if self.is_offline() {
-            task::spawn(async {
-                PreimageServer::new(
-                    OracleServer::new(preimage),
-                    HintReader::new(hint),
-                    Arc::new(OfflineHostBackend::new(kv_store)),
-                )
-                .start()
-                .await
-                .map_err(SingleChainHostError::from)
-            })
+            let backend = self.create_offline_backend(kv_store, profiler);
+            Self::spawn_server(hint, preimage, backend, transcript)
         } else {
             let providers = self.create_providers().await?;
-            let backend = OnlineHostBackend::new(
-                self.clone(),
-                kv_store.clone(),
-                providers,
-                SingleChainHintHandler,
-            )
-            .with_proactive_hint(HintType::L2PayloadWitness);
-
-            task::spawn(async {
-                PreimageServer::new(
-                    OracleServer::new(preimage),
-                    HintReader::new(hint),
-                    Arc::new(backend),
-                )
-                .start()
-                .await
-                .map_err(SingleChainHostError::from)
-            })
+            let backend = self.create_online_backend(kv_store, providers, profiler);
+            Self::spawn_server(hint, preimage, backend, transcript)
         };
 
         Ok(task_handle)
     }
 
+    /// Spawns a [PreimageServer] serving the given backend, recording its traffic to the
+    /// [TranscriptWriter] if one is given.
+    fn spawn_server<C, B>(
+        hint: C,
+        preimage: C,
+        backend: B,
+        transcript: Option<&Arc<TranscriptWriter>>,
+    ) -> JoinHandle<Result<(), SingleChainHostError>>
+    where
+        C: Channel + Send + Sync + 'static,
+        B: PreimageServerBackend + Send + Sync + 'static,
+    {
+        let (oracle_server, hint_reader) = (OracleServer::new(preimage), HintReader::new(hint));
+        match transcript {
+            Some(transcript) => {
+                let backend = Arc::new(TranscriptRecorder::new(backend, transcript.clone()));
+                task::spawn(async {
+                    PreimageServer::new(oracle_server, hint_reader, backend)
+                        .start()
+                        .await
+                        .map_err(SingleChainHostError::from)
+                })
+            }
+            None => task::spawn(async {
+                PreimageServer::new(oracle_server, hint_reader, Arc::new(backend))
+                    .start()
+                    .await
+                    .map_err(SingleChainHostError::from)
+            }),
+        }
+    }
+
+    /// Starts the preimage server on a socket listener, serving every client that connects to it
+    /// until the host is interrupted.
+    ///
+    /// Each client session gets its own backend and local inputs, built from the
+    /// [SessionInputs] the client sends when it connects, so that sessions can prove different
+    /// claims. The remote key-value store and providers are shared across all of them.
+    async fn start_listener(
+        &self,
+        addr: &ChannelAddr,
+        profiler: Option<&Arc<HostProfiler>>,
+    ) -> Result<(), SingleChainHostError> {
+        let listener = SocketListener::bind(addr).await?;
+        info!(target: "host", "Listening for preimage clients on {addr}");
+
+        let remote_store = self.create_remote_key_value_store();
+        let providers = if self.is_offline() { None } else { Some(self.create_providers().await?) };
+
+        let result = tokio::select! {
+            result = self.serve_listener(&listener, remote_store, providers, profiler) => result,
+            _ = tokio::signal::ctrl_c() => {
+                info!(target: "host", "Received interrupt, shutting down preimage listener");
+                Ok(())
+            }
+        };
+        self.write_profile_report(profiler.map(Arc::as_ref))?;
+        result
+    }
+
+    /// Accepts client sessions from the listener, serving each on its own task.
+    async fn serve_listener(
+        &self,
+        listener: &SocketListener,
+        remote_store: SyncKeyValueStore,
+        providers: Option<SingleChainProviders>,
+        profiler: Option<&Arc<HostProfiler>>,
+    ) -> Result<(), SingleChainHostError> {
+        loop {
+            // A failed accept only affects the connecting client, so keep serving the others.
+            let channels = match listener.accept().await {
+                Ok(channels) => channels,
+                Err(e) => {
+                    warn!(target: "host", "Failed to accept preimage client session: {e}");
+                    continue;
+                }
+            };
+            debug!(target: "host", "Accepted preimage client session");
+
+            let host = self.clone();
+            let (remote_store, providers) = (remote_store.clone(), providers.clone());
+            let profiler = profiler.cloned();
+            task::spawn(async move {
+                match host.serve_session(channels, remote_store, providers, profiler).await {
+                    Ok(()) => debug!(target: "host", "Preimage client session closed"),
+                    Err(e) => warn!(target: "host", "Preimage client session failed: {e}"),
+                }
+            });
+        }
+    }
+
+    /// Serves a single client session accepted by the listener, once the client sent the
+    /// [SessionInputs] it proves.
+    async fn serve_session(
+        &self,
+        channels: SocketChannels,
+        remote_store: SyncKeyValueStore,
+        providers: Option<SingleChainProviders>,
+        profiler: Option<Arc<HostProfiler>>,
+    ) -> Result<(), SingleChainHostError> {
+        let inputs: SessionInputs = serde_json::from_slice(&channels.session.recv().await?)?;
+        debug!(
+            target: "host",
+            l1_head = %inputs.l1_head,
+            claim = %inputs.claimed_l2_output_root,
+            "Serving preimage client session"
+        );
+        let host = self.with_session_inputs(inputs);
+        let kv_store = host.create_session_key_value_store(remote_store);
+
+        let oracle_server = OracleServer::new(channels.preimage);
+        let hint_reader = HintReader::new(channels.hint);
+        if let Some(providers) = providers {
+            // The session's L1 head must be canonical on both L1 providers as well.
+            if let Some(cross_check) = providers.l1_cross_check.as_ref() {
+                for provider in [&providers.l1, cross_check] {
+                    ensure_l1_canonical(provider, inputs.l1_head)
+                        .await
+                        .map_err(SingleChainHostError::CrossCheck)?;
+                }
+            }
+            let backend = host.create_online_backend(kv_store, providers, profiler.as_ref());
+            PreimageServer::new(oracle_server, hint_reader, Arc::new(backend)).start().await?;
+        } else {
+            let backend = host.create_offline_backend(kv_store, profiler.as_ref());
+            PreimageServer::new(oracle_server, hint_reader, Arc::new(backend)).start().await?;
+        }
+        Ok(())
+    }
+
+    /// Starts the client program natively, communicating with a remote preimage server over a
+    /// socket. The [SessionInputs] of the host config are sent to the server first, so that it
+    /// serves the claim this client proves.
+    async fn start_client(&self, addr: &ChannelAddr) -> Result<(), SingleChainHostError> {
+        let channels = SocketChannels::connect(addr).await?;
+        channels.session.send(serde_json::to_vec(&self.session_inputs())?).await?;
+        let client_result = kona_client::single::run(
+            OracleReader::new(channels.preimage),
+            HintWriter::new(channels.hint),
+        )
+        .await;
+
+        // Bubble up the exit status of the client program if execution completes.
+        std::process::exit(client_result.is_err() as i32)
+    }
+
     /// Starts the host in native mode, running both the client and preimage server in the same
     /// process.
-    async fn start_native(&self) -> Result<(), SingleChainHostError> {
+    async fn start_native(
+        &self,
+        profiler: Option<&Arc<HostProfiler>>,
+        transcript: Option<&Arc<TranscriptWriter>>,
+    ) -> Result<(), SingleChainHostError> {
         let hint = BidirectionalChannel::new()?;
         let preimage = BidirectionalChannel::new()?;
 
-        let server_task = self.start_server(hint.host, preimage.host).await?;
+        let server_task = self.start_server(hint.host, preimage.host, profiler, transcript).await?;
         let client_task = task::spawn(kona_client::single::run(
             OracleReader::new(preimage.client),
             HintWriter::new(hint.client),
         ));
 
         let (_, client_result) = tokio::try_join!(server_task, client_task)?;
+        self.write_profile_report(profiler.map(Arc::as_ref))?;
+        if let Some(transcript) = transcript {
+            transcript.flush()?;
+        }
 
         // Bubble up the exit status of the client program if execution completes.
         std::process::exit(client_result.is_err() as i32)
@@ -241,6 +478,14 @@ impl SingleChainHost {
         serde_json::from_str(&ser_config).map_err(SingleChainHostError::ParseError)
     }
 
+    /// Returns the [RollupConfig] read from the file system, or the registry's config for the L2
+    /// chain ID, if either is available.
+    pub(crate) fn known_rollup_config(&self) -> Option<RollupConfig> {
+        self.read_rollup_config()
+            .ok()
+            .or_else(|| self.l2_chain_id.and_then(|id| ROLLUP_CONFIGS.get(&id).cloned()))
+    }
+
     /// Reads the [L1ChainConfig] from the file system and returns the deserialized configuration.
     pub fn read_l1_config(&self) -> Result<L1ChainConfig, SingleChainHostError> {
         let path = self.l1_config_path.as_ref().ok_or_else(|| SingleChainHostError::NoL1Config)?;
@@ -269,26 +514,153 @@ impl SingleChainHost {
         Ok(kv_store)
     }
 
+    /// Creates the remote key-value store shared by the sessions of a socket listener. Local
+    /// inputs are served by each session's store instead, see
+    /// [Self::create_session_key_value_store].
+    fn create_remote_key_value_store(&self) -> SyncKeyValueStore {
+        match self.data_dir.as_ref() {
+            Some(data_dir) => {
+                Arc::new(std::sync::RwLock::new(DiskKeyValueStore::new(data_dir.clone())))
+            }
+            None => Arc::new(std::sync::RwLock::new(MemoryKeyValueStore::new())),
+        }
+    }
+
+    /// Creates the key-value store of a client session, serving the local inputs of this config
+    /// over the shared remote store.
+    fn create_session_key_value_store(
+        &self,
+        remote_store: SyncKeyValueStore,
+    ) -> SharedKeyValueStore {
+        let local_kv_store = SingleChainLocalInputs::new(self.clone());
+        Arc::new(RwLock::new(SplitKeyValueStore::new(local_kv_store, remote_store)))
+    }
+
+    /// Creates the [OfflineHostBackend] for a client session.
+    fn create_offline_backend(
+        &self,
+        kv_store: SharedKeyValueStore,
+        profiler: Option<&Arc<HostProfiler>>,
+    ) -> OfflineHostBackend<dyn KeyValueStore + Send + Sync> {
+        let backend = OfflineHostBackend::new(kv_store);
+        match profiler {
+            Some(profiler) => backend.with_profile(profiler.session()),
+            None => backend,
+        }
+    }
+
+    /// Creates the [OnlineHostBackend] for a client session.
+    fn create_online_backend(
+        &self,
+        kv_store: SharedKeyValueStore,
+        providers: SingleChainProviders,
+        profiler: Option<&Arc<HostProfiler>>,
+    ) -> OnlineHostBackend<Self, SingleChainHintHandler> {
+        let mut policy = SingleChainPrefetchPolicy::default();
+        if let Some(rollup_config) =
+            self.enable_experimental_witness_endpoint.then(|| self.known_rollup_config()).flatten()
+        {
+            policy = policy.with_payload_witness(providers.l2.clone(), Arc::new(rollup_config));
+        }
+
+        let mut backend =
+            OnlineHostBackend::new(self.clone(), kv_store, providers, SingleChainHintHandler)
+                .with_proactive_hint(HintType::L2PayloadWitness);
+        if self.prefetch_concurrency > 0 {
+            backend = backend.with_prefetch_policy(policy, self.prefetch_concurrency);
+        }
+        match profiler {
+            Some(profiler) => backend.with_profile(profiler.session()),
+            None => backend,
+        }
+    }
+
+    /// Creates the [HostProfiler] if a profile report was requested.
+    pub fn create_profiler(&self) -> Option<Arc<HostProfiler>> {
+        if self.profile_report.is_none() {
+            return None;
+        }
+
+        let mut profiler = HostProfiler::new(self.profile_per_block);
+        if let Some(rollup_config) = self.known_rollup_config() {
+            profiler = profiler.with_rollup_config(rollup_config);
+        }
+        Some(Arc::new(profiler))
+    }
+
+    /// Creates the [TranscriptWriter] if a transcript recording was requested.
+    pub fn create_transcript(&self) -> Result<Option<Arc<TranscriptWriter>>, SingleChainHostError> {
+        self.transcript_record
+            .as_ref()
+            .map(|path| Ok(Arc::new(TranscriptWriter::create(path)?)))
+            .transpose()
+    }
+
+    /// Writes the report of the [HostProfiler] to the `--profile.report` path, if profiling is
+    /// enabled.
+    fn write_profile_report(
+        &self,
+        profiler: Option<&HostProfiler>,
+    ) -> Result<(), SingleChainHostError> {
+        if let (Some(profiler), Some(path)) = (profiler, self.profile_report.as_ref()) {
+            profiler.write_report(path)?;
+            info!(target: "host", "Wrote proof cost profile to {}", path.display());
+        }
+        Ok(())
+    }
+
     /// Creates the providers required for the host backend.
     pub async fn create_providers(&self) -> Result<SingleChainProviders, SingleChainHostError> {
-        let l1_provider = http_provider(
-            self.l1_node_address
-                .as_ref()
-                .ok_or(SingleChainHostError::Other("Provider must be set"))?,
-        );
-        let blob_provider = OnlineBlobProvider::init(OnlineBeaconClient::new_http(
-            self.l1_beacon_address
-                .clone()
-                .ok_or(SingleChainHostError::Other("Beacon API URL must be set"))?,
+        let endpoints = |address: Option<&String>, missing: &'static str| {
+            let endpoints = address.map(|a| parse_endpoints(a)).unwrap_or_default();
+            if endpoints.is_empty() {
+                return Err(SingleChainHostError::Other(missing));
+            }
+            Ok(endpoints)
+        };
+
+        let l1_provider = failover_provider(
+            &endpoints(self.l1_node_address.as_ref(), "Provider must be set")?,
+            self.rpc_rate_limit,
+        )?;
+        let mut blob_provider = OnlineBlobProvider::init(FailoverBeaconClient::new(
+            &endpoints(self.l1_beacon_address.as_ref(), "Beacon API URL must be set")?,
+            self.rpc_rate_limit,
         ))
         .await;
-        let l2_provider = http_provider::<Optimism>(
-            self.l2_node_address
-                .as_ref()
-                .ok_or(SingleChainHostError::Other("L2 node address must be set"))?,
-        );
+        if let Some(address) = self.l1_beacon_archiver_address.as_ref() {
+            blob_provider =
+                blob_provider.with_archive(Arc::new(OnlineBlobArchiver::new_http(address.clone())));
+        }
+        if let Some(dir) = self.l1_blob_dir.as_ref() {
+            blob_provider = blob_provider.with_archive(Arc::new(LocalBlobArchive::new(dir)));
+        }
+        let l2_provider = failover_provider::<Optimism>(
+            &endpoints(self.l2_node_address.as_ref(), "L2 node address must be set")?,
+            self.rpc_rate_limit,
+        )?;
+        let l1_cross_check = self
+            .l1_cross_check_address
+            .as_ref()
+            .map(|address| failover_provider(&[address.clone()], self.rpc_rate_limit))
+            .transpose()?;
+
+        // In cross-check mode, both L1 providers must agree that the L1 head is canonical. Every
+        // other L1 block the program requests is an ancestor of it.
+        if let Some(cross_check) = l1_cross_check.as_ref() {
+            for provider in [&l1_provider, cross_check] {
+                ensure_l1_canonical(provider, self.l1_head)
+                    .await
+                    .map_err(SingleChainHostError::CrossCheck)?;
+            }
+        }
 
-        Ok(SingleChainProviders { l1: l1_provider, blobs: blob_provider, l2: l2_provider })
+        Ok(SingleChainProviders {
+            l1: l1_provider,
+            l1_cross_check,
+            blobs: blob_provider,
+            l2: l2_provider,
+        })
     }
 }
 
@@ -302,17 +674,87 @@ impl OnlineHostBackendCfg for SingleChainHost {
 pub struct SingleChainProviders {
     /// The L1 EL provider.
     pub l1: RootProvider,
+    /// The L1 EL provider that L1 block headers are cross-checked against, if enabled.
+    pub l1_cross_check: Option<RootProvider>,
     /// The L1 beacon node provider.
-    pub blobs: OnlineBlobProvider<OnlineBeaconClient>,
+    pub blobs: OnlineBlobProvider<FailoverBeaconClient>,
     /// The L2 EL provider.
     pub l2: RootProvider<Optimism>,
 }
 
 #[cfg(test)]
 mod test {
-    use crate::single::SingleChainHost;
-    use alloy_primitives::B256;
+    use crate::{
+        KeyValueStore,
+        single::{SessionInputs, SingleChainHost},
+    };
+    use alloy_primitives::{B256, keccak256};
     use clap::Parser;
+    use kona_preimage::{
+        ChannelAddr, OracleReader, PreimageKey, PreimageKeyType, PreimageOracleClient,
+        SocketChannels, SocketListener,
+    };
+    use kona_proof::boot::{L1_HEAD_KEY, L2_CLAIM_BLOCK_NUMBER_KEY, L2_CLAIM_KEY};
+
+    #[tokio::test]
+    async fn test_listener_sessions_prove_own_claims() {
+        let data_dir = tempfile::tempdir().unwrap();
+        let host = SingleChainHost {
+            l2_chain_id: Some(10),
+            data_dir: Some(data_dir.path().to_path_buf()),
+            ..Default::default()
+        };
+
+        // A preimage warmed in the shared remote store is served to every session.
+        let remote_store = host.create_remote_key_value_store();
+        let preimage = b"shared".to_vec();
+        let shared_key = PreimageKey::new(*keccak256(&preimage), PreimageKeyType::Keccak256);
+        remote_store.clone().set(shared_key.into(), preimage.clone()).unwrap();
+
+        let listener =
+            SocketListener::bind(&"tcp:127.0.0.1:0".parse::<ChannelAddr>().unwrap()).await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let server = tokio::spawn(async move {
+            let _ = host.serve_listener(&listener, remote_store, None, None).await;
+        });
+
+        let sessions = [
+            SessionInputs {
+                l1_head: B256::repeat_byte(0x11),
+                claimed_l2_output_root: B256::repeat_byte(0x12),
+                claimed_l2_block_number: 1,
+                ..Default::default()
+            },
+            SessionInputs {
+                l1_head: B256::repeat_byte(0x21),
+                claimed_l2_output_root: B256::repeat_byte(0x22),
+                claimed_l2_block_number: 2,
+                ..Default::default()
+            },
+        ];
+        let mut clients = Vec::new();
+        for inputs in sessions {
+            let channels = SocketChannels::connect(&addr).await.unwrap();
+            channels.session.send(serde_json::to_vec(&inputs).unwrap()).await.unwrap();
+            clients.push((inputs, OracleReader::new(channels.preimage)));
+        }
+
+        // Read the sessions interleaved, so that they are served concurrently.
+        for (inputs, oracle) in clients.iter().rev() {
+            let claim = oracle.get(PreimageKey::new_local(L2_CLAIM_KEY)).await.unwrap();
+            assert_eq!(claim, inputs.claimed_l2_output_root.to_vec());
+        }
+        for (inputs, oracle) in &clients {
+            let l1_head = oracle.get(PreimageKey::new_local(L1_HEAD_KEY)).await.unwrap();
+            assert_eq!(l1_head, inputs.l1_head.to_vec());
+            let block_number =
+                oracle.get(PreimageKey::new_local(L2_CLAIM_BLOCK_NUMBER_KEY)).await.unwrap();
+            assert_eq!(block_number, inputs.claimed_l2_block_number.to_be_bytes().to_vec());
+            assert_eq!(oracle.get(shared_key).await.unwrap(), preimage);
+        }
+
+        server.abort();
+    }
 
     #[test]
     fn test_flags() {
@@ -364,8 +806,120 @@ mod test {
                 .as_slice(),
                 true,
             ),
+            (
+                [
+                    "--server.listen",
+                    "unix:/tmp/kona.sock",
+                    "--l2-chain-id",
+                    "0",
+                    "--data-dir",
+                    "dummy",
+                ]
+                .as_slice(),
+                true,
+            ),
+            (["--client.connect", "tcp:127.0.0.1:7300"].as_slice(), true),
+            (
+                [
+                    "--server",
+                    "--l2-chain-id",
+                    "0",
+                    "--data-dir",
+                    "dummy",
+                    "--profile.report",
+                    "profile.json",
+                    "--profile.per-block",
+                ]
+                .as_slice(),
+                true,
+            ),
+            (
+                [
+                    "--server",
+                    "--l2-chain-id",
+                    "0",
+                    "--data-dir",
+                    "dummy",
+                    "--prefetch.concurrency",
+                    "8",
+                ]
+                .as_slice(),
+                true,
+            ),
+            (
+                [
+                    "--l1-node-address",
+                    "http://a:8545,http://b:8545",
+                    "--l2-node-address",
+                    "dummy",
+                    "--l1-beacon-address",
+                    "dummy",
+                    "--rpc.rate-limit",
+                    "10",
+                    "--l1.cross-check-address",
+                    "http://c:8545",
+                    "--l1-beacon-archiver-address",
+                    "http://archiver:8080",
+                    "--l1-blob-dir",
+                    "blobs",
+                    "--server",
+                    "--l2-chain-id",
+                    "0",
+                ]
+                .as_slice(),
+                true,
+            ),
+            (
+                [
+                    "--native",
+                    "--l2-chain-id",
+                    "0",
+                    "--data-dir",
+                    "dummy",
+                    "--transcript.record",
+                    "transcript.jsonl",
+                ]
+                .as_slice(),
+                true,
+            ),
             // invalid
             (["--server", "--native", "--l2-chain-id", "0"].as_slice(), false),
+            (
+                [
+                    "--client.connect",
+                    "tcp:127.0.0.1:7300",
+                    "--transcript.record",
+                    "transcript.jsonl",
+                ]
+                .as_slice(),
+                false,
+            ),
+            (
+                ["--server", "--l2-chain-id", "0", "--data-dir", "dummy", "--rpc.rate-limit", "10"]
+                    .as_slice(),
+                false,
+            ),
+            (["--server.listen", "127.0.0.1:7300", "--l2-chain-id", "0"].as_slice(), false),
+            (
+                ["--server", "--l2-chain-id", "0", "--data-dir", "dummy", "--profile.per-block"]
+                    .as_slice(),
+                false,
+            ),
+            (
+                ["--client.connect", "tcp:127.0.0.1:7300", "--profile.report", "profile.json"]
+                    .as_slice(),
+                false,
+            ),
+            (
+                ["--server.listen", "tcp:127.0.0.1:7300", "--server", "--l2-chain-id", "0"]
+                    .as_slice(),
+                false,
+            ),
+            (
+                ["--client.connect", "tcp:127.0.0.1:7300", "--native", "--l2-chain-id", "0"]
+                    .as_slice(),
+                false,
+            ),
             (["--l2-chain-id", "0", "--rollup-config-path", "dummy", "--server"].as_slice(), false),
             (["--server"].as_slice(), false),
             (["--native"].as_slice(), false),
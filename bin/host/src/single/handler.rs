@@ -10,7 +10,7 @@ use alloy_eips::{
     eip4844::{BlobTransactionSidecarItem, FIELD_ELEMENTS_PER_BLOB, IndexedBlobHash},
 };
 use alloy_primitives::{Address, B256, Bytes, keccak256};
-use alloy_provider::Provider;
+use alloy_provider::{Provider, RootProvider};
 use alloy_rlp::Decodable;
 use alloy_rpc_types::{Block, debug::ExecutionWitness};
 use anyhow::{Result, anyhow, ensure};
@@ -44,6 +44,15 @@ impl HintHandler for SingleChainHintHandler {
                 let raw_header: Bytes =
                     providers.l1.client().request("debug_getRawHeader", [hash]).await?;
 
+                // In cross-check mode, require the header to be canonical on a second L1 provider.
+                if let Some(cross_check) = providers.l1_cross_check.as_ref() {
+                    ensure!(
+                        keccak256(&raw_header) == hash,
+                        "L1 header {hash} does not match its hash"
+                    );
+                    ensure_l1_canonical(cross_check, hash).await?;
+                }
+
                 let mut kv_lock = kv.write().await;
                 kv_lock.set(PreimageKey::new_keccak256(*hash).into(), raw_header.into())?;
             }
@@ -57,6 +66,9 @@ impl HintHandler for SingleChainHintHandler {
                     .full()
                     .await?
                     .ok_or(anyhow!("Block not found"))?;
+                if let Some(cross_check) = providers.l1_cross_check.as_ref() {
+                    ensure_l1_canonical(cross_check, hash).await?;
+                }
                 let encoded_transactions = transactions
                     .into_transactions()
                     .map(|tx| tx.inner.encoded_2718())
@@ -70,6 +82,9 @@ impl HintHandler for SingleChainHintHandler {
                 let hash: B256 = hint.data.as_ref().try_into()?;
                 let raw_receipts: Vec<Bytes> =
                     providers.l1.client().request("debug_getRawReceipts", [hash]).await?;
+                if let Some(cross_check) = providers.l1_cross_check.as_ref() {
+                    ensure_l1_canonical(cross_check, hash).await?;
+                }
 
                 store_ordered_trie(kv.as_ref(), raw_receipts.as_slice()).await?;
             }
@@ -382,3 +397,25 @@ impl HintHandler for SingleChainHintHandler {
         Ok(())
     }
 }
+
+/// Ensures that the L1 block with the given hash is known to the given provider, and that it is
+/// the provider's canonical block at its height.
+pub(crate) async fn ensure_l1_canonical(provider: &RootProvider, hash: B256) -> Result<()> {
+    let number = provider
+        .get_block_by_hash(hash)
+        .await?
+        .ok_or_else(|| anyhow!("L1 block {hash} not found"))?
+        .header
+        .number;
+    let canonical = provider
+        .get_block_by_number(number.into())
+        .await?
+        .ok_or_else(|| anyhow!("L1 block {number} not found"))?
+        .header
+        .hash;
+    ensure!(
+        canonical == hash,
+        "L1 block {hash} is not canonical, found {canonical} at height {number}"
+    );
+    Ok(())
+}
@@ -10,6 +10,51 @@ use kona_proof::boot::{
     L1_CONFIG_KEY, L1_HEAD_KEY, L2_CHAIN_ID_KEY, L2_CLAIM_BLOCK_NUMBER_KEY, L2_CLAIM_KEY,
     L2_OUTPUT_ROOT_KEY, L2_ROLLUP_CONFIG_KEY,
 };
+use serde::{Deserialize, Serialize};
+
+/// The inputs proven by a client session, sent by a `--client.connect` client to the
+/// `--server.listen` host it connects to.
+///
+/// The host serves every session with its own local inputs, built from its own config with these
+/// inputs substituted, over the key-value store shared by all sessions.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SessionInputs {
+    /// Hash of the L1 head block.
+    pub l1_head: B256,
+    /// Hash of the agreed upon safe L2 block.
+    pub agreed_l2_head_hash: B256,
+    /// Agreed safe L2 output root to start derivation from.
+    pub agreed_l2_output_root: B256,
+    /// Claimed L2 output root to validate.
+    pub claimed_l2_output_root: B256,
+    /// Number of the L2 block that the claimed output root commits to.
+    pub claimed_l2_block_number: u64,
+}
+
+impl SingleChainHost {
+    /// Returns the [SessionInputs] of the host config.
+    pub const fn session_inputs(&self) -> SessionInputs {
+        SessionInputs {
+            l1_head: self.l1_head,
+            agreed_l2_head_hash: self.agreed_l2_head_hash,
+            agreed_l2_output_root: self.agreed_l2_output_root,
+            claimed_l2_output_root: self.claimed_l2_output_root,
+            claimed_l2_block_number: self.claimed_l2_block_number,
+        }
+    }
+
+    /// Returns a copy of the host config proving the given [SessionInputs].
+    pub fn with_session_inputs(&self, inputs: SessionInputs) -> Self {
+        Self {
+            l1_head: inputs.l1_head,
+            agreed_l2_head_hash: inputs.agreed_l2_head_hash,
+            agreed_l2_output_root: inputs.agreed_l2_output_root,
+            claimed_l2_output_root: inputs.claimed_l2_output_root,
+            claimed_l2_block_number: inputs.claimed_l2_block_number,
+            ..self.clone()
+        }
+    }
+}
 
 /// A simple, synchronous key-value store that returns data from a [SingleChainHost] config.
 #[derive(Debug)]
@@ -4,7 +4,19 @@ mod cfg;
 pub use cfg::{SingleChainHost, SingleChainHostError, SingleChainProviders};
 
 mod local_kv;
-pub use local_kv::SingleChainLocalInputs;
+pub use local_kv::{SessionInputs, SingleChainLocalInputs};
 
 mod handler;
 pub use handler::SingleChainHintHandler;
+
+mod prefetch;
+pub use prefetch::SingleChainPrefetchPolicy;
+
+mod verify;
+pub use verify::{VerifyInputsCommand, VerifyInputsError};
+
+mod replay;
+pub use replay::{ReplayCommand, ReplayError};
+
+mod witness;
+pub use witness::{VerifyWitnessCommand, VerifyWitnessError};
@@ -0,0 +1,233 @@
+//! [PrefetchPolicy] for the [SingleChainHost].
+//!
+//! [SingleChainHost]: crate::single::SingleChainHost
+
+use crate::{PrefetchPolicy, SharedKeyValueStore};
+use alloy_consensus::Header;
+use alloy_eips::{eip2718::Encodable2718, eip4895::Withdrawal};
+use alloy_primitives::{B64, B256, Bytes};
+use alloy_provider::{Provider, RootProvider};
+use alloy_rlp::Decodable;
+use alloy_rpc_types_engine::PayloadAttributes;
+use async_trait::async_trait;
+use kona_genesis::RollupConfig;
+use kona_preimage::PreimageKey;
+use kona_proof::{Hint, HintType};
+use op_alloy_network::Optimism;
+use op_alloy_rpc_types_engine::OpPayloadAttributes;
+use std::sync::Arc;
+use tracing::debug;
+
+/// The [PrefetchPolicy] for the [SingleChainHost].
+///
+/// The client program walks block headers backwards from its starting point, and reads the
+/// transactions and receipts of the blocks it visits. After a block header has been fetched, this
+/// policy prefetches the block's transactions, its receipts (on L1), and its parent header. If
+/// enabled with [Self::with_payload_witness], the execution witness of L2 blocks is prefetched as
+/// well.
+///
+/// [SingleChainHost]: crate::single::SingleChainHost
+#[derive(Debug, Clone, Default)]
+pub struct SingleChainPrefetchPolicy {
+    /// The L2 provider and rollup config used to build the payload witness hints of L2 blocks, if
+    /// enabled.
+    witness: Option<(RootProvider<Optimism>, Arc<RollupConfig>)>,
+}
+
+impl SingleChainPrefetchPolicy {
+    /// Enables prefetching the execution witness of L2 blocks, by re-executing them with the
+    /// payload attributes built from the blocks fetched from `l2`.
+    pub fn with_payload_witness(
+        mut self,
+        l2: RootProvider<Optimism>,
+        rollup_config: Arc<RollupConfig>,
+    ) -> Self {
+        self.witness = Some((l2, rollup_config));
+        self
+    }
+
+    /// Returns the [HintType::L2PayloadWitness] hint for executing the L2 block with the given
+    /// hash and header on top of its parent, if payload witness prefetching is enabled.
+    async fn payload_witness(&self, hash: B256, header: &Header) -> Option<Hint<HintType>> {
+        let (l2, rollup_config) = self.witness.as_ref()?;
+        let block = match l2.get_block_by_hash(hash).full().await {
+            Ok(block) => block?,
+            Err(e) => {
+                debug!(target: "host_prefetch", "Failed to fetch L2 block {hash}: {e}");
+                return None;
+            }
+        };
+
+        let transactions = block
+            .transactions
+            .into_transactions()
+            .map(|tx| tx.inner.inner.encoded_2718().into())
+            .collect();
+        let withdrawals = block.withdrawals.map(|withdrawals| withdrawals.to_vec());
+        Some(payload_witness_hint(rollup_config, header, transactions, withdrawals))
+    }
+}
+
+#[async_trait]
+impl PrefetchPolicy for SingleChainPrefetchPolicy {
+    type HintType = HintType;
+
+    async fn follow_ups(
+        &self,
+        hint: &Hint<HintType>,
+        kv: &SharedKeyValueStore,
+    ) -> Vec<Hint<HintType>> {
+        let Ok(hash) = B256::try_from(hint.data.as_ref()) else {
+            return Vec::new();
+        };
+
+        let mut follow_ups = match hint.ty {
+            HintType::L1BlockHeader => vec![
+                HintType::L1Transactions.with_data(&[hash.as_slice()]),
+                HintType::L1Receipts.with_data(&[hash.as_slice()]),
+            ],
+            HintType::L2BlockHeader => {
+                vec![HintType::L2Transactions.with_data(&[hash.as_slice()])]
+            }
+            _ => return Vec::new(),
+        };
+
+        // Walk back to the parent header, which the client program is likely to request next.
+        let raw_header = kv.read().await.get(PreimageKey::new_keccak256(*hash).into());
+        let header = raw_header
+            .and_then(|raw| Header::decode(&mut raw.as_slice()).ok())
+            .filter(|header| header.number > 0);
+        if let Some(header) = header {
+            follow_ups.push(hint.ty.with_data(&[header.parent_hash.as_slice()]));
+
+            if hint.ty == HintType::L2BlockHeader {
+                follow_ups.extend(self.payload_witness(hash, &header).await);
+            }
+        }
+
+        follow_ups
+    }
+}
+
+/// Builds the [HintType::L2PayloadWitness] hint for executing the block with the given header,
+/// transactions and withdrawals on top of its parent.
+fn payload_witness_hint(
+    rollup_config: &RollupConfig,
+    header: &Header,
+    transactions: Vec<Bytes>,
+    withdrawals: Option<Vec<Withdrawal>>,
+) -> Hint<HintType> {
+    let attributes = OpPayloadAttributes {
+        payload_attributes: PayloadAttributes {
+            timestamp: header.timestamp,
+            prev_randao: header.mix_hash,
+            suggested_fee_recipient: header.beneficiary,
+            withdrawals,
+            parent_beacon_block_root: header.parent_beacon_block_root,
+        },
+        transactions: Some(transactions),
+        no_tx_pool: Some(true),
+        gas_limit: Some(header.gas_limit),
+        // The EIP-1559 parameters are the bytes 1-9 of the extra data after Holocene, followed by
+        // the minimum base fee after Jovian.
+        eip_1559_params: rollup_config
+            .is_holocene_active(header.timestamp)
+            .then(|| header.extra_data.get(1..9).map(B64::from_slice))
+            .flatten(),
+        min_base_fee: rollup_config
+            .is_jovian_active(header.timestamp)
+            .then(|| header.extra_data.get(9..17).and_then(|b| b.try_into().ok()))
+            .flatten()
+            .map(u64::from_be_bytes),
+    };
+
+    let mut data = header.parent_hash.to_vec();
+    data.extend(serde_json::to_vec(&attributes).expect("payload attributes serialize"));
+    HintType::L2PayloadWitness.with_data(&[data.as_slice()])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MemoryKeyValueStore;
+    use alloy_primitives::address;
+    use alloy_rlp::Encodable;
+    use kona_genesis::HardForkConfig;
+    use tokio::sync::RwLock;
+
+    #[tokio::test]
+    async fn test_l1_header_follow_ups() {
+        let header =
+            Header { number: 10, parent_hash: B256::repeat_byte(0xaa), ..Default::default() };
+        let mut raw_header = Vec::new();
+        header.encode(&mut raw_header);
+        let hash = header.hash_slow();
+
+        let kv: SharedKeyValueStore = Arc::new(RwLock::new(MemoryKeyValueStore::new()));
+        let hint = HintType::L1BlockHeader.with_data(&[hash.as_slice()]);
+
+        // Without the header in the store, the parent can not be predicted.
+        let follow_ups = SingleChainPrefetchPolicy::default().follow_ups(&hint, &kv).await;
+        assert_eq!(
+            follow_ups.iter().map(|h| h.ty).collect::<Vec<_>>(),
+            [HintType::L1Transactions, HintType::L1Receipts]
+        );
+        assert!(follow_ups.iter().all(|h| h.data.as_ref() == hash.as_slice()));
+
+        kv.write().await.set(PreimageKey::new_keccak256(*hash).into(), raw_header).unwrap();
+        let follow_ups = SingleChainPrefetchPolicy::default().follow_ups(&hint, &kv).await;
+        assert_eq!(follow_ups.len(), 3);
+        assert_eq!(follow_ups[2].ty, HintType::L1BlockHeader);
+        assert_eq!(follow_ups[2].data.as_ref(), header.parent_hash.as_slice());
+    }
+
+    #[tokio::test]
+    async fn test_l2_header_follow_ups() {
+        let kv: SharedKeyValueStore = Arc::new(RwLock::new(MemoryKeyValueStore::new()));
+        let hint = HintType::L2BlockHeader.with_data(&[B256::ZERO.as_slice()]);
+
+        let follow_ups = SingleChainPrefetchPolicy::default().follow_ups(&hint, &kv).await;
+        assert_eq!(follow_ups.len(), 1);
+        assert_eq!(follow_ups[0].ty, HintType::L2Transactions);
+    }
+
+    #[test]
+    fn test_payload_witness_hint() {
+        let rollup_config = RollupConfig {
+            hardforks: HardForkConfig { holocene_time: Some(0), ..Default::default() },
+            ..Default::default()
+        };
+        let header = Header {
+            number: 10,
+            parent_hash: B256::repeat_byte(0xaa),
+            beneficiary: address!("0x4200000000000000000000000000000000000011"),
+            timestamp: 20,
+            gas_limit: 30_000_000,
+            extra_data: [0, 0, 0, 0, 250, 0, 0, 0, 6].into(),
+            ..Default::default()
+        };
+        let transactions = vec![Bytes::from_static(&[0x7e, 0x01])];
+
+        let hint = payload_witness_hint(&rollup_config, &header, transactions.clone(), None);
+        assert_eq!(hint.ty, HintType::L2PayloadWitness);
+        assert_eq!(&hint.data[..32], header.parent_hash.as_slice());
+
+        let attributes: OpPayloadAttributes = serde_json::from_slice(&hint.data[32..]).unwrap();
+        assert_eq!(attributes.payload_attributes.timestamp, header.timestamp);
+        assert_eq!(attributes.payload_attributes.suggested_fee_recipient, header.beneficiary);
+        assert_eq!(attributes.transactions, Some(transactions));
+        assert_eq!(attributes.gas_limit, Some(header.gas_limit));
+        assert_eq!(attributes.eip_1559_params, Some(B64::from_slice(&header.extra_data[1..9])));
+        assert_eq!(attributes.min_base_fee, None);
+    }
+
+    #[tokio::test]
+    async fn test_no_follow_ups() {
+        let kv: SharedKeyValueStore = Arc::new(RwLock::new(MemoryKeyValueStore::new()));
+        let code = HintType::L2Code.with_data(&[B256::ZERO.as_slice()]);
+        assert!(SingleChainPrefetchPolicy::default().follow_ups(&code, &kv).await.is_empty());
+
+        let malformed = HintType::L1BlockHeader.with_data(&[&[0u8; 8]]);
+        assert!(SingleChainPrefetchPolicy::default().follow_ups(&malformed, &kv).await.is_empty());
+    }
+}
@@ -0,0 +1,222 @@
+//! This module contains the `replay` command, which serves a recorded oracle transcript to the
+//! single-chain client program.
+
+use crate::{PreimageServer, PreimageServerError, TranscriptReplayer};
+use clap::Parser;
+use kona_cli::cli_styles;
+use kona_preimage::{
+    BidirectionalChannel, Channel, ChannelAddr, HintReader, HintWriter, OracleReader, OracleServer,
+    SocketListener,
+};
+use kona_std_fpvm::{FileChannel, FileDescriptor};
+use serde::Serialize;
+use std::{path::PathBuf, sync::Arc};
+use tokio::task;
+use tracing::info;
+
+/// The `replay` command, which serves an oracle transcript recorded with `--transcript.record` to
+/// the single-chain client program, without any providers or data directory.
+///
+/// By default, the client program is run natively. With `--server` or `--server.listen`, the
+/// transcript is served to an external client program instead, such as a build for another
+/// target running in an emulator. The replay fails on the first hint or preimage request that
+/// deviates from the transcript in order or key, which makes it possible to bisect
+/// non-determinism between client builds.
+#[derive(Default, Parser, Serialize, Clone, Debug)]
+#[command(styles = cli_styles())]
+pub struct ReplayCommand {
+    /// Path to the oracle transcript to replay.
+    #[arg(long, value_name = "PATH", env)]
+    pub transcript: PathBuf,
+    /// Serve the transcript over the preimage and hint file descriptors to a client program in
+    /// the parent process, instead of running the client program natively.
+    #[arg(long, conflicts_with = "server_listen")]
+    pub server: bool,
+    /// Serve the transcript to the first client program that connects to the given socket
+    /// address (`unix:<path>` or `tcp:<host>:<port>`), such as a `single --client.connect` client.
+    #[arg(long = "server.listen", value_name = "ADDR", conflicts_with = "server", env)]
+    pub server_listen: Option<ChannelAddr>,
+}
+
+/// An error that can occur when replaying an oracle transcript.
+#[derive(Debug, thiserror::Error)]
+pub enum ReplayError {
+    /// An IO error.
+    #[error("IO error: {0}")]
+    IOError(#[from] std::io::Error),
+    /// The client program deviated from the transcript.
+    #[error("Client deviated from the transcript: {0}")]
+    Deviation(String),
+    /// The client program failed without deviating from the transcript.
+    #[error("Client program failed: {0}")]
+    ClientError(#[from] kona_client::single::FaultProofProgramError),
+    /// The preimage server failed without the client deviating from the transcript.
+    #[error("Preimage server failed: {0}")]
+    ServerError(#[from] PreimageServerError),
+    /// Task failed to execute to completion.
+    #[error("Join error: {0}")]
+    ExecutionError(#[from] tokio::task::JoinError),
+}
+
+impl ReplayCommand {
+    /// Replays the transcript to the client program.
+    pub async fn run(self) -> Result<(), ReplayError> {
+        let replayer = Arc::new(TranscriptReplayer::open(&self.transcript)?);
+
+        if self.server {
+            let hint = FileChannel::new(FileDescriptor::HintRead, FileDescriptor::HintWrite);
+            let preimage =
+                FileChannel::new(FileDescriptor::PreimageRead, FileDescriptor::PreimageWrite);
+            Self::serve(hint, preimage, replayer).await
+        } else if let Some(addr) = self.server_listen.as_ref() {
+            let listener = SocketListener::bind(addr).await?;
+            info!(target: "host", "Listening for the client program on {addr}");
+            Self::serve_listener(&listener, replayer).await
+        } else {
+            Self::run_native(replayer).await
+        }
+    }
+
+    /// Serves the transcript to the first client program that connects to the listener.
+    async fn serve_listener(
+        listener: &SocketListener,
+        replayer: Arc<TranscriptReplayer>,
+    ) -> Result<(), ReplayError> {
+        // The session inputs sent by the client are not needed, as the transcript already holds
+        // the local inputs it reads.
+        let channels = listener.accept().await?;
+        Self::serve(channels.hint, channels.preimage, replayer).await
+    }
+
+    /// Serves the transcript to an external client program over the given channels, until the
+    /// client program disconnects or deviates from the transcript.
+    async fn serve<C>(
+        hint: C,
+        preimage: C,
+        replayer: Arc<TranscriptReplayer>,
+    ) -> Result<(), ReplayError>
+    where
+        C: Channel + Send + Sync + 'static,
+    {
+        let result = PreimageServer::new(
+            OracleServer::new(preimage),
+            HintReader::new(hint),
+            replayer.clone(),
+        )
+        .start()
+        .await;
+
+        if let Some(deviation) = replayer.deviation() {
+            return Err(ReplayError::Deviation(deviation));
+        }
+        result?;
+
+        info!(target: "host", "Client program matched the transcript");
+        Ok(())
+    }
+
+    /// Runs the client program natively against the transcript.
+    async fn run_native(replayer: Arc<TranscriptReplayer>) -> Result<(), ReplayError> {
+        let hint = BidirectionalChannel::new()?;
+        let preimage = BidirectionalChannel::new()?;
+
+        let server_task = task::spawn(
+            PreimageServer::new(
+                OracleServer::new(preimage.host),
+                HintReader::new(hint.host),
+                replayer.clone(),
+            )
+            .start(),
+        );
+        let client_task = task::spawn(kona_client::single::run(
+            OracleReader::new(preimage.client),
+            HintWriter::new(hint.client),
+        ));
+        let (_, client_result) = tokio::try_join!(server_task, client_task)?;
+
+        if let Some(deviation) = replayer.deviation() {
+            return Err(ReplayError::Deviation(deviation));
+        }
+        client_result?;
+
+        info!(target: "host", "Client program matched the transcript");
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::TranscriptEntry;
+    use alloy_primitives::{B256, Bytes};
+    use kona_preimage::{HintWriterClient, PreimageKey, PreimageOracleClient, SocketChannels};
+
+    /// Binds a listener on an ephemeral port, and serves the transcript to the first client that
+    /// connects to it.
+    async fn spawn_listener(
+        entries: Vec<TranscriptEntry>,
+    ) -> (ChannelAddr, tokio::task::JoinHandle<Result<(), ReplayError>>) {
+        let listener = SocketListener::bind(&"tcp:127.0.0.1:0".parse().unwrap()).await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let replayer = Arc::new(TranscriptReplayer::new(entries));
+        let server =
+            task::spawn(async move { ReplayCommand::serve_listener(&listener, replayer).await });
+        (addr, server)
+    }
+
+    fn transcript() -> (PreimageKey, Vec<TranscriptEntry>) {
+        let key = PreimageKey::new_keccak256([1; 32]);
+        let entries = vec![
+            TranscriptEntry::Hint("l1-block-header 0x01".to_string()),
+            TranscriptEntry::Preimage { key: B256::from(key), value: Bytes::from_static(&[0xaa]) },
+        ];
+        (key, entries)
+    }
+
+    #[tokio::test]
+    async fn test_serves_external_client() {
+        let (key, entries) = transcript();
+        let (addr, server) = spawn_listener(entries).await;
+
+        let SocketChannels { hint, preimage, .. } = SocketChannels::connect(&addr).await.unwrap();
+        let (hints, oracle) = (HintWriter::new(hint), OracleReader::new(preimage));
+        hints.write("l1-block-header 0x01").await.unwrap();
+        assert_eq!(oracle.get(key).await.unwrap(), vec![0xaa]);
+        drop((hints, oracle));
+
+        server.await.unwrap().unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_fails_on_first_deviation() {
+        let (key, entries) = transcript();
+        let (addr, server) = spawn_listener(entries).await;
+
+        // Requesting the preimage before the recorded hint deviates at the first entry, and the
+        // server stops serving the client without answering the request.
+        let SocketChannels { preimage, .. } = SocketChannels::connect(&addr).await.unwrap();
+        let request = task::spawn(async move { OracleReader::new(preimage).get(key).await });
+
+        match server.await.unwrap() {
+            Err(ReplayError::Deviation(deviation)) => {
+                assert!(deviation.contains("entry 0"), "{deviation}")
+            }
+            result => panic!("expected a deviation, got {result:?}"),
+        }
+        request.abort();
+    }
+
+    #[tokio::test]
+    async fn test_fails_on_unconsumed_transcript() {
+        let (_, entries) = transcript();
+        let (addr, server) = spawn_listener(entries).await;
+
+        // A client that disconnects before the end of the transcript deviates from it as well.
+        let SocketChannels { hint, .. } = SocketChannels::connect(&addr).await.unwrap();
+        let hints = HintWriter::new(hint);
+        hints.write("l1-block-header 0x01").await.unwrap();
+        drop(hints);
+
+        assert!(matches!(server.await.unwrap(), Err(ReplayError::Deviation(_))));
+    }
+}
@@ -0,0 +1,495 @@
+//! This module contains the `verify-inputs` preflight command, which checks the inputs of a
+//! single-chain proof against the L1 and L2 chains before the proof is started.
+
+use crate::eth::{failover_provider, parse_endpoints};
+use alloy_consensus::{EMPTY_ROOT_HASH, Header};
+use alloy_eips::BlockNumberOrTag;
+use alloy_primitives::{B256, Bytes};
+use alloy_provider::{Network, Provider, RootProvider};
+use alloy_rlp::Decodable;
+use alloy_transport::{RpcError, TransportErrorKind};
+use clap::Parser;
+use kona_cli::cli_styles;
+use kona_genesis::RollupConfig;
+use kona_protocol::{OutputRoot, Predeploys};
+use kona_registry::ROLLUP_CONFIGS;
+use op_alloy_network::Optimism;
+use op_revm::OpSpecId;
+use serde::Serialize;
+use std::path::PathBuf;
+use tracing::{error, info};
+
+/// The `verify-inputs` command, which checks everything that the client program's boot info and
+/// driver rely on before starting a single-chain proof.
+///
+/// Misconfigured inputs otherwise only surface as opaque client errors, deep into a proof.
+#[derive(Default, Parser, Serialize, Clone, Debug)]
+#[command(styles = cli_styles())]
+pub struct VerifyInputsCommand {
+    /// Hash of the L1 head block. Derivation stops after this block is processed.
+    #[arg(long, env)]
+    pub l1_head: B256,
+    /// Hash of the agreed upon safe L2 block committed to by `--agreed-l2-output-root`.
+    #[arg(long, visible_alias = "l2-head", env)]
+    pub agreed_l2_head_hash: B256,
+    /// Agreed safe L2 Output Root to start derivation from.
+    #[arg(long, visible_alias = "l2-output-root", env)]
+    pub agreed_l2_output_root: B256,
+    /// Claimed L2 output root at block # `--claimed-l2-block-number` to validate.
+    #[arg(long, visible_alias = "l2-claim", env)]
+    pub claimed_l2_output_root: B256,
+    /// Number of the L2 block that the claimed output root commits to.
+    #[arg(long, visible_alias = "l2-block-number", env)]
+    pub claimed_l2_block_number: u64,
+    /// Address of L2 JSON-RPC endpoint to use (eth and debug namespace required). Accepts a
+    /// comma-separated list of endpoints, which are failed over between in order of preference.
+    #[arg(long, visible_alias = "l2", env)]
+    pub l2_node_address: String,
+    /// Address of L1 JSON-RPC endpoint to use (eth and debug namespace required). Accepts a
+    /// comma-separated list of endpoints, which are failed over between in order of preference.
+    #[arg(long, visible_alias = "l1", env)]
+    pub l1_node_address: String,
+    /// The L2 chain ID of a supported chain. If provided, the command will look for the
+    /// corresponding rollup config in the superchain registry.
+    #[arg(
+        long,
+        conflicts_with = "rollup_config_path",
+        required_unless_present = "rollup_config_path",
+        env
+    )]
+    pub l2_chain_id: Option<u64>,
+    /// Path to rollup config. If provided, the command will use this config instead of attempting
+    /// to look up the config in the superchain registry.
+    #[arg(
+        long,
+        alias = "rollup-cfg",
+        conflicts_with = "l2_chain_id",
+        required_unless_present = "l2_chain_id",
+        env
+    )]
+    pub rollup_config_path: Option<PathBuf>,
+}
+
+/// A failed preflight check, or an error that prevented a check from running.
+#[derive(Debug, thiserror::Error)]
+pub enum VerifyInputsError {
+    /// The L1 head block does not exist.
+    #[error("L1 head {0} not found")]
+    L1HeadNotFound(B256),
+    /// The L1 head block is not part of the canonical L1 chain.
+    #[error("L1 head {hash} is not canonical; block #{number} is {canonical}")]
+    L1HeadNotCanonical {
+        /// The hash of the L1 head.
+        hash: B256,
+        /// The number of the L1 head.
+        number: u64,
+        /// The hash of the canonical L1 block at the same height.
+        canonical: B256,
+    },
+    /// The agreed L2 head block does not exist.
+    #[error("Agreed L2 head {0} not found")]
+    L2HeadNotFound(B256),
+    /// A header returned by a provider does not match its hash.
+    #[error("Header returned for {0} does not match its hash")]
+    InvalidHeader(B256),
+    /// The agreed output root does not commit to the agreed L2 head.
+    #[error("Agreed output root {agreed} does not match output root {computed} of the L2 head")]
+    OutputRootMismatch {
+        /// The agreed output root.
+        agreed: B256,
+        /// The output root computed from the L2 head.
+        computed: B256,
+    },
+    /// The claimed block number is behind the agreed L2 head.
+    #[error("Claimed L2 block #{claimed} is behind the agreed L2 head #{agreed}")]
+    ClaimBehindAgreedHead {
+        /// The claimed L2 block number.
+        claimed: u64,
+        /// The number of the agreed L2 head.
+        agreed: u64,
+    },
+    /// The claim targets the agreed L2 head, but claims a different output root.
+    #[error("Claim targets the agreed L2 head #{0}, but the claimed output root differs")]
+    TraceExtensionMismatch(u64),
+    /// The rollup config's hardforks disagree with the L2 chain.
+    #[error("Rollup config expects {spec} at L2 block #{number}, but the header {reason}")]
+    HardforkMismatch {
+        /// The spec of the rollup config at the block.
+        spec: String,
+        /// The number of the block.
+        number: u64,
+        /// The header field that disagrees with the spec.
+        reason: &'static str,
+    },
+    /// No rollup config could be found.
+    #[error("No rollup config found")]
+    NoRollupConfig,
+    /// One or more checks failed.
+    #[error("{0} preflight check(s) failed")]
+    ChecksFailed(usize),
+    /// An invalid provider endpoint URL.
+    #[error("Invalid endpoint URL: {0}")]
+    InvalidEndpoint(#[from] url::ParseError),
+    /// A RPC error.
+    #[error("Rpc Error: {0}")]
+    RpcError(#[from] RpcError<TransportErrorKind>),
+    /// An IO error.
+    #[error("IO error: {0}")]
+    IOError(#[from] std::io::Error),
+    /// A JSON parse error.
+    #[error("Failed deserializing RollupConfig: {0}")]
+    ParseError(#[from] serde_json::Error),
+    /// An RLP decoding error.
+    #[error("RLP error: {0}")]
+    RlpError(#[from] alloy_rlp::Error),
+}
+
+impl VerifyInputsCommand {
+    /// Runs every preflight check, logging the outcome of each, and fails if any check failed.
+    pub async fn run(self) -> Result<(), VerifyInputsError> {
+        let rollup_config = self.read_rollup_config()?;
+        let l1 = failover_provider(&parse_endpoints(&self.l1_node_address), None)?;
+        let l2 = failover_provider::<Optimism>(&parse_endpoints(&self.l2_node_address), None)?;
+
+        let l2_head = raw_header(&l2, self.agreed_l2_head_hash)
+            .await?
+            .ok_or(VerifyInputsError::L2HeadNotFound(self.agreed_l2_head_hash))?;
+
+        let checks = [
+            ("l1-head", self.check_l1_head(&l1).await),
+            ("agreed-output-root", self.check_output_root(&l2, &l2_head).await),
+            ("claimed-block-number", self.check_claimed_block_number(&l2_head)),
+            ("hardforks", check_hardforks(&rollup_config, &l2_head)),
+        ];
+
+        let mut failed = 0;
+        for (check, result) in checks {
+            match result {
+                Ok(()) => info!(target: "host", check, "Preflight check passed"),
+                Err(e) => {
+                    error!(target: "host", check, "Preflight check failed: {e}");
+                    failed += 1;
+                }
+            }
+        }
+
+        if failed > 0 {
+            return Err(VerifyInputsError::ChecksFailed(failed));
+        }
+        info!(target: "host", "All preflight checks passed");
+        Ok(())
+    }
+
+    /// Checks that the L1 head exists and is part of the canonical L1 chain.
+    async fn check_l1_head(&self, l1: &RootProvider) -> Result<(), VerifyInputsError> {
+        let l1_head = raw_header(l1, self.l1_head)
+            .await?
+            .ok_or(VerifyInputsError::L1HeadNotFound(self.l1_head))?;
+
+        let canonical = l1
+            .get_block_by_number(BlockNumberOrTag::Number(l1_head.number))
+            .await?
+            .map(|block| block.header.hash)
+            .unwrap_or_default();
+        if canonical != self.l1_head {
+            return Err(VerifyInputsError::L1HeadNotCanonical {
+                hash: self.l1_head,
+                number: l1_head.number,
+                canonical,
+            });
+        }
+        Ok(())
+    }
+
+    /// Checks that the agreed output root matches the output root of the agreed L2 head, computed
+    /// from its state root and the storage root of the L2-to-L1 message passer.
+    async fn check_output_root(
+        &self,
+        l2: &RootProvider<Optimism>,
+        l2_head: &Header,
+    ) -> Result<(), VerifyInputsError> {
+        let message_passer = l2
+            .get_proof(Predeploys::L2_TO_L1_MESSAGE_PASSER, Vec::new())
+            .block_id(self.agreed_l2_head_hash.into())
+            .await?;
+
+        let computed = OutputRoot::from_parts(
+            l2_head.state_root,
+            message_passer.storage_hash,
+            self.agreed_l2_head_hash,
+        )
+        .hash();
+        if computed != self.agreed_l2_output_root {
+            return Err(VerifyInputsError::OutputRootMismatch {
+                agreed: self.agreed_l2_output_root,
+                computed,
+            });
+        }
+        Ok(())
+    }
+
+    /// Checks that the claimed L2 block is ahead of the agreed L2 head. A claim on the agreed head
+    /// itself is a trace extension, which is only valid if it claims the agreed output root.
+    fn check_claimed_block_number(&self, l2_head: &Header) -> Result<(), VerifyInputsError> {
+        if self.claimed_l2_block_number < l2_head.number {
+            return Err(VerifyInputsError::ClaimBehindAgreedHead {
+                claimed: self.claimed_l2_block_number,
+                agreed: l2_head.number,
+            });
+        }
+        if self.claimed_l2_block_number == l2_head.number &&
+            self.claimed_l2_output_root != self.agreed_l2_output_root
+        {
+            return Err(VerifyInputsError::TraceExtensionMismatch(l2_head.number));
+        }
+        Ok(())
+    }
+
+    /// Reads the [RollupConfig] from the file system, or the superchain registry.
+    fn read_rollup_config(&self) -> Result<RollupConfig, VerifyInputsError> {
+        if let Some(path) = self.rollup_config_path.as_ref() {
+            let ser_config = std::fs::read_to_string(path)?;
+            return Ok(serde_json::from_str(&ser_config)?);
+        }
+        self.l2_chain_id
+            .and_then(|id| ROLLUP_CONFIGS.get(&id).cloned())
+            .ok_or(VerifyInputsError::NoRollupConfig)
+    }
+}
+
+/// Returns the spec whose header fields the executor produces at `timestamp`.
+///
+/// Mantle chains produce the Ecotone and Isthmus header fields from Skadi on, and bedrock headers
+/// before it.
+fn header_spec_id(cfg: &RollupConfig, timestamp: u64) -> OpSpecId {
+    if cfg.is_mantle() && !cfg.is_mantle_skadi_active(timestamp) {
+        return OpSpecId::BEDROCK;
+    }
+    cfg.revm_spec_id(timestamp)
+}
+
+/// Checks that the header fields introduced by each hardfork are present exactly when the rollup
+/// config activates the hardfork, as the executor produces headers according to the config.
+fn check_hardforks(cfg: &RollupConfig, header: &Header) -> Result<(), VerifyInputsError> {
+    let ts = header.timestamp;
+    let spec = header_spec_id(cfg, ts);
+    let expectations = [
+        (
+            spec.is_enabled_in(OpSpecId::CANYON),
+            header.withdrawals_root.is_some(),
+            "has no withdrawals root",
+            "has a withdrawals root",
+        ),
+        (
+            spec.is_enabled_in(OpSpecId::ECOTONE),
+            header.parent_beacon_block_root.is_some(),
+            "has no parent beacon block root",
+            "has a parent beacon block root",
+        ),
+        (
+            spec.is_enabled_in(OpSpecId::ECOTONE),
+            header.blob_gas_used.is_some(),
+            "has no blob gas used",
+            "has blob gas used",
+        ),
+        // Mantle only encodes the EIP-1559 parameters into the extra data from Arsia on, which
+        // the Holocene activation of the config accounts for.
+        (
+            cfg.is_holocene_active(ts),
+            !header.extra_data.is_empty(),
+            "has no EIP-1559 parameters in its extra data",
+            "has extra data",
+        ),
+        (
+            spec.is_enabled_in(OpSpecId::ISTHMUS),
+            header.requests_hash.is_some(),
+            "has no requests hash",
+            "has a requests hash",
+        ),
+    ];
+
+    let mismatch =
+        expectations.into_iter().find_map(|(active, present, missing, unexpected)| {
+            match (active, present) {
+                (true, false) => Some(missing),
+                (false, true) => Some(unexpected),
+                _ => None,
+            }
+        });
+    // Before Isthmus, the withdrawals root of a Canyon header is always the empty root.
+    let mismatch = mismatch.or_else(|| {
+        (spec.is_enabled_in(OpSpecId::CANYON) &&
+            !spec.is_enabled_in(OpSpecId::ISTHMUS) &&
+            header.withdrawals_root != Some(EMPTY_ROOT_HASH))
+        .then_some("has a non-empty withdrawals root")
+    });
+
+    match mismatch {
+        Some(reason) => Err(VerifyInputsError::HardforkMismatch {
+            spec: format!("{spec:?}"),
+            number: header.number,
+            reason,
+        }),
+        None => Ok(()),
+    }
+}
+
+/// Fetches the raw header with the given hash, verifying it against the hash. Returns `None` if
+/// the provider does not know the header.
+async fn raw_header<N: Network>(
+    provider: &RootProvider<N>,
+    hash: B256,
+) -> Result<Option<Header>, VerifyInputsError> {
+    let raw: Option<Bytes> = provider.client().request("debug_getRawHeader", [hash]).await?;
+    let Some(raw) = raw.filter(|raw| !raw.is_empty()) else {
+        return Ok(None);
+    };
+
+    let header = Header::decode(&mut raw.as_ref())?;
+    if header.hash_slow() != hash {
+        return Err(VerifyInputsError::InvalidHeader(hash));
+    }
+    Ok(Some(header))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use kona_genesis::{HardForkConfig, MantleHardForkConfig};
+
+    fn header(timestamp: u64) -> Header {
+        Header { timestamp, number: 10, ..Default::default() }
+    }
+
+    #[test]
+    fn test_check_hardforks() {
+        let cfg = RollupConfig {
+            hardforks: HardForkConfig {
+                regolith_time: Some(0),
+                canyon_time: Some(10),
+                ecotone_time: Some(20),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        assert!(check_hardforks(&cfg, &header(5)).is_ok());
+
+        let mut canyon = header(10);
+        assert!(check_hardforks(&cfg, &canyon).is_err());
+        canyon.withdrawals_root = Some(EMPTY_ROOT_HASH);
+        assert!(check_hardforks(&cfg, &canyon).is_ok());
+        canyon.withdrawals_root = Some(B256::repeat_byte(1));
+        assert!(check_hardforks(&cfg, &canyon).is_err());
+
+        let mut ecotone = header(20);
+        ecotone.withdrawals_root = Some(EMPTY_ROOT_HASH);
+        ecotone.parent_beacon_block_root = Some(B256::ZERO);
+        assert!(matches!(
+            check_hardforks(&cfg, &ecotone),
+            Err(VerifyInputsError::HardforkMismatch { reason: "has no blob gas used", .. })
+        ));
+        ecotone.blob_gas_used = Some(0);
+        assert!(check_hardforks(&cfg, &ecotone).is_ok());
+
+        // A header from a later hardfork than the config expects is rejected as well.
+        ecotone.requests_hash = Some(B256::ZERO);
+        assert!(check_hardforks(&cfg, &ecotone).is_err());
+    }
+
+    #[test]
+    fn test_check_hardforks_mantle() {
+        let cfg = RollupConfig {
+            mantle_hardforks: MantleHardForkConfig {
+                mantle_skadi_time: Some(10),
+                mantle_arsia_time: Some(20),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        assert!(check_hardforks(&cfg, &header(5)).is_ok());
+
+        // Skadi headers carry the Ecotone and Isthmus fields, but no EIP-1559 parameters.
+        let mut skadi = header(10);
+        skadi.withdrawals_root = Some(B256::repeat_byte(1));
+        skadi.parent_beacon_block_root = Some(B256::ZERO);
+        skadi.blob_gas_used = Some(0);
+        assert!(matches!(
+            check_hardforks(&cfg, &skadi),
+            Err(VerifyInputsError::HardforkMismatch { reason: "has no requests hash", .. })
+        ));
+        skadi.requests_hash = Some(B256::ZERO);
+        assert!(check_hardforks(&cfg, &skadi).is_ok());
+        skadi.extra_data = vec![0; 9].into();
+        assert!(check_hardforks(&cfg, &skadi).is_err());
+
+        let mut arsia = skadi.clone();
+        arsia.timestamp = 20;
+        assert!(check_hardforks(&cfg, &arsia).is_ok());
+        arsia.extra_data = Default::default();
+        assert!(matches!(
+            check_hardforks(&cfg, &arsia),
+            Err(VerifyInputsError::HardforkMismatch {
+                reason: "has no EIP-1559 parameters in its extra data",
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_check_claimed_block_number() {
+        let cmd = VerifyInputsCommand {
+            claimed_l2_block_number: 10,
+            agreed_l2_output_root: B256::repeat_byte(1),
+            claimed_l2_output_root: B256::repeat_byte(2),
+            ..Default::default()
+        };
+        let mut head = header(0);
+
+        head.number = 9;
+        assert!(cmd.check_claimed_block_number(&head).is_ok());
+        head.number = 11;
+        assert!(matches!(
+            cmd.check_claimed_block_number(&head),
+            Err(VerifyInputsError::ClaimBehindAgreedHead { claimed: 10, agreed: 11 })
+        ));
+
+        // Trace extensions must claim the agreed output root.
+        head.number = 10;
+        assert!(matches!(
+            cmd.check_claimed_block_number(&head),
+            Err(VerifyInputsError::TraceExtensionMismatch(10))
+        ));
+        let cmd = VerifyInputsCommand { claimed_l2_output_root: B256::repeat_byte(1), ..cmd };
+        assert!(cmd.check_claimed_block_number(&head).is_ok());
+    }
+
+    #[test]
+    fn test_parse_verify_inputs() {
+        let hash = "0xffd7db0f9d5cdeb49c4c9eba649d4dc6d852d64671e65488e57f58584992ac68";
+        let args = [
+            "verify-inputs",
+            "--l1-head",
+            hash,
+            "--l2-head",
+            hash,
+            "--l2-output-root",
+            hash,
+            "--l2-claim",
+            hash,
+            "--l2-block-number",
+            "10",
+            "--l1-node-address",
+            "http://localhost:8545",
+            "--l2-node-address",
+            "http://localhost:9545",
+        ];
+        let cmd = VerifyInputsCommand::parse_from(args.iter().chain(&["--l2-chain-id", "10"]));
+        assert_eq!(cmd.l2_chain_id, Some(10));
+        assert_eq!(cmd.claimed_l2_block_number, 10);
+
+        // A rollup config source is required.
+        assert!(VerifyInputsCommand::try_parse_from(args).is_err());
+    }
+}
@@ -0,0 +1,246 @@
+//! This module contains the `verify-witness` command, which runs the single-chain client program
+//! natively against a witness, verifying every preimage it reads.
+
+use super::{SingleChainHost, SingleChainLocalInputs};
+use crate::{DiskKeyValueStore, KeyValueStore, MemoryKeyValueStore};
+use alloy_consensus::Header;
+use alloy_primitives::B256;
+use alloy_rlp::Decodable;
+use clap::Parser;
+use kona_cli::cli_styles;
+use kona_genesis::RollupConfig;
+use kona_preimage::PreimageKey;
+use kona_proof::{
+    WitnessOracle,
+    boot::{
+        L1_CONFIG_KEY, L1_HEAD_KEY, L2_CHAIN_ID_KEY, L2_CLAIM_BLOCK_NUMBER_KEY, L2_CLAIM_KEY,
+        L2_OUTPUT_ROOT_KEY, L2_ROLLUP_CONFIG_KEY,
+    },
+    errors::WitnessOracleError,
+};
+use op_revm::OpSpecId;
+use serde::Serialize;
+use std::{collections::BTreeMap, path::PathBuf};
+use tracing::info;
+
+/// The local keys of the boot info, which are served from the command's inputs.
+const LOCAL_KEYS: [u64; 7] = [
+    L1_HEAD_KEY,
+    L2_OUTPUT_ROOT_KEY,
+    L2_CLAIM_KEY,
+    L2_CLAIM_BLOCK_NUMBER_KEY,
+    L2_CHAIN_ID_KEY,
+    L2_ROLLUP_CONFIG_KEY,
+    L1_CONFIG_KEY,
+];
+
+/// The `verify-witness` command, which runs the single-chain client program natively over a
+/// [WitnessOracle], without any providers or hints.
+///
+/// The witness is the data directory of a previous host run. Every preimage the client program
+/// reads is verified against its key before it is served, the same way it is inside of a zkVM or
+/// TEE, so the command succeeds only if the witness is complete and proves the claim.
+#[derive(Default, Parser, Serialize, Clone, Debug)]
+#[command(styles = cli_styles())]
+pub struct VerifyWitnessCommand {
+    /// Hash of the L1 head block. Derivation stops after this block is processed.
+    #[arg(long, env)]
+    pub l1_head: B256,
+    /// Hash of the agreed upon safe L2 block committed to by `--agreed-l2-output-root`.
+    #[arg(long, visible_alias = "l2-head", env)]
+    pub agreed_l2_head_hash: B256,
+    /// Agreed safe L2 Output Root to start derivation from.
+    #[arg(long, visible_alias = "l2-output-root", env)]
+    pub agreed_l2_output_root: B256,
+    /// Claimed L2 output root at block # `--claimed-l2-block-number` to validate.
+    #[arg(long, visible_alias = "l2-claim", env)]
+    pub claimed_l2_output_root: B256,
+    /// Number of the L2 block that the claimed output root commits to.
+    #[arg(long, visible_alias = "l2-block-number", env)]
+    pub claimed_l2_block_number: u64,
+    /// The data directory holding the witness.
+    #[arg(long, visible_alias = "db", env)]
+    pub data_dir: PathBuf,
+    /// The L2 chain ID of a supported chain. If provided, the command will look for the
+    /// corresponding rollup config in the superchain registry.
+    #[arg(
+        long,
+        conflicts_with = "rollup_config_path",
+        required_unless_present = "rollup_config_path",
+        env
+    )]
+    pub l2_chain_id: Option<u64>,
+    /// Path to rollup config. If provided, the command will use this config instead of attempting
+    /// to look up the config in the superchain registry.
+    #[arg(
+        long,
+        alias = "rollup-cfg",
+        conflicts_with = "l2_chain_id",
+        required_unless_present = "l2_chain_id",
+        env
+    )]
+    pub rollup_config_path: Option<PathBuf>,
+    /// Path to l1 config. If provided, the command will use this config instead of attempting to
+    /// look up the config in the known l1 configs.
+    #[arg(long, alias = "l1-cfg", env)]
+    pub l1_config_path: Option<PathBuf>,
+}
+
+/// An error that can occur when verifying a witness.
+#[derive(Debug, thiserror::Error)]
+pub enum VerifyWitnessError {
+    /// The witness could not be read from the data directory.
+    #[error("Failed to read witness: {0}")]
+    Store(#[from] anyhow::Error),
+    /// The rollup config is unavailable.
+    #[error("No rollup config found for the L2 chain")]
+    NoRollupConfig,
+    /// A preimage the command relies on failed verification.
+    #[error(transparent)]
+    Witness(#[from] WitnessOracleError),
+    /// The agreed L2 head header could not be decoded.
+    #[error("Invalid agreed L2 head header: {0}")]
+    InvalidHeader(#[from] alloy_rlp::Error),
+    /// The client program failed.
+    #[error("Client program failed: {0}")]
+    ClientError(#[from] kona_client::single::FaultProofProgramError),
+}
+
+impl VerifyWitnessCommand {
+    /// Reads the witness from the data directory and verifies it.
+    pub async fn run(self) -> Result<(), VerifyWitnessError> {
+        let disk_store = DiskKeyValueStore::new(self.data_dir.clone());
+        let witness = MemoryKeyValueStore::try_from(disk_store)?
+            .store
+            .into_iter()
+            .filter_map(|(key, value)| Some((PreimageKey::try_from(key.0).ok()?, value)))
+            .collect();
+        self.verify(witness).await
+    }
+
+    /// Runs the client program over a [WitnessOracle] serving the given witness, with the local
+    /// inputs of the command.
+    async fn verify(
+        &self,
+        mut witness: BTreeMap<PreimageKey, Vec<u8>>,
+    ) -> Result<(), VerifyWitnessError> {
+        let host = self.host();
+        let local_inputs = SingleChainLocalInputs::new(host.clone());
+        for local_key in LOCAL_KEYS {
+            let key = PreimageKey::new_local(local_key);
+            if let Some(value) = local_inputs.get(key.into()) {
+                witness.insert(key, value);
+            }
+        }
+
+        let rollup_config = host.known_rollup_config().ok_or(VerifyWitnessError::NoRollupConfig)?;
+        let oracle = WitnessOracle::new(witness);
+
+        // Precompile results are verified against the specs the client executes the proven blocks
+        // with, which are read from the verified agreed L2 head.
+        let agreed_header = oracle.verify(PreimageKey::new_keccak256(*self.agreed_l2_head_hash))?;
+        let agreed_header = Header::decode(&mut &agreed_header[..])?;
+        let specs = proof_specs(&rollup_config, &agreed_header, self.claimed_l2_block_number);
+        info!(target: "host", entries = oracle.len(), ?specs, "Verifying witness");
+
+        let oracle = oracle.with_specs(specs);
+        kona_client::single::run(oracle.clone(), oracle).await?;
+
+        info!(target: "host", "Witness proves the claim");
+        Ok(())
+    }
+
+    /// Returns the [SingleChainHost] config serving the local inputs of the command.
+    fn host(&self) -> SingleChainHost {
+        SingleChainHost {
+            l1_head: self.l1_head,
+            agreed_l2_head_hash: self.agreed_l2_head_hash,
+            agreed_l2_output_root: self.agreed_l2_output_root,
+            claimed_l2_output_root: self.claimed_l2_output_root,
+            claimed_l2_block_number: self.claimed_l2_block_number,
+            data_dir: Some(self.data_dir.clone()),
+            l2_chain_id: self.l2_chain_id,
+            rollup_config_path: self.rollup_config_path.clone(),
+            l1_config_path: self.l1_config_path.clone(),
+            ..Default::default()
+        }
+    }
+}
+
+/// Returns the specs that the client program executes the blocks from the agreed L2 head up to the
+/// claimed block with, in order of activation.
+fn proof_specs(
+    rollup_config: &RollupConfig,
+    agreed_header: &Header,
+    claimed_block_number: u64,
+) -> Vec<OpSpecId> {
+    let mut specs = vec![rollup_config.revm_spec_id(agreed_header.timestamp)];
+    for number in agreed_header.number + 1..=claimed_block_number {
+        let timestamp =
+            agreed_header.timestamp + (number - agreed_header.number) * rollup_config.block_time;
+        let spec = rollup_config.revm_spec_id(timestamp);
+        if specs.last() != Some(&spec) {
+            specs.push(spec);
+        }
+    }
+    specs
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use alloy_primitives::keccak256;
+    use alloy_rlp::Encodable;
+
+    fn header(number: u64, timestamp: u64) -> Header {
+        Header { number, timestamp, ..Default::default() }
+    }
+
+    #[test]
+    fn test_proof_specs() {
+        let mut rollup_config = RollupConfig { block_time: 2, ..Default::default() };
+        rollup_config.hardforks.regolith_time = Some(0);
+        rollup_config.hardforks.canyon_time = Some(0);
+        rollup_config.hardforks.ecotone_time = Some(0);
+        rollup_config.hardforks.fjord_time = Some(0);
+        rollup_config.hardforks.granite_time = Some(0);
+        rollup_config.hardforks.holocene_time = Some(0);
+        rollup_config.hardforks.isthmus_time = Some(110);
+
+        // A proof crossing the isthmus activation executes with both specs.
+        let agreed = header(50, 100);
+        assert_eq!(
+            proof_specs(&rollup_config, &agreed, 60),
+            vec![OpSpecId::HOLOCENE, OpSpecId::ISTHMUS]
+        );
+        assert_eq!(proof_specs(&rollup_config, &agreed, 54), vec![OpSpecId::HOLOCENE]);
+        assert_eq!(proof_specs(&rollup_config, &agreed, 50), vec![OpSpecId::HOLOCENE]);
+    }
+
+    #[tokio::test]
+    async fn test_rejects_tampered_agreed_header() {
+        let mut encoded = Vec::new();
+        header(1, 2).encode(&mut encoded);
+        let cmd = VerifyWitnessCommand {
+            agreed_l2_head_hash: keccak256(&encoded),
+            l2_chain_id: Some(10),
+            ..Default::default()
+        };
+
+        let mut witness = BTreeMap::new();
+        let tampered = header(1, 3);
+        encoded.clear();
+        tampered.encode(&mut encoded);
+        witness.insert(PreimageKey::new_keccak256(*cmd.agreed_l2_head_hash), encoded);
+        assert!(matches!(
+            cmd.verify(witness).await,
+            Err(VerifyWitnessError::Witness(WitnessOracleError::DigestMismatch(_)))
+        ));
+
+        // A witness without the agreed L2 head can not be verified at all.
+        assert!(matches!(
+            cmd.verify(BTreeMap::new()).await,
+            Err(VerifyWitnessError::Witness(WitnessOracleError::Missing(_)))
+        ));
+    }
+}
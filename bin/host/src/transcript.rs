@@ -0,0 +1,277 @@
+//! This module contains the oracle transcript, an ordered record of the hints and preimage
+//! requests that a client program issued, which can be replayed to the client program to detect
+//! non-determinism.
+
+use alloy_primitives::{B256, Bytes};
+use async_trait::async_trait;
+use kona_preimage::{
+    HintRouter, PreimageFetcher, PreimageKey,
+    errors::{PreimageOracleError, PreimageOracleResult},
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    fs::File,
+    io::{BufRead, BufReader, BufWriter, Write},
+    path::Path,
+    sync::{Arc, Mutex},
+};
+use tracing::error;
+
+/// A single entry of an oracle transcript. Transcripts are stored as JSON lines, one entry per
+/// line, in the order the client program issued them.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TranscriptEntry {
+    /// A hint sent by the client.
+    Hint(String),
+    /// A preimage requested by the client, and the preimage that was served.
+    Preimage {
+        /// The requested preimage key.
+        key: B256,
+        /// The preimage served to the client.
+        value: Bytes,
+    },
+}
+
+impl core::fmt::Display for TranscriptEntry {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Hint(hint) => write!(f, "hint {hint:?}"),
+            Self::Preimage { key, .. } => write!(f, "preimage request {key}"),
+        }
+    }
+}
+
+/// Records the entries of an oracle transcript to a file.
+#[derive(Debug)]
+pub struct TranscriptWriter {
+    /// The buffered transcript file.
+    file: Mutex<BufWriter<File>>,
+}
+
+impl TranscriptWriter {
+    /// Creates a new [TranscriptWriter], truncating the file at the given path.
+    pub fn create(path: &Path) -> std::io::Result<Self> {
+        Ok(Self { file: Mutex::new(BufWriter::new(File::create(path)?)) })
+    }
+
+    /// Appends an entry to the transcript.
+    pub fn record(&self, entry: &TranscriptEntry) -> std::io::Result<()> {
+        let mut file = self.file.lock().unwrap_or_else(|e| e.into_inner());
+        serde_json::to_writer(&mut *file, entry)?;
+        file.write_all(b"\n")
+    }
+
+    /// Flushes the buffered entries to the transcript file.
+    pub fn flush(&self) -> std::io::Result<()> {
+        self.file.lock().unwrap_or_else(|e| e.into_inner()).flush()
+    }
+}
+
+/// A [PreimageFetcher] and [HintRouter] that records every hint and preimage request served by
+/// the wrapped backend to a [TranscriptWriter].
+#[derive(Debug)]
+pub struct TranscriptRecorder<B> {
+    /// The backend serving the client.
+    inner: B,
+    /// The transcript that hints and preimages are recorded to.
+    transcript: Arc<TranscriptWriter>,
+}
+
+impl<B> TranscriptRecorder<B> {
+    /// Creates a new [TranscriptRecorder] that records the traffic served by `inner`.
+    pub const fn new(inner: B, transcript: Arc<TranscriptWriter>) -> Self {
+        Self { inner, transcript }
+    }
+
+    fn record(&self, entry: TranscriptEntry) -> PreimageOracleResult<()> {
+        self.transcript.record(&entry).map_err(|e| PreimageOracleError::Other(e.to_string()))
+    }
+}
+
+#[async_trait]
+impl<B> PreimageFetcher for TranscriptRecorder<B>
+where
+    B: PreimageFetcher + Send + Sync,
+{
+    async fn get_preimage(&self, key: PreimageKey) -> PreimageOracleResult<Vec<u8>> {
+        let preimage = self.inner.get_preimage(key).await?;
+        self.record(TranscriptEntry::Preimage {
+            key: B256::from(<[u8; 32]>::from(key)),
+            value: Bytes::copy_from_slice(&preimage),
+        })?;
+        Ok(preimage)
+    }
+}
+
+#[async_trait]
+impl<B> HintRouter for TranscriptRecorder<B>
+where
+    B: HintRouter + Send + Sync,
+{
+    async fn route_hint(&self, hint: String) -> PreimageOracleResult<()> {
+        self.record(TranscriptEntry::Hint(hint.clone()))?;
+        self.inner.route_hint(hint).await
+    }
+}
+
+/// A [PreimageFetcher] and [HintRouter] that serves a recorded oracle transcript, failing on the
+/// first request that deviates from the transcript in order, type, or key.
+#[derive(Debug)]
+pub struct TranscriptReplayer {
+    /// The entries of the transcript.
+    entries: Vec<TranscriptEntry>,
+    /// The replay state.
+    state: Mutex<ReplayState>,
+}
+
+/// The mutable state of a [TranscriptReplayer].
+#[derive(Debug, Default)]
+struct ReplayState {
+    /// The index of the next expected entry.
+    cursor: usize,
+    /// The first deviation from the transcript, if any.
+    deviation: Option<String>,
+}
+
+impl TranscriptReplayer {
+    /// Creates a new [TranscriptReplayer] serving the given entries.
+    pub fn new(entries: Vec<TranscriptEntry>) -> Self {
+        Self { entries, state: Default::default() }
+    }
+
+    /// Reads a transcript from the file at the given path.
+    pub fn open(path: &Path) -> std::io::Result<Self> {
+        let entries = BufReader::new(File::open(path)?)
+            .lines()
+            .filter(|line| !matches!(line, Ok(l) if l.trim().is_empty()))
+            .map(|line| Ok(serde_json::from_str(&line?)?))
+            .collect::<std::io::Result<_>>()?;
+        Ok(Self::new(entries))
+    }
+
+    /// Returns the first deviation from the transcript, or a deviation describing the unconsumed
+    /// tail of the transcript if the client program issued fewer requests than recorded.
+    pub fn deviation(&self) -> Option<String> {
+        let state = self.state();
+        state.deviation.clone().or_else(|| {
+            (state.cursor < self.entries.len()).then(|| {
+                format!(
+                    "client stopped after {} of {} transcript entries",
+                    state.cursor,
+                    self.entries.len()
+                )
+            })
+        })
+    }
+
+    /// Advances the transcript, returning the next entry if `matches` accepts it. Otherwise, the
+    /// deviation is recorded and returned as an error.
+    fn advance(
+        &self,
+        request: &TranscriptEntry,
+        matches: impl Fn(&TranscriptEntry) -> bool,
+    ) -> PreimageOracleResult<&TranscriptEntry> {
+        let mut state = self.state();
+        if let Some(deviation) = state.deviation.as_ref() {
+            return Err(PreimageOracleError::Other(deviation.clone()));
+        }
+
+        let index = state.cursor;
+        match self.entries.get(index) {
+            Some(entry) if matches(entry) => {
+                state.cursor += 1;
+                Ok(entry)
+            }
+            expected => {
+                let expected =
+                    expected.map_or_else(|| "end of transcript".to_string(), ToString::to_string);
+                let deviation = format!(
+                    "transcript deviation at entry {index}: expected {expected}, got {request}"
+                );
+                error!(target: "host", "{deviation}");
+                state.deviation = Some(deviation.clone());
+                Err(PreimageOracleError::Other(deviation))
+            }
+        }
+    }
+
+    fn state(&self) -> std::sync::MutexGuard<'_, ReplayState> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+#[async_trait]
+impl PreimageFetcher for TranscriptReplayer {
+    async fn get_preimage(&self, key: PreimageKey) -> PreimageOracleResult<Vec<u8>> {
+        let key = B256::from(<[u8; 32]>::from(key));
+        let request = TranscriptEntry::Preimage { key, value: Bytes::new() };
+        let entry = self.advance(
+            &request,
+            |entry| matches!(entry, TranscriptEntry::Preimage { key: k, .. } if *k == key),
+        )?;
+        match entry {
+            TranscriptEntry::Preimage { value, .. } => Ok(value.to_vec()),
+            TranscriptEntry::Hint(_) => unreachable!("matched a preimage entry"),
+        }
+    }
+}
+
+#[async_trait]
+impl HintRouter for TranscriptReplayer {
+    async fn route_hint(&self, hint: String) -> PreimageOracleResult<()> {
+        let request = TranscriptEntry::Hint(hint);
+        self.advance(&request, |entry| *entry == request)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{KeyValueStore, MemoryKeyValueStore, OfflineHostBackend};
+    use tokio::sync::RwLock;
+
+    #[tokio::test]
+    async fn test_record_and_replay() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("transcript.jsonl");
+
+        let key = PreimageKey::new_keccak256([1; 32]);
+        let mut kv = MemoryKeyValueStore::new();
+        kv.set(key.into(), vec![0xaa; 4]).unwrap();
+        let backend = OfflineHostBackend::new(Arc::new(RwLock::new(kv)));
+
+        let writer = Arc::new(TranscriptWriter::create(&path).unwrap());
+        let recorder = TranscriptRecorder::new(backend, writer.clone());
+        recorder.route_hint("l1-block-header 0x01".to_string()).await.unwrap();
+        assert_eq!(recorder.get_preimage(key).await.unwrap(), vec![0xaa; 4]);
+        assert!(recorder.get_preimage(PreimageKey::new_local(0)).await.is_err());
+        writer.flush().unwrap();
+
+        let replayer = TranscriptReplayer::open(&path).unwrap();
+        assert_eq!(replayer.entries.len(), 2);
+        assert!(replayer.deviation().is_some());
+        replayer.route_hint("l1-block-header 0x01".to_string()).await.unwrap();
+        assert_eq!(replayer.get_preimage(key).await.unwrap(), vec![0xaa; 4]);
+        assert_eq!(replayer.deviation(), None);
+    }
+
+    #[tokio::test]
+    async fn test_replay_deviation() {
+        let key = B256::repeat_byte(1);
+        let replayer = TranscriptReplayer::new(vec![
+            TranscriptEntry::Hint("l1-block-header 0x01".to_string()),
+            TranscriptEntry::Preimage { key, value: Bytes::from_static(&[1]) },
+        ]);
+
+        // Requesting a preimage where a hint was recorded is a deviation.
+        let preimage_key = PreimageKey::try_from(key.0).unwrap();
+        assert!(replayer.get_preimage(preimage_key).await.is_err());
+        let deviation = replayer.deviation().unwrap();
+        assert!(deviation.contains("entry 0"), "{deviation}");
+
+        // Once deviated, the replay fails every subsequent request.
+        assert!(replayer.route_hint("l1-block-header 0x01".to_string()).await.is_err());
+    }
+}
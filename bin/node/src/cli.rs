@@ -1,7 +1,9 @@
 //! Contains the node CLI.
 
 use crate::{
-    commands::{BootstoreCommand, InfoCommand, NetCommand, NodeCommand, RegistryCommand},
+    commands::{
+        BootstoreCommand, ConfigCommand, InfoCommand, NetCommand, NodeCommand, RegistryCommand,
+    },
     flags::{GlobalArgs, init_unified_metrics},
     version,
 };
@@ -27,6 +29,8 @@ pub enum Commands {
     Bootstore(BootstoreCommand),
     /// Get info about op chain.
     Info(InfoCommand),
+    /// Inspects the resolved node configuration.
+    Config(ConfigCommand),
 }
 
 /// The node CLI.
@@ -51,6 +55,9 @@ pub struct Cli {
 impl Cli {
     /// Runs the CLI.
     pub fn run(self) -> Result<()> {
+        // Load the runtime registry before any of the registry statics are accessed.
+        self.global.registry.load()?;
+
         // Initialize telemetry - allow subcommands to customize the filter.
         match self.subcommand {
             Commands::Node(ref node) => node.init_logs(&self.global)?,
@@ -58,6 +65,8 @@ impl Cli {
             Commands::Registry(ref registry) => registry.init_logs(&self.global)?,
             Commands::Bootstore(ref bootstore) => bootstore.init_logs(&self.global)?,
             Commands::Info(ref info) => info.init_logs(&self.global)?,
+            // Logs would interleave with the configuration printed to stdout.
+            Commands::Config(_) => {}
         }
 
         // Initialize unified metrics
@@ -72,13 +81,19 @@ impl Cli {
         }
 
         // Run the subcommand.
-        match self.subcommand {
+        let res = match self.subcommand {
             Commands::Node(node) => Self::run_until_ctrl_c(node.run(&self.global)),
             Commands::Net(net) => Self::run_until_ctrl_c(net.run(&self.global)),
             Commands::Registry(registry) => registry.run(&self.global),
             Commands::Bootstore(bootstore) => bootstore.run(&self.global),
             Commands::Info(info) => info.run(&self.global),
-        }
+            Commands::Config(config) => config.run(&self.global),
+        };
+
+        // Flush any spans that have not been exported yet.
+        kona_cli::shutdown_tracing();
+
+        res
     }
 
     /// Run until ctrl-c is pressed.
@@ -0,0 +1,100 @@
+//! Config Subcommand
+
+use crate::{cli::Cli, commands::NodeCommand, flags::GlobalArgs};
+use clap::{CommandFactory, Parser, Subcommand};
+use std::ffi::OsString;
+
+/// The `config` Subcommand
+///
+/// The `config` subcommand inspects the configuration of the `node` subcommand, as resolved from
+/// the `--config` file, environment variables and command line flags.
+///
+/// # Usage
+///
+/// ```sh
+/// kona-node --config kona.toml config dump [FLAGS] [OPTIONS]
+/// ```
+#[derive(Parser, PartialEq, Debug, Clone)]
+#[command(about = "Inspects the resolved node configuration")]
+pub struct ConfigCommand {
+    /// The config subcommand to run.
+    #[command(subcommand)]
+    pub subcommand: ConfigSubcommand,
+}
+
+/// Subcommands of the `config` subcommand.
+#[derive(Subcommand, PartialEq, Debug, Clone)]
+#[allow(clippy::large_enum_variant)]
+pub enum ConfigSubcommand {
+    /// Prints the fully resolved `node` configuration as TOML, with secrets redacted.
+    ///
+    /// Accepts the same flags as the `node` subcommand. The output can be used as a `--config`
+    /// file.
+    Dump(NodeCommand),
+}
+
+impl ConfigCommand {
+    /// Runs the subcommand.
+    ///
+    /// The typed [`NodeCommand`] does not retain which flags were set, so the process arguments
+    /// are resolved and matched again to render every flag under its own name.
+    pub fn run(self, _args: &GlobalArgs) -> anyhow::Result<()> {
+        print!("{}", Self::dump(std::env::args_os())?);
+        Ok(())
+    }
+
+    /// Renders the resolved configuration for the given process arguments.
+    pub fn dump<I, T>(args: I) -> anyhow::Result<String>
+    where
+        I: IntoIterator<Item = T>,
+        T: Into<OsString>,
+    {
+        let cmd = Cli::command();
+        let args = kona_cli::resolve_config_args(&cmd, args)?;
+        let matches = cmd.clone().try_get_matches_from(args)?;
+        Ok(kona_cli::dump_config(&cmd, &matches)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_config_dump() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("kona.toml");
+        std::fs::write(
+            &path,
+            r#"
+            l1-eth-rpc = "http://localhost:8545"
+            l1-beacon = "http://localhost:5052"
+            l2-engine-rpc = "http://localhost:8551"
+            l2-engine-jwt-secret = "/secrets/jwt.hex"
+
+            [p2p]
+            "listen.tcp" = 9333
+            "priv.raw" = "1d2b0bda21d56b8bd12d4f94ebacffdfb35f5e226f84b461103bb8beab6353be"
+            "#,
+        )
+        .unwrap();
+
+        let dump = ConfigCommand::dump([
+            "kona-node".into(),
+            OsString::from("--config"),
+            path.into_os_string(),
+            "config".into(),
+            "dump".into(),
+            "--p2p.listen.udp".into(),
+            "9334".into(),
+        ])
+        .unwrap();
+
+        assert!(dump.contains("l1-eth-rpc = \"http://localhost:8545\""), "{dump}");
+        assert!(dump.contains("\"p2p.listen.tcp\" = \"9333\""), "{dump}");
+        assert!(dump.contains("\"p2p.listen.udp\" = \"9334\""), "{dump}");
+        assert!(dump.contains("\"p2p.priv.raw\" = \"<redacted>\""), "{dump}");
+        assert!(!dump.contains("1d2b0bda"), "{dump}");
+        assert!(!dump.contains("jwt.hex"), "{dump}");
+    }
+}
@@ -0,0 +1,468 @@
+//! The `info hardforks` subcommand.
+
+use crate::flags::GlobalArgs;
+use alloy_primitives::{B256, keccak256};
+use anyhow::{Result, bail};
+use clap::Parser;
+use kona_engine::{EngineForkchoiceVersion, EngineGetPayloadVersion, EngineNewPayloadVersion};
+use kona_genesis::RollupConfig;
+use kona_hardforks::{Hardfork, MantleHardforks};
+use kona_registry::scr_rollup_config_by_alloy_ident;
+use op_revm::OpSpecId;
+use std::{
+    fmt,
+    fs::File,
+    path::{Path, PathBuf},
+};
+use tabled::Tabled;
+
+/// The name of the Mantle hardfork that gates the OP Stack hardforks on Mantle chains.
+const MANTLE_ARSIA: &str = "Mantle Arsia";
+
+/// The `info hardforks` subcommand.
+///
+/// Prints the ordered hardfork timeline of a rollup config, and flags inconsistent schedules.
+/// If a second config is given, the two schedules are diffed.
+///
+/// # Usage
+///
+/// ```sh
+/// kona-node info hardforks [--rollup-cfg <PATH>] [--diff <PATH> | --diff-chain <CHAIN>]
+/// ```
+#[derive(Parser, Default, PartialEq, Debug, Clone)]
+pub struct HardforksCommand {
+    /// Path to a rollup config to inspect instead of the registry config of the chain.
+    #[arg(long, visible_alias = "rollup-cfg")]
+    pub l2_config_file: Option<PathBuf>,
+    /// Path to a rollup config to diff against.
+    #[arg(long = "diff")]
+    pub diff_config_file: Option<PathBuf>,
+    /// Chain whose registry config to diff against.
+    #[arg(long, conflicts_with = "diff_config_file")]
+    pub diff_chain: Option<alloy_chains::Chain>,
+}
+
+impl HardforksCommand {
+    /// Runs the subcommand.
+    pub fn run(&self, args: &GlobalArgs) -> Result<()> {
+        let config = match &self.l2_config_file {
+            Some(path) => Self::read_config(path)?,
+            None => Self::registry_config(&args.l2_chain_id)?,
+        };
+        let timeline = HardforkTimeline::new(&config);
+        println!("{timeline}");
+
+        let other = match (&self.diff_config_file, &self.diff_chain) {
+            (Some(path), _) => Self::read_config(path)?,
+            (None, Some(chain)) => Self::registry_config(chain)?,
+            (None, None) => return Ok(()),
+        };
+        let other_timeline = HardforkTimeline::new(&other);
+        println!("{other_timeline}");
+
+        let diff = diff_schedules(&config, &other);
+        if diff.is_empty() {
+            println!("The hardfork schedules are identical.");
+        } else {
+            println!("Hardfork schedule differences:");
+            for line in diff {
+                println!("  {line}");
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Reads a rollup config from a JSON file.
+    fn read_config(path: &Path) -> Result<RollupConfig> {
+        let file = File::open(path)
+            .map_err(|e| anyhow::anyhow!("Failed to open rollup config {path:?}: {e}"))?;
+        serde_json::from_reader(file)
+            .map_err(|e| anyhow::anyhow!("Failed to parse rollup config {path:?}: {e}"))
+    }
+
+    /// Returns the registry rollup config of the chain.
+    fn registry_config(chain: &alloy_chains::Chain) -> Result<RollupConfig> {
+        let Some(config) = scr_rollup_config_by_alloy_ident(chain) else {
+            bail!("Failed to find rollup config for chain ID {chain}");
+        };
+        Ok(config.clone())
+    }
+}
+
+/// A scheduled hardfork activation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Activation {
+    /// The name of the hardfork.
+    pub name: &'static str,
+    /// The activation timestamp.
+    pub timestamp: u64,
+    /// The number of the first L2 block at or after the activation timestamp.
+    pub block: u64,
+    /// The [`OpSpecId`] used by the protocol at activation.
+    pub spec_id: OpSpecId,
+    /// The [`OpSpecId`] used by the EVM at activation.
+    pub revm_spec_id: OpSpecId,
+    /// The `engine_forkchoiceUpdated` version at activation.
+    pub forkchoice_version: EngineForkchoiceVersion,
+    /// The `engine_newPayload` version at activation.
+    pub new_payload_version: EngineNewPayloadVersion,
+    /// The `engine_getPayload` version at activation.
+    pub get_payload_version: EngineGetPayloadVersion,
+    /// The hashes of the upgrade transactions included in the activation block.
+    pub upgrade_txs: Vec<B256>,
+}
+
+impl Activation {
+    fn new(config: &RollupConfig, name: &'static str, timestamp: u64) -> Self {
+        // Derivation only injects the Mantle upgrade transactions, see the
+        // `StatefulAttributesBuilder` in `kona-derive`.
+        let upgrade_txs = match name {
+            MANTLE_ARSIA => MantleHardforks::ARSIA.txs().map(keccak256).collect(),
+            _ => Vec::new(),
+        };
+
+        Self {
+            name,
+            timestamp,
+            block: activation_block(config, timestamp),
+            spec_id: config.spec_id(timestamp),
+            revm_spec_id: config.revm_spec_id(timestamp),
+            forkchoice_version: EngineForkchoiceVersion::from_cfg(config, timestamp),
+            new_payload_version: EngineNewPayloadVersion::from_cfg(config, timestamp),
+            get_payload_version: EngineGetPayloadVersion::from_cfg(config, timestamp),
+            upgrade_txs,
+        }
+    }
+}
+
+/// An inconsistency in a hardfork schedule.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ScheduleIssue {
+    /// A hardfork activates before a hardfork that precedes it.
+    OutOfOrder {
+        /// The hardfork.
+        fork: &'static str,
+        /// The preceding hardfork that activates later.
+        previous: &'static str,
+    },
+    /// A hardfork is scheduled while a hardfork that precedes it is not.
+    MissingPredecessor {
+        /// The hardfork.
+        fork: &'static str,
+        /// The unscheduled preceding hardfork.
+        missing: &'static str,
+    },
+    /// The activation timestamp is not aligned with the L2 block time.
+    Unaligned {
+        /// The hardfork.
+        fork: &'static str,
+    },
+    /// An OP Stack hardfork on a Mantle chain activates before Mantle Arsia, which gates it.
+    GatedByArsia {
+        /// The hardfork.
+        fork: &'static str,
+    },
+}
+
+impl fmt::Display for ScheduleIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::OutOfOrder { fork, previous } => {
+                write!(f, "{fork} activates before the preceding {previous} hardfork")
+            }
+            Self::MissingPredecessor { fork, missing } => {
+                write!(f, "{fork} is scheduled, but the preceding {missing} hardfork is not")
+            }
+            Self::Unaligned { fork } => {
+                write!(f, "{fork} activation is not aligned with the L2 block time")
+            }
+            Self::GatedByArsia { fork } => {
+                write!(f, "{fork} has no effect before {MANTLE_ARSIA} on Mantle chains")
+            }
+        }
+    }
+}
+
+/// The hardfork timeline of a [`RollupConfig`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HardforkTimeline {
+    /// The L2 chain ID.
+    pub chain_id: u64,
+    /// The scheduled activations, ordered by timestamp.
+    pub activations: Vec<Activation>,
+    /// The hardforks that are not scheduled.
+    pub unscheduled: Vec<&'static str>,
+    /// The inconsistencies in the schedule.
+    pub issues: Vec<ScheduleIssue>,
+}
+
+impl HardforkTimeline {
+    /// Builds the timeline of the given [`RollupConfig`].
+    pub fn new(config: &RollupConfig) -> Self {
+        let op_forks: Vec<_> = config.hardforks.iter().collect();
+        let mantle_forks: Vec<_> = config.mantle_hardforks.iter().collect();
+
+        // Hardforks at the same timestamp keep their canonical order.
+        let mut activations: Vec<_> = op_forks
+            .iter()
+            .chain(mantle_forks.iter())
+            .filter_map(|&(name, time)| time.map(|t| Activation::new(config, name, t)))
+            .collect();
+        activations.sort_by_key(|a| a.timestamp);
+
+        let unscheduled = op_forks
+            .iter()
+            .chain(mantle_forks.iter())
+            .filter(|(_, time)| time.is_none())
+            .map(|&(name, _)| name)
+            .collect();
+
+        // OP Stack hardforks implicitly activate the hardforks preceding them, Mantle hardforks
+        // do not.
+        let mut issues = ordering_issues(&op_forks, false);
+        issues.extend(ordering_issues(&mantle_forks, true));
+        issues.extend(
+            activations
+                .iter()
+                .filter(|a| {
+                    a.timestamp > config.genesis.l2_time &&
+                        (a.timestamp - config.genesis.l2_time) % config.block_time.max(1) != 0
+                })
+                .map(|a| ScheduleIssue::Unaligned { fork: a.name }),
+        );
+        if config.is_mantle() {
+            let arsia = config.mantle_hardforks.mantle_arsia_time;
+            issues.extend(
+                op_forks
+                    .iter()
+                    // Regolith is activated by Mantle Skadi.
+                    .filter(|&&(name, _)| name != "Regolith")
+                    .filter(|(_, time)| time.is_some_and(|t| arsia.is_none_or(|a| t < a)))
+                    .map(|&(fork, _)| ScheduleIssue::GatedByArsia { fork }),
+            );
+        }
+
+        Self { chain_id: config.l2_chain_id.id(), activations, unscheduled, issues }
+    }
+}
+
+/// A row of the timeline table.
+#[derive(Tabled)]
+struct TimelineRow {
+    #[tabled(rename = "Hardfork")]
+    name: &'static str,
+    #[tabled(rename = "Timestamp")]
+    timestamp: u64,
+    #[tabled(rename = "L2 Block")]
+    block: u64,
+    #[tabled(rename = "Spec")]
+    spec_id: String,
+    #[tabled(rename = "EVM Spec")]
+    revm_spec_id: String,
+    #[tabled(rename = "FCU")]
+    forkchoice_version: String,
+    #[tabled(rename = "NewPayload")]
+    new_payload_version: String,
+    #[tabled(rename = "GetPayload")]
+    get_payload_version: String,
+    #[tabled(rename = "Upgrade Txs")]
+    upgrade_txs: usize,
+}
+
+impl From<&Activation> for TimelineRow {
+    fn from(a: &Activation) -> Self {
+        Self {
+            name: a.name,
+            timestamp: a.timestamp,
+            block: a.block,
+            spec_id: format!("{:?}", a.spec_id),
+            revm_spec_id: format!("{:?}", a.revm_spec_id),
+            forkchoice_version: format!("{:?}", a.forkchoice_version),
+            new_payload_version: format!("{:?}", a.new_payload_version),
+            get_payload_version: format!("{:?}", a.get_payload_version),
+            upgrade_txs: a.upgrade_txs.len(),
+        }
+    }
+}
+
+impl fmt::Display for HardforkTimeline {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Hardfork timeline for chain {}:", self.chain_id)?;
+        let mut table = tabled::Table::new(self.activations.iter().map(TimelineRow::from));
+        table.with(tabled::settings::Style::modern());
+        writeln!(f, "{table}")?;
+
+        for activation in self.activations.iter().filter(|a| !a.upgrade_txs.is_empty()) {
+            writeln!(f, "{} upgrade transactions:", activation.name)?;
+            for hash in &activation.upgrade_txs {
+                writeln!(f, "  {hash}")?;
+            }
+        }
+        if !self.unscheduled.is_empty() {
+            writeln!(f, "Not scheduled: {}", self.unscheduled.join(", "))?;
+        }
+        for issue in &self.issues {
+            writeln!(f, "WARNING: {issue}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Returns the number of the first L2 block at or after the given timestamp.
+const fn activation_block(config: &RollupConfig, timestamp: u64) -> u64 {
+    let elapsed = timestamp.saturating_sub(config.genesis.l2_time);
+    let block_time = if config.block_time == 0 { 1 } else { config.block_time };
+    config.genesis.l2.number + elapsed.div_ceil(block_time)
+}
+
+/// Returns the ordering issues of a list of hardforks in canonical order.
+///
+/// If `require_predecessors` is set, scheduled hardforks that follow an unscheduled hardfork are
+/// flagged.
+fn ordering_issues(
+    forks: &[(&'static str, Option<u64>)],
+    require_predecessors: bool,
+) -> Vec<ScheduleIssue> {
+    let mut issues = Vec::new();
+    for (i, &(fork, time)) in forks.iter().enumerate() {
+        let Some(time) = time else {
+            continue;
+        };
+        for &(previous, previous_time) in &forks[..i] {
+            match previous_time {
+                Some(t) if t > time => issues.push(ScheduleIssue::OutOfOrder { fork, previous }),
+                None if require_predecessors => {
+                    issues.push(ScheduleIssue::MissingPredecessor { fork, missing: previous })
+                }
+                _ => {}
+            }
+        }
+    }
+    issues
+}
+
+/// Returns the differences between the hardfork schedules of two configs.
+fn diff_schedules(a: &RollupConfig, b: &RollupConfig) -> Vec<String> {
+    fn fmt_time(t: Option<u64>) -> String {
+        t.map(|t| t.to_string()).unwrap_or_else(|| "Not scheduled".to_string())
+    }
+
+    let a_forks = a.hardforks.iter().chain(a.mantle_hardforks.iter());
+    let b_forks = b.hardforks.iter().chain(b.mantle_hardforks.iter());
+    a_forks
+        .zip(b_forks)
+        .filter(|((_, a_time), (_, b_time))| a_time != b_time)
+        .map(|((name, a_time), (_, b_time))| {
+            format!("{name}: {} -> {}", fmt_time(a_time), fmt_time(b_time))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kona_genesis::{ChainGenesis, HardForkConfig, MantleHardForkConfig};
+
+    fn mantle_config() -> RollupConfig {
+        RollupConfig {
+            genesis: ChainGenesis { l2_time: 100, ..Default::default() },
+            block_time: 2,
+            mantle_hardforks: MantleHardForkConfig {
+                mantle_base_fee_time: Some(0),
+                mantle_everest_time: Some(0),
+                mantle_euboea_time: Some(0),
+                mantle_skadi_time: Some(200),
+                mantle_limb_time: Some(300),
+                mantle_arsia_time: Some(400),
+            },
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_timeline_orders_activations() {
+        let config = mantle_config();
+        let timeline = HardforkTimeline::new(&config);
+
+        let names: Vec<_> = timeline.activations.iter().map(|a| a.name).collect();
+        assert_eq!(
+            names,
+            [
+                "Mantle BaseFee",
+                "Mantle Everest",
+                "Mantle Euboea",
+                "Mantle Skadi",
+                "Mantle Limb",
+                MANTLE_ARSIA
+            ]
+        );
+        assert!(timeline.issues.is_empty());
+
+        let arsia = timeline.activations.last().unwrap();
+        assert_eq!(arsia.block, 150);
+        assert_eq!(arsia.revm_spec_id, OpSpecId::ARSIA);
+        assert_eq!(arsia.upgrade_txs.len(), MantleHardforks::ARSIA.txs().count());
+
+        let limb = &timeline.activations[4];
+        assert_eq!(limb.revm_spec_id, OpSpecId::OSAKA);
+        assert!(limb.upgrade_txs.is_empty());
+    }
+
+    #[test]
+    fn test_timeline_flags_misordered_mantle_forks() {
+        let mut config = mantle_config();
+        config.mantle_hardforks.mantle_limb_time = Some(500);
+        config.mantle_hardforks.mantle_skadi_time = None;
+        config.hardforks.isthmus_time = Some(301);
+
+        let issues = HardforkTimeline::new(&config).issues;
+        assert!(
+            issues.contains(&ScheduleIssue::OutOfOrder {
+                fork: MANTLE_ARSIA,
+                previous: "Mantle Limb"
+            })
+        );
+        assert!(issues.contains(&ScheduleIssue::MissingPredecessor {
+            fork: "Mantle Limb",
+            missing: "Mantle Skadi"
+        }));
+        assert!(issues.contains(&ScheduleIssue::Unaligned { fork: "Isthmus" }));
+        assert!(issues.contains(&ScheduleIssue::GatedByArsia { fork: "Isthmus" }));
+    }
+
+    #[test]
+    fn test_timeline_op_chain() {
+        let config = RollupConfig {
+            block_time: 2,
+            hardforks: HardForkConfig {
+                regolith_time: Some(0),
+                canyon_time: Some(20),
+                delta_time: Some(10),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        let timeline = HardforkTimeline::new(&config);
+        assert_eq!(timeline.activations[1].name, "Delta");
+        assert_eq!(
+            timeline.issues,
+            [ScheduleIssue::OutOfOrder { fork: "Delta", previous: "Canyon" }]
+        );
+    }
+
+    #[test]
+    fn test_diff_schedules() {
+        let a = mantle_config();
+        let mut b = mantle_config();
+        assert!(diff_schedules(&a, &b).is_empty());
+
+        b.mantle_hardforks.mantle_arsia_time = None;
+        b.hardforks.isthmus_time = Some(10);
+        assert_eq!(
+            diff_schedules(&a, &b),
+            ["Isthmus: Not scheduled -> 10", "Mantle Arsia: 400 -> Not scheduled"]
+        );
+    }
+}
@@ -1,11 +1,14 @@
 //! Info Subcommand
 
 use crate::flags::GlobalArgs;
-use clap::Parser;
+use clap::{Parser, Subcommand};
 use kona_cli::LogConfig;
 use kona_registry::{OPCHAINS, ROLLUP_CONFIGS};
 use tracing::info;
 
+mod hardforks;
+pub use hardforks::HardforksCommand;
+
 /// The `info` Subcommand
 ///
 /// The `info` subcommand is used to run the information stack for the `kona-node`.
@@ -13,12 +16,22 @@ use tracing::info;
 /// # Usage
 ///
 /// ```sh
-/// kona-node info
+/// kona-node info [hardforks]
 /// ```
-
 #[derive(Parser, Default, PartialEq, Debug, Clone)]
 #[command(about = "Runs the information stack for the kona-node.")]
-pub struct InfoCommand;
+pub struct InfoCommand {
+    /// The information to print. Prints a summary of the chain if not set.
+    #[command(subcommand)]
+    pub mode: Option<InfoMode>,
+}
+
+/// The modes of the `info` subcommand.
+#[derive(Subcommand, PartialEq, Debug, Clone)]
+pub enum InfoMode {
+    /// Prints the hardfork timeline of a rollup config.
+    Hardforks(HardforksCommand),
+}
 
 impl InfoCommand {
     /// Initializes the logging system based on global arguments.
@@ -31,6 +44,10 @@ impl InfoCommand {
     pub fn run(&self, args: &GlobalArgs) -> anyhow::Result<()> {
         info!(target: "node_info", "Running info command");
 
+        if let Some(InfoMode::Hardforks(hardforks)) = &self.mode {
+            return hardforks.run(args);
+        }
+
         let op_chain_config = OPCHAINS.get(&args.l2_chain_id.id()).expect("No Chain config found");
         let op_rollup_config =
             ROLLUP_CONFIGS.get(&args.l2_chain_id.id()).expect("No Rollup config found");
@@ -47,3 +64,40 @@ impl InfoCommand {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_parse_info_hardforks() {
+        let info = InfoCommand::try_parse_from(["info"]).unwrap();
+        assert_eq!(info.mode, None);
+
+        let info = InfoCommand::try_parse_from([
+            "info",
+            "hardforks",
+            "--rollup-cfg",
+            "a.json",
+            "--diff",
+            "b.json",
+        ])
+        .unwrap();
+        let Some(InfoMode::Hardforks(hardforks)) = info.mode else {
+            panic!("expected the hardforks mode");
+        };
+        assert_eq!(hardforks.l2_config_file, Some(PathBuf::from("a.json")));
+        assert_eq!(hardforks.diff_config_file, Some(PathBuf::from("b.json")));
+
+        let result = InfoCommand::try_parse_from([
+            "info",
+            "hardforks",
+            "--diff",
+            "b.json",
+            "--diff-chain",
+            "5003",
+        ]);
+        assert!(result.is_err());
+    }
+}
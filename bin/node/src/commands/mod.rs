@@ -14,3 +14,6 @@ pub use net::NetCommand;
 
 mod registry;
 pub use registry::RegistryCommand;
+
+mod config;
+pub use config::{ConfigCommand, ConfigSubcommand};
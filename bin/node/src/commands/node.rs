@@ -11,7 +11,9 @@ use clap::Parser;
 use kona_cli::{LogConfig, MetricsArgs};
 use kona_genesis::{L1ChainConfig, RollupConfig};
 use kona_node_service::{NodeMode, RollupNode, RollupNodeService};
+use kona_providers_alloy::{LocalBlobArchive, OnlineBlobArchiver};
 use kona_registry::{L1Config, scr_rollup_config_by_alloy_ident};
+use kona_rpc::RpcBuilder;
 use op_alloy_provider::ext::engine::OpEngineApi;
 use serde_json::from_reader;
 use std::{fs::File, path::PathBuf, sync::Arc};
@@ -39,6 +41,8 @@ pub(super) enum JwtValidationError {
 /// The node can operate in different modes:
 /// - **Validator**: Validates L2 blocks and participates in consensus
 /// - **Sequencer**: Sequences transactions and produces L2 blocks
+/// - **FollowSource**: Tracks the safe and finalized heads of a trusted rollup node instead of
+///   deriving them from L1
 ///
 /// # Configuration Sources
 ///
@@ -82,6 +86,11 @@ pub struct NodeCommand {
     /// URL of the L1 execution client RPC API.
     #[arg(long, visible_alias = "l1", env = "KONA_NODE_L1_ETH_RPC")]
     pub l1_eth_rpc: Url,
+    /// WebSocket URL of the L1 execution client RPC API. If set, new L1 heads are received over
+    /// a `newHeads` subscription instead of being polled over `--l1-eth-rpc`, which falls back to
+    /// polling while the WebSocket endpoint is unavailable.
+    #[arg(long, visible_alias = "l1.ws", env = "KONA_NODE_L1_ETH_WS")]
+    pub l1_eth_ws: Option<Url>,
     /// Whether to trust the L1 RPC.
     /// If false, block hash verification is performed for all retrieved blocks.
     #[arg(
@@ -94,9 +103,49 @@ pub struct NodeCommand {
     /// URL of the L1 beacon API.
     #[arg(long, visible_alias = "l1.beacon", env = "KONA_NODE_L1_BEACON")]
     pub l1_beacon: Url,
+    /// URL of a blob-archiver API to fetch blobs from when they have expired from the L1 beacon
+    /// API.
+    #[arg(long, visible_alias = "l1.beacon-archiver", env = "KONA_NODE_L1_BEACON_ARCHIVER")]
+    pub l1_beacon_archiver: Option<Url>,
+    /// Path to a local directory of blob sidecars, named by versioned hash, to fetch blobs from
+    /// when they are unavailable from the L1 beacon API and blob archiver.
+    #[arg(long, visible_alias = "l1.blob-dir", env = "KONA_NODE_L1_BLOB_DIR")]
+    pub l1_blob_dir: Option<PathBuf>,
     /// URL of the engine API endpoint of an L2 execution client.
     #[arg(long, visible_alias = "l2", env = "KONA_NODE_L2_ENGINE_RPC")]
     pub l2_engine_rpc: Url,
+    /// URLs of the engine API endpoints of shadow L2 execution clients. Every
+    /// `engine_newPayload` and `engine_forkchoiceUpdated` call is mirrored to the shadows, and
+    /// their responses are compared with the primary's without affecting consensus. Shadows are
+    /// authenticated with the same JWT secret as the primary.
+    #[arg(
+        long,
+        visible_alias = "l2.shadow",
+        env = "KONA_NODE_L2_SHADOW_ENGINE_RPC",
+        value_delimiter = ','
+    )]
+    pub l2_shadow_engine_rpc: Vec<Url>,
+    /// URL of the rollup node RPC API of a trusted node to follow. Required in `FollowSource`
+    /// mode, where the safe and finalized heads are taken from this node instead of being derived
+    /// from L1. The node halts if its execution client disagrees with the followed node's output
+    /// roots.
+    #[arg(
+        long,
+        visible_alias = "follow.source",
+        env = "KONA_NODE_FOLLOW_SOURCE",
+        required_if_eq("node_mode", "FollowSource")
+    )]
+    pub follow_source: Option<Url>,
+    /// URL of the execution client RPC API of the trusted node to follow. Required in
+    /// `FollowSource` mode, to fetch the blocks that the local execution client did not receive
+    /// over gossip, or that were reorged out on the followed node.
+    #[arg(
+        long,
+        visible_alias = "follow.source.l2",
+        env = "KONA_NODE_FOLLOW_SOURCE_L2",
+        required_if_eq("node_mode", "FollowSource")
+    )]
+    pub follow_source_l2: Option<Url>,
     /// Whether to trust the L2 RPC.
     /// If false, block hash verification is performed for all retrieved blocks.
     #[arg(
@@ -133,9 +182,15 @@ impl Default for NodeCommand {
     fn default() -> Self {
         Self {
             l1_eth_rpc: Url::parse("http://localhost:8545").unwrap(),
+            l1_eth_ws: None,
             l1_trust_rpc: true,
             l1_beacon: Url::parse("http://localhost:5052").unwrap(),
+            l1_beacon_archiver: None,
+            l1_blob_dir: None,
             l2_engine_rpc: Url::parse("http://localhost:8551").unwrap(),
+            l2_shadow_engine_rpc: Vec::new(),
+            follow_source: None,
+            follow_source_l2: None,
             l2_trust_rpc: true,
             l2_engine_jwt_secret: None,
             l2_config_file: None,
@@ -285,7 +340,10 @@ impl NodeCommand {
 
         self.p2p_flags.check_ports()?;
         let p2p_config = self.p2p_flags.config(&cfg, args, Some(self.l1_eth_rpc.clone())).await?;
-        let rpc_config = self.rpc_flags.into();
+        // Unless overridden, the readiness probe requires at least the low-tide peer count.
+        let min_peers = self.rpc_flags.health_min_peers.unwrap_or(self.p2p_flags.peers_lo as usize);
+        let rpc_config = Option::<RpcBuilder>::from(self.rpc_flags)
+            .map(|rpc| rpc.with_health_min_peers(min_peers));
 
         info!(
             target: "rollup_node",
@@ -296,13 +354,32 @@ impl NodeCommand {
             info!(target: "rollup_node", "{hf}");
         }
 
-        RollupNode::builder(cfg, l1_cfg)
+        let mut builder = RollupNode::builder(cfg, l1_cfg);
+        if let Some(archiver) = self.l1_beacon_archiver.as_ref() {
+            builder = builder
+                .with_l1_blob_archive(Arc::new(OnlineBlobArchiver::new_http(archiver.to_string())));
+        }
+        if let Some(dir) = self.l1_blob_dir.as_ref() {
+            builder = builder.with_l1_blob_archive(Arc::new(LocalBlobArchive::new(dir)));
+        }
+        if let Some(ws_url) = self.l1_eth_ws {
+            builder = builder.with_l1_ws_rpc_url(ws_url);
+        }
+        if let Some(follow_source) = self.follow_source {
+            builder = builder.with_follow_source_url(follow_source);
+        }
+        if let Some(follow_source_l2) = self.follow_source_l2 {
+            builder = builder.with_follow_source_l2_url(follow_source_l2);
+        }
+
+        builder
             .with_mode(self.node_mode)
             .with_jwt_secret(jwt_secret)
             .with_l1_provider_rpc_url(self.l1_eth_rpc)
             .with_l1_trust_rpc(self.l1_trust_rpc)
             .with_l1_beacon_api_url(self.l1_beacon)
             .with_l2_engine_rpc_url(self.l2_engine_rpc)
+            .with_l2_shadow_engine_rpc_urls(self.l2_shadow_engine_rpc)
             .with_l2_trust_rpc(self.l2_trust_rpc)
             .with_p2p_config(p2p_config)
             .with_rpc_config(rpc_config)
@@ -426,6 +503,76 @@ mod tests {
         assert_eq!(args.node_mode, NodeMode::Validator);
     }
 
+    #[test]
+    fn test_node_cli_blob_archives() {
+        let archive_flags =
+            ["--l1.beacon-archiver", "http://localhost:8080", "--l1.blob-dir", "blobs"];
+        let args = NodeCommand::parse_from(
+            ["node"].iter().chain(default_flags().iter()).chain(archive_flags.iter()).copied(),
+        );
+        assert_eq!(args.l1_beacon_archiver, Some(Url::parse("http://localhost:8080").unwrap()));
+        assert_eq!(args.l1_blob_dir, Some(PathBuf::from("blobs")));
+    }
+
+    #[test]
+    fn test_node_cli_l1_ws() {
+        let args = NodeCommand::parse_from(["node"].iter().chain(default_flags().iter()).copied());
+        assert_eq!(args.l1_eth_ws, None);
+
+        let ws_flags = ["--l1.ws", "ws://localhost:8546"];
+        let args = NodeCommand::parse_from(
+            ["node"].iter().chain(default_flags().iter()).chain(ws_flags.iter()).copied(),
+        );
+        assert_eq!(args.l1_eth_ws, Some(Url::parse("ws://localhost:8546").unwrap()));
+    }
+
+    #[test]
+    fn test_node_cli_shadow_engines() {
+        let args = NodeCommand::parse_from(["node"].iter().chain(default_flags().iter()).copied());
+        assert!(args.l2_shadow_engine_rpc.is_empty());
+
+        let shadow_flags = ["--l2.shadow", "http://localhost:9551,http://localhost:10551"];
+        let args = NodeCommand::parse_from(
+            ["node"].iter().chain(default_flags().iter()).chain(shadow_flags.iter()).copied(),
+        );
+        assert_eq!(
+            args.l2_shadow_engine_rpc,
+            vec![
+                Url::parse("http://localhost:9551").unwrap(),
+                Url::parse("http://localhost:10551").unwrap()
+            ]
+        );
+    }
+
+    #[test]
+    fn test_node_cli_follow_source() {
+        let err = NodeCommand::try_parse_from(
+            ["node", "--mode", "FollowSource"].iter().chain(default_flags().iter()).copied(),
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("--follow-source"));
+
+        let follow_flags = ["--mode", "FollowSource", "--follow.source", "http://localhost:9545"];
+        let err = NodeCommand::try_parse_from(
+            ["node"].iter().chain(default_flags().iter()).chain(follow_flags.iter()).copied(),
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("--follow-source-l2"));
+
+        let l2_flags = ["--follow.source.l2", "http://localhost:8545"];
+        let args = NodeCommand::parse_from(
+            ["node"]
+                .iter()
+                .chain(default_flags().iter())
+                .chain(follow_flags.iter())
+                .chain(l2_flags.iter())
+                .copied(),
+        );
+        assert_eq!(args.node_mode, NodeMode::FollowSource);
+        assert_eq!(args.follow_source, Some(Url::parse("http://localhost:9545").unwrap()));
+        assert_eq!(args.follow_source_l2, Some(Url::parse("http://localhost:8545").unwrap()));
+    }
+
     #[test]
     fn test_node_cli_missing_l1_eth_rpc() {
         let err = NodeCommand::try_parse_from(["node"]).unwrap_err();
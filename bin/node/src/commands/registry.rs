@@ -6,7 +6,8 @@ use kona_cli::LogConfig;
 
 /// The `registry` Subcommand
 ///
-/// The `registry` subcommand lists the OP Stack chains available in the `superchain-registry`.
+/// The `registry` subcommand lists the OP Stack chains available in the `superchain-registry`,
+/// along with the Mantle chains bundled with `kona-registry`.
 ///
 /// # Usage
 ///
@@ -14,7 +15,7 @@ use kona_cli::LogConfig;
 /// kona-node registry [FLAGS] [OPTIONS]
 /// ```
 #[derive(Parser, Default, PartialEq, Debug, Clone)]
-#[command(about = "Lists the OP Stack chains available in the superchain-registry")]
+#[command(about = "Lists the OP Stack and Mantle chains available in the registry")]
 pub struct RegistryCommand;
 
 impl RegistryCommand {
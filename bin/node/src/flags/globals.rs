@@ -2,7 +2,7 @@
 
 use alloy_primitives::Address;
 use clap::Parser;
-use kona_cli::{LogArgs, MetricsArgs};
+use kona_cli::{ConfigArgs, LogArgs, MetricsArgs, RegistryArgs};
 use kona_genesis::RollupConfig;
 use kona_registry::OPCHAINS;
 
@@ -23,12 +23,18 @@ pub struct GlobalArgs {
         help = "The L2 chain ID to use"
     )]
     pub l2_chain_id: alloy_chains::Chain,
+    /// Chain registry arguments.
+    #[command(flatten)]
+    pub registry: RegistryArgs,
     /// Embed the override flags globally to provide override values adjacent to the configs.
     #[command(flatten)]
     pub override_args: super::OverrideArgs,
     /// Prometheus CLI arguments.
     #[command(flatten)]
     pub metrics: MetricsArgs,
+    /// Configuration file arguments.
+    #[command(flatten)]
+    pub config: ConfigArgs,
 }
 
 impl GlobalArgs {
@@ -16,9 +16,9 @@ use kona_disc::LocalNode;
 use kona_genesis::RollupConfig;
 use kona_gossip::GaterConfig;
 use kona_node_service::NetworkConfig;
-use kona_peers::{BootStoreFile, PeerMonitoring, PeerScoreLevel};
+use kona_peers::{BootStoreFile, PeerMonitoring, PeerScoreLevel, PeerStoreFile};
 use kona_providers_alloy::AlloyChainProvider;
-use libp2p::identity::Keypair;
+use libp2p::{Multiaddr, identity::Keypair};
 use std::{
     net::{IpAddr, SocketAddr},
     num::ParseIntError,
@@ -158,6 +158,13 @@ pub struct P2PArgs {
     /// Disables the bootstore.
     #[arg(long = "p2p.no-bootstore", env = "KONA_NODE_P2P_NO_BOOTSTORE")]
     pub disable_bootstore: bool,
+    /// The path to the peerstore, which persists known peers, peer scores, bans and blocked
+    /// peers, addresses and subnets across restarts.
+    #[arg(long = "p2p.peerstore", env = "KONA_NODE_P2P_PEERSTORE")]
+    pub peerstore: Option<PathBuf>,
+    /// Disables the peerstore.
+    #[arg(long = "p2p.no-peerstore", env = "KONA_NODE_P2P_NO_PEERSTORE")]
+    pub disable_peerstore: bool,
     /// Peer Redialing threshold is the maximum amount of times to attempt to redial a peer that
     /// disconnects. By default, peers are *not* redialed. If set to 0, the peer will be
     /// redialed indefinitely.
@@ -174,6 +181,14 @@ pub struct P2PArgs {
     #[arg(long = "p2p.bootnodes", value_delimiter = ',', env = "KONA_NODE_P2P_BOOTNODES")]
     pub bootnodes: Vec<Enr>,
 
+    /// An optional list of static peers, as multiaddrs that include the peer id
+    /// (e.g. `/ip4/1.2.3.4/tcp/9222/p2p/<peer id>`).
+    ///
+    /// Static peers are always redialed when they disconnect, and are protected from dial
+    /// thresholds and peer score based pruning.
+    #[arg(long = "p2p.static", value_delimiter = ',', env = "KONA_NODE_P2P_STATIC")]
+    pub static_peers: Vec<Multiaddr>,
+
     /// Optionally enable topic scoring.
     ///
     /// Topic scoring is a mechanism to score peers based on their behavior in the gossip network.
@@ -208,7 +223,8 @@ pub struct P2PArgs {
     pub discovery_randomize: Option<u64>,
 
     /// Specify optional remote signer configuration. Note that this argument is mutually exclusive
-    /// with `p2p.sequencer.key` that specifies a local sequencer signer.
+    /// with `p2p.sequencer.key` and `p2p.sequencer.keystore` that specify a local sequencer
+    /// signer.
     #[command(flatten)]
     pub signer: SignerArgs,
 }
@@ -396,6 +412,15 @@ impl P2PArgs {
             ))
         };
 
+        let peer_store = if self.disable_peerstore {
+            None
+        } else {
+            Some(self.peerstore.map_or(
+                PeerStoreFile::Default { chain_id: args.l2_chain_id.into() },
+                PeerStoreFile::Custom,
+            ))
+        };
+
         Ok(NetworkConfig {
             discovery_config,
             discovery_interval: Duration::from_secs(self.discovery_interval),
@@ -415,6 +440,8 @@ impl P2PArgs {
                 dial_period: Duration::from_secs(60 * self.redial_period),
             },
             bootnodes: self.bootnodes,
+            static_peers: self.static_peers,
+            peer_store,
             rollup_config: config.clone(),
             gossip_signer: self.signer.config(args)?,
         })
@@ -540,6 +567,59 @@ mod tests {
         assert_eq!(args.p2p.signer.sequencer_key, Some(key));
     }
 
+    #[test]
+    fn test_p2p_args_sequencer_keystore() {
+        let args = MockCommand::parse_from([
+            "test",
+            "--p2p.sequencer.keystore",
+            "keystore.json",
+            "--p2p.sequencer.keystore.password-file",
+            "password.txt",
+        ]);
+        assert_eq!(args.p2p.signer.keystore, Some(PathBuf::from("keystore.json")));
+        assert_eq!(args.p2p.signer.keystore_password_file, Some(PathBuf::from("password.txt")));
+
+        // The keystore requires a password file, and conflicts with a raw sequencer key.
+        assert!(
+            MockCommand::try_parse_from(["test", "--p2p.sequencer.keystore", "keystore.json"])
+                .is_err()
+        );
+        assert!(
+            MockCommand::try_parse_from([
+                "test",
+                "--p2p.sequencer.keystore",
+                "keystore.json",
+                "--p2p.sequencer.keystore.password-file",
+                "password.txt",
+                "--p2p.sequencer.key",
+                "bcc617ea05150ff60490d3c6058630ba94ae9f12a02a87efd291349ca0e54e0a",
+            ])
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn test_p2p_args_static_peers() {
+        let peer = "16Uiu2HAmJtBf1BW2mmxzoPrsA3YjaDp1HARCPbg96uMPk1cQ6vKX";
+        let first = format!("/ip4/127.0.0.1/tcp/9222/p2p/{peer}");
+        let second = format!("/ip4/127.0.0.2/tcp/9222/p2p/{peer}");
+        let args = MockCommand::parse_from(["test", "--p2p.static", &format!("{first},{second}")]);
+        assert_eq!(args.p2p.static_peers, vec![first.parse().unwrap(), second.parse().unwrap()]);
+
+        let args = MockCommand::parse_from(["test"]);
+        assert!(args.p2p.static_peers.is_empty());
+    }
+
+    #[test]
+    fn test_p2p_args_peerstore() {
+        let args = MockCommand::parse_from(["test", "--p2p.peerstore", "/tmp/peerstore.json"]);
+        assert_eq!(args.p2p.peerstore, Some(PathBuf::from("/tmp/peerstore.json")));
+        assert!(!args.p2p.disable_peerstore);
+
+        let args = MockCommand::parse_from(["test", "--p2p.no-peerstore"]);
+        assert!(args.p2p.disable_peerstore);
+    }
+
     #[test]
     fn test_p2p_args_listen_ip() {
         let args = MockCommand::parse_from(["test", "--p2p.listen.ip", "127.0.0.1"]);
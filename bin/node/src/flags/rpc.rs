@@ -3,10 +3,11 @@
 //! Flags for configuring the RPC server.
 
 use clap::Parser;
-use kona_rpc::RpcBuilder;
+use kona_rpc::{HealthConfig, RpcBuilder};
 use std::{
     net::{IpAddr, SocketAddr},
     path::PathBuf,
+    time::Duration,
 };
 
 /// RPC CLI Arguments
@@ -37,6 +38,32 @@ pub struct RpcArgs {
     /// Enables development RPC endpoints for engine state introspection
     #[arg(long = "rpc.dev-enabled", default_value = "false", env = "KONA_NODE_RPC_DEV_ENABLED")]
     pub dev_enabled: bool,
+    /// Maximum age, in seconds, of the L1 head before `/readyz` fails.
+    #[arg(
+        long = "rpc.health.max-l1-head-age",
+        default_value = "120",
+        env = "KONA_NODE_RPC_HEALTH_MAX_L1_HEAD_AGE"
+    )]
+    pub health_max_l1_head_age: u64,
+    /// Maximum number of blocks the unsafe head may be ahead of the safe head before `/readyz`
+    /// fails.
+    #[arg(
+        long = "rpc.health.max-unsafe-lag",
+        default_value = "1800",
+        env = "KONA_NODE_RPC_HEALTH_MAX_UNSAFE_LAG"
+    )]
+    pub health_max_unsafe_lag: u64,
+    /// Window, in seconds, within which the safe head must advance while there are unsafe blocks
+    /// left to derive. `/readyz` fails if derivation stalls for longer.
+    #[arg(
+        long = "rpc.health.derivation-window",
+        default_value = "600",
+        env = "KONA_NODE_RPC_HEALTH_DERIVATION_WINDOW"
+    )]
+    pub health_derivation_window: u64,
+    /// Minimum number of connected peers before `/readyz` passes. Defaults to `--p2p.peers.lo`.
+    #[arg(long = "rpc.health.min-peers", env = "KONA_NODE_RPC_HEALTH_MIN_PEERS")]
+    pub health_min_peers: Option<usize>,
 }
 
 impl Default for RpcArgs {
@@ -59,6 +86,12 @@ impl From<RpcArgs> for Option<RpcBuilder> {
             admin_persistence: args.admin_persistence,
             ws_enabled: args.ws_enabled,
             dev_enabled: args.dev_enabled,
+            health: HealthConfig {
+                max_l1_head_age: Duration::from_secs(args.health_max_l1_head_age),
+                max_unsafe_lag: args.health_max_unsafe_lag,
+                derivation_window: Duration::from_secs(args.health_derivation_window),
+                min_peers: args.health_min_peers.unwrap_or_default(),
+            },
         })
     }
 }
@@ -77,6 +110,10 @@ mod tests {
     #[case::disable_rpc_alias(&["--rpc.port", "8743"], |args: &mut RpcArgs| { args.listen_port = 8743; })]
     #[case::disable_rpc(&["--rpc.enable-admin"], |args: &mut RpcArgs| { args.enable_admin = true; })]
     #[case::disable_rpc(&["--rpc.admin-state", "/"], |args: &mut RpcArgs| { args.admin_persistence = Some(PathBuf::from("/")); })]
+    #[case::health_max_l1_head_age(&["--rpc.health.max-l1-head-age", "60"], |args: &mut RpcArgs| { args.health_max_l1_head_age = 60; })]
+    #[case::health_max_unsafe_lag(&["--rpc.health.max-unsafe-lag", "100"], |args: &mut RpcArgs| { args.health_max_unsafe_lag = 100; })]
+    #[case::health_derivation_window(&["--rpc.health.derivation-window", "30"], |args: &mut RpcArgs| { args.health_derivation_window = 30; })]
+    #[case::health_min_peers(&["--rpc.health.min-peers", "1"], |args: &mut RpcArgs| { args.health_min_peers = Some(1); })]
     fn test_parse_rpc_args(#[case] args: &[&str], #[case] mutate: impl Fn(&mut RpcArgs)) {
         let args = [&["kona-node"], args].concat();
         let cli = RpcArgs::parse_from(args);
@@ -84,4 +121,22 @@ mod tests {
         mutate(&mut expected);
         assert_eq!(cli, expected);
     }
+
+    #[test]
+    fn test_rpc_args_health_config() {
+        let args = RpcArgs::parse_from([
+            "kona-node",
+            "--rpc.health.max-l1-head-age",
+            "60",
+            "--rpc.health.derivation-window",
+            "30",
+            "--rpc.health.min-peers",
+            "2",
+        ]);
+        let builder = Option::<RpcBuilder>::from(args).unwrap();
+        assert_eq!(builder.health.max_l1_head_age, Duration::from_secs(60));
+        assert_eq!(builder.health.max_unsafe_lag, 1800);
+        assert_eq!(builder.health.derivation_window, Duration::from_secs(30));
+        assert_eq!(builder.health.min_peers, 2);
+    }
 }
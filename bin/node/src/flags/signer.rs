@@ -4,7 +4,7 @@ use alloy_primitives::{Address, B256};
 use alloy_signer::{Signer, k256::ecdsa};
 use alloy_signer_local::PrivateKeySigner;
 use clap::{Parser, arg};
-use kona_sources::{BlockSigner, ClientCert, RemoteSigner};
+use kona_sources::{BlockSigner, ClientCert, KeystoreError, LocalKeystore, RemoteSigner};
 use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
 use std::str::FromStr;
 use url::Url;
@@ -21,6 +21,24 @@ pub struct SignerArgs {
         conflicts_with = "endpoint"
     )]
     pub sequencer_key: Option<B256>,
+    /// An optional path to an encrypted JSON keystore holding the private key for the sequencer
+    /// to sign unsafe blocks. Requires `p2p.sequencer.keystore.password-file`.
+    ///
+    /// This is mutually exclusive with `p2p.sequencer.key` and `p2p.signer.endpoint`.
+    #[arg(
+        long = "p2p.sequencer.keystore",
+        env = "KONA_NODE_P2P_SEQUENCER_KEYSTORE",
+        conflicts_with_all = ["sequencer_key", "endpoint"],
+        requires = "keystore_password_file"
+    )]
+    pub keystore: Option<PathBuf>,
+    /// The path to a file holding the password of `p2p.sequencer.keystore`.
+    #[arg(
+        long = "p2p.sequencer.keystore.password-file",
+        env = "KONA_NODE_P2P_SEQUENCER_KEYSTORE_PASSWORD_FILE",
+        requires = "keystore"
+    )]
+    pub keystore_password_file: Option<PathBuf>,
     /// The URL of the remote signer endpoint. If not provided, remote signer will be disabled.
     /// This is mutually exclusive with `p2p.sequencer.key`.
     /// This is required if any of the other signer flags are provided.
@@ -71,6 +89,15 @@ pub enum SignerArgsParseError {
     /// The local sequencer key and remote signer cannot be specified at the same time.
     #[error("A local sequencer key and a remote signer cannot be specified at the same time.")]
     LocalAndRemoteSigner,
+    /// The sequencer key and keystore cannot be specified at the same time.
+    #[error("A sequencer key and a sequencer keystore cannot be specified at the same time.")]
+    KeyAndKeystore,
+    /// The password file is required if `p2p.sequencer.keystore` is provided.
+    #[error("The password file is required if `p2p.sequencer.keystore` is provided.")]
+    KeystorePasswordRequired,
+    /// The sequencer keystore could not be unlocked.
+    #[error(transparent)]
+    Keystore(#[from] KeystoreError),
     /// The sequencer key is invalid.
     #[error("The sequencer key is invalid.")]
     SequencerKeyInvalid(#[from] ecdsa::Error),
@@ -95,12 +122,12 @@ impl SignerArgs {
     /// Creates a [`BlockSigner`] from the [`SignerArgs`].
     pub fn config(self, args: &GlobalArgs) -> Result<Option<BlockSigner>, SignerArgsParseError> {
         // The sequencer signer obtained from the CLI arguments.
-        let gossip_signer: Option<BlockSigner> = match (self.sequencer_key, self.config_remote()?) {
+        let gossip_signer: Option<BlockSigner> = match (self.config_local()?, self.config_remote()?)
+        {
             (Some(_), Some(_)) => return Err(SignerArgsParseError::LocalAndRemoteSigner),
-            (Some(key), None) => {
-                let signer: BlockSigner = PrivateKeySigner::from_bytes(&key)?
-                    .with_chain_id(Some(args.l2_chain_id.into()))
-                    .into();
+            (Some(signer), None) => {
+                let signer: BlockSigner =
+                    signer.with_chain_id(Some(args.l2_chain_id.into())).into();
                 Some(signer)
             }
             (None, Some(signer)) => Some(signer.into()),
@@ -110,6 +137,26 @@ impl SignerArgs {
         Ok(gossip_signer)
     }
 
+    /// Creates a local [`PrivateKeySigner`] from the [`SignerArgs`], either from the raw sequencer
+    /// key or by decrypting the sequencer keystore.
+    fn config_local(&self) -> Result<Option<PrivateKeySigner>, SignerArgsParseError> {
+        match (self.sequencer_key, &self.keystore, &self.keystore_password_file) {
+            (Some(_), Some(_), _) => Err(SignerArgsParseError::KeyAndKeystore),
+            (Some(key), None, _) => {
+                tracing::warn!(
+                    target: "cli",
+                    "Using a plaintext sequencer key, consider `--p2p.sequencer.keystore` instead"
+                );
+                Ok(Some(PrivateKeySigner::from_bytes(&key)?))
+            }
+            (None, Some(path), Some(password_file)) => {
+                Ok(Some(LocalKeystore::new(path.clone(), password_file.clone()).decrypt()?))
+            }
+            (None, Some(_), None) => Err(SignerArgsParseError::KeystorePasswordRequired),
+            (None, None, _) => Ok(None),
+        }
+    }
+
     /// Creates a [`RemoteSigner`] from the [`SignerArgs`].
     fn config_remote(self) -> Result<Option<RemoteSigner>, SignerArgsParseError> {
         let Some(endpoint) = self.endpoint else {
@@ -3,7 +3,7 @@
 use crate::{flags::SupervisorArgs, metrics::VersionInfo};
 use anyhow::Result;
 use clap::Parser;
-use kona_cli::{LogArgs, LogConfig, MetricsArgs, cli_styles};
+use kona_cli::{ConfigArgs, LogArgs, LogConfig, MetricsArgs, cli_styles};
 use kona_supervisor_service::Service;
 use tracing::{error, info};
 
@@ -22,6 +22,10 @@ pub struct Cli {
     /// Supervisor args
     #[command(flatten)]
     pub supervisor: SupervisorArgs,
+
+    /// Configuration file args
+    #[command(flatten)]
+    pub config: ConfigArgs,
 }
 
 impl Cli {
@@ -11,13 +11,11 @@ pub mod flags;
 pub mod metrics;
 pub(crate) mod version;
 
-use clap::Parser;
-
 fn main() {
     kona_cli::sigsegv_handler::install();
     kona_cli::backtrace::enable();
 
-    if let Err(err) = cli::Cli::parse().run() {
+    if let Err(err) = kona_cli::parse_with_config::<cli::Cli>().run() {
         eprintln!("Error: {err:?}");
         std::process::exit(1);
     }
@@ -70,3 +70,6 @@ pub use handler::{Discv5Handler, HandlerRequest};
 
 mod metrics;
 pub use metrics::Metrics;
+
+#[cfg(any(test, feature = "test-utils"))]
+pub mod test_utils;
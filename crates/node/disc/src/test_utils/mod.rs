@@ -0,0 +1,7 @@
+//! Test utilities for `kona-disc`.
+//!
+//! Provides a [`TestDiscovery`] network of in-process [`Discv5Driver`](crate::Discv5Driver)s,
+//! listening on loopback UDP ports.
+
+mod network;
+pub use network::{TEST_CHAIN_ID, TestDiscNode, TestDiscovery, TestDiscoveryError};
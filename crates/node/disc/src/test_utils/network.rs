@@ -0,0 +1,271 @@
+//! An in-process network of [`Discv5Driver`]s.
+
+use crate::{
+    Discv5Builder, Discv5BuilderError, Discv5Driver, Discv5Handler, HandlerRequest, LocalNode,
+};
+use discv5::{
+    ConfigBuilder, Enr, ListenConfig,
+    enr::{CombinedKey, NodeId},
+};
+use std::{
+    net::{IpAddr, Ipv4Addr, UdpSocket},
+    time::Duration,
+};
+use tokio::{sync::mpsc, time::Instant};
+
+/// The L2 chain id of the [`TestDiscovery`] network.
+///
+/// The chain is not in the registry, so the drivers never dial the public bootnodes.
+pub const TEST_CHAIN_ID: u64 = 901;
+
+/// The interval at which the drivers look up random nodes.
+const LOOKUP_INTERVAL: Duration = Duration::from_millis(100);
+
+/// The interval at which [`TestDiscovery::run_until`] polls the drivers.
+const POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// The number of ports a node is spawned on before giving up.
+const SPAWN_ATTEMPTS: usize = 3;
+
+/// An error returned while setting up a [`TestDiscovery`] network.
+#[derive(Debug, thiserror::Error)]
+pub enum TestDiscoveryError {
+    /// An error occurred building a discovery driver.
+    #[error(transparent)]
+    Builder(#[from] Discv5BuilderError),
+    /// An error occurred reserving a UDP port for a discovery driver.
+    #[error("error reserving a discovery port: {0}")]
+    Port(#[from] std::io::Error),
+    /// The discovery driver failed to bind any of the reserved ports.
+    #[error("the discovery driver failed to bind a port after {0} attempts")]
+    Unbound(usize),
+}
+
+/// A node of the [`TestDiscovery`] network.
+#[derive(Debug)]
+pub struct TestDiscNode {
+    /// The handler to the spawned discovery driver of the node.
+    pub handler: Discv5Handler,
+    /// The local ENR of the node.
+    pub enr: Enr,
+    /// The receiver of the ENRs the driver forwards to the gossip layer.
+    pub enr_rx: mpsc::Receiver<Enr>,
+    /// The ENRs the driver forwarded to the gossip layer.
+    pub forwarded: Vec<Enr>,
+    /// The ENRs in the discovery table of the node, as of the last poll.
+    pub table: Vec<Enr>,
+}
+
+impl TestDiscNode {
+    /// Returns the node id of the node.
+    pub fn node_id(&self) -> NodeId {
+        self.enr.node_id()
+    }
+
+    /// Returns whether the given node is in the discovery table of the node.
+    pub fn knows(&self, node_id: &NodeId) -> bool {
+        self.table.iter().any(|enr| enr.node_id() == *node_id)
+    }
+
+    /// Returns whether the node forwarded the given node to the gossip layer.
+    pub fn has_forwarded(&self, node_id: &NodeId) -> bool {
+        self.forwarded.iter().any(|enr| enr.node_id() == *node_id)
+    }
+
+    /// Polls the discovery table and the forwarded ENRs of the node.
+    async fn poll(&mut self) {
+        if let Ok(table) = self.handler.table_enrs().await {
+            self.table = table;
+        }
+        while let Ok(enr) = self.enr_rx.try_recv() {
+            self.forwarded.push(enr);
+        }
+    }
+}
+
+/// An in-process network of [`Discv5Driver`]s, listening on loopback UDP ports.
+///
+/// Every node but the first is bootstrapped off the first node, and finds the rest of the network
+/// through discv5 lookups. The drivers run in their own tasks; their discovery tables and the
+/// ENRs they forward to the gossip layer are polled with [`TestDiscovery::run_until`] and
+/// [`TestDiscovery::run_for`].
+#[derive(Debug, Default)]
+pub struct TestDiscovery {
+    /// The nodes of the network.
+    pub nodes: Vec<TestDiscNode>,
+}
+
+impl TestDiscovery {
+    /// Spawns a network of `size` nodes.
+    pub async fn new(size: usize) -> Result<Self, TestDiscoveryError> {
+        Self::with_builder(size, |builder| builder).await
+    }
+
+    /// Spawns a network of `size` nodes.
+    ///
+    /// The `configure` closure is applied to the [`Discv5Builder`] of every node.
+    pub async fn with_builder(
+        size: usize,
+        configure: impl Fn(Discv5Builder) -> Discv5Builder,
+    ) -> Result<Self, TestDiscoveryError> {
+        let mut network = Self::default();
+        for _ in 0..size {
+            network.spawn(TEST_CHAIN_ID, &configure).await?;
+        }
+        Ok(network)
+    }
+
+    /// Spawns a node for the given chain id, bootstrapped off the first node of the network.
+    ///
+    /// Returns the index of the node once its driver listens on its port.
+    ///
+    /// The ENR of a node must advertise the port its driver listens on, which is known before the
+    /// driver binds it. A free port is reserved and released right before the driver binds it, so
+    /// another socket can take it in between: the driver then fails to start, and the node is
+    /// spawned again on a fresh port.
+    pub async fn spawn(
+        &mut self,
+        chain_id: u64,
+        configure: impl Fn(Discv5Builder) -> Discv5Builder,
+    ) -> Result<usize, TestDiscoveryError> {
+        for _ in 0..SPAWN_ATTEMPTS {
+            // The driver only answers once its service is started, and exits if it cannot bind
+            // its port.
+            let node = self.try_spawn(chain_id, &configure)?;
+            if node.handler.local_enr().await.is_ok() {
+                self.nodes.push(node);
+                return Ok(self.nodes.len() - 1);
+            }
+        }
+        Err(TestDiscoveryError::Unbound(SPAWN_ATTEMPTS))
+    }
+
+    /// Spawns the driver of a node for the given chain id on a free port.
+    fn try_spawn(
+        &self,
+        chain_id: u64,
+        configure: impl Fn(Discv5Builder) -> Discv5Builder,
+    ) -> Result<TestDiscNode, TestDiscoveryError> {
+        let CombinedKey::Secp256k1(signing_key) = CombinedKey::generate_secp256k1() else {
+            unreachable!()
+        };
+        let ip = IpAddr::V4(Ipv4Addr::LOCALHOST);
+        let port = UdpSocket::bind((ip, 0))?.local_addr()?.port();
+
+        let builder = Discv5Driver::builder(
+            LocalNode::new(signing_key, ip, port, port),
+            chain_id,
+            ConfigBuilder::new(ListenConfig::from_ip(ip, port)).build(),
+        )
+        .with_interval(LOOKUP_INTERVAL)
+        .with_bootnodes(self.nodes.first().map(|node| node.enr.clone()).into_iter().collect());
+        let driver = configure(builder).build()?;
+        let enr = driver.disc.local_enr();
+        let (handler, enr_rx) = driver.start();
+
+        Ok(TestDiscNode { handler, enr, enr_rx, forwarded: Vec::new(), table: Vec::new() })
+    }
+
+    /// Adds the ENR of node `to` to the discovery table of node `from`, regardless of its chain.
+    pub async fn connect(&self, from: usize, to: usize) {
+        let enr = self.nodes[to].enr.clone();
+        let _ = self.nodes[from].handler.sender.send(HandlerRequest::AddEnr(enr)).await;
+    }
+
+    /// Returns whether every node has every other node in its discovery table.
+    pub fn is_fully_discovered(&self) -> bool {
+        self.nodes.iter().all(|node| {
+            self.nodes
+                .iter()
+                .all(|peer| peer.node_id() == node.node_id() || node.knows(&peer.node_id()))
+        })
+    }
+
+    /// Polls the network until the condition holds, or until the timeout elapses.
+    ///
+    /// Returns whether the condition holds.
+    pub async fn run_until(
+        &mut self,
+        timeout: Duration,
+        condition: impl Fn(&Self) -> bool,
+    ) -> bool {
+        let deadline = Instant::now() + timeout;
+        loop {
+            for node in &mut self.nodes {
+                // A driver still starting up does not answer until its service is started.
+                let _ = tokio::time::timeout_at(deadline, node.poll()).await;
+            }
+            if condition(self) {
+                return true;
+            }
+            if Instant::now() >= deadline {
+                return false;
+            }
+            tokio::time::sleep(POLL_INTERVAL).await;
+        }
+    }
+
+    /// Polls the network for the given duration.
+    pub async fn run_for(&mut self, duration: Duration) {
+        self.run_until(duration, |_| false).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TIMEOUT: Duration = Duration::from_secs(30);
+
+    #[tokio::test]
+    async fn test_full_discovery() {
+        let mut network = TestDiscovery::new(4).await.unwrap();
+        assert!(network.run_until(TIMEOUT, TestDiscovery::is_fully_discovered).await);
+    }
+
+    #[tokio::test]
+    async fn test_forwards_discovered_enrs() {
+        let mut network = TestDiscovery::new(3).await.unwrap();
+        let ids: Vec<_> = network.nodes.iter().map(TestDiscNode::node_id).collect();
+
+        // Every valid ENR a node discovers, through sessions or lookups, is forwarded to the
+        // gossip layer.
+        assert!(
+            network
+                .run_until(TIMEOUT, |n| {
+                    n.nodes.iter().all(|node| {
+                        ids.iter().all(|id| *id == node.node_id() || node.has_forwarded(id))
+                    })
+                })
+                .await
+        );
+    }
+
+    #[tokio::test]
+    async fn test_foreign_chain_not_forwarded() {
+        let mut network = TestDiscovery::new(2).await.unwrap();
+        let foreign = network.spawn(TEST_CHAIN_ID + 1, |builder| builder).await.unwrap();
+        network.connect(0, foreign).await;
+        let (peer, foreign_id) = (network.nodes[1].node_id(), network.nodes[foreign].node_id());
+
+        // ENRs of other chains reach the discovery table, but are not forwarded.
+        assert!(
+            network
+                .run_until(TIMEOUT, |n| n.nodes[0].has_forwarded(&peer) &&
+                    n.nodes[0].knows(&foreign_id))
+                .await
+        );
+        network.run_for(Duration::from_secs(1)).await;
+        assert!(!network.nodes[0].has_forwarded(&foreign_id));
+    }
+
+    #[tokio::test]
+    async fn test_late_join() {
+        let mut network = TestDiscovery::new(3).await.unwrap();
+        assert!(network.run_until(TIMEOUT, TestDiscovery::is_fully_discovered).await);
+
+        // A node joining later still finds the whole network through the first node.
+        network.spawn(TEST_CHAIN_ID, |builder| builder).await.unwrap();
+        assert!(network.run_until(TIMEOUT, TestDiscovery::is_fully_discovered).await);
+    }
+}
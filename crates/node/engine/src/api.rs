@@ -0,0 +1,132 @@
+//! The [`EngineApi`] trait, used by the engine tasks to communicate with the execution layer.
+
+use crate::EngineClientError;
+use alloy_eips::eip1898::BlockNumberOrTag;
+use alloy_primitives::B256;
+use alloy_rpc_types_engine::{
+    ExecutionPayloadEnvelopeV2, ExecutionPayloadInputV2, ExecutionPayloadV1, ExecutionPayloadV3,
+    ForkchoiceState, ForkchoiceUpdated, PayloadId, PayloadStatus,
+};
+use alloy_rpc_types_eth::Block;
+use alloy_transport::TransportResult;
+use async_trait::async_trait;
+use kona_genesis::RollupConfig;
+use kona_protocol::{BlockInfo, L2BlockInfo};
+use kona_sources::{L2ForkchoiceState, SyncStartError};
+use op_alloy_rpc_types::Transaction;
+use op_alloy_rpc_types_engine::{
+    OpExecutionPayloadEnvelopeV3, OpExecutionPayloadEnvelopeV4, OpExecutionPayloadV4,
+    OpPayloadAttributes,
+};
+use std::fmt::Debug;
+
+/// The interface between the engine tasks and the execution layer.
+///
+/// The [`Engine`] and its tasks are generic over this trait, so that they can be driven by the
+/// JWT-authenticated [`EngineClient`] in production and by an in-memory execution layer in tests.
+/// The Engine API methods mirror their `engine_*` JSON-RPC counterparts, and are selected by the
+/// tasks based on the hardforks active at the payload timestamp.
+///
+/// [`Engine`]: crate::Engine
+/// [`EngineClient`]: crate::EngineClient
+#[async_trait]
+pub trait EngineApi: Debug + Send + Sync + 'static {
+    /// Returns the [`RollupConfig`] of the chain.
+    fn cfg(&self) -> &RollupConfig;
+
+    /// Sends an `engine_newPayloadV1` call.
+    async fn new_payload_v1(&self, payload: ExecutionPayloadV1) -> TransportResult<PayloadStatus>;
+
+    /// Sends an `engine_newPayloadV2` call.
+    async fn new_payload_v2(
+        &self,
+        payload: ExecutionPayloadInputV2,
+    ) -> TransportResult<PayloadStatus>;
+
+    /// Sends an `engine_newPayloadV3` call.
+    async fn new_payload_v3(
+        &self,
+        payload: ExecutionPayloadV3,
+        parent_beacon_block_root: B256,
+    ) -> TransportResult<PayloadStatus>;
+
+    /// Sends an `engine_newPayloadV4` call.
+    async fn new_payload_v4(
+        &self,
+        payload: OpExecutionPayloadV4,
+        parent_beacon_block_root: B256,
+    ) -> TransportResult<PayloadStatus>;
+
+    /// Sends an `engine_forkchoiceUpdatedV2` call.
+    async fn fork_choice_updated_v2(
+        &self,
+        fork_choice_state: ForkchoiceState,
+        payload_attributes: Option<OpPayloadAttributes>,
+    ) -> TransportResult<ForkchoiceUpdated>;
+
+    /// Sends an `engine_forkchoiceUpdatedV3` call.
+    async fn fork_choice_updated_v3(
+        &self,
+        fork_choice_state: ForkchoiceState,
+        payload_attributes: Option<OpPayloadAttributes>,
+    ) -> TransportResult<ForkchoiceUpdated>;
+
+    /// Sends an `engine_getPayloadV2` call.
+    async fn get_payload_v2(
+        &self,
+        payload_id: PayloadId,
+    ) -> TransportResult<ExecutionPayloadEnvelopeV2>;
+
+    /// Sends an `engine_getPayloadV3` call.
+    async fn get_payload_v3(
+        &self,
+        payload_id: PayloadId,
+    ) -> TransportResult<OpExecutionPayloadEnvelopeV3>;
+
+    /// Sends an `engine_getPayloadV4` call.
+    async fn get_payload_v4(
+        &self,
+        payload_id: PayloadId,
+    ) -> TransportResult<OpExecutionPayloadEnvelopeV4>;
+
+    /// Fetches the L2 [`Block`] for the given [`BlockNumberOrTag`], with full transactions.
+    async fn l2_block_by_label(
+        &self,
+        numtag: BlockNumberOrTag,
+    ) -> Result<Option<Block<Transaction>>, EngineClientError>;
+
+    /// Fetches the L2 [`Block`] with the given hash, with full transactions.
+    async fn l2_block_by_hash(
+        &self,
+        hash: B256,
+    ) -> Result<Option<Block<Transaction>>, EngineClientError>;
+
+    /// Fetches the [`L2BlockInfo`] for the given [`BlockNumberOrTag`].
+    async fn l2_block_info_by_label(
+        &self,
+        numtag: BlockNumberOrTag,
+    ) -> Result<Option<L2BlockInfo>, EngineClientError> {
+        let Some(block) = self.l2_block_by_label(numtag).await? else {
+            return Ok(None);
+        };
+        Ok(Some(L2BlockInfo::from_block_and_genesis(&block.into_consensus(), &self.cfg().genesis)?))
+    }
+
+    /// Fetches the L1 [`BlockInfo`] at the given block number.
+    async fn l1_block_info_by_number(
+        &self,
+        number: u64,
+    ) -> Result<Option<BlockInfo>, EngineClientError>;
+
+    /// Fetches the storage root of the `L2ToL1MessagePasser` predeploy at the given L2 block.
+    ///
+    /// Only used to compute output roots before Isthmus. After Isthmus, the storage root is
+    /// committed to in the block header's withdrawals root.
+    async fn message_passer_storage_root(
+        &self,
+        block: BlockNumberOrTag,
+    ) -> Result<B256, EngineClientError>;
+
+    /// Finds the forkchoice state that the engine should be reset to.
+    async fn sync_start(&self) -> Result<L2ForkchoiceState, SyncStartError>;
+}
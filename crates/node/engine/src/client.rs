@@ -1,6 +1,6 @@
 //! An Engine API Client.
 
-use crate::Metrics;
+use crate::{EngineApi, Metrics};
 use alloy_eips::eip1898::BlockNumberOrTag;
 use alloy_network::Network;
 use alloy_primitives::{B256, BlockHash, Bytes};
@@ -8,7 +8,8 @@ use alloy_provider::{Provider, RootProvider};
 use alloy_rpc_client::RpcClient;
 use alloy_rpc_types_engine::{
     ClientVersionV1, ExecutionPayloadBodiesV1, ExecutionPayloadEnvelopeV2, ExecutionPayloadInputV2,
-    ExecutionPayloadV3, ForkchoiceState, ForkchoiceUpdated, JwtSecret, PayloadId, PayloadStatus,
+    ExecutionPayloadV1, ExecutionPayloadV3, ForkchoiceState, ForkchoiceUpdated, JwtSecret,
+    PayloadId, PayloadStatus,
 };
 use alloy_rpc_types_eth::Block;
 use alloy_transport::{RpcError, TransportErrorKind, TransportResult};
@@ -22,7 +23,8 @@ use alloy_transport_http::{
 use derive_more::Deref;
 use http_body_util::Full;
 use kona_genesis::RollupConfig;
-use kona_protocol::{FromBlockError, L2BlockInfo};
+use kona_protocol::{BlockInfo, FromBlockError, Predeploys};
+use kona_sources::{L2ForkchoiceState, SyncStartError, find_starting_forkchoice};
 use op_alloy_network::Optimism;
 use op_alloy_provider::ext::engine::OpEngineApi;
 use op_alloy_rpc_types::Transaction;
@@ -130,26 +132,141 @@ impl EngineClient {
     pub fn cfg(&self) -> &RollupConfig {
         self.cfg.as_ref()
     }
+}
+
+#[async_trait::async_trait]
+impl EngineApi for EngineClient {
+    fn cfg(&self) -> &RollupConfig {
+        self.cfg.as_ref()
+    }
+
+    async fn new_payload_v1(&self, payload: ExecutionPayloadV1) -> TransportResult<PayloadStatus> {
+        <RootProvider<Optimism> as alloy_provider::ext::EngineApi<Optimism>>::new_payload_v1(
+            &self.engine,
+            payload,
+        )
+        .await
+    }
+
+    async fn new_payload_v2(
+        &self,
+        payload: ExecutionPayloadInputV2,
+    ) -> TransportResult<PayloadStatus> {
+        <Self as OpEngineApi<Optimism, Http<HyperAuthClient>>>::new_payload_v2(self, payload).await
+    }
 
-    /// Fetches the [`Block<T>`] for the given [`BlockNumberOrTag`].
-    pub async fn l2_block_by_label(
+    async fn new_payload_v3(
+        &self,
+        payload: ExecutionPayloadV3,
+        parent_beacon_block_root: B256,
+    ) -> TransportResult<PayloadStatus> {
+        <Self as OpEngineApi<Optimism, Http<HyperAuthClient>>>::new_payload_v3(
+            self,
+            payload,
+            parent_beacon_block_root,
+        )
+        .await
+    }
+
+    async fn new_payload_v4(
+        &self,
+        payload: OpExecutionPayloadV4,
+        parent_beacon_block_root: B256,
+    ) -> TransportResult<PayloadStatus> {
+        <Self as OpEngineApi<Optimism, Http<HyperAuthClient>>>::new_payload_v4(
+            self,
+            payload,
+            parent_beacon_block_root,
+        )
+        .await
+    }
+
+    async fn fork_choice_updated_v2(
+        &self,
+        fork_choice_state: ForkchoiceState,
+        payload_attributes: Option<OpPayloadAttributes>,
+    ) -> TransportResult<ForkchoiceUpdated> {
+        <Self as OpEngineApi<Optimism, Http<HyperAuthClient>>>::fork_choice_updated_v2(
+            self,
+            fork_choice_state,
+            payload_attributes,
+        )
+        .await
+    }
+
+    async fn fork_choice_updated_v3(
+        &self,
+        fork_choice_state: ForkchoiceState,
+        payload_attributes: Option<OpPayloadAttributes>,
+    ) -> TransportResult<ForkchoiceUpdated> {
+        <Self as OpEngineApi<Optimism, Http<HyperAuthClient>>>::fork_choice_updated_v3(
+            self,
+            fork_choice_state,
+            payload_attributes,
+        )
+        .await
+    }
+
+    async fn get_payload_v2(
+        &self,
+        payload_id: PayloadId,
+    ) -> TransportResult<ExecutionPayloadEnvelopeV2> {
+        <Self as OpEngineApi<Optimism, Http<HyperAuthClient>>>::get_payload_v2(self, payload_id)
+            .await
+    }
+
+    async fn get_payload_v3(
+        &self,
+        payload_id: PayloadId,
+    ) -> TransportResult<OpExecutionPayloadEnvelopeV3> {
+        <Self as OpEngineApi<Optimism, Http<HyperAuthClient>>>::get_payload_v3(self, payload_id)
+            .await
+    }
+
+    async fn get_payload_v4(
+        &self,
+        payload_id: PayloadId,
+    ) -> TransportResult<OpExecutionPayloadEnvelopeV4> {
+        <Self as OpEngineApi<Optimism, Http<HyperAuthClient>>>::get_payload_v4(self, payload_id)
+            .await
+    }
+
+    async fn l2_block_by_label(
         &self,
         numtag: BlockNumberOrTag,
     ) -> Result<Option<Block<Transaction>>, EngineClientError> {
         Ok(<RootProvider<Optimism>>::get_block_by_number(&self.engine, numtag).full().await?)
     }
 
-    /// Fetches the [L2BlockInfo] by [BlockNumberOrTag].
-    pub async fn l2_block_info_by_label(
+    async fn l2_block_by_hash(
         &self,
-        numtag: BlockNumberOrTag,
-    ) -> Result<Option<L2BlockInfo>, EngineClientError> {
-        let block =
-            <RootProvider<Optimism>>::get_block_by_number(&self.engine, numtag).full().await?;
-        let Some(block) = block else {
-            return Ok(None);
-        };
-        Ok(Some(L2BlockInfo::from_block_and_genesis(&block.into_consensus(), &self.cfg.genesis)?))
+        hash: B256,
+    ) -> Result<Option<Block<Transaction>>, EngineClientError> {
+        Ok(<RootProvider<Optimism>>::get_block_by_hash(&self.engine, hash).full().await?)
+    }
+
+    async fn l1_block_info_by_number(
+        &self,
+        number: u64,
+    ) -> Result<Option<BlockInfo>, EngineClientError> {
+        let block = self.l1_provider.get_block(number.into()).await?;
+        Ok(block.map(|block| block.into_consensus().into()))
+    }
+
+    async fn message_passer_storage_root(
+        &self,
+        block: BlockNumberOrTag,
+    ) -> Result<B256, EngineClientError> {
+        let proof = self
+            .engine
+            .get_proof(Predeploys::L2_TO_L1_MESSAGE_PASSER, Default::default())
+            .block_id(block.into())
+            .await?;
+        Ok(proof.storage_hash)
+    }
+
+    async fn sync_start(&self) -> Result<L2ForkchoiceState, SyncStartError> {
+        find_starting_forkchoice(&self.cfg, &self.l1_provider, &self.engine).await
     }
 }
 
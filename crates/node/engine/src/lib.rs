@@ -27,6 +27,9 @@
 //!
 //! - **Task Queue** - Core engine task queue and execution logic via [`Engine`]
 //! - **Client** - HTTP client for Engine API communication via [`EngineClient`]
+//! - **API** - The execution layer interface the engine tasks are generic over via [`EngineApi`]
+//! - **Multiplexer** - Mirroring of Engine API calls to shadow execution layers via
+//!   [`EngineMultiplexer`]
 //! - **State** - Engine state management and synchronization via [`EngineState`]
 //! - **Versions** - Engine API version selection via [`EngineForkchoiceVersion`],
 //!   [`EngineNewPayloadVersion`], [`EngineGetPayloadVersion`]
@@ -42,16 +45,22 @@ mod task_queue;
 pub use task_queue::{
     BuildTask, BuildTaskError, ConsolidateTask, ConsolidateTaskError, Engine, EngineBuildError,
     EngineResetError, EngineTask, EngineTaskError, EngineTaskErrorSeverity, EngineTaskErrors,
-    EngineTaskExt, FinalizeTask, FinalizeTaskError, InsertTask, InsertTaskError, SynchronizeTask,
-    SynchronizeTaskError,
+    EngineTaskExt, FinalizeTask, FinalizeTaskError, InsertTask, InsertTaskError, PromoteTask,
+    PromoteTaskError, SynchronizeTask, SynchronizeTaskError,
 };
 
 mod attributes;
 pub use attributes::{AttributesMatch, AttributesMismatch};
 
+mod api;
+pub use api::EngineApi;
+
 mod client;
 pub use client::{EngineClient, EngineClientError};
 
+mod multiplexer;
+pub use multiplexer::EngineMultiplexer;
+
 mod versions;
 pub use versions::{EngineForkchoiceVersion, EngineGetPayloadVersion, EngineNewPayloadVersion};
 
@@ -66,3 +75,6 @@ pub use query::{EngineQueries, EngineQueriesError, EngineQuerySender};
 
 mod metrics;
 pub use metrics::Metrics;
+
+#[cfg(any(test, feature = "test-utils"))]
+pub mod test_utils;
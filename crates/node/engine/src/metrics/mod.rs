@@ -11,6 +11,7 @@
 /// - Block progression through safety levels (unsafe → finalized)
 /// - Task execution success/failure rates by type
 /// - Engine API method call latencies
+/// - Divergences of shadow execution layers from the primary
 ///
 /// # Usage
 ///
@@ -59,6 +60,8 @@ impl Metrics {
     pub const BUILD_TASK_LABEL: &str = "build";
     /// Finalize task label.
     pub const FINALIZE_TASK_LABEL: &str = "finalize";
+    /// Promote task label.
+    pub const PROMOTE_TASK_LABEL: &str = "promote";
 
     /// Identifier for the histogram that tracks engine method call time.
     pub const ENGINE_METHOD_REQUEST_DURATION: &str = "kona_node_engine_method_request_duration";
@@ -72,6 +75,11 @@ impl Metrics {
     /// Identifier for the counter that tracks the number of times the engine has been reset.
     pub const ENGINE_RESET_COUNT: &str = "kona_node_engine_reset_count";
 
+    /// Identifier for the counter that records the outcomes of calls mirrored to shadow execution
+    /// layers, by shadow, method and outcome (`match`, `lagging`, `diverged`, `error` or
+    /// `dropped`).
+    pub const SHADOW_ENGINE_RESULT: &str = "kona_node_engine_shadow_result";
+
     /// Initializes metrics for the engine.
     ///
     /// This does two things:
@@ -106,6 +114,13 @@ impl Metrics {
             metrics::Unit::Count,
             "Engine reset count"
         );
+
+        // Shadow engine results
+        metrics::describe_counter!(
+            Self::SHADOW_ENGINE_RESULT,
+            metrics::Unit::Count,
+            "Outcomes of calls mirrored to shadow execution layers"
+        );
     }
 
     /// Initializes metrics to `0` so they can be queried immediately by consumers of prometheus
@@ -117,11 +132,13 @@ impl Metrics {
         kona_macros::set!(counter, Self::ENGINE_TASK_SUCCESS, Self::CONSOLIDATE_TASK_LABEL, 0);
         kona_macros::set!(counter, Self::ENGINE_TASK_SUCCESS, Self::BUILD_TASK_LABEL, 0);
         kona_macros::set!(counter, Self::ENGINE_TASK_SUCCESS, Self::FINALIZE_TASK_LABEL, 0);
+        kona_macros::set!(counter, Self::ENGINE_TASK_SUCCESS, Self::PROMOTE_TASK_LABEL, 0);
 
         kona_macros::set!(counter, Self::ENGINE_TASK_FAILURE, Self::INSERT_TASK_LABEL, 0);
         kona_macros::set!(counter, Self::ENGINE_TASK_FAILURE, Self::CONSOLIDATE_TASK_LABEL, 0);
         kona_macros::set!(counter, Self::ENGINE_TASK_FAILURE, Self::BUILD_TASK_LABEL, 0);
         kona_macros::set!(counter, Self::ENGINE_TASK_FAILURE, Self::FINALIZE_TASK_LABEL, 0);
+        kona_macros::set!(counter, Self::ENGINE_TASK_FAILURE, Self::PROMOTE_TASK_LABEL, 0);
 
         // Engine reset count
         kona_macros::set!(counter, Self::ENGINE_RESET_COUNT, 0);
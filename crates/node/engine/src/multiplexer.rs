@@ -0,0 +1,561 @@
+//! An [`EngineApi`] multiplexer that mirrors consensus-critical calls to shadow execution layers.
+
+use crate::{EngineApi, EngineClient, EngineClientError, Metrics};
+use alloy_eips::eip1898::BlockNumberOrTag;
+use alloy_primitives::B256;
+use alloy_rpc_types_engine::{
+    ExecutionPayloadEnvelopeV2, ExecutionPayloadInputV2, ExecutionPayloadV1, ExecutionPayloadV3,
+    ForkchoiceState, ForkchoiceUpdated, PayloadId, PayloadStatus, PayloadStatusEnum,
+};
+use alloy_rpc_types_eth::Block;
+use alloy_transport::TransportResult;
+use async_trait::async_trait;
+use kona_genesis::RollupConfig;
+use kona_protocol::BlockInfo;
+use kona_sources::{L2ForkchoiceState, SyncStartError};
+use op_alloy_rpc_types::Transaction;
+use op_alloy_rpc_types_engine::{
+    OpExecutionPayloadEnvelopeV3, OpExecutionPayloadEnvelopeV4, OpExecutionPayloadV4,
+    OpPayloadAttributes,
+};
+use std::sync::Arc;
+use tokio::sync::mpsc::{self, error::TrySendError};
+
+/// The number of mirrored calls that may be queued for a single shadow execution layer before
+/// further calls are dropped.
+const SHADOW_QUEUE_CAPACITY: usize = 1024;
+
+/// An [`EngineApi`] that drives a primary execution layer and mirrors every `engine_newPayload`
+/// and `engine_forkchoiceUpdated` call to a set of shadow execution layers.
+///
+/// Every call is answered by the primary alone, so a shadow can never affect consensus. Once the
+/// primary has responded, the call is queued for each shadow, which replays its queue in order on
+/// a background task and compares the returned [`PayloadStatus`] with the primary's. A shadow
+/// that considers a payload or forkchoice state `INVALID` while the primary considers it `VALID`
+/// (or vice versa), or that reports a different latest valid hash, has diverged: the divergence
+/// is logged as an error with both statuses, and counted per shadow in
+/// [`Metrics::SHADOW_ENGINE_RESULT`]. A shadow that is still syncing is only counted as lagging.
+///
+/// Shadows never build blocks: payload attributes are stripped from mirrored forkchoice updates,
+/// and blocks built by the primary reach the shadows through the mirrored `engine_newPayload`
+/// call that inserts them. Calls that fail on the primary are not mirrored. A shadow that falls
+/// more than 1024 calls behind has further calls dropped rather than slowing the primary down.
+#[derive(Debug)]
+pub struct EngineMultiplexer<P = EngineClient> {
+    /// The primary execution layer, whose responses are returned to the engine tasks.
+    primary: P,
+    /// The shadow execution layers.
+    shadows: Vec<ShadowEngine>,
+}
+
+/// A handle to the background task mirroring calls to a shadow execution layer.
+#[derive(Debug)]
+struct ShadowEngine {
+    /// The name of the shadow execution layer.
+    name: String,
+    /// The queue of calls to mirror.
+    calls: mpsc::Sender<ShadowCall>,
+    /// The outcomes of the mirrored calls, in order.
+    #[cfg(test)]
+    outcomes: mpsc::UnboundedReceiver<ShadowOutcome>,
+}
+
+/// A call mirrored to a shadow execution layer, with the primary's response to it.
+#[derive(Debug)]
+struct ShadowCall {
+    /// The request to send to the shadow.
+    request: ShadowRequest,
+    /// The [`PayloadStatus`] returned by the primary.
+    primary_status: PayloadStatus,
+}
+
+/// A consensus-critical Engine API request.
+#[derive(Debug, Clone)]
+enum ShadowRequest {
+    /// An `engine_newPayloadV1` request.
+    NewPayloadV1(ExecutionPayloadV1),
+    /// An `engine_newPayloadV2` request.
+    NewPayloadV2(ExecutionPayloadInputV2),
+    /// An `engine_newPayloadV3` request, with the parent beacon block root.
+    NewPayloadV3(ExecutionPayloadV3, B256),
+    /// An `engine_newPayloadV4` request, with the parent beacon block root.
+    NewPayloadV4(OpExecutionPayloadV4, B256),
+    /// An `engine_forkchoiceUpdatedV2` request, without payload attributes.
+    ForkchoiceUpdatedV2(ForkchoiceState),
+    /// An `engine_forkchoiceUpdatedV3` request, without payload attributes.
+    ForkchoiceUpdatedV3(ForkchoiceState),
+}
+
+/// The outcome of comparing a shadow execution layer's response with the primary's.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, derive_more::Display)]
+enum ShadowOutcome {
+    /// The shadow agreed with the primary.
+    #[display("match")]
+    Match,
+    /// Either the shadow or the primary could not yet validate the call.
+    #[display("lagging")]
+    Lagging,
+    /// The shadow disagreed with the primary.
+    #[display("diverged")]
+    Diverged,
+}
+
+impl<P: EngineApi> EngineMultiplexer<P> {
+    /// Creates a new [`EngineMultiplexer`] without any shadow execution layers.
+    pub const fn new(primary: P) -> Self {
+        Self { primary, shadows: Vec::new() }
+    }
+
+    /// Adds a shadow execution layer, identified by `name` in logs and metrics.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called outside of a Tokio runtime, as the shadow's calls are mirrored on a
+    /// background task.
+    pub fn with_shadow(mut self, name: impl Into<String>, shadow: Arc<dyn EngineApi>) -> Self {
+        let name = name.into();
+        let (calls, calls_rx) = mpsc::channel(SHADOW_QUEUE_CAPACITY);
+        #[cfg(test)]
+        let (outcomes_tx, outcomes) = mpsc::unbounded_channel();
+        tokio::spawn(run_shadow(
+            name.clone(),
+            shadow,
+            calls_rx,
+            #[cfg(test)]
+            outcomes_tx,
+        ));
+        self.shadows.push(ShadowEngine {
+            name,
+            calls,
+            #[cfg(test)]
+            outcomes,
+        });
+        self
+    }
+
+    /// Returns the primary execution layer.
+    pub const fn primary(&self) -> &P {
+        &self.primary
+    }
+
+    /// Returns the names of the shadow execution layers.
+    pub fn shadows(&self) -> impl Iterator<Item = &str> {
+        self.shadows.iter().map(|shadow| shadow.name.as_str())
+    }
+
+    /// Returns `true` if calls are mirrored to at least one shadow execution layer.
+    const fn is_mirroring(&self) -> bool {
+        !self.shadows.is_empty()
+    }
+
+    /// Queues `request` for every shadow execution layer, if the primary handled it successfully.
+    fn mirror(&self, request: Option<ShadowRequest>, primary_status: Option<&PayloadStatus>) {
+        let (Some(request), Some(primary_status)) = (request, primary_status) else {
+            return;
+        };
+
+        for shadow in &self.shadows {
+            let call =
+                ShadowCall { request: request.clone(), primary_status: primary_status.clone() };
+            if let Err(err) = shadow.calls.try_send(call) {
+                let reason = match err {
+                    TrySendError::Full(_) => "queue full",
+                    TrySendError::Closed(_) => "shadow task stopped",
+                };
+                warn!(
+                    target: "engine",
+                    shadow = %shadow.name,
+                    method = request.method(),
+                    block_hash = %request.block_hash(),
+                    reason,
+                    "Dropped call to shadow engine"
+                );
+                kona_macros::inc!(
+                    counter,
+                    Metrics::SHADOW_ENGINE_RESULT,
+                    "shadow" => shadow.name.clone(),
+                    "method" => request.method(),
+                    "outcome" => "dropped"
+                );
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl<P: EngineApi> EngineApi for EngineMultiplexer<P> {
+    fn cfg(&self) -> &RollupConfig {
+        self.primary.cfg()
+    }
+
+    async fn new_payload_v1(&self, payload: ExecutionPayloadV1) -> TransportResult<PayloadStatus> {
+        let request = self.is_mirroring().then(|| ShadowRequest::NewPayloadV1(payload.clone()));
+        let result = self.primary.new_payload_v1(payload).await;
+        self.mirror(request, result.as_ref().ok());
+        result
+    }
+
+    async fn new_payload_v2(
+        &self,
+        payload: ExecutionPayloadInputV2,
+    ) -> TransportResult<PayloadStatus> {
+        let request = self.is_mirroring().then(|| ShadowRequest::NewPayloadV2(payload.clone()));
+        let result = self.primary.new_payload_v2(payload).await;
+        self.mirror(request, result.as_ref().ok());
+        result
+    }
+
+    async fn new_payload_v3(
+        &self,
+        payload: ExecutionPayloadV3,
+        parent_beacon_block_root: B256,
+    ) -> TransportResult<PayloadStatus> {
+        let request = self
+            .is_mirroring()
+            .then(|| ShadowRequest::NewPayloadV3(payload.clone(), parent_beacon_block_root));
+        let result = self.primary.new_payload_v3(payload, parent_beacon_block_root).await;
+        self.mirror(request, result.as_ref().ok());
+        result
+    }
+
+    async fn new_payload_v4(
+        &self,
+        payload: OpExecutionPayloadV4,
+        parent_beacon_block_root: B256,
+    ) -> TransportResult<PayloadStatus> {
+        let request = self
+            .is_mirroring()
+            .then(|| ShadowRequest::NewPayloadV4(payload.clone(), parent_beacon_block_root));
+        let result = self.primary.new_payload_v4(payload, parent_beacon_block_root).await;
+        self.mirror(request, result.as_ref().ok());
+        result
+    }
+
+    async fn fork_choice_updated_v2(
+        &self,
+        fork_choice_state: ForkchoiceState,
+        payload_attributes: Option<OpPayloadAttributes>,
+    ) -> TransportResult<ForkchoiceUpdated> {
+        let request =
+            self.is_mirroring().then_some(ShadowRequest::ForkchoiceUpdatedV2(fork_choice_state));
+        let result =
+            self.primary.fork_choice_updated_v2(fork_choice_state, payload_attributes).await;
+        self.mirror(request, result.as_ref().ok().map(|updated| &updated.payload_status));
+        result
+    }
+
+    async fn fork_choice_updated_v3(
+        &self,
+        fork_choice_state: ForkchoiceState,
+        payload_attributes: Option<OpPayloadAttributes>,
+    ) -> TransportResult<ForkchoiceUpdated> {
+        let request =
+            self.is_mirroring().then_some(ShadowRequest::ForkchoiceUpdatedV3(fork_choice_state));
+        let result =
+            self.primary.fork_choice_updated_v3(fork_choice_state, payload_attributes).await;
+        self.mirror(request, result.as_ref().ok().map(|updated| &updated.payload_status));
+        result
+    }
+
+    async fn get_payload_v2(
+        &self,
+        payload_id: PayloadId,
+    ) -> TransportResult<ExecutionPayloadEnvelopeV2> {
+        self.primary.get_payload_v2(payload_id).await
+    }
+
+    async fn get_payload_v3(
+        &self,
+        payload_id: PayloadId,
+    ) -> TransportResult<OpExecutionPayloadEnvelopeV3> {
+        self.primary.get_payload_v3(payload_id).await
+    }
+
+    async fn get_payload_v4(
+        &self,
+        payload_id: PayloadId,
+    ) -> TransportResult<OpExecutionPayloadEnvelopeV4> {
+        self.primary.get_payload_v4(payload_id).await
+    }
+
+    async fn l2_block_by_label(
+        &self,
+        numtag: BlockNumberOrTag,
+    ) -> Result<Option<Block<Transaction>>, EngineClientError> {
+        self.primary.l2_block_by_label(numtag).await
+    }
+
+    async fn l2_block_by_hash(
+        &self,
+        hash: B256,
+    ) -> Result<Option<Block<Transaction>>, EngineClientError> {
+        self.primary.l2_block_by_hash(hash).await
+    }
+
+    async fn l1_block_info_by_number(
+        &self,
+        number: u64,
+    ) -> Result<Option<BlockInfo>, EngineClientError> {
+        self.primary.l1_block_info_by_number(number).await
+    }
+
+    async fn message_passer_storage_root(
+        &self,
+        block: BlockNumberOrTag,
+    ) -> Result<B256, EngineClientError> {
+        self.primary.message_passer_storage_root(block).await
+    }
+
+    async fn sync_start(&self) -> Result<L2ForkchoiceState, SyncStartError> {
+        self.primary.sync_start().await
+    }
+}
+
+impl ShadowRequest {
+    /// Returns the Engine API method of the request, without its version suffix.
+    const fn method(&self) -> &'static str {
+        match self {
+            Self::NewPayloadV1(_) |
+            Self::NewPayloadV2(_) |
+            Self::NewPayloadV3(..) |
+            Self::NewPayloadV4(..) => Metrics::NEW_PAYLOAD_METHOD,
+            Self::ForkchoiceUpdatedV2(_) | Self::ForkchoiceUpdatedV3(_) => {
+                Metrics::FORKCHOICE_UPDATE_METHOD
+            }
+        }
+    }
+
+    /// Returns the block hash of the payload, or the head block hash of the forkchoice state.
+    const fn block_hash(&self) -> B256 {
+        match self {
+            Self::NewPayloadV1(payload) => payload.block_hash,
+            Self::NewPayloadV2(payload) => payload.execution_payload.block_hash,
+            Self::NewPayloadV3(payload, _) => payload.payload_inner.payload_inner.block_hash,
+            Self::NewPayloadV4(payload, _) => {
+                payload.payload_inner.payload_inner.payload_inner.block_hash
+            }
+            Self::ForkchoiceUpdatedV2(state) | Self::ForkchoiceUpdatedV3(state) => {
+                state.head_block_hash
+            }
+        }
+    }
+
+    /// Sends the request to the given execution layer.
+    async fn send(self, engine: &dyn EngineApi) -> TransportResult<PayloadStatus> {
+        match self {
+            Self::NewPayloadV1(payload) => engine.new_payload_v1(payload).await,
+            Self::NewPayloadV2(payload) => engine.new_payload_v2(payload).await,
+            Self::NewPayloadV3(payload, root) => engine.new_payload_v3(payload, root).await,
+            Self::NewPayloadV4(payload, root) => engine.new_payload_v4(payload, root).await,
+            Self::ForkchoiceUpdatedV2(state) => {
+                engine.fork_choice_updated_v2(state, None).await.map(|u| u.payload_status)
+            }
+            Self::ForkchoiceUpdatedV3(state) => {
+                engine.fork_choice_updated_v3(state, None).await.map(|u| u.payload_status)
+            }
+        }
+    }
+}
+
+impl ShadowOutcome {
+    /// Compares the [`PayloadStatus`] returned by a shadow with the primary's.
+    fn compare(primary: &PayloadStatus, shadow: &PayloadStatus) -> Self {
+        match (&primary.status, &shadow.status) {
+            (PayloadStatusEnum::Valid, PayloadStatusEnum::Valid) => {
+                if primary.latest_valid_hash == shadow.latest_valid_hash {
+                    Self::Match
+                } else {
+                    Self::Diverged
+                }
+            }
+            (PayloadStatusEnum::Invalid { .. }, PayloadStatusEnum::Invalid { .. }) |
+            (PayloadStatusEnum::Syncing, PayloadStatusEnum::Syncing) |
+            (PayloadStatusEnum::Accepted, PayloadStatusEnum::Accepted) => Self::Match,
+            (PayloadStatusEnum::Valid, PayloadStatusEnum::Invalid { .. }) |
+            (PayloadStatusEnum::Invalid { .. }, PayloadStatusEnum::Valid) => Self::Diverged,
+            _ => Self::Lagging,
+        }
+    }
+}
+
+/// Mirrors the queued calls to a shadow execution layer, in order, until the
+/// [`EngineMultiplexer`] is dropped.
+async fn run_shadow(
+    name: String,
+    shadow: Arc<dyn EngineApi>,
+    mut calls: mpsc::Receiver<ShadowCall>,
+    #[cfg(test)] outcomes: mpsc::UnboundedSender<ShadowOutcome>,
+) {
+    while let Some(ShadowCall { request, primary_status }) = calls.recv().await {
+        let method = request.method();
+        let block_hash = request.block_hash();
+
+        let shadow_status = match request.send(shadow.as_ref()).await {
+            Ok(status) => status,
+            Err(err) => {
+                warn!(target: "engine", shadow = %name, method, %block_hash, ?err, "Shadow engine request failed");
+                kona_macros::inc!(
+                    counter,
+                    Metrics::SHADOW_ENGINE_RESULT,
+                    "shadow" => name.clone(),
+                    "method" => method,
+                    "outcome" => "error"
+                );
+                continue;
+            }
+        };
+
+        let outcome = ShadowOutcome::compare(&primary_status, &shadow_status);
+        match outcome {
+            ShadowOutcome::Diverged => {
+                error!(
+                    target: "engine",
+                    shadow = %name,
+                    method,
+                    %block_hash,
+                    primary = ?primary_status.status,
+                    primary_latest_valid_hash = ?primary_status.latest_valid_hash,
+                    shadow_status = ?shadow_status.status,
+                    shadow_latest_valid_hash = ?shadow_status.latest_valid_hash,
+                    "Shadow engine diverged from the primary execution layer"
+                );
+            }
+            ShadowOutcome::Lagging => {
+                debug!(
+                    target: "engine",
+                    shadow = %name,
+                    method,
+                    %block_hash,
+                    primary = ?primary_status.status,
+                    shadow_status = ?shadow_status.status,
+                    "Shadow engine is lagging behind the primary execution layer"
+                );
+            }
+            ShadowOutcome::Match => {}
+        }
+        kona_macros::inc!(
+            counter,
+            Metrics::SHADOW_ENGINE_RESULT,
+            "shadow" => name.clone(),
+            "method" => method,
+            "outcome" => outcome.to_string()
+        );
+        #[cfg(test)]
+        let _ = outcomes.send(outcome);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::TestEngineApi;
+    use alloy_consensus::{BlockBody, Header};
+    use alloy_rpc_types_engine::{ExecutionPayloadFieldV2, PayloadAttributes};
+    use op_alloy_consensus::OpBlock;
+    use std::time::Duration;
+
+    /// The time to wait for a shadow to handle a mirrored call before failing the test.
+    const TIMEOUT: Duration = Duration::from_secs(10);
+
+    /// Returns a primary and a shadow [`TestEngineApi`] sharing the same L2 genesis block.
+    fn setup() -> (TestEngineApi, TestEngineApi) {
+        let genesis = OpBlock::new(
+            Header { base_fee_per_gas: Some(1), gas_limit: 30_000_000, ..Default::default() },
+            BlockBody::default(),
+        );
+        let mut cfg = RollupConfig { block_time: 2, ..Default::default() };
+        cfg.genesis.l2.hash = genesis.header.hash_slow();
+        let cfg = Arc::new(cfg);
+
+        (TestEngineApi::new(cfg.clone(), genesis.clone()), TestEngineApi::new(cfg, genesis))
+    }
+
+    /// Builds a block on top of the multiplexer's current head and inserts it with
+    /// `engine_newPayload`. Returns the block hash and the primary's status.
+    async fn build_and_insert(
+        engine: &EngineMultiplexer<TestEngineApi>,
+        timestamp: u64,
+    ) -> (B256, PayloadStatus) {
+        let forkchoice = engine.primary().forkchoice();
+        let attributes = OpPayloadAttributes {
+            payload_attributes: PayloadAttributes { timestamp, ..Default::default() },
+            gas_limit: Some(30_000_000),
+            ..Default::default()
+        };
+        let updated = engine.fork_choice_updated_v2(forkchoice, Some(attributes)).await.unwrap();
+        let envelope = engine.get_payload_v2(updated.payload_id.unwrap()).await.unwrap();
+        let ExecutionPayloadFieldV2::V1(payload) = envelope.execution_payload else {
+            panic!("expected a V1 payload before Shanghai");
+        };
+
+        let hash = payload.block_hash;
+        (hash, engine.new_payload_v1(payload).await.unwrap())
+    }
+
+    /// Waits for the outcomes of the next calls mirrored to the first shadow.
+    async fn next_outcomes(
+        engine: &mut EngineMultiplexer<TestEngineApi>,
+        count: usize,
+    ) -> Vec<ShadowOutcome> {
+        let mut outcomes = Vec::with_capacity(count);
+        for _ in 0..count {
+            let outcome = tokio::time::timeout(TIMEOUT, engine.shadows[0].outcomes.recv());
+            outcomes.push(outcome.await.unwrap().unwrap());
+        }
+        outcomes
+    }
+
+    #[test]
+    fn test_compare_statuses() {
+        let hash = B256::repeat_byte(0x01);
+        let valid = PayloadStatus::new(PayloadStatusEnum::Valid, Some(hash));
+        let other_valid = PayloadStatus::new(PayloadStatusEnum::Valid, Some(B256::ZERO));
+        let invalid = PayloadStatus::new(
+            PayloadStatusEnum::Invalid { validation_error: "bad".into() },
+            Some(B256::ZERO),
+        );
+        let syncing = PayloadStatus::new(PayloadStatusEnum::Syncing, None);
+
+        assert_eq!(ShadowOutcome::compare(&valid, &valid), ShadowOutcome::Match);
+        assert_eq!(ShadowOutcome::compare(&invalid, &invalid), ShadowOutcome::Match);
+        assert_eq!(ShadowOutcome::compare(&valid, &other_valid), ShadowOutcome::Diverged);
+        assert_eq!(ShadowOutcome::compare(&valid, &invalid), ShadowOutcome::Diverged);
+        assert_eq!(ShadowOutcome::compare(&invalid, &valid), ShadowOutcome::Diverged);
+        assert_eq!(ShadowOutcome::compare(&valid, &syncing), ShadowOutcome::Lagging);
+        assert_eq!(ShadowOutcome::compare(&syncing, &invalid), ShadowOutcome::Lagging);
+    }
+
+    #[tokio::test]
+    async fn test_mirrors_calls_and_reports_divergence() {
+        use ShadowOutcome::*;
+
+        let (primary, shadow) = setup();
+        let mut engine =
+            EngineMultiplexer::new(primary.clone()).with_shadow("shadow", Arc::new(shadow.clone()));
+
+        // Blocks built by the primary are inserted into the shadow by the mirrored calls, and
+        // the shadow follows the primary's forkchoice state.
+        let (hash, status) = build_and_insert(&engine, 2).await;
+        assert_eq!(status.status, PayloadStatusEnum::Valid);
+        let forkchoice = ForkchoiceState { head_block_hash: hash, ..primary.forkchoice() };
+        engine.fork_choice_updated_v2(forkchoice, None).await.unwrap();
+        assert_eq!(next_outcomes(&mut engine, 3).await, [Match, Match, Match]);
+        assert_eq!(shadow.forkchoice(), forkchoice);
+        assert_eq!(shadow.block(hash), primary.block(hash));
+
+        // A syncing shadow is lagging, not diverged.
+        shadow.set_syncing(true);
+        let (_, status) = build_and_insert(&engine, 4).await;
+        assert_eq!(status.status, PayloadStatusEnum::Valid);
+        assert_eq!(next_outcomes(&mut engine, 2).await, [Lagging, Lagging]);
+        shadow.set_syncing(false);
+
+        // A shadow rejecting a payload accepted by the primary has diverged, without affecting
+        // the status returned to the caller.
+        shadow.script_new_payload(PayloadStatusEnum::Invalid { validation_error: "bad".into() });
+        let (hash, status) = build_and_insert(&engine, 6).await;
+        assert_eq!(status.status, PayloadStatusEnum::Valid);
+        assert_eq!(next_outcomes(&mut engine, 2).await, [Match, Diverged]);
+        assert!(shadow.block(hash).is_none());
+    }
+}
@@ -4,16 +4,21 @@
 //! from external actors. Uses oneshot channels for responses to maintain
 //! clean async communication patterns.
 
-use std::sync::Arc;
+use std::{sync::Arc, time::Duration};
 
 use alloy_eips::BlockNumberOrTag;
-use alloy_provider::Provider;
 use alloy_transport::{RpcError, TransportErrorKind};
 use kona_genesis::RollupConfig;
-use kona_protocol::{L2BlockInfo, OutputRoot, Predeploys};
+use kona_protocol::{L2BlockInfo, OutputRoot};
 use tokio::sync::oneshot::Sender;
 
-use crate::{EngineClient, EngineClientError, EngineState};
+use crate::{EngineApi, EngineClientError, EngineState};
+
+/// How long [`EngineQueries::ElReachable`] waits for the execution layer.
+///
+/// Queries are handled sequentially, so a slow execution layer must not hold up the queries
+/// queued behind the reachability check.
+const EL_REACHABLE_TIMEOUT: Duration = Duration::from_millis(500);
 
 /// Channel sender for submitting [`EngineQueries`] to the engine.
 pub type EngineQuerySender = tokio::sync::mpsc::Sender<EngineQueries>;
@@ -44,6 +49,8 @@ pub enum EngineQueries {
     QueueLengthReceiver(Sender<tokio::sync::watch::Receiver<usize>>),
     /// Development API: Get the current number of pending tasks in the queue.
     TaskQueueLength(Sender<usize>),
+    /// Check whether the execution layer responds to requests within a short timeout.
+    ElReachable(Sender<bool>),
 }
 
 /// An error that can occur when querying the engine.
@@ -68,11 +75,11 @@ pub enum EngineQueriesError {
 
 impl EngineQueries {
     /// Handles the engine query request.
-    pub async fn handle(
+    pub async fn handle<E: EngineApi>(
         self,
         state_recv: &tokio::sync::watch::Receiver<EngineState>,
         queue_length_recv: &tokio::sync::watch::Receiver<usize>,
-        client: &Arc<EngineClient>,
+        client: &Arc<E>,
         rollup_config: &Arc<RollupConfig>,
     ) -> Result<(), EngineQueriesError> {
         let state = *state_recv.borrow();
@@ -108,12 +115,12 @@ impl EngineQueries {
                             .ok_or(EngineQueriesError::NoWithdrawalsRoot)?
                     } else {
                         // Fetch the storage root for the L2 head block.
-                        let l2_to_l1_message_passer = client
-                            .get_proof(Predeploys::L2_TO_L1_MESSAGE_PASSER, Default::default())
-                            .block_id(block.into())
-                            .await?;
-
-                        l2_to_l1_message_passer.storage_hash
+                        client.message_passer_storage_root(block).await.map_err(|e| match e {
+                            EngineClientError::RpcError(e) => {
+                                EngineQueriesError::FailedToRetrieveWithdrawalsRoot(e)
+                            }
+                            e => EngineQueriesError::BlockRetrievalFailed(e),
+                        })?
                     };
 
                 let output_response_v0 = OutputRoot::from_parts(
@@ -139,6 +146,15 @@ impl EngineQueries {
                 }
                 Ok(())
             }
+            Self::ElReachable(sender) => {
+                let reachable = tokio::time::timeout(
+                    EL_REACHABLE_TIMEOUT,
+                    client.l2_block_by_label(BlockNumberOrTag::Latest),
+                )
+                .await
+                .is_ok_and(|res| res.is_ok());
+                sender.send(reachable).map_err(|_| EngineQueriesError::OutputChannelClosed)
+            }
         }
     }
 }
@@ -2,19 +2,17 @@
 
 use super::EngineTaskExt;
 use crate::{
-    EngineClient, EngineState, EngineSyncStateUpdate, EngineTask, EngineTaskError,
-    EngineTaskErrorSeverity, Metrics, SynchronizeTask, SynchronizeTaskError,
+    EngineApi, EngineClient, EngineClientError, EngineState, EngineSyncStateUpdate, EngineTask,
+    EngineTaskError, EngineTaskErrorSeverity, Metrics, SynchronizeTask, SynchronizeTaskError,
     task_queue::EngineTaskErrors,
 };
-use alloy_provider::Provider;
-use alloy_rpc_types_eth::Transaction;
 use kona_genesis::{RollupConfig, SystemConfig};
 use kona_protocol::{BlockInfo, L2BlockInfo, OpBlockConversionError, to_system_config};
-use kona_sources::{SyncStartError, find_starting_forkchoice};
-use op_alloy_consensus::OpTxEnvelope;
-use std::{collections::BinaryHeap, sync::Arc};
+use kona_sources::SyncStartError;
+use std::{cmp::Ordering, collections::BinaryHeap, sync::Arc};
 use thiserror::Error;
 use tokio::sync::watch::Sender;
+use tracing::{Instrument, Span};
 
 /// The [`Engine`] task queue.
 ///
@@ -26,11 +24,14 @@ use tokio::sync::watch::Sender;
 ///  Because tasks are executed one at a time, they are considered to be atomic operations over the
 /// [`EngineState`], and are given exclusive access to the engine state during execution.
 ///
+/// Each task is executed in an `engine_task` span that is a child of the span it was enqueued
+/// from, so that its execution can be attributed to the message that caused it.
+///
 /// Tasks within the queue are also considered fallible. If they fail with a temporary error,
 /// they are not popped from the queue, the error is returned, and they are retried on the
 /// next call to [`Engine::drain`].
 #[derive(Debug)]
-pub struct Engine {
+pub struct Engine<E = EngineClient> {
     /// The state of the engine.
     state: EngineState,
     /// A sender that can be used to notify the engine actor of state changes.
@@ -38,10 +39,10 @@ pub struct Engine {
     /// A sender that can be used to notify the engine actor of task queue length changes.
     task_queue_length: Sender<usize>,
     /// The task queue.
-    tasks: BinaryHeap<EngineTask>,
+    tasks: BinaryHeap<QueuedTask<E>>,
 }
 
-impl Engine {
+impl<E: EngineApi> Engine<E> {
     /// Creates a new [`Engine`] with an empty task queue and the passed initial [`EngineState`].
     pub fn new(
         initial_state: EngineState,
@@ -68,24 +69,23 @@ impl Engine {
 
     /// Enqueues a new [`EngineTask`] for execution.
     /// Updates the queue length and notifies listeners of the change.
-    pub fn enqueue(&mut self, task: EngineTask) {
-        self.tasks.push(task);
+    pub fn enqueue(&mut self, task: EngineTask<E>) {
+        self.tasks.push(QueuedTask { task, span: Span::current() });
         self.task_queue_length.send_replace(self.tasks.len());
     }
 
     /// Resets the engine by finding a plausible sync starting point via
-    /// [`find_starting_forkchoice`]. The state will be updated to the starting point, and a
+    /// [`EngineApi::sync_start`]. The state will be updated to the starting point, and a
     /// forkchoice update will be enqueued in order to reorg the execution layer.
     pub async fn reset(
         &mut self,
-        client: Arc<EngineClient>,
+        client: Arc<E>,
         config: Arc<RollupConfig>,
     ) -> Result<(L2BlockInfo, BlockInfo, SystemConfig), EngineResetError> {
         // Clear any outstanding tasks to prepare for the reset.
         self.clear();
 
-        let start =
-            find_starting_forkchoice(&config, client.l1_provider(), client.l2_engine()).await?;
+        let start = client.sync_start().await?;
 
         // Retry to synchronize the engine until we succeeds or a critical error occurs.
         while let Err(err) = SynchronizeTask::new(
@@ -120,23 +120,16 @@ impl Engine {
             .l1_origin
             .number
             .saturating_sub(config.channel_timeout(start.safe.block_info.timestamp));
-        let l1_origin_info: BlockInfo = client
-            .l1_provider()
-            .get_block(origin_block.into())
-            .await
-            .map_err(SyncStartError::RpcError)?
-            .ok_or(SyncStartError::BlockNotFound(origin_block.into()))?
-            .into_consensus()
-            .into();
+        let l1_origin_info = client
+            .l1_block_info_by_number(origin_block)
+            .await?
+            .ok_or(SyncStartError::BlockNotFound(origin_block.into()))?;
         let l2_safe_block = client
-            .l2_engine()
-            .get_block(start.safe.block_info.hash.into())
-            .full()
-            .await
-            .map_err(SyncStartError::RpcError)?
-            .ok_or(SyncStartError::BlockNotFound(origin_block.into()))?
+            .l2_block_by_hash(start.safe.block_info.hash)
+            .await?
+            .ok_or(SyncStartError::BlockNotFound(start.safe.block_info.hash.into()))?
             .into_consensus()
-            .map_transactions(|t| <Transaction<OpTxEnvelope> as Clone>::clone(&t).into_inner());
+            .map_transactions(|t| t.inner.inner.into_inner());
         let system_config = to_system_config(&l2_safe_block, &config)?;
 
         kona_macros::inc!(counter, Metrics::ENGINE_RESET_COUNT);
@@ -154,9 +147,15 @@ impl Engine {
     /// the error is returned.
     pub async fn drain(&mut self) -> Result<(), EngineTaskErrors> {
         // Drain tasks in order of priority, halting on errors for a retry to be attempted.
-        while let Some(task) = self.tasks.peek() {
+        while let Some(QueuedTask { task, span }) = self.tasks.peek() {
             // Execute the task
-            task.execute(&mut self.state).await?;
+            let span = info_span!(
+                target: "engine",
+                parent: span,
+                "engine_task",
+                task = task.task_metrics_label()
+            );
+            task.execute(&mut self.state).instrument(span).await?;
 
             // Update the state and notify the engine actor.
             self.state_sender.send_replace(self.state);
@@ -171,6 +170,33 @@ impl Engine {
     }
 }
 
+/// An [`EngineTask`] in the [`Engine`]'s queue, along with the span it was enqueued from.
+#[derive(Debug)]
+struct QueuedTask<E> {
+    task: EngineTask<E>,
+    span: Span,
+}
+
+impl<E> PartialEq for QueuedTask<E> {
+    fn eq(&self, other: &Self) -> bool {
+        self.task == other.task
+    }
+}
+
+impl<E> Eq for QueuedTask<E> {}
+
+impl<E> PartialOrd for QueuedTask<E> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<E> Ord for QueuedTask<E> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.task.cmp(&other.task)
+    }
+}
+
 /// An error occurred while attempting to reset the [`Engine`].
 #[derive(Debug, Error)]
 pub enum EngineResetError {
@@ -183,4 +209,7 @@ pub enum EngineResetError {
     /// An error occurred while constructing the SystemConfig for the new safe head.
     #[error(transparent)]
     SystemConfigConversion(#[from] OpBlockConversionError),
+    /// An error occurred while fetching the new safe head or its L1 origin.
+    #[error(transparent)]
+    Client(#[from] EngineClientError),
 }
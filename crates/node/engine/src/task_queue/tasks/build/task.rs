@@ -1,8 +1,8 @@
 //! A task for building a new block and importing it.
 use super::BuildTaskError;
 use crate::{
-    EngineClient, EngineForkchoiceVersion, EngineGetPayloadVersion, EngineState, EngineTaskExt,
-    InsertTask,
+    EngineApi, EngineClient, EngineForkchoiceVersion, EngineGetPayloadVersion, EngineState,
+    EngineTaskExt, InsertTask,
     InsertTaskError::{self},
     state::EngineSyncStateUpdate,
     task_queue::tasks::build::error::EngineBuildError,
@@ -11,7 +11,6 @@ use alloy_rpc_types_engine::{ExecutionPayload, PayloadId, PayloadStatusEnum};
 use async_trait::async_trait;
 use kona_genesis::RollupConfig;
 use kona_protocol::{L2BlockInfo, OpAttributesWithParent};
-use op_alloy_provider::ext::engine::OpEngineApi;
 use op_alloy_rpc_types_engine::{OpExecutionPayload, OpExecutionPayloadEnvelope};
 use std::{
     sync::Arc,
@@ -44,9 +43,9 @@ use tokio::{sync::mpsc, time::sleep};
 /// [`EngineBuildError`]: crate::EngineBuildError
 /// [`InsertTaskError`]: crate::InsertTaskError
 #[derive(Debug, Clone)]
-pub struct BuildTask {
+pub struct BuildTask<E = EngineClient> {
     /// The engine API client.
-    pub engine: Arc<EngineClient>,
+    pub engine: Arc<E>,
     /// The [`RollupConfig`].
     pub cfg: Arc<RollupConfig>,
     /// The [`OpAttributesWithParent`] to instruct the execution layer to build.
@@ -58,10 +57,10 @@ pub struct BuildTask {
     pub payload_tx: Option<mpsc::Sender<OpExecutionPayloadEnvelope>>,
 }
 
-impl BuildTask {
+impl<E: EngineApi> BuildTask<E> {
     /// Creates a new block building task.
     pub const fn new(
-        engine: Arc<EngineClient>,
+        engine: Arc<E>,
         cfg: Arc<RollupConfig>,
         attributes: OpAttributesWithParent,
         is_attributes_derived: bool,
@@ -90,7 +89,7 @@ impl BuildTask {
     async fn start_build(
         &self,
         state: &EngineState,
-        engine_client: &EngineClient,
+        engine_client: &E,
         attributes_envelope: OpAttributesWithParent,
     ) -> Result<PayloadId, BuildTaskError> {
         // Sanity check if the head is behind the finalized head. If it is, this is a critical
@@ -182,7 +181,7 @@ impl BuildTask {
     async fn fetch_payload(
         &self,
         cfg: &RollupConfig,
-        engine: &EngineClient,
+        engine: &E,
         payload_id: PayloadId,
         payload_attrs: OpAttributesWithParent,
     ) -> Result<OpExecutionPayloadEnvelope, BuildTaskError> {
@@ -241,7 +240,7 @@ impl BuildTask {
 }
 
 #[async_trait]
-impl EngineTaskExt for BuildTask {
+impl<E: EngineApi> EngineTaskExt for BuildTask<E> {
     type Output = ();
 
     type Error = BuildTaskError;
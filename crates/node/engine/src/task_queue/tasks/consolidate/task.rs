@@ -1,8 +1,8 @@
 //! A task to consolidate the engine state.
 
 use crate::{
-    BuildTask, ConsolidateTaskError, EngineClient, EngineState, EngineTaskExt, SynchronizeTask,
-    state::EngineSyncStateUpdate,
+    BuildTask, ConsolidateTaskError, EngineApi, EngineClient, EngineState, EngineTaskExt,
+    SynchronizeTask, state::EngineSyncStateUpdate,
 };
 use async_trait::async_trait;
 use kona_genesis::RollupConfig;
@@ -14,9 +14,9 @@ use std::{sync::Arc, time::Instant};
 ///
 /// If consolidation fails, payload attributes processing is attempted using the [`BuildTask`].
 #[derive(Debug, Clone)]
-pub struct ConsolidateTask {
+pub struct ConsolidateTask<E = EngineClient> {
     /// The engine client.
-    pub client: Arc<EngineClient>,
+    pub client: Arc<E>,
     /// The [`RollupConfig`].
     pub cfg: Arc<RollupConfig>,
     /// The [`OpAttributesWithParent`] to instruct the execution layer to build.
@@ -25,10 +25,10 @@ pub struct ConsolidateTask {
     pub is_attributes_derived: bool,
 }
 
-impl ConsolidateTask {
+impl<E: EngineApi> ConsolidateTask<E> {
     /// Creates a new [`ConsolidateTask`].
     pub const fn new(
-        client: Arc<EngineClient>,
+        client: Arc<E>,
         config: Arc<RollupConfig>,
         attributes: OpAttributesWithParent,
         is_attributes_derived: bool,
@@ -163,7 +163,7 @@ impl ConsolidateTask {
 }
 
 #[async_trait]
-impl EngineTaskExt for ConsolidateTask {
+impl<E: EngineApi> EngineTaskExt for ConsolidateTask<E> {
     type Output = ();
 
     type Error = ConsolidateTaskError;
@@ -1,7 +1,8 @@
 //! Contains error types for the [crate::FinalizeTask].
 
 use crate::{
-    EngineTaskError, SynchronizeTaskError, task_queue::tasks::task::EngineTaskErrorSeverity,
+    EngineClientError, EngineTaskError, SynchronizeTaskError,
+    task_queue::tasks::task::EngineTaskErrorSeverity,
 };
 use alloy_transport::{RpcError, TransportErrorKind};
 use kona_protocol::FromBlockError;
@@ -29,6 +30,15 @@ pub enum FinalizeTaskError {
     ForkchoiceUpdateFailed(#[from] SynchronizeTaskError),
 }
 
+impl From<EngineClientError> for FinalizeTaskError {
+    fn from(err: EngineClientError) -> Self {
+        match err {
+            EngineClientError::RpcError(err) => Self::TransportError(err),
+            EngineClientError::BlockInfoDecodeError(err) => Self::FromBlock(err),
+        }
+    }
+}
+
 impl EngineTaskError for FinalizeTaskError {
     fn severity(&self) -> EngineTaskErrorSeverity {
         match self {
@@ -1,10 +1,9 @@
 //! A task for finalizing an L2 block.
 
 use crate::{
-    EngineClient, EngineState, EngineTaskExt, FinalizeTaskError, SynchronizeTask,
+    EngineApi, EngineClient, EngineState, EngineTaskExt, FinalizeTaskError, SynchronizeTask,
     state::EngineSyncStateUpdate,
 };
-use alloy_provider::Provider;
 use async_trait::async_trait;
 use kona_genesis::RollupConfig;
 use kona_protocol::L2BlockInfo;
@@ -13,24 +12,24 @@ use std::{sync::Arc, time::Instant};
 /// The [`FinalizeTask`] fetches the [`L2BlockInfo`] at `block_number`, updates the [`EngineState`],
 /// and dispatches a forkchoice update to finalize the block.
 #[derive(Debug, Clone)]
-pub struct FinalizeTask {
+pub struct FinalizeTask<E = EngineClient> {
     /// The engine client.
-    pub client: Arc<EngineClient>,
+    pub client: Arc<E>,
     /// The rollup config.
     pub cfg: Arc<RollupConfig>,
     /// The number of the L2 block to finalize.
     pub block_number: u64,
 }
 
-impl FinalizeTask {
+impl<E: EngineApi> FinalizeTask<E> {
     /// Creates a new [`SynchronizeTask`].
-    pub const fn new(client: Arc<EngineClient>, cfg: Arc<RollupConfig>, block_number: u64) -> Self {
+    pub const fn new(client: Arc<E>, cfg: Arc<RollupConfig>, block_number: u64) -> Self {
         Self { client, cfg, block_number }
     }
 }
 
 #[async_trait]
-impl EngineTaskExt for FinalizeTask {
+impl<E: EngineApi> EngineTaskExt for FinalizeTask<E> {
     type Output = ();
 
     type Error = FinalizeTaskError;
@@ -44,11 +43,8 @@ impl EngineTaskExt for FinalizeTask {
         let block_fetch_start = Instant::now();
         let block = self
             .client
-            .l2_engine()
-            .get_block(self.block_number.into())
-            .full()
-            .await
-            .map_err(FinalizeTaskError::TransportError)?
+            .l2_block_by_label(self.block_number.into())
+            .await?
             .ok_or(FinalizeTaskError::BlockNotFound(self.block_number))?
             .into_consensus();
         let block_info = L2BlockInfo::from_block_and_genesis(&block, &self.client.cfg().genesis)
@@ -1,11 +1,10 @@
 //! A task to insert an unsafe payload into the execution engine.
 
 use crate::{
-    EngineClient, EngineState, EngineTaskExt, InsertTaskError, SynchronizeTask,
+    EngineApi, EngineClient, EngineState, EngineTaskExt, InsertTaskError, SynchronizeTask,
     state::EngineSyncStateUpdate,
 };
 use alloy_eips::eip7685::EMPTY_REQUESTS_HASH;
-use alloy_provider::ext::EngineApi;
 use alloy_rpc_types_engine::{
     CancunPayloadFields, ExecutionPayloadInputV2, PayloadStatusEnum, PraguePayloadFields,
 };
@@ -13,7 +12,6 @@ use async_trait::async_trait;
 use kona_genesis::RollupConfig;
 use kona_protocol::L2BlockInfo;
 use op_alloy_consensus::OpBlock;
-use op_alloy_provider::ext::engine::OpEngineApi;
 use op_alloy_rpc_types_engine::{
     OpExecutionPayload, OpExecutionPayloadEnvelope, OpExecutionPayloadSidecar,
 };
@@ -21,9 +19,9 @@ use std::{sync::Arc, time::Instant};
 
 /// The task to insert a payload into the execution engine.
 #[derive(Debug, Clone)]
-pub struct InsertTask {
+pub struct InsertTask<E = EngineClient> {
     /// The engine client.
-    client: Arc<EngineClient>,
+    client: Arc<E>,
     /// The rollup config.
     rollup_config: Arc<RollupConfig>,
     /// The network payload envelope.
@@ -33,10 +31,10 @@ pub struct InsertTask {
     is_payload_safe: bool,
 }
 
-impl InsertTask {
+impl<E: EngineApi> InsertTask<E> {
     /// Creates a new insert task.
     pub const fn new(
-        client: Arc<EngineClient>,
+        client: Arc<E>,
         rollup_config: Arc<RollupConfig>,
         envelope: OpExecutionPayloadEnvelope,
         is_attributes_derived: bool,
@@ -51,7 +49,7 @@ impl InsertTask {
 }
 
 #[async_trait]
-impl EngineTaskExt for InsertTask {
+impl<E: EngineApi> EngineTaskExt for InsertTask<E> {
     type Output = ();
 
     type Error = InsertTaskError;
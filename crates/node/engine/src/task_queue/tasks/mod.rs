@@ -19,3 +19,6 @@ pub use consolidate::{ConsolidateTask, ConsolidateTaskError};
 
 mod finalize;
 pub use finalize::{FinalizeTask, FinalizeTaskError};
+
+mod promote;
+pub use promote::{PromoteTask, PromoteTaskError};
@@ -0,0 +1,26 @@
+//! Contains error types for the [crate::PromoteTask].
+
+use crate::{
+    EngineTaskError, SynchronizeTaskError, task_queue::tasks::task::EngineTaskErrorSeverity,
+};
+use thiserror::Error;
+
+/// An error that occurs when running the [crate::PromoteTask].
+#[derive(Debug, Error)]
+pub enum PromoteTaskError {
+    /// The finalized head to promote is ahead of the safe head to promote.
+    #[error("Finalized head {0} is ahead of safe head {1}")]
+    FinalizedAheadOfSafe(u64, u64),
+    /// The forkchoice update call to promote the heads failed.
+    #[error(transparent)]
+    ForkchoiceUpdateFailed(#[from] SynchronizeTaskError),
+}
+
+impl EngineTaskError for PromoteTaskError {
+    fn severity(&self) -> EngineTaskErrorSeverity {
+        match self {
+            Self::FinalizedAheadOfSafe(_, _) => EngineTaskErrorSeverity::Critical,
+            Self::ForkchoiceUpdateFailed(inner) => inner.severity(),
+        }
+    }
+}
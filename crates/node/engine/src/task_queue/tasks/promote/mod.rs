@@ -0,0 +1,8 @@
+//! Task and its associated types for promoting the safe and finalized heads to blocks verified by
+//! an external source.
+
+mod task;
+pub use task::PromoteTask;
+
+mod error;
+pub use error::PromoteTaskError;
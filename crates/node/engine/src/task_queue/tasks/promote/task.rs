@@ -0,0 +1,114 @@
+//! A task for promoting the safe and finalized heads to blocks verified by an external source.
+
+use crate::{
+    EngineApi, EngineClient, EngineState, EngineTaskExt, PromoteTaskError, SynchronizeTask,
+    state::EngineSyncStateUpdate,
+};
+use async_trait::async_trait;
+use kona_genesis::RollupConfig;
+use kona_protocol::L2BlockInfo;
+use std::{sync::Arc, time::Instant};
+
+/// The [`PromoteTask`] moves the safe and finalized heads of the [`EngineState`] to blocks that
+/// were not derived locally, but verified against a trusted rollup node, and dispatches a
+/// forkchoice update to the execution layer.
+///
+/// The blocks must already be canonical in the execution layer. If the unsafe head lags behind
+/// the promoted safe head, it is moved forward along with it.
+#[derive(Debug, Clone)]
+pub struct PromoteTask<E = EngineClient> {
+    /// The engine client.
+    pub client: Arc<E>,
+    /// The rollup config.
+    pub cfg: Arc<RollupConfig>,
+    /// The block to promote to the safe head.
+    pub safe_head: L2BlockInfo,
+    /// The block to promote to the finalized head, if it advanced.
+    pub finalized_head: Option<L2BlockInfo>,
+}
+
+impl<E: EngineApi> PromoteTask<E> {
+    /// Creates a new [`PromoteTask`].
+    pub const fn new(
+        client: Arc<E>,
+        cfg: Arc<RollupConfig>,
+        safe_head: L2BlockInfo,
+        finalized_head: Option<L2BlockInfo>,
+    ) -> Self {
+        Self { client, cfg, safe_head, finalized_head }
+    }
+
+    /// Returns the [`EngineSyncStateUpdate`] that promotes the heads, or `None` if the engine state
+    /// is already at or beyond them.
+    fn state_update(
+        &self,
+        state: &EngineState,
+    ) -> Result<Option<EngineSyncStateUpdate>, PromoteTaskError> {
+        let safe_number = self.safe_head.block_info.number;
+        if let Some(finalized) = self.finalized_head {
+            if finalized.block_info.number > safe_number {
+                return Err(PromoteTaskError::FinalizedAheadOfSafe(
+                    finalized.block_info.number,
+                    safe_number,
+                ));
+            }
+        }
+
+        let sync_state = state.sync_state;
+        let safe_head =
+            (safe_number > sync_state.safe_head().block_info.number).then_some(self.safe_head);
+        let finalized_head = self.finalized_head.filter(|finalized| {
+            finalized.block_info.number > sync_state.finalized_head().block_info.number
+        });
+        if safe_head.is_none() && finalized_head.is_none() {
+            return Ok(None);
+        }
+
+        // The promoted blocks are canonical in the execution layer, so a lagging unsafe head can
+        // safely be moved forward.
+        let unsafe_head =
+            (safe_number > sync_state.unsafe_head().block_info.number).then_some(self.safe_head);
+
+        Ok(Some(EngineSyncStateUpdate {
+            unsafe_head,
+            cross_unsafe_head: unsafe_head,
+            local_safe_head: safe_head,
+            safe_head,
+            finalized_head,
+        }))
+    }
+}
+
+#[async_trait]
+impl<E: EngineApi> EngineTaskExt for PromoteTask<E> {
+    type Output = ();
+
+    type Error = PromoteTaskError;
+
+    async fn execute(&self, state: &mut EngineState) -> Result<(), PromoteTaskError> {
+        let Some(update) = self.state_update(state)? else {
+            debug!(
+                target: "engine",
+                safe = self.safe_head.block_info.number,
+                finalized = ?self.finalized_head.map(|head| head.block_info.number),
+                "Skipping promotion of stale heads"
+            );
+            return Ok(());
+        };
+
+        let fcu_start = Instant::now();
+        SynchronizeTask::new(self.client.clone(), self.cfg.clone(), update).execute(state).await?;
+        let fcu_duration = fcu_start.elapsed();
+
+        info!(
+            target: "engine",
+            safe = %state.sync_state.safe_head().block_info.hash,
+            safe_number = state.sync_state.safe_head().block_info.number,
+            finalized_number = state.sync_state.finalized_head().block_info.number,
+            ?fcu_duration,
+            "Promoted heads from follow source"
+        );
+
+        Ok(())
+    }
+}
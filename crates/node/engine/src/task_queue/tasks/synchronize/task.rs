@@ -1,12 +1,12 @@
 //! A task for the `engine_forkchoiceUpdated` method, with no attributes.
 
 use crate::{
-    EngineClient, EngineState, EngineTaskExt, SynchronizeTaskError, state::EngineSyncStateUpdate,
+    EngineApi, EngineClient, EngineState, EngineTaskExt, SynchronizeTaskError,
+    state::EngineSyncStateUpdate,
 };
 use alloy_rpc_types_engine::{INVALID_FORK_CHOICE_STATE_ERROR, PayloadStatusEnum};
 use async_trait::async_trait;
 use kona_genesis::RollupConfig;
-use op_alloy_provider::ext::engine::OpEngineApi;
 use std::sync::Arc;
 use tokio::time::Instant;
 
@@ -35,19 +35,19 @@ use tokio::time::Instant;
 /// [`FinalizeTask`]: crate::FinalizeTask
 /// [`BuildTask`]: crate::BuildTask
 #[derive(Debug, Clone)]
-pub struct SynchronizeTask {
+pub struct SynchronizeTask<E = EngineClient> {
     /// The engine client.
-    pub client: Arc<EngineClient>,
+    pub client: Arc<E>,
     /// The rollup config.
     pub rollup: Arc<RollupConfig>,
     /// The sync state update to apply to the engine state.
     pub state_update: EngineSyncStateUpdate,
 }
 
-impl SynchronizeTask {
+impl<E: EngineApi> SynchronizeTask<E> {
     /// Creates a new [`SynchronizeTask`].
     pub const fn new(
-        client: Arc<EngineClient>,
+        client: Arc<E>,
         rollup: Arc<RollupConfig>,
         state_update: EngineSyncStateUpdate,
     ) -> Self {
@@ -87,7 +87,7 @@ impl SynchronizeTask {
 }
 
 #[async_trait]
-impl EngineTaskExt for SynchronizeTask {
+impl<E: EngineApi> EngineTaskExt for SynchronizeTask<E> {
     type Output = ();
     type Error = SynchronizeTaskError;
 
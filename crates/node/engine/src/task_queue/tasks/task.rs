@@ -2,9 +2,10 @@
 //!
 //! [`Engine`]: crate::Engine
 
-use super::{BuildTask, ConsolidateTask, FinalizeTask, InsertTask};
+use super::{BuildTask, ConsolidateTask, FinalizeTask, InsertTask, PromoteTask};
 use crate::{
-    BuildTaskError, ConsolidateTaskError, EngineState, FinalizeTaskError, InsertTaskError,
+    BuildTaskError, ConsolidateTaskError, EngineApi, EngineClient, EngineState, FinalizeTaskError,
+    InsertTaskError, PromoteTaskError,
 };
 use async_trait::async_trait;
 use derive_more::Display;
@@ -68,6 +69,9 @@ pub enum EngineTaskErrors {
     /// An error that occurred while finalizing an L2 block.
     #[error(transparent)]
     Finalize(#[from] FinalizeTaskError),
+    /// An error that occurred while promoting the safe and finalized heads.
+    #[error(transparent)]
+    Promote(#[from] PromoteTaskError),
 }
 
 impl EngineTaskError for EngineTaskErrors {
@@ -77,6 +81,7 @@ impl EngineTaskError for EngineTaskErrors {
             Self::Build(inner) => inner.severity(),
             Self::Consolidate(inner) => inner.severity(),
             Self::Finalize(inner) => inner.severity(),
+            Self::Promote(inner) => inner.severity(),
         }
     }
 }
@@ -85,64 +90,70 @@ impl EngineTaskError for EngineTaskErrors {
 ///
 /// [`Engine`]: crate::Engine
 #[derive(Debug, Clone)]
-pub enum EngineTask {
+pub enum EngineTask<E = EngineClient> {
     /// Inserts a payload into the execution engine.
-    Insert(Box<InsertTask>),
+    Insert(Box<InsertTask<E>>),
     /// Builds a new block with the given attributes, and inserts it into the execution engine.
-    Build(Box<BuildTask>),
+    Build(Box<BuildTask<E>>),
     /// Performs consolidation on the engine state, reverting to payload attribute processing
     /// via the [`BuildTask`] if consolidation fails.
-    Consolidate(Box<ConsolidateTask>),
+    Consolidate(Box<ConsolidateTask<E>>),
     /// Finalizes an L2 block
-    Finalize(Box<FinalizeTask>),
+    Finalize(Box<FinalizeTask<E>>),
+    /// Promotes the safe and finalized heads to blocks verified against a trusted rollup node.
+    Promote(Box<PromoteTask<E>>),
 }
 
-impl EngineTask {
+impl<E: EngineApi> EngineTask<E> {
     /// Executes the task without consuming it.
     async fn execute_inner(&self, state: &mut EngineState) -> Result<(), EngineTaskErrors> {
-        match self.clone() {
+        match self {
             Self::Insert(task) => task.execute(state).await?,
             Self::Build(task) => task.execute(state).await?,
             Self::Consolidate(task) => task.execute(state).await?,
             Self::Finalize(task) => task.execute(state).await?,
+            Self::Promote(task) => task.execute(state).await?,
         };
 
         Ok(())
     }
 
-    const fn task_metrics_label(&self) -> &'static str {
+    /// Returns the label identifying the task's variant.
+    pub(crate) const fn task_metrics_label(&self) -> &'static str {
         match self {
             Self::Insert(_) => crate::Metrics::INSERT_TASK_LABEL,
             Self::Consolidate(_) => crate::Metrics::CONSOLIDATE_TASK_LABEL,
             Self::Build(_) => crate::Metrics::BUILD_TASK_LABEL,
             Self::Finalize(_) => crate::Metrics::FINALIZE_TASK_LABEL,
+            Self::Promote(_) => crate::Metrics::PROMOTE_TASK_LABEL,
         }
     }
 }
 
-impl PartialEq for EngineTask {
+impl<E> PartialEq for EngineTask<E> {
     fn eq(&self, other: &Self) -> bool {
         matches!(
             (self, other),
             (Self::Insert(_), Self::Insert(_)) |
                 (Self::Build(_), Self::Build(_)) |
                 (Self::Consolidate(_), Self::Consolidate(_)) |
-                (Self::Finalize(_), Self::Finalize(_))
+                (Self::Finalize(_), Self::Finalize(_)) |
+                (Self::Promote(_), Self::Promote(_))
         )
     }
 }
 
-impl Eq for EngineTask {}
+impl<E> Eq for EngineTask<E> {}
 
-impl PartialOrd for EngineTask {
+impl<E> PartialOrd for EngineTask<E> {
     fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
         Some(self.cmp(other))
     }
 }
 
-impl Ord for EngineTask {
+impl<E> Ord for EngineTask<E> {
     fn cmp(&self, other: &Self) -> Ordering {
-        // Order (descending): BuildBlock -> InsertUnsafe -> Consolidate -> Finalize
+        // Order (descending): BuildBlock -> InsertUnsafe -> Consolidate -> Promote -> Finalize
         //
         // https://specs.optimism.io/protocol/derivation.html#forkchoice-synchronization
         //
@@ -152,6 +163,8 @@ impl Ord for EngineTask {
         //   gossip is imported promptly.
         // - Consolidate tasks are prioritized over Finalize tasks, as they advance the safe chain
         //   via derivation.
+        // - Promote tasks advance the safe chain from a follow source, and are mutually exclusive
+        //   with Consolidate tasks.
         // - Finalize tasks have the lowest priority, as they only update finalized status.
         match (self, other) {
             // Same variant cases
@@ -159,6 +172,7 @@ impl Ord for EngineTask {
             (Self::Consolidate(_), Self::Consolidate(_)) => Ordering::Equal,
             (Self::Build(_), Self::Build(_)) => Ordering::Equal,
             (Self::Finalize(_), Self::Finalize(_)) => Ordering::Equal,
+            (Self::Promote(_), Self::Promote(_)) => Ordering::Equal,
 
             // BuildBlock tasks are prioritized over InsertUnsafe and Consolidate tasks
             (Self::Build(_), _) => Ordering::Greater,
@@ -168,15 +182,19 @@ impl Ord for EngineTask {
             (Self::Insert(_), _) => Ordering::Greater,
             (_, Self::Insert(_)) => Ordering::Less,
 
-            // Consolidate tasks are prioritized over Finalize tasks
+            // Consolidate tasks are prioritized over Promote and Finalize tasks
             (Self::Consolidate(_), _) => Ordering::Greater,
             (_, Self::Consolidate(_)) => Ordering::Less,
+
+            // Promote tasks are prioritized over Finalize tasks
+            (Self::Promote(_), _) => Ordering::Greater,
+            (_, Self::Promote(_)) => Ordering::Less,
         }
     }
 }
 
 #[async_trait]
-impl EngineTaskExt for EngineTask {
+impl<E: EngineApi> EngineTaskExt for EngineTask<E> {
     type Output = ();
 
     type Error = EngineTaskErrors;
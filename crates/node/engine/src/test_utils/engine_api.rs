@@ -0,0 +1,767 @@
+//! An in-memory [`EngineApi`] implementation for testing.
+
+use crate::{EngineApi, EngineClientError};
+use alloy_consensus::{
+    BlockBody, Header,
+    constants::{EMPTY_OMMER_ROOT_HASH, EMPTY_ROOT_HASH},
+    proofs::{calculate_transaction_root, calculate_withdrawals_root},
+    transaction::Recovered,
+};
+use alloy_eips::{
+    eip1898::BlockNumberOrTag,
+    eip2718::{Decodable2718, Encodable2718},
+    eip4895::Withdrawals,
+    eip7685::EMPTY_REQUESTS_HASH,
+};
+use alloy_json_rpc::ErrorPayload;
+use alloy_primitives::{Address, B256, Sealed, U256, b256};
+use alloy_rpc_types_engine::{
+    CancunPayloadFields, ExecutionPayloadEnvelopeV2, ExecutionPayloadFieldV2,
+    ExecutionPayloadInputV2, ExecutionPayloadV1, ExecutionPayloadV2, ExecutionPayloadV3,
+    ForkchoiceState, ForkchoiceUpdated, INVALID_FORK_CHOICE_STATE_ERROR, PayloadAttributes,
+    PayloadId, PayloadStatus, PayloadStatusEnum, PraguePayloadFields,
+};
+use alloy_rpc_types_eth::{Block, BlockTransactions};
+use alloy_transport::{RpcError, TransportErrorKind, TransportResult};
+use async_trait::async_trait;
+use kona_genesis::{RollupConfig, SystemConfig};
+use kona_protocol::{
+    BlockInfo, L1BlockInfoBedrock, L1BlockInfoTx, L2BlockInfo, OpAttributesWithParent,
+};
+use kona_sources::{L2ForkchoiceState, SyncStartError};
+use op_alloy_consensus::{OpBlock, OpTxEnvelope, TxDeposit, encode_holocene_extra_data};
+use op_alloy_rpc_types::Transaction;
+use op_alloy_rpc_types_engine::{
+    OpExecutionPayload, OpExecutionPayloadEnvelopeV3, OpExecutionPayloadEnvelopeV4,
+    OpExecutionPayloadSidecar, OpExecutionPayloadV4, OpPayloadAttributes, OpPayloadError,
+};
+use std::{
+    collections::{BTreeMap, HashMap, VecDeque},
+    sync::{Arc, Mutex, MutexGuard},
+};
+
+/// The error code returned by `engine_getPayload` for an unknown payload ID.
+const UNKNOWN_PAYLOAD_ERROR: i64 = -38001;
+
+/// A deterministic, in-memory execution layer implementing [`EngineApi`].
+///
+/// The [`TestEngineApi`] stores L2 blocks by hash and tracks the canonical chain implied by the
+/// last forkchoice update. It does not execute transactions: payloads are built by applying the
+/// payload attributes to the parent header, with the parent's state root carried over, and
+/// inserted payloads are only checked for a consistent block hash and a known parent. This is
+/// enough to drive the engine tasks through building, insertion, consolidation, finalization and
+/// resets without a real execution client.
+///
+/// Chains are scripted by seeding blocks with [`TestEngineApi::insert_block`] and L1 blocks with
+/// [`TestEngineApi::with_l1_blocks`], and failures by [`TestEngineApi::set_syncing`] and
+/// [`TestEngineApi::script_new_payload`]. Clones share the same underlying chain.
+#[derive(Debug, Clone)]
+pub struct TestEngineApi {
+    /// The [`RollupConfig`] of the chain.
+    cfg: Arc<RollupConfig>,
+    /// The state of the execution layer.
+    state: Arc<Mutex<TestEngineState>>,
+}
+
+/// The mutable state of a [`TestEngineApi`].
+#[derive(Debug, Default)]
+struct TestEngineState {
+    /// All known L2 blocks, by hash.
+    blocks: HashMap<B256, OpBlock>,
+    /// The canonical L2 chain, by number.
+    canonical: BTreeMap<u64, B256>,
+    /// The last accepted forkchoice state.
+    forkchoice: ForkchoiceState,
+    /// Payloads built by forkchoice updates with attributes, by payload ID.
+    payloads: HashMap<PayloadId, (B256, OpBlock)>,
+    /// The number of payloads built so far, used to derive payload IDs.
+    payload_count: u64,
+    /// The L1 chain, by number.
+    l1_blocks: BTreeMap<u64, BlockInfo>,
+    /// Whether the execution layer reports that it is syncing.
+    syncing: bool,
+    /// Statuses returned by the next `engine_newPayload` calls, in order.
+    scripted_new_payload: VecDeque<PayloadStatusEnum>,
+}
+
+impl TestEngineApi {
+    /// The hash of the L1 genesis block of [`TestEngineApi::genesis`].
+    pub const L1_GENESIS_HASH: B256 =
+        b256!("1111111111111111111111111111111111111111111111111111111111111111");
+
+    /// Creates a new [`TestEngineApi`] whose canonical chain and forkchoice state consist of the
+    /// given L2 genesis block.
+    pub fn new(cfg: Arc<RollupConfig>, genesis: OpBlock) -> Self {
+        let hash = genesis.header.hash_slow();
+        let mut state = TestEngineState {
+            forkchoice: ForkchoiceState {
+                head_block_hash: hash,
+                safe_block_hash: hash,
+                finalized_block_hash: hash,
+            },
+            ..Default::default()
+        };
+        state.canonical.insert(genesis.header.number, hash);
+        state.blocks.insert(hash, genesis);
+        Self { cfg, state: Arc::new(Mutex::new(state)) }
+    }
+
+    /// Adds the given L1 blocks to the L1 chain.
+    pub fn with_l1_blocks(self, blocks: impl IntoIterator<Item = BlockInfo>) -> Self {
+        self.state().l1_blocks.extend(blocks.into_iter().map(|block| (block.number, block)));
+        self
+    }
+
+    /// Stores an L2 block without making it canonical, as if it had been synced from peers.
+    /// Returns the hash of the block.
+    pub fn insert_block(&self, block: OpBlock) -> B256 {
+        let hash = block.header.hash_slow();
+        self.state().blocks.insert(hash, block);
+        hash
+    }
+
+    /// Sets whether the execution layer reports that it is syncing. While syncing, every
+    /// `engine_newPayload` and `engine_forkchoiceUpdated` call returns `SYNCING`.
+    pub fn set_syncing(&self, syncing: bool) {
+        self.state().syncing = syncing;
+    }
+
+    /// Makes the next `engine_newPayload` call return `status` without inserting the payload.
+    /// Scripted statuses are consumed in the order they were added.
+    pub fn script_new_payload(&self, status: PayloadStatusEnum) {
+        self.state().scripted_new_payload.push_back(status);
+    }
+
+    /// Returns the last accepted [`ForkchoiceState`].
+    pub fn forkchoice(&self) -> ForkchoiceState {
+        self.state().forkchoice
+    }
+
+    /// Returns the L2 block with the given hash, if known.
+    pub fn block(&self, hash: B256) -> Option<OpBlock> {
+        self.state().blocks.get(&hash).cloned()
+    }
+
+    /// Returns the hash of the canonical L2 block at the given number, if any.
+    pub fn canonical_hash(&self, number: u64) -> Option<B256> {
+        self.state().canonical.get(&number).copied()
+    }
+
+    /// Returns a [`RollupConfig`] and a [`TestEngineApi`] whose L2 chain consists of a genesis
+    /// block only, anchored to an L1 genesis block with hash [`Self::L1_GENESIS_HASH`].
+    pub fn genesis() -> (Arc<RollupConfig>, Self) {
+        let genesis = OpBlock::new(
+            Header { base_fee_per_gas: Some(1), gas_limit: 30_000_000, ..Default::default() },
+            BlockBody::default(),
+        );
+
+        let mut cfg =
+            RollupConfig { block_time: 2, max_sequencer_drift: 600, ..Default::default() };
+        cfg.genesis.l1.hash = Self::L1_GENESIS_HASH;
+        cfg.genesis.l2.hash = genesis.header.hash_slow();
+        cfg.genesis.system_config = Some(SystemConfig::default());
+        let cfg = Arc::new(cfg);
+
+        let engine = Self::new(cfg.clone(), genesis).with_l1_blocks([Self::l1_genesis()]);
+        (cfg, engine)
+    }
+
+    /// Returns derived attributes for the L2 block after `parent`, with an L1 info deposit
+    /// referencing the L1 genesis block of [`TestEngineApi::genesis`].
+    pub fn next_attributes(parent: L2BlockInfo, cfg: &RollupConfig) -> OpAttributesWithParent {
+        let l1_info = L1BlockInfoTx::Bedrock(L1BlockInfoBedrock {
+            block_hash: Self::L1_GENESIS_HASH,
+            sequence_number: parent.seq_num + 1,
+            ..Default::default()
+        });
+        let deposit = OpTxEnvelope::Deposit(Sealed::new(TxDeposit {
+            input: l1_info.encode_calldata(),
+            ..Default::default()
+        }));
+
+        OpAttributesWithParent::new(
+            OpPayloadAttributes {
+                payload_attributes: PayloadAttributes {
+                    timestamp: parent.block_info.timestamp + cfg.block_time,
+                    ..Default::default()
+                },
+                transactions: Some(vec![deposit.encoded_2718().into()]),
+                no_tx_pool: Some(true),
+                gas_limit: Some(30_000_000),
+                ..Default::default()
+            },
+            parent,
+            Some(Self::l1_genesis()),
+            true,
+        )
+    }
+
+    /// Returns the L1 genesis block of [`TestEngineApi::genesis`].
+    pub fn l1_genesis() -> BlockInfo {
+        BlockInfo { hash: Self::L1_GENESIS_HASH, ..Default::default() }
+    }
+
+    fn state(&self) -> MutexGuard<'_, TestEngineState> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Inserts a payload that was converted into a block, checking that the block hash matches
+    /// the one committed to by the payload and that its parent is known.
+    fn new_payload(
+        &self,
+        block_hash: B256,
+        block: Result<OpBlock, OpPayloadError>,
+    ) -> TransportResult<PayloadStatus> {
+        let mut state = self.state();
+        if let Some(status) = state.scripted_new_payload.pop_front() {
+            return Ok(PayloadStatus::new(status, None));
+        }
+        if state.syncing {
+            return Ok(PayloadStatus::new(PayloadStatusEnum::Syncing, None));
+        }
+
+        let block = match block {
+            Ok(block) => block,
+            Err(e) => return Ok(invalid(e.to_string())),
+        };
+        let hash = block.header.hash_slow();
+        if hash != block_hash {
+            return Ok(invalid(format!("block hash mismatch: want {block_hash}, got {hash}")));
+        }
+        if !state.blocks.contains_key(&block.header.parent_hash) {
+            return Ok(PayloadStatus::new(PayloadStatusEnum::Syncing, None));
+        }
+
+        state.blocks.insert(hash, block);
+        Ok(PayloadStatus::new(PayloadStatusEnum::Valid, Some(hash)))
+    }
+
+    /// Applies a forkchoice update, starting a payload build job if attributes are given.
+    fn fork_choice_updated(
+        &self,
+        fork_choice_state: ForkchoiceState,
+        payload_attributes: Option<OpPayloadAttributes>,
+    ) -> TransportResult<ForkchoiceUpdated> {
+        let mut state = self.state();
+        let head = fork_choice_state.head_block_hash;
+        if state.syncing || !state.blocks.contains_key(&head) {
+            return Ok(ForkchoiceUpdated {
+                payload_status: PayloadStatus::new(PayloadStatusEnum::Syncing, None),
+                payload_id: None,
+            });
+        }
+
+        // Walk back from the new head to find the canonical chain it implies.
+        let mut canonical = BTreeMap::new();
+        let mut cursor = state.blocks.get(&head).map(|block| (head, block));
+        while let Some((hash, block)) = cursor {
+            canonical.insert(block.header.number, hash);
+            cursor = state
+                .blocks
+                .get(&block.header.parent_hash)
+                .map(|parent| (block.header.parent_hash, parent));
+        }
+
+        // The safe and finalized blocks must be ancestors of the new head.
+        let is_canonical = |hash: B256| {
+            hash.is_zero() ||
+                state.blocks.get(&hash).is_some_and(|block| {
+                    canonical.get(&block.header.number).is_some_and(|h| *h == hash)
+                })
+        };
+        if !is_canonical(fork_choice_state.safe_block_hash) ||
+            !is_canonical(fork_choice_state.finalized_block_hash)
+        {
+            return Err(RpcError::ErrorResp(ErrorPayload {
+                code: INVALID_FORK_CHOICE_STATE_ERROR as i64,
+                message: "Invalid forkchoice state".into(),
+                data: None,
+            }));
+        }
+
+        state.canonical = canonical;
+        state.forkchoice = fork_choice_state;
+
+        let mut payload_id = None;
+        if let Some(attributes) = payload_attributes {
+            let parent = &state.blocks[&head];
+            let block = match build_block(&self.cfg, head, parent, attributes) {
+                Ok(block) => block,
+                Err(e) => {
+                    return Ok(ForkchoiceUpdated { payload_status: invalid(e), payload_id: None });
+                }
+            };
+
+            state.payload_count += 1;
+            let id = PayloadId::new(state.payload_count.to_be_bytes());
+            state.payloads.insert(id, (block.header.hash_slow(), block));
+            payload_id = Some(id);
+        }
+
+        Ok(ForkchoiceUpdated {
+            payload_status: PayloadStatus::new(PayloadStatusEnum::Valid, Some(head)),
+            payload_id,
+        })
+    }
+
+    /// Returns the payload built for the given payload ID.
+    fn payload(&self, payload_id: PayloadId) -> TransportResult<(B256, OpBlock)> {
+        self.state().payloads.get(&payload_id).cloned().ok_or_else(|| {
+            RpcError::ErrorResp(ErrorPayload {
+                code: UNKNOWN_PAYLOAD_ERROR,
+                message: "Unknown payload".into(),
+                data: None,
+            })
+        })
+    }
+
+    /// Returns the hash of the block for the given [`BlockNumberOrTag`] on the canonical chain.
+    fn hash_by_label(&self, numtag: BlockNumberOrTag) -> Option<B256> {
+        let state = self.state();
+        let hash = match numtag {
+            BlockNumberOrTag::Number(number) => state.canonical.get(&number).copied()?,
+            BlockNumberOrTag::Earliest => state.canonical.values().next().copied()?,
+            BlockNumberOrTag::Latest | BlockNumberOrTag::Pending => {
+                state.forkchoice.head_block_hash
+            }
+            BlockNumberOrTag::Safe => state.forkchoice.safe_block_hash,
+            BlockNumberOrTag::Finalized => state.forkchoice.finalized_block_hash,
+        };
+        (!hash.is_zero()).then_some(hash)
+    }
+
+    /// Returns the [`L2BlockInfo`] of the block with the given hash.
+    fn l2_block_info(&self, hash: B256) -> Result<L2BlockInfo, SyncStartError> {
+        let block = self.block(hash).ok_or(SyncStartError::BlockNotFound(hash.into()))?;
+        Ok(L2BlockInfo::from_block_and_genesis(&block, &self.cfg.genesis)?)
+    }
+}
+
+#[async_trait]
+impl EngineApi for TestEngineApi {
+    fn cfg(&self) -> &RollupConfig {
+        self.cfg.as_ref()
+    }
+
+    async fn new_payload_v1(&self, payload: ExecutionPayloadV1) -> TransportResult<PayloadStatus> {
+        let block_hash = payload.block_hash;
+        self.new_payload(block_hash, OpExecutionPayload::V1(payload).try_into_block())
+    }
+
+    async fn new_payload_v2(
+        &self,
+        payload: ExecutionPayloadInputV2,
+    ) -> TransportResult<PayloadStatus> {
+        let block_hash = payload.execution_payload.block_hash;
+        let payload = match payload.withdrawals {
+            Some(withdrawals) => OpExecutionPayload::V2(ExecutionPayloadV2 {
+                payload_inner: payload.execution_payload,
+                withdrawals,
+            }),
+            None => OpExecutionPayload::V1(payload.execution_payload),
+        };
+        self.new_payload(block_hash, payload.try_into_block())
+    }
+
+    async fn new_payload_v3(
+        &self,
+        payload: ExecutionPayloadV3,
+        parent_beacon_block_root: B256,
+    ) -> TransportResult<PayloadStatus> {
+        let block_hash = payload.payload_inner.payload_inner.block_hash;
+        let block = OpExecutionPayload::V3(payload).try_into_block_with_sidecar(
+            &OpExecutionPayloadSidecar::v3(CancunPayloadFields::new(
+                parent_beacon_block_root,
+                vec![],
+            )),
+        );
+        self.new_payload(block_hash, block)
+    }
+
+    async fn new_payload_v4(
+        &self,
+        payload: OpExecutionPayloadV4,
+        parent_beacon_block_root: B256,
+    ) -> TransportResult<PayloadStatus> {
+        let block_hash = payload.payload_inner.payload_inner.payload_inner.block_hash;
+        let block = OpExecutionPayload::V4(payload).try_into_block_with_sidecar(
+            &OpExecutionPayloadSidecar::v4(
+                CancunPayloadFields::new(parent_beacon_block_root, vec![]),
+                PraguePayloadFields::new(EMPTY_REQUESTS_HASH),
+            ),
+        );
+        self.new_payload(block_hash, block)
+    }
+
+    async fn fork_choice_updated_v2(
+        &self,
+        fork_choice_state: ForkchoiceState,
+        payload_attributes: Option<OpPayloadAttributes>,
+    ) -> TransportResult<ForkchoiceUpdated> {
+        self.fork_choice_updated(fork_choice_state, payload_attributes)
+    }
+
+    async fn fork_choice_updated_v3(
+        &self,
+        fork_choice_state: ForkchoiceState,
+        payload_attributes: Option<OpPayloadAttributes>,
+    ) -> TransportResult<ForkchoiceUpdated> {
+        self.fork_choice_updated(fork_choice_state, payload_attributes)
+    }
+
+    async fn get_payload_v2(
+        &self,
+        payload_id: PayloadId,
+    ) -> TransportResult<ExecutionPayloadEnvelopeV2> {
+        let (hash, block) = self.payload(payload_id)?;
+        let execution_payload = if block.body.withdrawals.is_some() {
+            ExecutionPayloadFieldV2::V2(ExecutionPayloadV2::from_block_unchecked(hash, &block))
+        } else {
+            ExecutionPayloadFieldV2::V1(ExecutionPayloadV1::from_block_unchecked(hash, &block))
+        };
+        Ok(ExecutionPayloadEnvelopeV2 { execution_payload, block_value: U256::ZERO })
+    }
+
+    async fn get_payload_v3(
+        &self,
+        payload_id: PayloadId,
+    ) -> TransportResult<OpExecutionPayloadEnvelopeV3> {
+        let (hash, block) = self.payload(payload_id)?;
+        Ok(OpExecutionPayloadEnvelopeV3 {
+            execution_payload: ExecutionPayloadV3::from_block_unchecked(hash, &block),
+            block_value: U256::ZERO,
+            blobs_bundle: Default::default(),
+            should_override_builder: false,
+            parent_beacon_block_root: block.header.parent_beacon_block_root.unwrap_or_default(),
+        })
+    }
+
+    async fn get_payload_v4(
+        &self,
+        payload_id: PayloadId,
+    ) -> TransportResult<OpExecutionPayloadEnvelopeV4> {
+        let (hash, block) = self.payload(payload_id)?;
+        Ok(OpExecutionPayloadEnvelopeV4 {
+            execution_payload: OpExecutionPayloadV4 {
+                payload_inner: ExecutionPayloadV3::from_block_unchecked(hash, &block),
+                withdrawals_root: block.header.withdrawals_root.unwrap_or(EMPTY_ROOT_HASH),
+            },
+            block_value: U256::ZERO,
+            blobs_bundle: Default::default(),
+            should_override_builder: false,
+            parent_beacon_block_root: block.header.parent_beacon_block_root.unwrap_or_default(),
+            execution_requests: Default::default(),
+        })
+    }
+
+    async fn l2_block_by_label(
+        &self,
+        numtag: BlockNumberOrTag,
+    ) -> Result<Option<Block<Transaction>>, EngineClientError> {
+        let Some(hash) = self.hash_by_label(numtag) else {
+            return Ok(None);
+        };
+        Ok(self.block(hash).map(|block| rpc_block(hash, block)))
+    }
+
+    async fn l2_block_by_hash(
+        &self,
+        hash: B256,
+    ) -> Result<Option<Block<Transaction>>, EngineClientError> {
+        Ok(self.block(hash).map(|block| rpc_block(hash, block)))
+    }
+
+    async fn l1_block_info_by_number(
+        &self,
+        number: u64,
+    ) -> Result<Option<BlockInfo>, EngineClientError> {
+        Ok(self.state().l1_blocks.get(&number).copied())
+    }
+
+    async fn message_passer_storage_root(
+        &self,
+        block: BlockNumberOrTag,
+    ) -> Result<B256, EngineClientError> {
+        // State is not tracked, so the storage root is the one committed to in the header, if any.
+        let block = self
+            .hash_by_label(block)
+            .and_then(|hash| self.block(hash))
+            .ok_or_else(|| TransportErrorKind::custom_str(&format!("block {block} not found")))?;
+        Ok(block.header.withdrawals_root.unwrap_or(EMPTY_ROOT_HASH))
+    }
+
+    async fn sync_start(&self) -> Result<L2ForkchoiceState, SyncStartError> {
+        let forkchoice = self.forkchoice();
+        let finalized = self.l2_block_info(forkchoice.finalized_block_hash)?;
+        let safe = if forkchoice.safe_block_hash.is_zero() {
+            finalized
+        } else {
+            self.l2_block_info(forkchoice.safe_block_hash)?
+        };
+        let un_safe = self.l2_block_info(forkchoice.head_block_hash)?;
+        Ok(L2ForkchoiceState { un_safe, safe, finalized })
+    }
+}
+
+/// Returns an `INVALID` [`PayloadStatus`] with the given validation error.
+fn invalid(validation_error: String) -> PayloadStatus {
+    PayloadStatus::new(PayloadStatusEnum::Invalid { validation_error }, None)
+}
+
+/// Builds a block on top of `parent` from the given payload attributes, without executing it.
+fn build_block(
+    cfg: &RollupConfig,
+    parent_hash: B256,
+    parent: &OpBlock,
+    attributes: OpPayloadAttributes,
+) -> Result<OpBlock, String> {
+    let timestamp = attributes.payload_attributes.timestamp;
+    let transactions = attributes
+        .transactions
+        .unwrap_or_default()
+        .iter()
+        .map(|tx| OpTxEnvelope::decode_2718(&mut tx.as_ref()))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| format!("invalid transaction in payload attributes: {e}"))?;
+    let withdrawals = attributes.payload_attributes.withdrawals.map(Withdrawals::new);
+
+    let withdrawals_root = if cfg.is_isthmus_active(timestamp) {
+        // Post-Isthmus, the withdrawals root commits to the message passer storage root, which
+        // is unchanged since transactions are not executed.
+        Some(parent.header.withdrawals_root.unwrap_or(EMPTY_ROOT_HASH))
+    } else {
+        withdrawals.as_ref().map(|withdrawals| calculate_withdrawals_root(withdrawals))
+    };
+    let extra_data = if cfg.is_holocene_active(timestamp) {
+        encode_holocene_extra_data(
+            attributes.eip_1559_params.unwrap_or_default(),
+            cfg.chain_op_config.as_canyon_base_fee_params(),
+        )
+        .map_err(|e| format!("invalid EIP-1559 parameters: {e:?}"))?
+    } else {
+        Default::default()
+    };
+    let is_ecotone = cfg.is_ecotone_active(timestamp);
+
+    let header = Header {
+        parent_hash,
+        ommers_hash: EMPTY_OMMER_ROOT_HASH,
+        beneficiary: attributes.payload_attributes.suggested_fee_recipient,
+        state_root: parent.header.state_root,
+        transactions_root: calculate_transaction_root(&transactions),
+        receipts_root: EMPTY_ROOT_HASH,
+        number: parent.header.number + 1,
+        gas_limit: attributes.gas_limit.unwrap_or(parent.header.gas_limit),
+        timestamp,
+        extra_data,
+        mix_hash: attributes.payload_attributes.prev_randao,
+        base_fee_per_gas: Some(parent.header.base_fee_per_gas.unwrap_or_default()),
+        withdrawals_root,
+        blob_gas_used: is_ecotone.then_some(0),
+        excess_blob_gas: is_ecotone.then_some(0),
+        parent_beacon_block_root: attributes.payload_attributes.parent_beacon_block_root,
+        requests_hash: cfg.is_isthmus_active(timestamp).then_some(EMPTY_REQUESTS_HASH),
+        ..Default::default()
+    };
+    Ok(OpBlock::new(header, BlockBody { transactions, ommers: vec![], withdrawals }))
+}
+
+/// Converts a block into its RPC representation, with full transactions.
+fn rpc_block(hash: B256, block: OpBlock) -> Block<Transaction> {
+    let number = block.header.number;
+    let transactions = block
+        .body
+        .transactions
+        .into_iter()
+        .enumerate()
+        .map(|(index, tx)| {
+            // Signatures are not recovered; only deposits carry their sender.
+            let signer = match &tx {
+                OpTxEnvelope::Deposit(tx) => tx.from,
+                _ => Address::ZERO,
+            };
+            Transaction {
+                inner: alloy_rpc_types_eth::Transaction {
+                    inner: Recovered::new_unchecked(tx, signer),
+                    block_hash: Some(hash),
+                    block_number: Some(number),
+                    transaction_index: Some(index as u64),
+                    effective_gas_price: None,
+                },
+                deposit_nonce: None,
+                deposit_receipt_version: None,
+            }
+        })
+        .collect();
+
+    Block {
+        header: alloy_rpc_types_eth::Header { hash, inner: block.header, ..Default::default() },
+        uncles: vec![],
+        transactions: BlockTransactions::Full(transactions),
+        withdrawals: block.body.withdrawals,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        BuildTask, Engine, EngineState, EngineSyncStateUpdate, EngineTaskExt, FinalizeTask,
+        InsertTask, InsertTaskError, PromoteTask, PromoteTaskError, SynchronizeTask,
+        SynchronizeTaskError,
+    };
+    use op_alloy_rpc_types_engine::OpExecutionPayloadEnvelope;
+
+    /// Returns a rollup config and a test engine with only the L2 genesis block.
+    fn setup() -> (Arc<RollupConfig>, Arc<TestEngineApi>) {
+        let (cfg, engine) = TestEngineApi::genesis();
+        (cfg, Arc::new(engine))
+    }
+
+    #[tokio::test]
+    async fn test_reset_build_and_finalize() {
+        let (cfg, client) = setup();
+        let state = EngineState::default();
+        let (state_sender, _) = tokio::sync::watch::channel(state);
+        let (queue_sender, _) = tokio::sync::watch::channel(0);
+        let mut engine = Engine::new(state, state_sender, queue_sender);
+
+        let (safe_head, l1_origin, _) = engine.reset(client.clone(), cfg.clone()).await.unwrap();
+        assert_eq!(safe_head.block_info.hash, cfg.genesis.l2.hash);
+        assert_eq!(l1_origin.hash, TestEngineApi::L1_GENESIS_HASH);
+
+        let (payload_tx, mut payload_rx) = tokio::sync::mpsc::channel(1);
+        let mut state = *engine.state();
+        BuildTask::new(
+            client.clone(),
+            cfg.clone(),
+            TestEngineApi::next_attributes(safe_head, &cfg),
+            true,
+            Some(payload_tx),
+        )
+        .execute(&mut state)
+        .await
+        .unwrap();
+
+        let hash = client.canonical_hash(1).unwrap();
+        let OpExecutionPayloadEnvelope { execution_payload, .. } = payload_rx.recv().await.unwrap();
+        let payload_info =
+            L2BlockInfo::from_payload_and_genesis(execution_payload, None, &cfg.genesis).unwrap();
+        assert_eq!(payload_info.block_info.hash, hash);
+        assert_eq!(state.sync_state.unsafe_head().block_info.hash, hash);
+        assert_eq!(state.sync_state.safe_head().block_info.hash, hash);
+        assert_eq!(client.forkchoice().head_block_hash, hash);
+        assert_eq!(client.canonical_hash(1), Some(hash));
+
+        let block = client.l2_block_info_by_label(BlockNumberOrTag::Latest).await.unwrap();
+        assert_eq!(block.map(|block| block.l1_origin.hash), Some(TestEngineApi::L1_GENESIS_HASH));
+
+        FinalizeTask::new(client.clone(), cfg.clone(), 1).execute(&mut state).await.unwrap();
+        assert_eq!(state.sync_state.finalized_head().block_info.hash, hash);
+        assert_eq!(client.forkchoice().finalized_block_hash, hash);
+    }
+
+    #[tokio::test]
+    async fn test_scripted_failures() {
+        let (cfg, client) = setup();
+        let genesis = client.block(cfg.genesis.l2.hash).unwrap();
+        let genesis_info = L2BlockInfo::from_block_and_genesis(&genesis, &cfg.genesis).unwrap();
+
+        // A forkchoice state whose safe block is unknown is rejected.
+        let err = SynchronizeTask::new(
+            client.clone(),
+            cfg.clone(),
+            EngineSyncStateUpdate {
+                unsafe_head: Some(genesis_info),
+                safe_head: Some(L2BlockInfo {
+                    block_info: BlockInfo { hash: B256::repeat_byte(0xff), ..Default::default() },
+                    ..Default::default()
+                }),
+                ..Default::default()
+            },
+        )
+        .execute(&mut EngineState::default())
+        .await
+        .unwrap_err();
+        assert!(matches!(err, SynchronizeTaskError::InvalidForkchoiceState));
+
+        // Scripted `engine_newPayload` statuses are returned instead of inserting the payload.
+        let (hash, block) = {
+            let attributes = TestEngineApi::next_attributes(genesis_info, &cfg);
+            let block = build_block(&cfg, cfg.genesis.l2.hash, &genesis, attributes.inner).unwrap();
+            (block.header.hash_slow(), block)
+        };
+        let envelope = OpExecutionPayloadEnvelope {
+            parent_beacon_block_root: None,
+            execution_payload: OpExecutionPayload::V1(ExecutionPayloadV1::from_block_unchecked(
+                hash, &block,
+            )),
+        };
+        client.script_new_payload(PayloadStatusEnum::Invalid { validation_error: "bad".into() });
+        let insert = InsertTask::new(client.clone(), cfg.clone(), envelope, false);
+        let err = insert.execute(&mut EngineState::default()).await.unwrap_err();
+        assert!(matches!(err, InsertTaskError::UnexpectedPayloadStatus(_)));
+        assert!(client.block(hash).is_none());
+
+        // Once the script is exhausted, the payload is inserted and canonicalized.
+        insert.execute(&mut EngineState::default()).await.unwrap();
+        assert_eq!(client.forkchoice().head_block_hash, hash);
+    }
+
+    #[tokio::test]
+    async fn test_promote() {
+        let (cfg, client) = setup();
+        let state = EngineState::default();
+        let (state_sender, _) = tokio::sync::watch::channel(state);
+        let (queue_sender, _) = tokio::sync::watch::channel(0);
+        let mut engine = Engine::new(state, state_sender, queue_sender);
+        let (genesis_info, _, _) = engine.reset(client.clone(), cfg.clone()).await.unwrap();
+
+        // Import the next block as an unsafe block.
+        let genesis = client.block(cfg.genesis.l2.hash).unwrap();
+        let block = build_block(
+            &cfg,
+            cfg.genesis.l2.hash,
+            &genesis,
+            TestEngineApi::next_attributes(genesis_info, &cfg).inner,
+        )
+        .unwrap();
+        let block_info = L2BlockInfo::from_block_and_genesis(&block, &cfg.genesis).unwrap();
+        let hash = client.insert_block(block);
+        let mut state = *engine.state();
+        SynchronizeTask::new(
+            client.clone(),
+            cfg.clone(),
+            EngineSyncStateUpdate { unsafe_head: Some(block_info), ..Default::default() },
+        )
+        .execute(&mut state)
+        .await
+        .unwrap();
+        assert_eq!(state.sync_state.safe_head(), genesis_info);
+
+        // Promoting the block moves the safe and finalized heads.
+        PromoteTask::new(client.clone(), cfg.clone(), block_info, Some(block_info))
+            .execute(&mut state)
+            .await
+            .unwrap();
+        assert_eq!(state.sync_state.safe_head().block_info.hash, hash);
+        assert_eq!(state.sync_state.finalized_head().block_info.hash, hash);
+        assert_eq!(client.forkchoice().safe_block_hash, hash);
+        assert_eq!(client.forkchoice().finalized_block_hash, hash);
+
+        // Stale promotions are ignored.
+        PromoteTask::new(client.clone(), cfg.clone(), genesis_info, Some(genesis_info))
+            .execute(&mut state)
+            .await
+            .unwrap();
+        assert_eq!(state.sync_state.safe_head().block_info.hash, hash);
+        assert_eq!(client.forkchoice().finalized_block_hash, hash);
+
+        // The finalized head may never be ahead of the safe head.
+        let err = PromoteTask::new(client.clone(), cfg.clone(), genesis_info, Some(block_info))
+            .execute(&mut state)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, PromoteTaskError::FinalizedAheadOfSafe(1, 0)));
+    }
+}
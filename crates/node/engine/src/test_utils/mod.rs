@@ -0,0 +1,4 @@
+//! Test utilities for `kona-engine`.
+
+mod engine_api;
+pub use engine_api::TestEngineApi;
@@ -103,7 +103,7 @@ impl BlockHandler {
     /// The maximum number of blocks to keep per height.
     /// This value is chosen according to the optimism specs:
     /// <https://specs.optimism.io/protocol/rollup-node-p2p.html#block-validation>
-    const MAX_BLOCKS_TO_KEEP: usize = 5;
+    pub const MAX_BLOCKS_TO_KEEP: usize = 5;
 
     /// Determines if a block is valid.
     ///
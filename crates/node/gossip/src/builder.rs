@@ -2,7 +2,7 @@
 
 use alloy_primitives::Address;
 use kona_genesis::RollupConfig;
-use kona_peers::{PeerMonitoring, PeerScoreLevel};
+use kona_peers::{PeerMonitoring, PeerScoreLevel, PeerStore, PeerStoreFile};
 use libp2p::{
     Multiaddr, StreamProtocol, SwarmBuilder, gossipsub::Config, identity::Keypair,
     noise::Config as NoiseConfig, tcp::Config as TcpConfig, yamux::Config as YamuxConfig,
@@ -10,7 +10,24 @@ use libp2p::{
 use std::time::Duration;
 use tokio::sync::watch::{self};
 
-use crate::{Behaviour, BlockHandler, GaterConfig, GossipDriver, GossipDriverBuilderError};
+use crate::{
+    Behaviour, BlockHandler, ConnectionGate, ConnectionGater, GaterConfig, GossipDriver,
+    GossipDriverBuilderError,
+};
+
+/// The transport used by the swarm of the [`GossipDriver`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+enum SwarmTransport {
+    /// The TCP transport.
+    #[default]
+    Tcp,
+    /// The in-process memory transport, with a simulated latency.
+    #[cfg(any(test, feature = "test-utils"))]
+    Memory {
+        /// The latency added to every write.
+        latency: Duration,
+    },
+}
 
 /// A builder for the [`GossipDriver`].
 #[derive(Debug)]
@@ -36,6 +53,12 @@ pub struct GossipDriverBuilder {
     gater_config: Option<GaterConfig>,
     /// Topic scoring. Disabled by default.
     topic_scoring: bool,
+    /// Peers that are always redialed and protected from pruning.
+    static_peers: Vec<Multiaddr>,
+    /// An optional path to the peerstore.
+    peer_store: Option<PeerStoreFile>,
+    /// The transport used by the swarm.
+    transport: SwarmTransport,
 }
 
 impl GossipDriverBuilder {
@@ -57,9 +80,36 @@ impl GossipDriverBuilder {
             gater_config: None,
             rollup_config,
             topic_scoring: false,
+            static_peers: Vec::new(),
+            peer_store: None,
+            transport: SwarmTransport::Tcp,
         }
     }
 
+    /// Uses the in-process memory transport instead of TCP, delaying every write by `latency`.
+    ///
+    /// The gossip address must be a memory address, e.g. `/memory/0`.
+    #[cfg(any(test, feature = "test-utils"))]
+    pub const fn with_memory_transport(mut self, latency: Duration) -> Self {
+        self.transport = SwarmTransport::Memory { latency };
+        self
+    }
+
+    /// Sets the static peers, which are always redialed and protected from pruning.
+    ///
+    /// Each [`Multiaddr`] must contain the peer id of the static peer.
+    pub fn with_static_peers(mut self, static_peers: Vec<Multiaddr>) -> Self {
+        self.static_peers = static_peers;
+        self
+    }
+
+    /// Sets the peerstore file, used to persist known peers, scores, bans and blocks across
+    /// restarts.
+    pub fn with_peer_store(mut self, peer_store: Option<PeerStoreFile>) -> Self {
+        self.peer_store = peer_store;
+        self
+    }
+
     /// Sets the configuration for the connection gater.
     pub const fn with_gater_config(mut self, config: GaterConfig) -> Self {
         self.gater_config = Some(config);
@@ -194,25 +244,69 @@ impl GossipDriverBuilder {
 
         // Build the swarm.
         debug!(target: "gossip", "Building Swarm with Peer ID: {}", keypair.public().to_peer_id());
-        let swarm = SwarmBuilder::with_existing_identity(keypair)
-            .with_tokio()
-            .with_tcp(
-                TcpConfig::default().nodelay(true),
-                |i: &Keypair| {
-                    debug!(target: "gossip", "Noise Config Peer ID: {}", i.public().to_peer_id());
-                    NoiseConfig::new(i)
-                },
-                YamuxConfig::default,
-            )
-            .map_err(|_| GossipDriverBuilderError::TcpError)?
-            .with_behaviour(|_| behaviour)
-            .map_err(|_| GossipDriverBuilderError::WithBehaviourError)?
-            .with_swarm_config(|c| c.with_idle_connection_timeout(timeout))
-            .build();
+        let swarm_builder = SwarmBuilder::with_existing_identity(keypair).with_tokio();
+        let swarm = match self.transport {
+            SwarmTransport::Tcp => swarm_builder
+                .with_tcp(
+                    TcpConfig::default().nodelay(true),
+                    |i: &Keypair| {
+                        debug!(target: "gossip", "Noise Config Peer ID: {}", i.public().to_peer_id());
+                        NoiseConfig::new(i)
+                    },
+                    YamuxConfig::default,
+                )
+                .map_err(|_| GossipDriverBuilderError::TcpError)?
+                .with_behaviour(|_| behaviour)
+                .map_err(|_| GossipDriverBuilderError::WithBehaviourError)?
+                .with_swarm_config(|c| c.with_idle_connection_timeout(timeout))
+                .build(),
+            #[cfg(any(test, feature = "test-utils"))]
+            SwarmTransport::Memory { latency } => swarm_builder
+                .with_other_transport(|i: &Keypair| crate::test_utils::memory_transport(i, latency))
+                .map_err(|_| GossipDriverBuilderError::MemoryTransportError)?
+                .with_behaviour(|_| behaviour)
+                .map_err(|_| GossipDriverBuilderError::WithBehaviourError)?
+                .with_swarm_config(|c| c.with_idle_connection_timeout(timeout))
+                .build(),
+        };
 
         let gater_config = self.gater_config.take().unwrap_or_default();
-        let gate = crate::ConnectionGater::new(gater_config);
+        let mut gate = ConnectionGater::new(gater_config);
+
+        // Note: if the peerstore file cannot be opened, we use an in-memory peerstore.
+        let peer_store = self.peer_store.map_or_else(PeerStore::default, |peer_store| {
+            peer_store.try_into().unwrap_or_else(|e| {
+                warn!(target: "gossip", "Failed to open peer store, peers will not be persisted: {:?}", e);
+                PeerStore::default()
+            })
+        });
+
+        // Restore the blocks that were applied before the last restart.
+        peer_store.blocked_peers.iter().for_each(|peer_id| gate.block_peer(peer_id));
+        peer_store.blocked_addrs.iter().for_each(|ip| gate.block_addr(*ip));
+        peer_store.blocked_subnets.iter().for_each(|subnet| gate.block_subnet(*subnet));
+
+        // Static peers are protected from dial thresholds and peer scoring.
+        let static_peers = self
+            .static_peers
+            .into_iter()
+            .filter(|addr| match ConnectionGater::peer_id_from_addr(addr) {
+                Some(peer_id) => {
+                    gate.protect_peer(peer_id);
+                    true
+                }
+                None => {
+                    warn!(target: "gossip", peer=?addr, "Ignoring static peer without a peer id");
+                    false
+                }
+            })
+            .collect();
+
+        let mut driver = GossipDriver::new(swarm, addr, handler, sync_handler, sync_protocol, gate);
+        driver.peer_monitoring = self.peer_monitoring;
+        driver.static_peers = static_peers;
+        driver.peer_store = peer_store;
 
-        Ok((GossipDriver::new(swarm, addr, handler, sync_handler, sync_protocol, gate), signer_tx))
+        Ok((driver, signer_tx))
     }
 }
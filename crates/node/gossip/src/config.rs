@@ -54,6 +54,14 @@ lazy_static! {
     /// The peer score inspect frequency.
     /// The frequency at which peer scores are inspected.
     pub static ref PEER_SCORE_INSPECT_FREQUENCY: Duration = 15 * Duration::from_secs(1);
+
+    /// The static peer redial frequency.
+    /// The frequency at which disconnected static peers are redialed.
+    pub static ref STATIC_PEER_REDIAL_FREQUENCY: Duration = 15 * Duration::from_secs(1);
+
+    /// The peer store sync frequency.
+    /// The frequency at which the peer store is written to disk.
+    pub static ref PEER_STORE_SYNC_FREQUENCY: Duration = 60 * Duration::from_secs(1);
 }
 
 ////////////////////////////////////////////////////////////////////////////////////////////////
@@ -5,7 +5,7 @@ use derive_more::Debug;
 use discv5::Enr;
 use futures::{AsyncReadExt, AsyncWriteExt, stream::StreamExt};
 use kona_genesis::RollupConfig;
-use kona_peers::{EnrValidation, PeerMonitoring, enr_to_multiaddr};
+use kona_peers::{EnrValidation, PeerMonitoring, PeerStore, enr_to_multiaddr};
 use libp2p::{
     Multiaddr, PeerId, Swarm, TransportError,
     gossipsub::{IdentTopic, MessageId},
@@ -15,7 +15,7 @@ use libp2p_identity::Keypair;
 use libp2p_stream::IncomingStreams;
 use op_alloy_rpc_types_engine::OpNetworkPayloadEnvelope;
 use std::{
-    collections::HashMap,
+    collections::{HashMap, HashSet},
     sync::Arc,
     time::{Duration, Instant},
 };
@@ -61,6 +61,10 @@ pub struct GossipDriver<G: ConnectionGate> {
     pub connection_gate: G,
     /// Tracks ping times for peers.
     pub ping: Arc<Mutex<HashMap<PeerId, Duration>>>,
+    /// Peers that are always redialed and protected from pruning.
+    pub static_peers: Vec<Multiaddr>,
+    /// The [`PeerStore`] that persists known peers, scores, bans and blocks across restarts.
+    pub peer_store: PeerStore,
 }
 
 impl<G> GossipDriver<G>
@@ -97,6 +101,8 @@ where
             sync_protocol: Some(sync_protocol),
             connection_gate: gate,
             ping: Arc::new(Mutex::new(Default::default())),
+            static_peers: Vec::new(),
+            peer_store: PeerStore::default(),
         }
     }
 
@@ -245,6 +251,102 @@ where
         self.dial_multiaddr(multiaddr);
     }
 
+    /// Dials the static peers that are not connected.
+    pub fn dial_static_peers(&mut self) {
+        for addr in self.static_peers.clone() {
+            let connected = ConnectionGater::peer_id_from_addr(&addr)
+                .is_some_and(|peer_id| self.swarm.is_connected(&peer_id));
+            if !connected {
+                debug!(target: "gossip", peer=?addr, "Dialing static peer");
+                self.dial_multiaddr(addr);
+            }
+        }
+    }
+
+    /// Records the scores of the connected peers and the blocks of the connection gate in the
+    /// [`PeerStore`], and writes it to disk.
+    pub fn sync_peer_store(&mut self) -> Result<(), std::io::Error> {
+        for peer_id in self.swarm.connected_peers() {
+            if let Some(score) = self.swarm.behaviour().gossipsub.peer_score(peer_id) {
+                self.peer_store.set_score(peer_id, score);
+            }
+        }
+        self.peer_store.blocked_peers =
+            self.connection_gate.list_blocked_peers().into_iter().collect();
+        self.peer_store.blocked_addrs =
+            self.connection_gate.list_blocked_addrs().into_iter().collect();
+        self.peer_store.blocked_subnets =
+            self.connection_gate.list_blocked_subnets().into_iter().collect();
+        self.peer_store.sync()
+    }
+
+    /// Bans the connected peers whose score is below the ban threshold of the [`PeerMonitoring`].
+    ///
+    /// Banned peers are disconnected, and their ban is recorded in the [`PeerStore`] so that it
+    /// survives restarts. Protected peers, such as static peers, are never banned.
+    ///
+    /// Returns the listen addresses of the banned peers, which should also be banned from
+    /// discovery. If peer monitoring is disabled, no peer is banned.
+    pub fn ban_peers_below_threshold(&mut self) -> HashSet<Multiaddr> {
+        let Some(monitoring) = self.peer_monitoring.as_ref() else {
+            return HashSet::new();
+        };
+
+        // We iterate over all connected peers and check their scores.
+        let protected_peers = self.connection_gate.list_protected_peers();
+        let peers_to_ban = self
+            .swarm
+            .connected_peers()
+            .filter(|peer_id| {
+                // If the score is not available, we use a default value of 0.
+                let score =
+                    self.swarm.behaviour().gossipsub.peer_score(peer_id).unwrap_or_default();
+
+                // Record the peer score in the metrics.
+                kona_macros::record!(
+                    histogram,
+                    crate::Metrics::PEER_SCORES,
+                    "peer",
+                    peer_id.to_string(),
+                    score
+                );
+
+                !protected_peers.contains(peer_id) && score < monitoring.ban_threshold
+            })
+            .copied()
+            .collect::<Vec<_>>();
+
+        let ban_duration = monitoring.ban_duration;
+        let mut addrs_to_ban = HashSet::new();
+        for peer_id in peers_to_ban {
+            if self.swarm.disconnect_peer_id(peer_id).is_err() {
+                warn!(target: "gossip", ?peer_id, "Trying to disconnect a non-existing peer from the gossip driver.");
+            }
+
+            // Record the duration of the peer connection.
+            if let Some(start_time) = self.peer_connection_start.remove(&peer_id) {
+                let _peer_duration = start_time.elapsed();
+                kona_macros::record!(
+                    histogram,
+                    crate::Metrics::GOSSIP_PEER_CONNECTION_DURATION_SECONDS,
+                    _peer_duration.as_secs_f64()
+                );
+            }
+
+            // Persist the ban so that it survives restarts.
+            self.peer_store.ban(peer_id, ban_duration);
+
+            if let Some(info) = self.peerstore.remove(&peer_id) {
+                self.connection_gate.remove_dial(&peer_id);
+                let _score =
+                    self.swarm.behaviour().gossipsub.peer_score(&peer_id).unwrap_or_default();
+                kona_macros::inc!(gauge, crate::Metrics::BANNED_PEERS, "peer_id" => peer_id.to_string(), "score" => _score.to_string());
+                addrs_to_ban.extend(info.listen_addrs);
+            }
+        }
+        addrs_to_ban
+    }
+
     /// Dials the given [`Multiaddr`].
     pub fn dial_multiaddr(&mut self, addr: Multiaddr) {
         // Check if we're allowed to dial the address.
@@ -259,6 +361,12 @@ where
             return;
         };
 
+        if self.peer_store.is_banned(&peer_id) {
+            debug!(target: "gossip", peer=?addr, "Peer is banned, not dialing");
+            kona_macros::inc!(gauge, crate::Metrics::DIAL_PEER_ERROR, "type" => "banned_peer", "peer" => peer_id.to_string());
+            return;
+        }
+
         if self.swarm.connected_peers().any(|p| p == &peer_id) {
             debug!(target: "gossip", peer=?addr, "Already connected to peer, not dialing");
             kona_macros::inc!(gauge, crate::Metrics::DIAL_PEER_ERROR, "type" => "already_connected", "peer" => peer_id.to_string());
@@ -331,6 +439,7 @@ where
         match event {
             libp2p::identify::Event::Received { connection_id, peer_id, info } => {
                 debug!(target: "gossip", ?connection_id, ?peer_id, ?info, "Received identify info from peer");
+                self.peer_store.record_peer(peer_id, info.listen_addrs.clone());
                 self.peerstore.insert(peer_id, info);
             }
             libp2p::identify::Event::Sent { connection_id, peer_id } => {
@@ -395,6 +504,13 @@ where
                 return self.handle_gossip_event(behavior_event)
             }
             SwarmEvent::ConnectionEstablished { peer_id, .. } => {
+                // Peers banned before a restart are still banned, including inbound ones.
+                if self.peer_store.is_banned(&peer_id) {
+                    debug!(target: "gossip", ?peer_id, "Disconnecting banned peer");
+                    let _ = self.swarm.disconnect_peer_id(peer_id);
+                    return None;
+                }
+
                 let peer_count = self.swarm.connected_peers().count();
                 info!(target: "gossip", "Connection established: {:?} | Peer Count: {}", peer_id, peer_count);
                 kona_macros::inc!(
@@ -407,14 +523,19 @@ where
 
                 self.peer_connection_start.insert(peer_id, Instant::now());
             }
-            SwarmEvent::OutgoingConnectionError { peer_id: _peer_id, error, .. } => {
+            SwarmEvent::OutgoingConnectionError { peer_id, error, .. } => {
                 debug!(target: "gossip", "Outgoing connection error: {:?}", error);
                 kona_macros::inc!(
                     gauge,
                     crate::Metrics::GOSSIPSUB_CONNECTION,
                     "type" => "outgoing_error",
-                    "peer" => _peer_id.map(|p| p.to_string()).unwrap_or_default()
+                    "peer" => peer_id.map(|p| p.to_string()).unwrap_or_default()
                 );
+
+                // The dial failed, so the peer can be dialed again.
+                if let Some(peer_id) = peer_id {
+                    self.connection_gate.remove_dial(&peer_id);
+                }
             }
             SwarmEvent::IncomingConnectionError {
                 error, connection_id: _connection_id, ..
@@ -448,14 +569,15 @@ where
                     );
                 }
 
-                // Record the peer score in the metrics if available.
-                if let Some(_peer_score) = self.behaviour_mut().gossipsub.peer_score(&peer_id) {
+                // Record the peer score in the peer store and the metrics if available.
+                if let Some(peer_score) = self.behaviour_mut().gossipsub.peer_score(&peer_id) {
+                    self.peer_store.set_score(&peer_id, peer_score);
                     kona_macros::record!(
                         histogram,
                         crate::Metrics::PEER_SCORES,
                         "peer",
                         peer_id.to_string(),
-                        _peer_score
+                        peer_score
                     );
                 }
 
@@ -66,6 +66,10 @@ pub enum GossipDriverBuilderError {
     /// The sync request/response protocol has already been accepted.
     #[error("sync request/response protocol already accepted")]
     SyncReqRespAlreadyAccepted,
+    /// An error when setting up the in-process memory transport.
+    #[cfg(any(test, feature = "test-utils"))]
+    #[error("error setting up the memory transport")]
+    MemoryTransportError,
 }
 
 /// An error type representing reasons why a peer cannot be dialed.
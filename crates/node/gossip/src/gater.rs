@@ -133,6 +133,11 @@ impl ConnectionGater {
         })
     }
 
+    /// Returns whether the given [`Multiaddr`] is an in-process memory address.
+    pub fn is_memory_addr(addr: &Multiaddr) -> bool {
+        addr.iter().any(|component| matches!(component, libp2p::multiaddr::Protocol::Memory(_)))
+    }
+
     /// Constructs the [`IpAddr`] from the given [`Multiaddr`].
     pub fn ip_from_addr(addr: &Multiaddr) -> Option<IpAddr> {
         addr.iter().find_map(|component| match component {
@@ -188,6 +193,11 @@ impl ConnectionGate for ConnectionGater {
             return Err(DialError::PeerBlocked { peer_id });
         }
 
+        // In-process memory addresses have no IP address to check.
+        if Self::is_memory_addr(addr) {
+            return Ok(());
+        }
+
         // There must be a reachable IP Address in the Multiaddr protocol stack.
         let ip_addr = Self::ip_from_addr(addr).ok_or_else(|| {
             warn!(target: "p2p", peer=?addr, "Failed to extract IpAddr from Multiaddr");
@@ -369,3 +379,21 @@ fn test_dial_error_handling() {
     let result = gater.can_dial(&valid_addr);
     assert!(matches!(result, Err(DialError::AlreadyDialing { .. })));
 }
+
+#[test]
+fn test_memory_addr_dial() {
+    use crate::{ConnectionGate, DialError};
+    use std::str::FromStr;
+
+    let mut gater = ConnectionGater::new(GaterConfig::default());
+    let peer_id = PeerId::from_str("12D3KooWEyoppNCUx8Yx66oV9fJnriXwCcXwDDUA2kj6vnc6iDEp").unwrap();
+
+    // Memory addresses have no ip address but can still be dialed.
+    let memory_addr = Multiaddr::from_str(&format!("/memory/1234/p2p/{peer_id}")).unwrap();
+    assert!(ConnectionGater::is_memory_addr(&memory_addr));
+    assert!(gater.can_dial(&memory_addr).is_ok());
+
+    // Blocked peers are still refused.
+    gater.block_peer(&peer_id);
+    assert!(matches!(gater.can_dial(&memory_addr), Err(DialError::PeerBlocked { .. })));
+}
@@ -40,8 +40,8 @@ mod config;
 pub use config::{
     DEFAULT_MESH_D, DEFAULT_MESH_DHI, DEFAULT_MESH_DLAZY, DEFAULT_MESH_DLO,
     GLOBAL_VALIDATE_THROTTLE, GOSSIP_HEARTBEAT, MAX_GOSSIP_SIZE, MAX_OUTBOUND_QUEUE,
-    MAX_VALIDATE_QUEUE, MIN_GOSSIP_SIZE, PEER_SCORE_INSPECT_FREQUENCY, SEEN_MESSAGES_TTL,
-    default_config, default_config_builder,
+    MAX_VALIDATE_QUEUE, MIN_GOSSIP_SIZE, PEER_SCORE_INSPECT_FREQUENCY, PEER_STORE_SYNC_FREQUENCY,
+    SEEN_MESSAGES_TTL, STATIC_PEER_REDIAL_FREQUENCY, default_config, default_config_builder,
 };
 
 mod gate;
@@ -72,5 +72,8 @@ pub use driver::GossipDriver;
 mod block_validity;
 pub use block_validity::BlockInvalidError;
 
+#[cfg(any(test, feature = "test-utils"))]
+pub mod test_utils;
+
 #[cfg(test)]
 pub(crate) use block_validity::tests::*;
@@ -0,0 +1,13 @@
+//! Test utilities for `kona-gossip`.
+//!
+//! Provides a [`TestNetwork`] of in-process [`GossipDriver`](crate::GossipDriver)s, connected
+//! over libp2p's memory transport.
+
+mod transport;
+pub use transport::{LatencyStream, memory_transport};
+
+mod network;
+pub use network::{TestNetwork, TestNetworkError, TestNode, signed_block};
+
+/// The L2 chain id shared by the gossip and discovery test networks.
+pub use kona_disc::test_utils::TEST_CHAIN_ID;
@@ -0,0 +1,522 @@
+//! An in-process network of [`GossipDriver`]s.
+
+use crate::{
+    ConnectionGate, ConnectionGater, GossipDriver, GossipDriverBuilder, GossipDriverBuilderError,
+    PublishError,
+};
+use alloy_consensus::{Block, BlockBody, EMPTY_OMMER_ROOT_HASH, EMPTY_ROOT_HASH, Header};
+use alloy_primitives::{Address, B256, Bytes};
+use alloy_rpc_types_engine::ExecutionPayloadV1;
+use alloy_signer::SignerSync;
+use alloy_signer_local::PrivateKeySigner;
+use futures::future::select_all;
+use kona_disc::test_utils::TEST_CHAIN_ID;
+use kona_genesis::RollupConfig;
+use libp2p::{
+    Multiaddr, PeerId, TransportError, gossipsub::MessageId, identity::Keypair, multiaddr::Protocol,
+};
+use op_alloy_consensus::OpTxEnvelope;
+use op_alloy_rpc_types_engine::{
+    OpExecutionPayload, OpExecutionPayloadEnvelope, OpNetworkPayloadEnvelope,
+};
+use std::{
+    collections::HashSet,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+use tokio::{sync::watch, time::Instant};
+
+/// Builds a V1 block at the given height, signed by the given unsafe block signer.
+///
+/// The `salt` is written to the extra data of the block, so that different blocks can be built
+/// for the same height.
+pub fn signed_block(signer: &PrivateKeySigner, number: u64, salt: u8) -> OpNetworkPayloadEnvelope {
+    let timestamp = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs();
+    let header = Header {
+        number,
+        timestamp,
+        base_fee_per_gas: Some(1),
+        extra_data: Bytes::from(vec![salt]),
+        ommers_hash: EMPTY_OMMER_ROOT_HASH,
+        transactions_root: EMPTY_ROOT_HASH,
+        ..Default::default()
+    };
+    let block = Block::<OpTxEnvelope> {
+        header,
+        body: BlockBody { transactions: Vec::new(), ommers: Vec::new(), withdrawals: None },
+    };
+
+    let envelope = OpExecutionPayloadEnvelope {
+        execution_payload: OpExecutionPayload::V1(ExecutionPayloadV1::from_block_slow(&block)),
+        parent_beacon_block_root: None,
+    };
+    let payload_hash = envelope.payload_hash();
+    let signature = signer
+        .sign_hash_sync(&payload_hash.signature_message(TEST_CHAIN_ID))
+        .expect("signing with a local key cannot fail");
+
+    OpNetworkPayloadEnvelope {
+        payload: envelope.execution_payload,
+        parent_beacon_block_root: None,
+        signature,
+        payload_hash,
+    }
+}
+
+/// An error returned while setting up a [`TestNetwork`].
+#[derive(Debug, thiserror::Error)]
+pub enum TestNetworkError {
+    /// An error occurred building a gossip driver.
+    #[error(transparent)]
+    Builder(#[from] GossipDriverBuilderError),
+    /// An error occurred starting a gossip driver.
+    #[error("error starting the gossip driver: {0}")]
+    Listen(#[from] TransportError<std::io::Error>),
+}
+
+/// A node of the [`TestNetwork`].
+#[derive(Debug)]
+pub struct TestNode {
+    /// The gossip driver of the node.
+    pub driver: GossipDriver<ConnectionGater>,
+    /// The sender used to rotate the unsafe block signer of the node.
+    pub signer_tx: watch::Sender<Address>,
+    /// The address the node listens on, including its peer id.
+    pub addr: Multiaddr,
+    /// The valid blocks the node received over gossip.
+    pub received: Vec<OpNetworkPayloadEnvelope>,
+}
+
+impl TestNode {
+    /// Returns the peer id of the node.
+    pub fn peer_id(&self) -> PeerId {
+        *self.driver.local_peer_id()
+    }
+
+    /// Returns whether the node is connected to the given peer.
+    pub fn is_connected(&self, peer_id: &PeerId) -> bool {
+        self.driver.swarm.is_connected(peer_id)
+    }
+
+    /// Returns whether the node received the block with the given hash.
+    pub fn has_received(&self, block_hash: B256) -> bool {
+        self.received.iter().any(|block| block.payload.block_hash() == block_hash)
+    }
+
+    /// Returns the number of peers in the node's mesh for the V1 blocks topic.
+    pub fn mesh_peers(&self) -> usize {
+        let topic = self.driver.handler.blocks_v1_topic.hash();
+        self.driver.swarm.behaviour().gossipsub.mesh_peers(&topic).count()
+    }
+}
+
+/// An in-process network of [`GossipDriver`]s, each with its own [`ConnectionGater`] and
+/// [`BlockHandler`](crate::BlockHandler), connected over libp2p's memory transport.
+///
+/// Nodes are connected explicitly with [`TestNetwork::connect`]; discv5 discovery is covered by
+/// the `kona_disc::test_utils::TestDiscovery` harness. The swarms are driven from the test task
+/// with [`TestNetwork::run_until`] and [`TestNetwork::run_for`].
+#[derive(Debug)]
+pub struct TestNetwork {
+    /// The nodes of the network.
+    pub nodes: Vec<TestNode>,
+    /// The unsafe block signer of the network.
+    pub signer: PrivateKeySigner,
+    /// The latency of the connections of the network.
+    pub latency: Duration,
+}
+
+impl TestNetwork {
+    /// Spawns a network of `size` nodes without latency.
+    pub async fn new(size: usize) -> Result<Self, TestNetworkError> {
+        Self::with_builder(size, Duration::ZERO, |builder| builder).await
+    }
+
+    /// Spawns a network of `size` nodes, whose connections delay every write by `latency`.
+    ///
+    /// The `configure` closure is applied to the [`GossipDriverBuilder`] of every node.
+    pub async fn with_builder(
+        size: usize,
+        latency: Duration,
+        configure: impl Fn(GossipDriverBuilder) -> GossipDriverBuilder,
+    ) -> Result<Self, TestNetworkError> {
+        let mut network =
+            Self { nodes: Vec::with_capacity(size), signer: PrivateKeySigner::random(), latency };
+        for _ in 0..size {
+            let node = network.spawn_node(&configure).await?;
+            network.nodes.push(node);
+        }
+        Ok(network)
+    }
+
+    /// Builds and starts a node of the network, applying `configure` to its
+    /// [`GossipDriverBuilder`].
+    async fn spawn_node(
+        &self,
+        configure: impl Fn(GossipDriverBuilder) -> GossipDriverBuilder,
+    ) -> Result<TestNode, TestNetworkError> {
+        let rollup_config =
+            RollupConfig { l2_chain_id: TEST_CHAIN_ID.into(), block_time: 2, ..Default::default() };
+        let builder = GossipDriverBuilder::new(
+            rollup_config,
+            self.signer.address(),
+            Multiaddr::empty().with(Protocol::Memory(0)),
+            Keypair::generate_secp256k1(),
+        )
+        .with_memory_transport(self.latency);
+        let (mut driver, signer_tx) = configure(builder).build()?;
+        let addr = driver.start().await?.with(Protocol::P2p(*driver.local_peer_id()));
+        Ok(TestNode { driver, signer_tx, addr, received: Vec::new() })
+    }
+
+    /// Restarts node `node` with a fresh identity, applying `configure` to its
+    /// [`GossipDriverBuilder`].
+    ///
+    /// The previous driver is dropped, so that the restarted node only keeps the state it reloads,
+    /// such as its [`PeerStore`](kona_peers::PeerStore).
+    pub async fn restart(
+        &mut self,
+        node: usize,
+        configure: impl Fn(GossipDriverBuilder) -> GossipDriverBuilder,
+    ) -> Result<(), TestNetworkError> {
+        let restarted = self.spawn_node(configure).await?;
+        self.nodes[node] = restarted;
+        Ok(())
+    }
+
+    /// Builds a block at the given height, signed by the unsafe block signer of the network.
+    pub fn block(&self, number: u64, salt: u8) -> OpNetworkPayloadEnvelope {
+        signed_block(&self.signer, number, salt)
+    }
+
+    /// Dials node `to` from node `from`.
+    pub fn connect(&mut self, from: usize, to: usize) {
+        let addr = self.nodes[to].addr.clone();
+        self.nodes[from].driver.dial_multiaddr(addr);
+    }
+
+    /// Connects every pair of nodes.
+    pub fn connect_all(&mut self) {
+        for from in 0..self.nodes.len() {
+            for to in from + 1..self.nodes.len() {
+                self.connect(from, to);
+            }
+        }
+    }
+
+    /// Partitions the network between the given nodes and the rest of the network.
+    ///
+    /// Nodes on either side of the partition block each other in their [`ConnectionGater`] and
+    /// disconnect, until [`TestNetwork::heal`] is called.
+    pub fn partition(&mut self, side: &[usize]) {
+        for a in side.iter().copied() {
+            for b in (0..self.nodes.len()).filter(|b| !side.contains(b)) {
+                let (peer_a, peer_b) = (self.nodes[a].peer_id(), self.nodes[b].peer_id());
+                self.nodes[a].driver.connection_gate.block_peer(&peer_b);
+                self.nodes[b].driver.connection_gate.block_peer(&peer_a);
+                let _ = self.nodes[a].driver.swarm.disconnect_peer_id(peer_b);
+            }
+        }
+    }
+
+    /// Heals all partitions, and reconnects every pair of nodes.
+    pub fn heal(&mut self) {
+        for node in &mut self.nodes {
+            for peer_id in node.driver.connection_gate.list_blocked_peers() {
+                node.driver.connection_gate.unblock_peer(&peer_id);
+            }
+        }
+        self.connect_all();
+    }
+
+    /// Runs a peer monitoring round on node `node`, as the network actor does on every tick of its
+    /// peer score inspector.
+    ///
+    /// Returns the listen addresses of the peers that were banned.
+    pub fn monitor_peers(&mut self, node: usize) -> HashSet<Multiaddr> {
+        self.nodes[node].driver.ban_peers_below_threshold()
+    }
+
+    /// Rotates the unsafe block signer of every node.
+    pub fn set_unsafe_signer(&self, signer: Address) {
+        for node in &self.nodes {
+            node.signer_tx.send_replace(signer);
+        }
+    }
+
+    /// Publishes the block from the given node.
+    pub fn publish(
+        &mut self,
+        node: usize,
+        block: OpNetworkPayloadEnvelope,
+    ) -> Result<Option<MessageId>, PublishError> {
+        let timestamp = block.payload.timestamp();
+        self.nodes[node].driver.publish(|handler| handler.topic(timestamp), Some(block))
+    }
+
+    /// Handles the next swarm event of any node.
+    pub async fn step(&mut self) {
+        let (index, event) = {
+            let next =
+                self.nodes.iter_mut().enumerate().map(|(index, node)| {
+                    Box::pin(async move { (index, node.driver.next().await) })
+                });
+            select_all(next).await.0
+        };
+
+        let Some(event) = event else {
+            return;
+        };
+        let node = &mut self.nodes[index];
+        if let Some(block) = node.driver.handle_event(event) {
+            node.received.push(block);
+        }
+    }
+
+    /// Drives the network until the condition holds, or until the timeout elapses.
+    ///
+    /// Returns whether the condition holds.
+    pub async fn run_until(
+        &mut self,
+        timeout: Duration,
+        condition: impl Fn(&Self) -> bool,
+    ) -> bool {
+        let deadline = Instant::now() + timeout;
+        while !condition(self) {
+            if tokio::time::timeout_at(deadline, self.step()).await.is_err() {
+                return condition(self);
+            }
+        }
+        true
+    }
+
+    /// Drives the network for the given duration.
+    pub async fn run_for(&mut self, duration: Duration) {
+        self.run_until(duration, |_| false).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::BlockHandler;
+    use kona_peers::{PeerMonitoring, PeerScoreLevel, PeerStoreFile};
+
+    const TIMEOUT: Duration = Duration::from_secs(30);
+
+    const BAN_DURATION: Duration = Duration::from_secs(60);
+
+    /// Fully connects the network, and waits for the mesh to form.
+    async fn meshed_network(mut network: TestNetwork) -> TestNetwork {
+        let size = network.nodes.len();
+        network.connect_all();
+        assert!(
+            network
+                .run_until(TIMEOUT, |n| n.nodes.iter().all(|node| node.mesh_peers() == size - 1))
+                .await
+        );
+        network
+    }
+
+    #[tokio::test]
+    async fn test_mesh_formation() {
+        let mut network = meshed_network(TestNetwork::new(4).await.unwrap()).await;
+
+        let block = network.block(1, 0);
+        let hash = block.payload.block_hash();
+        network.publish(0, block).unwrap();
+
+        assert!(
+            network
+                .run_until(TIMEOUT, |n| n.nodes[1..].iter().all(|node| node.has_received(hash)))
+                .await
+        );
+        assert!(!network.nodes[0].has_received(hash));
+    }
+
+    #[tokio::test]
+    async fn test_mesh_formation_with_latency() {
+        let network = TestNetwork::with_builder(3, Duration::from_millis(50), |b| b).await.unwrap();
+        let mut network = meshed_network(network).await;
+
+        let block = network.block(1, 0);
+        let hash = block.payload.block_hash();
+        network.publish(2, block).unwrap();
+
+        assert!(
+            network
+                .run_until(TIMEOUT, |n| n.nodes[..2].iter().all(|node| node.has_received(hash)))
+                .await
+        );
+    }
+
+    #[tokio::test]
+    async fn test_partition_and_heal() {
+        let mut network = meshed_network(TestNetwork::new(4).await.unwrap()).await;
+        let (peer_2, peer_3) = (network.nodes[2].peer_id(), network.nodes[3].peer_id());
+
+        network.partition(&[0, 1]);
+        assert!(
+            network
+                .run_until(TIMEOUT, |n| {
+                    n.nodes[..2]
+                        .iter()
+                        .all(|node| !node.is_connected(&peer_2) && !node.is_connected(&peer_3))
+                })
+                .await
+        );
+
+        // Blocks do not cross the partition.
+        let block = network.block(1, 0);
+        let hash = block.payload.block_hash();
+        network.publish(0, block).unwrap();
+        assert!(network.run_until(TIMEOUT, |n| n.nodes[1].has_received(hash)).await);
+        network.run_for(Duration::from_secs(2)).await;
+        assert!(!network.nodes[2].has_received(hash));
+        assert!(!network.nodes[3].has_received(hash));
+
+        // Blocked peers cannot be redialed while the partition holds.
+        network.connect(0, 2);
+        network.run_for(Duration::from_secs(1)).await;
+        assert!(!network.nodes[0].is_connected(&peer_2));
+
+        network.heal();
+        assert!(
+            network
+                .run_until(TIMEOUT, |n| n.nodes[..2]
+                    .iter()
+                    .all(|node| { node.is_connected(&peer_2) && node.is_connected(&peer_3) }))
+                .await
+        );
+
+        let block = network.block(2, 0);
+        let hash = block.payload.block_hash();
+        network.publish(0, block).unwrap();
+        assert!(
+            network
+                .run_until(TIMEOUT, |n| n.nodes[1..].iter().all(|node| node.has_received(hash)))
+                .await
+        );
+    }
+
+    #[tokio::test]
+    async fn test_signer_rotation() {
+        let mut network = meshed_network(TestNetwork::new(3).await.unwrap()).await;
+        let old_signer = network.signer.clone();
+        let new_signer = PrivateKeySigner::random();
+        network.set_unsafe_signer(new_signer.address());
+
+        // Blocks signed by the previous signer are rejected.
+        let stale = signed_block(&old_signer, 1, 0);
+        let stale_hash = stale.payload.block_hash();
+        network.publish(0, stale).unwrap();
+        network.run_for(Duration::from_secs(2)).await;
+        assert!(network.nodes.iter().all(|node| !node.has_received(stale_hash)));
+
+        // Blocks signed by the new signer are accepted.
+        let fresh = signed_block(&new_signer, 1, 1);
+        let fresh_hash = fresh.payload.block_hash();
+        network.publish(0, fresh).unwrap();
+        assert!(
+            network
+                .run_until(TIMEOUT, |n| n.nodes[1..]
+                    .iter()
+                    .all(|node| node.has_received(fresh_hash)))
+                .await
+        );
+    }
+
+    #[tokio::test]
+    async fn test_duplicate_block_limits() {
+        let mut network = meshed_network(TestNetwork::new(2).await.unwrap()).await;
+
+        // Publishing the same block twice is deduplicated by gossipsub.
+        let block = network.block(1, 0);
+        network.publish(0, block.clone()).unwrap();
+        assert!(matches!(
+            network.publish(0, block),
+            Err(PublishError::PublishError(libp2p::gossipsub::PublishError::Duplicate))
+        ));
+
+        // The block handler rejects blocks at a height once it has seen more than
+        // `MAX_BLOCKS_TO_KEEP` different blocks for it.
+        let accepted = BlockHandler::MAX_BLOCKS_TO_KEEP + 1;
+        for salt in 1..=accepted as u8 + 1 {
+            network.publish(0, network.block(1, salt)).unwrap();
+        }
+        assert!(network.run_until(TIMEOUT, |n| n.nodes[1].received.len() == accepted).await);
+        network.run_for(Duration::from_secs(2)).await;
+        assert_eq!(network.nodes[1].received.len(), accepted);
+
+        // Blocks at other heights are still accepted.
+        let block = network.block(2, 0);
+        let hash = block.payload.block_hash();
+        network.publish(0, block).unwrap();
+        assert!(network.run_until(TIMEOUT, |n| n.nodes[1].has_received(hash)).await);
+    }
+
+    #[tokio::test]
+    async fn test_scoring_and_bans() {
+        let monitoring = PeerMonitoring { ban_threshold: -100.0, ban_duration: BAN_DURATION };
+        let configure = |builder: GossipDriverBuilder| {
+            builder
+                .with_peer_scoring(PeerScoreLevel::Light)
+                .with_topic_scoring(true)
+                .with_peer_monitoring(Some(monitoring.clone()))
+        };
+        let peer_store_dir = tempfile::tempdir().unwrap();
+        let peer_store = PeerStoreFile::Custom(peer_store_dir.path().join("peerstore.json"));
+        let with_peer_store = |builder: GossipDriverBuilder| {
+            configure(builder).with_peer_store(Some(peer_store.clone()))
+        };
+
+        let mut network = TestNetwork::with_builder(3, Duration::ZERO, &configure).await.unwrap();
+        network.restart(1, &with_peer_store).await.unwrap();
+        let mut network = meshed_network(network).await;
+        let (offender, honest) = (network.nodes[0].peer_id(), network.nodes[2].peer_id());
+
+        // Invalid blocks lower the score of the peer that sent them below the ban threshold.
+        let rogue = PrivateKeySigner::random();
+        for number in 1..=5 {
+            network.publish(0, signed_block(&rogue, number, 0)).unwrap();
+        }
+        assert!(
+            network
+                .run_until(TIMEOUT, |n| {
+                    n.nodes[1]
+                        .driver
+                        .swarm
+                        .behaviour()
+                        .gossipsub
+                        .peer_score(&offender)
+                        .is_some_and(|score| score < monitoring.ban_threshold)
+                })
+                .await
+        );
+
+        // Peer monitoring bans the offender, and only the offender.
+        let banned_addrs = network.monitor_peers(1);
+        assert_eq!(banned_addrs, HashSet::from([network.nodes[0].driver.addr.clone()]));
+        assert!(network.nodes[1].driver.peer_store.is_banned(&offender));
+        assert!(!network.nodes[1].driver.peer_store.is_banned(&honest));
+
+        // Once banned, the offender is disconnected and cannot reconnect.
+        assert!(network.run_until(TIMEOUT, |n| !n.nodes[1].is_connected(&offender)).await);
+        network.connect(0, 1);
+        network.connect(1, 0);
+        network.run_for(Duration::from_secs(2)).await;
+        assert!(!network.nodes[1].is_connected(&offender));
+        assert!(network.nodes[1].is_connected(&honest));
+        assert!(network.nodes[2].is_connected(&offender));
+
+        // The ban persists across a restart of the node.
+        network.nodes[1].driver.sync_peer_store().unwrap();
+        network.restart(1, &with_peer_store).await.unwrap();
+        assert!(network.nodes[1].driver.peer_store.is_banned(&offender));
+        network.connect(0, 1);
+        network.connect(1, 0);
+        network.connect(1, 2);
+        assert!(network.run_until(TIMEOUT, |n| n.nodes[1].is_connected(&honest)).await);
+        network.run_for(Duration::from_secs(2)).await;
+        assert!(!network.nodes[1].is_connected(&offender));
+    }
+}
@@ -0,0 +1,96 @@
+//! The in-process memory transport used by the [`TestNetwork`](super::TestNetwork).
+
+use derive_more::Debug;
+use futures::{AsyncRead, AsyncWrite};
+use libp2p::{
+    PeerId, Transport,
+    core::{
+        muxing::StreamMuxerBox,
+        transport::{Boxed, MemoryTransport},
+        upgrade::Version,
+    },
+    identity::Keypair,
+    noise, yamux,
+};
+use std::{
+    future::Future,
+    io,
+    pin::Pin,
+    task::{Context, Poll, ready},
+    time::Duration,
+};
+use tokio::time::Sleep;
+
+/// Builds an authenticated and multiplexed in-process memory transport, which delays every write
+/// by the given `latency`.
+pub fn memory_transport(
+    keypair: &Keypair,
+    latency: Duration,
+) -> Result<Boxed<(PeerId, StreamMuxerBox)>, noise::Error> {
+    Ok(MemoryTransport::default()
+        .map(move |channel, _| LatencyStream::new(channel, latency))
+        .upgrade(Version::V1)
+        .authenticate(noise::Config::new(keypair)?)
+        .multiplex(yamux::Config::default())
+        .map(|(peer_id, muxer), _| (peer_id, StreamMuxerBox::new(muxer)))
+        .boxed())
+}
+
+/// A stream that delays every write by a fixed latency, to simulate the network latency between
+/// the nodes of a [`TestNetwork`](super::TestNetwork).
+#[derive(Debug)]
+pub struct LatencyStream<S> {
+    /// The inner stream.
+    #[debug(skip)]
+    inner: S,
+    /// The latency added to every write.
+    latency: Duration,
+    /// The pending delay of the current write, if any.
+    delay: Option<Pin<Box<Sleep>>>,
+}
+
+impl<S> LatencyStream<S> {
+    /// Creates a new [`LatencyStream`] wrapping the given stream.
+    pub const fn new(inner: S, latency: Duration) -> Self {
+        Self { inner, latency, delay: None }
+    }
+}
+
+impl<S: AsyncRead + Unpin> AsyncRead for LatencyStream<S> {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<usize>> {
+        Pin::new(&mut self.inner).poll_read(cx, buf)
+    }
+}
+
+impl<S: AsyncWrite + Unpin> AsyncWrite for LatencyStream<S> {
+    fn poll_write(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        let this = &mut *self;
+        if !this.latency.is_zero() {
+            let latency = this.latency;
+            let delay = this.delay.get_or_insert_with(|| Box::pin(tokio::time::sleep(latency)));
+            ready!(delay.as_mut().poll(cx));
+        }
+
+        // Only reset the delay once the write went through, so that a pending write is not
+        // delayed twice.
+        let written = ready!(Pin::new(&mut this.inner).poll_write(cx, buf));
+        this.delay = None;
+        Poll::Ready(written)
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.inner).poll_flush(cx)
+    }
+
+    fn poll_close(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.inner).poll_close(cx)
+    }
+}
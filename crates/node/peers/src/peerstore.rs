@@ -0,0 +1,273 @@
+//! Peer Store
+
+use ipnet::IpNet;
+use libp2p::{Multiaddr, PeerId};
+use std::{
+    collections::{HashMap, HashSet},
+    fs::File,
+    io::{BufReader, Seek, SeekFrom},
+    net::IpAddr,
+    path::PathBuf,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+/// The maximum number of peers that can be stored in the peerstore.
+const MAX_PEERS: usize = 2048;
+
+/// A known peer in the [`PeerStore`].
+#[derive(Debug, Clone, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct PeerRecord {
+    /// The addresses the peer is listening on.
+    pub addrs: Vec<Multiaddr>,
+    /// The last known gossip score of the peer.
+    pub score: f64,
+    /// The unix timestamp, in seconds, at which the peer was last seen.
+    pub last_seen: u64,
+}
+
+/// On-disk storage for known peers, their scores, and the bans and blocks applied to them.
+///
+/// The [`PeerStore`] is a simple JSON file that allows the gossip layer to keep its view of the
+/// network across restarts: previously banned peers stay banned until their ban expires, and peers,
+/// addresses and subnets blocked by the operator stay blocked.
+///
+/// When the number of known peers exceeds `MAX_PEERS`, the least recently seen peers are removed
+/// to make room for new ones.
+#[derive(Debug, Default, serde::Serialize, serde::Deserialize)]
+pub struct PeerStore {
+    /// The file for the [`PeerStore`].
+    #[serde(skip)]
+    pub file: Option<File>,
+    /// Known peers, keyed by [`PeerId`].
+    #[serde(default)]
+    pub peers: HashMap<PeerId, PeerRecord>,
+    /// Banned peers, mapped to the unix timestamp, in seconds, at which their ban expires.
+    #[serde(default)]
+    pub bans: HashMap<PeerId, u64>,
+    /// Peers blocked through the connection gate.
+    #[serde(default)]
+    pub blocked_peers: HashSet<PeerId>,
+    /// Ip addresses blocked through the connection gate.
+    #[serde(default)]
+    pub blocked_addrs: HashSet<IpAddr>,
+    /// Subnets blocked through the connection gate.
+    #[serde(default)]
+    pub blocked_subnets: HashSet<IpNet>,
+}
+
+/// The peerstore caching policy.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PeerStoreFile {
+    /// Default path for the peerstore, ie `~/.kona/<chain_id>/peerstore.json`.
+    Default {
+        /// The l2 chain ID.
+        chain_id: u64,
+    },
+    /// A custom peerstore path is used. This must be a valid path to a file.
+    Custom(PathBuf),
+}
+
+impl From<File> for PeerStore {
+    fn from(file: File) -> Self {
+        debug!(target: "peerstore", "Reading peer store from disk: {:?}", file);
+        let reader = BufReader::new(&file);
+        let store = match serde_json::from_reader(reader) {
+            Ok(store) => store,
+            Err(e) => {
+                warn!(target: "peerstore", "Failed to read peer store from disk: {:?}", e);
+                Self::default()
+            }
+        };
+        Self { file: Some(file), ..store }
+    }
+}
+
+impl TryInto<File> for PeerStoreFile {
+    type Error = std::io::Error;
+
+    /// Returns a pointer to the peerstore file for the given combination of chain id and peerstore
+    /// file type, creating its parent directories if necessary.
+    fn try_into(self) -> Result<File, std::io::Error> {
+        let path = TryInto::<PathBuf>::try_into(self)?;
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        File::options().read(true).write(true).create(true).truncate(false).open(path)
+    }
+}
+
+impl TryInto<PeerStore> for PeerStoreFile {
+    type Error = std::io::Error;
+
+    fn try_into(self) -> Result<PeerStore, std::io::Error> {
+        let file = TryInto::<File>::try_into(self)?;
+        Ok(file.into())
+    }
+}
+
+impl TryInto<PathBuf> for PeerStoreFile {
+    type Error = std::io::Error;
+
+    fn try_into(self) -> Result<PathBuf, std::io::Error> {
+        match self {
+            Self::Default { chain_id } => {
+                let mut path = dirs::home_dir()
+                    .ok_or(std::io::Error::other("Failed to get home directory"))?;
+                path.push(".kona");
+                path.push(chain_id.to_string());
+                path.push("peerstore.json");
+                Ok(path)
+            }
+            Self::Custom(path) => Ok(path),
+        }
+    }
+}
+
+impl PeerStore {
+    /// Records that the peer was seen at the given addresses.
+    ///
+    /// This method will **not** write to disk. It is the responsibility of the caller to call
+    /// [`PeerStore::sync`] prior to dropping the store.
+    pub fn record_peer(&mut self, peer_id: PeerId, addrs: Vec<Multiaddr>) {
+        let record = self.peers.entry(peer_id).or_default();
+        if !addrs.is_empty() {
+            record.addrs = addrs;
+        }
+        record.last_seen = now();
+
+        // Prune the least recently seen peer if we exceed the maximum number of peers.
+        if self.peers.len() > MAX_PEERS {
+            debug!(target: "peerstore", "Peer store exceeded maximum peers, removing oldest peer");
+            if let Some(oldest) =
+                self.peers.iter().min_by_key(|(_, record)| record.last_seen).map(|(id, _)| *id)
+            {
+                self.peers.remove(&oldest);
+            }
+        }
+    }
+
+    /// Updates the score of a known peer.
+    pub fn set_score(&mut self, peer_id: &PeerId, score: f64) {
+        if let Some(record) = self.peers.get_mut(peer_id) {
+            record.score = score;
+        }
+    }
+
+    /// Bans the peer for the given duration.
+    pub fn ban(&mut self, peer_id: PeerId, duration: Duration) {
+        self.bans.insert(peer_id, now().saturating_add(duration.as_secs()));
+    }
+
+    /// Returns whether the peer is currently banned.
+    pub fn is_banned(&self, peer_id: &PeerId) -> bool {
+        self.bans.get(peer_id).is_some_and(|expiry| *expiry > now())
+    }
+
+    /// Removes expired bans and returns the remaining bans, along with the time left until they
+    /// expire.
+    pub fn active_bans(&mut self) -> Vec<(PeerId, Duration)> {
+        let now = now();
+        self.bans.retain(|_, expiry| *expiry > now);
+        self.bans
+            .iter()
+            .map(|(peer_id, expiry)| (*peer_id, Duration::from_secs(expiry - now)))
+            .collect()
+    }
+
+    /// Returns the number of known peers in the in-memory store.
+    pub fn len(&self) -> usize {
+        self.peers.len()
+    }
+
+    /// Returns if the in-memory store has no known peers.
+    pub fn is_empty(&self) -> bool {
+        self.peers.is_empty()
+    }
+
+    /// Syncs the [`PeerStore`] with the contents on disk.
+    pub fn sync(&self) -> Result<(), std::io::Error> {
+        if let Some(mut file) = self.file.as_ref() {
+            // Reset the file pointer to the beginning of the file and truncate it to overwrite
+            // the previous contents.
+            file.seek(SeekFrom::Start(0))?;
+            file.set_len(0)?;
+
+            serde_json::to_writer(file, self)?;
+        }
+        Ok(())
+    }
+}
+
+/// Returns the current unix timestamp, in seconds.
+fn now() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn peer_id() -> PeerId {
+        libp2p::identity::Keypair::generate_secp256k1().public().to_peer_id()
+    }
+
+    #[test]
+    fn test_peerstore_bans() {
+        let mut store = PeerStore::default();
+        let banned = peer_id();
+        let expired = peer_id();
+
+        store.ban(banned, Duration::from_secs(60));
+        store.bans.insert(expired, now() - 1);
+
+        assert!(store.is_banned(&banned));
+        assert!(!store.is_banned(&expired));
+        assert!(!store.is_banned(&peer_id()));
+
+        let active = store.active_bans();
+        assert_eq!(active.len(), 1);
+        assert_eq!(active[0].0, banned);
+        assert!(!store.bans.contains_key(&expired));
+    }
+
+    #[test]
+    fn test_peerstore_sync_roundtrip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("nested").join("peerstore.json");
+        let file = PeerStoreFile::Custom(path.clone());
+
+        let peer = peer_id();
+        let addr: Multiaddr = format!("/ip4/127.0.0.1/tcp/9222/p2p/{peer}").parse().unwrap();
+        let subnet: IpNet = "10.0.0.0/8".parse().unwrap();
+
+        let mut store: PeerStore = file.clone().try_into().unwrap();
+        assert!(store.is_empty());
+        store.record_peer(peer, vec![addr.clone()]);
+        store.set_score(&peer, -12.5);
+        store.ban(peer, Duration::from_secs(3600));
+        store.blocked_addrs.insert("1.2.3.4".parse().unwrap());
+        store.blocked_subnets.insert(subnet);
+        store.sync().unwrap();
+        // Syncing twice must overwrite the previous contents.
+        store.sync().unwrap();
+
+        let restored: PeerStore = file.try_into().unwrap();
+        assert_eq!(restored.len(), 1);
+        assert_eq!(restored.peers[&peer].addrs, vec![addr]);
+        assert_eq!(restored.peers[&peer].score, -12.5);
+        assert!(restored.is_banned(&peer));
+        assert!(restored.blocked_addrs.contains(&"1.2.3.4".parse::<IpAddr>().unwrap()));
+        assert!(restored.blocked_subnets.contains(&subnet));
+    }
+
+    #[test]
+    fn test_peerstore_ignores_invalid_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("peerstore.json");
+        std::fs::write(&path, "not json").unwrap();
+
+        let store: PeerStore = PeerStoreFile::Custom(path).try_into().unwrap();
+        assert!(store.is_empty());
+        assert!(store.file.is_some());
+    }
+}
@@ -1,13 +1,16 @@
 //! Admin RPC Module
 
 use crate::AdminApiServer;
-use alloy_primitives::B256;
+use alloy_primitives::{Address, B256};
+use alloy_signer_local::PrivateKeySigner;
 use async_trait::async_trait;
 use jsonrpsee::{
     core::RpcResult,
     types::{ErrorCode, ErrorObject},
 };
+use kona_sources::LocalKeystore;
 use op_alloy_rpc_types_engine::OpExecutionPayloadEnvelope;
+use std::path::PathBuf;
 use tokio::sync::oneshot;
 
 /// The query types to the sequencer actor for the admin api.
@@ -35,6 +38,13 @@ pub enum NetworkAdminQuery {
         /// The payload to post.
         payload: OpExecutionPayloadEnvelope,
     },
+    /// An admin rpc request to rotate the local block signer to a key decrypted from a keystore.
+    RotateSequencerKey {
+        /// The new local block signer.
+        signer: PrivateKeySigner,
+        /// Receives the address of the new signer, or the reason it could not be staged.
+        sender: oneshot::Sender<Result<Address, String>>,
+    },
 }
 
 type SequencerQuerySender = tokio::sync::mpsc::Sender<SequencerAdminQuery>;
@@ -62,6 +72,34 @@ impl AdminApiServer for AdminRpc {
             .map_err(|_| ErrorObject::from(ErrorCode::InternalError))
     }
 
+    async fn admin_rotate_sequencer_key(
+        &self,
+        keystore: PathBuf,
+        password_file: PathBuf,
+    ) -> RpcResult<Address> {
+        kona_macros::inc!(gauge, kona_gossip::Metrics::RPC_CALLS, "method" => "admin_rotateSequencerKey");
+
+        // Key derivation is deliberately expensive. Decrypting here rather than in the network
+        // actor keeps the actor polling the gossip swarm in the meantime.
+        let keystore = LocalKeystore::new(keystore, password_file);
+        let signer = tokio::task::spawn_blocking(move || keystore.decrypt())
+            .await
+            .map_err(|_| ErrorObject::from(ErrorCode::InternalError))?
+            .map_err(|e| {
+                ErrorObject::owned(ErrorCode::InvalidParams.code(), e.to_string(), None::<()>)
+            })?;
+
+        let (sender, rx) = oneshot::channel();
+        self.network_sender
+            .send(NetworkAdminQuery::RotateSequencerKey { signer, sender })
+            .await
+            .map_err(|_| ErrorObject::from(ErrorCode::InternalError))?;
+
+        rx.await
+            .map_err(|_| ErrorObject::from(ErrorCode::InternalError))?
+            .map_err(|e| ErrorObject::owned(ErrorCode::InvalidParams.code(), e, None::<()>))
+    }
+
     async fn admin_sequencer_active(&self) -> RpcResult<bool> {
         // If the sequencer is not enabled (mode runs in validator mode), return an error.
         let Some(ref sequencer_sender) = self.sequencer_sender else {
@@ -2,6 +2,8 @@
 
 use std::{net::SocketAddr, path::PathBuf};
 
+use crate::HealthConfig;
+
 /// The RPC configuration.
 #[derive(Debug, Clone)]
 pub struct RpcBuilder {
@@ -18,6 +20,8 @@ pub struct RpcBuilder {
     pub ws_enabled: bool,
     /// Enable development RPC endpoints
     pub dev_enabled: bool,
+    /// The thresholds for the `/healthz` and `/readyz` probes.
+    pub health: HealthConfig,
 }
 
 impl RpcBuilder {
@@ -41,6 +45,17 @@ impl RpcBuilder {
         if self.no_restart { 0 } else { 3 }
     }
 
+    /// Returns the [`HealthConfig`] of the [`RpcBuilder`].
+    pub const fn health(&self) -> HealthConfig {
+        self.health
+    }
+
+    /// Sets the minimum number of connected peers required by the readiness probe.
+    pub const fn with_health_min_peers(mut self, min_peers: usize) -> Self {
+        self.health.min_peers = min_peers;
+        self
+    }
+
     /// Sets the given [`SocketAddr`] on the [`RpcBuilder`].
     pub fn set_addr(self, addr: SocketAddr) -> Self {
         Self { socket: addr, ..self }
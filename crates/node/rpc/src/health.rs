@@ -0,0 +1,468 @@
+//! Health and readiness checks served on `/healthz` and `/readyz`.
+
+use std::{
+    sync::Mutex,
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+
+use jsonrpsee::types::ErrorObjectOwned;
+use kona_engine::{EngineQueries, EngineQuerySender, EngineState};
+use kona_gossip::P2pRpcRequest;
+use tokio::sync::{mpsc, oneshot};
+
+use crate::{L1State, L1WatcherQueries, L1WatcherQuerySender};
+
+/// The JSON-RPC error code returned when a probe fails.
+pub const HEALTH_CHECK_FAILED_CODE: i32 = -32099;
+
+/// How long a single signal is awaited before it is reported as unavailable.
+///
+/// This must stay below the RPC server's request timeout so that a stuck actor surfaces as a
+/// failed check instead of a timed out probe.
+const SIGNAL_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// Thresholds for the node's health and readiness checks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HealthConfig {
+    /// The maximum age of the L1 head block before the node is considered stale.
+    pub max_l1_head_age: Duration,
+    /// The maximum number of blocks the unsafe head may be ahead of the safe head.
+    pub max_unsafe_lag: u64,
+    /// The minimum number of connected gossip peers.
+    pub min_peers: usize,
+    /// The window within which the safe head must advance while there are unsafe blocks left to
+    /// derive.
+    pub derivation_window: Duration,
+}
+
+impl Default for HealthConfig {
+    fn default() -> Self {
+        Self {
+            max_l1_head_age: Duration::from_secs(120),
+            max_unsafe_lag: 1800,
+            min_peers: 0,
+            derivation_window: Duration::from_secs(600),
+        }
+    }
+}
+
+/// The outcome of a single health check.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+pub struct HealthCheck {
+    /// The name of the check.
+    pub name: String,
+    /// Whether the check passed.
+    pub ok: bool,
+    /// A human readable description of the observed value.
+    pub detail: String,
+}
+
+impl HealthCheck {
+    fn new(name: &str, ok: bool, detail: impl Into<String>) -> Self {
+        Self { name: name.to_string(), ok, detail: detail.into() }
+    }
+}
+
+/// A healthcheck response for the RPC server.
+#[derive(Debug, Clone, Default, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+pub struct HealthzResponse {
+    /// The application version.
+    pub version: String,
+    /// The results of all checks, whether or not they gate the probe.
+    #[serde(default)]
+    pub checks: Vec<HealthCheck>,
+    /// The names of the failed checks that gate the probe.
+    #[serde(default)]
+    pub failed: Vec<String>,
+}
+
+impl HealthzResponse {
+    /// Returns whether none of the gating checks failed.
+    pub const fn is_ok(&self) -> bool {
+        self.failed.is_empty()
+    }
+}
+
+/// The probe being served.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Probe {
+    /// The liveness probe, served on `/healthz`.
+    ///
+    /// Only gates on the node's actors answering queries. External causes, such as an
+    /// unreachable execution layer or an L1 or batcher outage stalling derivation, are not fixed
+    /// by restarting the node and only gate readiness.
+    Liveness,
+    /// The readiness probe, served on `/readyz`.
+    ///
+    /// Gates on every check, so that traffic is only routed to nodes that are synced and
+    /// connected.
+    Readiness,
+}
+
+impl Probe {
+    /// Returns whether the named check gates this probe.
+    fn gates(self, check: &str) -> bool {
+        match self {
+            Self::Liveness => check == HealthChecker::ACTORS_RESPONSIVE,
+            Self::Readiness => true,
+        }
+    }
+}
+
+/// The signals gathered from the node's actors for a single probe.
+#[derive(Debug, Clone)]
+struct Signals {
+    l1: Option<L1State>,
+    engine: Option<EngineState>,
+    el_reachable: Option<bool>,
+    peers: Option<usize>,
+}
+
+/// Evaluates the node's health from the state of its actors.
+#[derive(Debug)]
+pub struct HealthChecker {
+    /// The check thresholds.
+    config: HealthConfig,
+    /// The channel to send [`EngineQueries`] to.
+    engine_sender: EngineQuerySender,
+    /// The channel to send [`L1WatcherQueries`] to.
+    l1_watcher_sender: L1WatcherQuerySender,
+    /// The channel to send [`P2pRpcRequest`]s to.
+    p2p_sender: mpsc::Sender<P2pRpcRequest>,
+    /// The last observed safe head number, and when it last advanced.
+    progress: Mutex<Option<(u64, Instant)>>,
+}
+
+impl HealthChecker {
+    /// The L1 head age check.
+    pub const L1_HEAD_AGE: &'static str = "l1_head_age";
+    /// The unsafe-vs-safe lag check.
+    pub const UNSAFE_LAG: &'static str = "unsafe_lag";
+    /// The execution layer reachability check.
+    pub const EL_REACHABLE: &'static str = "el_reachable";
+    /// The execution layer sync status check.
+    pub const EL_SYNC: &'static str = "el_sync";
+    /// The connected peer count check.
+    pub const PEER_COUNT: &'static str = "peer_count";
+    /// The derivation progress check.
+    pub const DERIVATION: &'static str = "derivation_progress";
+    /// The actor responsiveness check.
+    pub const ACTORS_RESPONSIVE: &'static str = "actors_responsive";
+
+    /// Constructs a new [`HealthChecker`].
+    pub const fn new(
+        config: HealthConfig,
+        engine_sender: EngineQuerySender,
+        l1_watcher_sender: L1WatcherQuerySender,
+        p2p_sender: mpsc::Sender<P2pRpcRequest>,
+    ) -> Self {
+        Self { config, engine_sender, l1_watcher_sender, p2p_sender, progress: Mutex::new(None) }
+    }
+
+    /// Runs all checks and returns the response for the given [`Probe`].
+    ///
+    /// Returns an error carrying the response as its data if any gating check failed, so that
+    /// the GET proxy answers with a non-success status.
+    pub async fn probe(&self, probe: Probe) -> Result<HealthzResponse, ErrorObjectOwned> {
+        let response = self.evaluate(probe, self.signals().await, Instant::now(), unix_now());
+        if response.is_ok() {
+            return Ok(response);
+        }
+
+        warn!(target: "rpc", ?probe, failed = ?response.failed, "Health probe failed");
+        Err(ErrorObjectOwned::owned(
+            HEALTH_CHECK_FAILED_CODE,
+            "health check failed",
+            Some(response),
+        ))
+    }
+
+    /// Queries the node's actors for the signals the checks are evaluated on.
+    async fn signals(&self) -> Signals {
+        let (l1, engine, el_reachable, peers) = tokio::join!(
+            query(&self.l1_watcher_sender, L1WatcherQueries::L1State),
+            query(&self.engine_sender, EngineQueries::State),
+            query(&self.engine_sender, EngineQueries::ElReachable),
+            query(&self.p2p_sender, P2pRpcRequest::PeerCount),
+        );
+        Signals { l1, engine, el_reachable, peers: peers.map(|(_, connected)| connected) }
+    }
+
+    /// Evaluates all checks against the given signals.
+    fn evaluate(&self, probe: Probe, signals: Signals, now: Instant, unix: u64) -> HealthzResponse {
+        let config = &self.config;
+        let mut checks = Vec::with_capacity(7);
+
+        let unresponsive = [
+            ("l1_watcher", signals.l1.is_some()),
+            ("engine", signals.engine.is_some()),
+            ("network", signals.peers.is_some()),
+        ]
+        .into_iter()
+        .filter_map(|(actor, answered)| (!answered).then_some(actor))
+        .collect::<Vec<_>>();
+        checks.push(if unresponsive.is_empty() {
+            HealthCheck::new(Self::ACTORS_RESPONSIVE, true, "all actors responsive")
+        } else {
+            HealthCheck::new(
+                Self::ACTORS_RESPONSIVE,
+                false,
+                format!("unresponsive actors: {}", unresponsive.join(", ")),
+            )
+        });
+
+        checks.push(match signals.l1.as_ref().and_then(|l1| l1.head_l1) {
+            Some(head) => {
+                let age = unix.saturating_sub(head.timestamp);
+                HealthCheck::new(
+                    Self::L1_HEAD_AGE,
+                    age <= config.max_l1_head_age.as_secs(),
+                    format!("l1 head #{} is {age}s old", head.number),
+                )
+            }
+            None => HealthCheck::new(Self::L1_HEAD_AGE, false, "l1 head unavailable"),
+        });
+
+        // A slow or hung execution layer is an external cause, so it only fails this check and
+        // not the engine's responsiveness.
+        let (reachable, detail) = match signals.el_reachable {
+            Some(true) => (true, "execution layer reachable"),
+            Some(false) => (false, "execution layer unreachable"),
+            None => (false, "execution layer did not answer"),
+        };
+        checks.push(HealthCheck::new(Self::EL_REACHABLE, reachable, detail));
+
+        match signals.engine {
+            Some(state) => {
+                let unsafe_head = state.sync_state.unsafe_head().block_info.number;
+                let safe_head = state.sync_state.safe_head().block_info.number;
+                let lag = unsafe_head.saturating_sub(safe_head);
+                checks.push(HealthCheck::new(
+                    Self::UNSAFE_LAG,
+                    lag <= config.max_unsafe_lag,
+                    format!("unsafe head #{unsafe_head} is {lag} blocks ahead of safe head"),
+                ));
+                checks.push(HealthCheck::new(
+                    Self::EL_SYNC,
+                    state.el_sync_finished,
+                    if state.el_sync_finished {
+                        "execution layer synced"
+                    } else {
+                        "execution layer syncing"
+                    },
+                ));
+                checks.push(self.derivation_check(state, now));
+            }
+            None => {
+                for name in [Self::UNSAFE_LAG, Self::EL_SYNC, Self::DERIVATION] {
+                    checks.push(HealthCheck::new(name, false, "engine state unavailable"));
+                }
+            }
+        }
+
+        checks.push(match signals.peers {
+            Some(peers) => HealthCheck::new(
+                Self::PEER_COUNT,
+                peers >= config.min_peers,
+                format!("{peers} connected peers, minimum {}", config.min_peers),
+            ),
+            None => HealthCheck::new(Self::PEER_COUNT, false, "peer count unavailable"),
+        });
+
+        let failed = checks
+            .iter()
+            .filter(|check| !check.ok && probe.gates(&check.name))
+            .map(|check| check.name.clone())
+            .collect();
+
+        HealthzResponse { version: env!("CARGO_PKG_VERSION").to_string(), checks, failed }
+    }
+
+    /// Checks that the safe head advanced within the derivation window.
+    ///
+    /// Derivation is only expected to make progress while the execution layer has finished
+    /// syncing and there are unsafe blocks that have not been derived yet.
+    fn derivation_check(&self, state: EngineState, now: Instant) -> HealthCheck {
+        let safe_head = state.sync_state.safe_head().block_info.number;
+        let pending = state.sync_state.unsafe_head().block_info.number > safe_head;
+
+        let mut progress = self.progress.lock().unwrap_or_else(|e| e.into_inner());
+        let since = match *progress {
+            Some((last, since)) if last == safe_head => since,
+            _ => {
+                *progress = Some((safe_head, now));
+                now
+            }
+        };
+        let stalled = now.saturating_duration_since(since);
+
+        if !state.el_sync_finished || !pending {
+            return HealthCheck::new(Self::DERIVATION, true, format!("safe head #{safe_head}"));
+        }
+
+        HealthCheck::new(
+            Self::DERIVATION,
+            stalled <= self.config.derivation_window,
+            format!("safe head #{safe_head} last advanced {}s ago", stalled.as_secs()),
+        )
+    }
+}
+
+/// Sends a query built from a oneshot sender and awaits its response, giving up after
+/// [`SIGNAL_TIMEOUT`].
+async fn query<Q, T>(
+    sender: &mpsc::Sender<Q>,
+    build: impl FnOnce(oneshot::Sender<T>) -> Q,
+) -> Option<T> {
+    let (tx, rx) = oneshot::channel();
+    sender.send(build(tx)).await.ok()?;
+    tokio::time::timeout(SIGNAL_TIMEOUT, rx).await.ok()?.ok()
+}
+
+/// Returns the current unix timestamp, in seconds.
+fn unix_now() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kona_engine::{EngineSyncState, EngineSyncStateUpdate};
+    use kona_protocol::{BlockInfo, L2BlockInfo};
+
+    const NOW: u64 = 1_000_000;
+
+    fn checker(config: HealthConfig) -> HealthChecker {
+        HealthChecker::new(config, mpsc::channel(1).0, mpsc::channel(1).0, mpsc::channel(1).0)
+    }
+
+    fn l2_block(number: u64) -> L2BlockInfo {
+        L2BlockInfo { block_info: BlockInfo { number, ..Default::default() }, ..Default::default() }
+    }
+
+    fn signals(unsafe_head: u64, safe_head: u64, peers: usize) -> Signals {
+        let engine = EngineState {
+            sync_state: EngineSyncState::default().apply_update(EngineSyncStateUpdate {
+                unsafe_head: Some(l2_block(unsafe_head)),
+                safe_head: Some(l2_block(safe_head)),
+                ..Default::default()
+            }),
+            el_sync_finished: true,
+            ..Default::default()
+        };
+
+        Signals {
+            l1: Some(L1State {
+                current_l1: None,
+                current_l1_finalized: None,
+                head_l1: Some(BlockInfo { number: 7, timestamp: NOW - 12, ..Default::default() }),
+                safe_l1: None,
+                finalized_l1: None,
+            }),
+            engine: Some(engine),
+            el_reachable: Some(true),
+            peers: Some(peers),
+        }
+    }
+
+    #[test]
+    fn test_healthy_node() {
+        let checker = checker(HealthConfig { min_peers: 5, ..Default::default() });
+        let response =
+            checker.evaluate(Probe::Readiness, signals(110, 100, 5), Instant::now(), NOW);
+        assert!(response.is_ok(), "{response:?}");
+        assert_eq!(response.checks.len(), 7);
+        assert!(response.checks.iter().all(|check| check.ok));
+    }
+
+    #[test]
+    fn test_failed_checks_are_reported() {
+        let checker = checker(HealthConfig {
+            max_l1_head_age: Duration::from_secs(10),
+            max_unsafe_lag: 5,
+            min_peers: 5,
+            ..Default::default()
+        });
+        let mut signals = signals(110, 100, 2);
+        signals.el_reachable = Some(false);
+
+        let response = checker.evaluate(Probe::Readiness, signals.clone(), Instant::now(), NOW);
+        assert_eq!(
+            response.failed,
+            [
+                HealthChecker::L1_HEAD_AGE,
+                HealthChecker::EL_REACHABLE,
+                HealthChecker::UNSAFE_LAG,
+                HealthChecker::PEER_COUNT
+            ]
+        );
+
+        // Liveness does not gate on external causes.
+        let response = checker.evaluate(Probe::Liveness, signals, Instant::now(), NOW);
+        assert!(response.is_ok(), "{response:?}");
+        assert_eq!(response.checks.iter().filter(|check| !check.ok).count(), 4);
+    }
+
+    #[test]
+    fn test_missing_engine_state() {
+        let checker = checker(HealthConfig::default());
+        let mut signals = signals(110, 100, 0);
+        signals.engine = None;
+
+        let response = checker.evaluate(Probe::Liveness, signals, Instant::now(), NOW);
+        assert_eq!(response.failed, [HealthChecker::ACTORS_RESPONSIVE]);
+        let detail = &response.checks[0].detail;
+        assert_eq!(detail, "unresponsive actors: engine");
+    }
+
+    #[test]
+    fn test_unanswered_el_only_gates_readiness() {
+        let checker = checker(HealthConfig::default());
+        let mut signals = signals(110, 100, 0);
+        signals.el_reachable = None;
+
+        let response = checker.evaluate(Probe::Readiness, signals.clone(), Instant::now(), NOW);
+        assert_eq!(response.failed, [HealthChecker::EL_REACHABLE]);
+
+        let response = checker.evaluate(Probe::Liveness, signals, Instant::now(), NOW);
+        assert!(response.is_ok(), "{response:?}");
+    }
+
+    #[test]
+    fn test_derivation_stall() {
+        let window = Duration::from_secs(60);
+        let checker = checker(HealthConfig { derivation_window: window, ..Default::default() });
+        let start = Instant::now();
+
+        let response = checker.evaluate(Probe::Readiness, signals(110, 100, 0), start, NOW);
+        assert!(response.is_ok());
+
+        // The safe head has not moved for longer than the window.
+        let later = start + window + Duration::from_secs(1);
+        let response = checker.evaluate(Probe::Readiness, signals(120, 100, 0), later, NOW);
+        assert_eq!(response.failed, [HealthChecker::DERIVATION]);
+
+        // A stall is not fixed by a restart, so the node stays live.
+        let response = checker.evaluate(Probe::Liveness, signals(120, 100, 0), later, NOW);
+        assert!(response.is_ok());
+
+        // Progress resets the window.
+        let response = checker.evaluate(Probe::Readiness, signals(120, 101, 0), later, NOW);
+        assert!(response.is_ok());
+
+        // A caught up node is not stalled.
+        let much_later = later + window * 2;
+        let response = checker.evaluate(Probe::Readiness, signals(101, 101, 0), much_later, NOW);
+        assert!(response.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_probe_error_carries_response() {
+        let checker = checker(HealthConfig::default());
+        let err = checker.probe(Probe::Liveness).await.unwrap_err();
+        assert_eq!(err.code(), HEALTH_CHECK_FAILED_CODE);
+
+        let response: HealthzResponse = serde_json::from_str(err.data().unwrap().get()).unwrap();
+        assert_eq!(response.failed, [HealthChecker::ACTORS_RESPONSIVE]);
+    }
+}
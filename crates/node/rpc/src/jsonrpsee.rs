@@ -2,7 +2,7 @@
 
 use crate::{OutputResponse, SafeHeadResponse};
 use alloy_eips::BlockNumberOrTag;
-use alloy_primitives::B256;
+use alloy_primitives::{Address, B256};
 use core::net::IpAddr;
 use ipnet::IpNet;
 use jsonrpsee::{
@@ -13,6 +13,7 @@ use kona_genesis::RollupConfig;
 use kona_gossip::{PeerCount, PeerDump, PeerInfo, PeerStats};
 use kona_protocol::SyncStatus;
 use op_alloy_rpc_types_engine::OpExecutionPayloadEnvelope;
+use std::path::PathBuf;
 
 #[cfg_attr(all(target_arch = "wasm32", target_os = "unknown"), allow(unused_imports))]
 use getrandom as _; // required for compiling wasm32-unknown-unknown
@@ -170,6 +171,19 @@ pub trait AdminApi {
     async fn admin_post_unsafe_payload(&self, payload: OpExecutionPayloadEnvelope)
     -> RpcResult<()>;
 
+    /// Stages the key in the given encrypted keystore as the local block signer, and returns its
+    /// address.
+    ///
+    /// Signing switches to the new key when the L1 system config designates its address as the
+    /// unsafe block signer, at the same L1 block that gossip validation switches to it. If it is
+    /// already the unsafe block signer, signing switches immediately.
+    #[method(name = "rotateSequencerKey")]
+    async fn admin_rotate_sequencer_key(
+        &self,
+        keystore: PathBuf,
+        password_file: PathBuf,
+    ) -> RpcResult<Address>;
+
     /// Checks if the sequencer is active.
     #[method(name = "sequencerActive")]
     async fn admin_sequencer_active(&self) -> RpcResult<bool>;
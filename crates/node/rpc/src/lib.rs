@@ -44,9 +44,7 @@ pub use l1_watcher::{L1State, L1WatcherQueries, L1WatcherQuerySender};
 mod ws;
 pub use ws::WsRPC;
 
-/// A healthcheck response for the RPC server.
-#[derive(Debug, Clone, serde::Deserialize, serde::Serialize)]
-pub struct HealthzResponse {
-    /// The application version.
-    pub version: String,
-}
+mod health;
+pub use health::{
+    HEALTH_CHECK_FAILED_CODE, HealthCheck, HealthChecker, HealthConfig, HealthzResponse, Probe,
+};
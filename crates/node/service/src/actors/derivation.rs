@@ -2,7 +2,7 @@
 
 use std::sync::Arc;
 
-use crate::{InteropMode, Metrics, NodeActor, actors::CancellableContext};
+use crate::{InteropMode, Metrics, NodeActor, Traced, actors::CancellableContext};
 use alloy_provider::RootProvider;
 use async_trait::async_trait;
 use kona_derive::{
@@ -12,7 +12,7 @@ use kona_derive::{
 use kona_genesis::{L1ChainConfig, RollupConfig};
 use kona_protocol::{BlockInfo, L2BlockInfo, OpAttributesWithParent};
 use kona_providers_alloy::{
-    AlloyChainProvider, AlloyL2ChainProvider, OnlineBeaconClient, OnlineBlobProvider,
+    AlloyChainProvider, AlloyL2ChainProvider, BlobArchive, OnlineBeaconClient, OnlineBlobProvider,
     OnlinePipeline,
 };
 use op_alloy_network::Optimism;
@@ -22,6 +22,7 @@ use tokio::{
     sync::{mpsc, oneshot, watch},
 };
 use tokio_util::sync::{CancellationToken, WaitForCancellationFuture};
+use tracing::{Instrument, Span};
 
 /// The [NodeActor] for the derivation sub-routine.
 ///
@@ -37,7 +38,7 @@ where
     state: B,
 
     /// The receiver for L1 head update notifications.
-    l1_head_updates: watch::Receiver<Option<BlockInfo>>,
+    l1_head_updates: watch::Receiver<Option<Traced<BlockInfo>>>,
     /// The receiver for L2 safe head update notifications.
     engine_l2_safe_head: watch::Receiver<L2BlockInfo>,
     /// A receiver used by the engine to signal derivation to begin. Completing EL sync consumes
@@ -78,6 +79,10 @@ where
     /// A flag indicating whether or not derivation is waiting for a signal. When waiting for a
     /// signal, derivation cannot process any incoming events.
     pub waiting_for_signal: bool,
+    /// The span of the L1 head that the pipeline is deriving from. Every derivation step is
+    /// parented to it, including steps triggered by safe head updates, so that all attributes
+    /// derived from an L1 block are attributed to its trace.
+    pub l1_head_span: Span,
 }
 
 /// The size of the cache used in the derivation pipeline's providers.
@@ -102,6 +107,8 @@ pub struct DerivationBuilder {
     pub l1_trust_rpc: bool,
     /// The L1 beacon client.
     pub l1_beacon: OnlineBeaconClient,
+    /// The archives to fetch blobs from when they have expired from the L1 beacon client.
+    pub l1_blob_archives: Vec<Arc<dyn BlobArchive>>,
     /// The L2 provider.
     pub l2_provider: RootProvider<Optimism>,
     /// Whether to trust the L2 RPC.
@@ -132,18 +139,23 @@ impl PipelineBuilder for DerivationBuilder {
             self.l2_trust_rpc,
         );
 
+        let mut blob_provider = OnlineBlobProvider::init(self.l1_beacon.clone()).await;
+        for archive in self.l1_blob_archives.iter().cloned() {
+            blob_provider = blob_provider.with_archive(archive);
+        }
+
         let pipeline = match self.interop_mode {
             InteropMode::Polled => OnlinePipeline::new_polled(
                 self.rollup_config.clone(),
                 self.l1_config.clone(),
-                OnlineBlobProvider::init(self.l1_beacon.clone()).await,
+                blob_provider,
                 l1_derivation_provider,
                 l2_derivation_provider,
             ),
             InteropMode::Indexed => OnlinePipeline::new_indexed(
                 self.rollup_config.clone(),
                 self.l1_config.clone(),
-                OnlineBlobProvider::init(self.l1_beacon.clone()).await,
+                blob_provider,
                 l1_derivation_provider,
                 l2_derivation_provider,
             ),
@@ -158,7 +170,7 @@ impl PipelineBuilder for DerivationBuilder {
 #[derive(Debug)]
 pub struct DerivationInboundChannels {
     /// The sender for L1 head update notifications.
-    pub l1_head_updates_tx: watch::Sender<Option<BlockInfo>>,
+    pub l1_head_updates_tx: watch::Sender<Option<Traced<BlockInfo>>>,
     /// The sender for L2 safe head update notifications.
     pub engine_l2_safe_head_tx: watch::Sender<L2BlockInfo>,
     /// A sender used by the engine to signal derivation to begin. Completing EL sync consumes the
@@ -176,8 +188,9 @@ pub struct DerivationInboundChannels {
 pub struct DerivationContext {
     /// The cancellation token, shared between all tasks.
     pub cancellation: CancellationToken,
-    /// Sends the derived [`OpAttributesWithParent`]s produced by the actor.
-    pub derived_attributes_tx: mpsc::Sender<OpAttributesWithParent>,
+    /// Sends the derived [`OpAttributesWithParent`]s produced by the actor, traced in the
+    /// `derivation_step` span that produced them.
+    pub derived_attributes_tx: mpsc::Sender<Traced<OpAttributesWithParent>>,
     /// The reset request sender, used to handle [`PipelineErrorKind::Reset`] events and forward
     /// them to the engine.
     pub reset_request_tx: mpsc::Sender<()>,
@@ -195,7 +208,12 @@ where
 {
     /// Creates a new instance of the [DerivationState].
     pub const fn new(pipeline: P) -> Self {
-        Self { pipeline, derivation_idle: true, waiting_for_signal: false }
+        Self {
+            pipeline,
+            derivation_idle: true,
+            waiting_for_signal: false,
+            l1_head_span: Span::none(),
+        }
     }
 
     /// Handles a [`Signal`] received over the derivation signal receiver channel.
@@ -330,7 +348,30 @@ where
         msg: InboundDerivationMessage,
         engine_l2_safe_head: &mut watch::Receiver<L2BlockInfo>,
         el_sync_complete_rx: &oneshot::Receiver<()>,
-        derived_attributes_tx: &mpsc::Sender<OpAttributesWithParent>,
+        derived_attributes_tx: &mpsc::Sender<Traced<OpAttributesWithParent>>,
+        reset_request_tx: &mpsc::Sender<()>,
+    ) -> Result<(), DerivationError> {
+        let step_span =
+            info_span!(target: "derivation", parent: &self.l1_head_span, "derivation_step", ?msg);
+        self.step(
+            msg,
+            engine_l2_safe_head,
+            el_sync_complete_rx,
+            derived_attributes_tx,
+            reset_request_tx,
+        )
+        .instrument(step_span)
+        .await
+    }
+
+    /// Processes the next payload attributes within the current `derivation_step` span. See
+    /// [`Self::process`].
+    async fn step(
+        &mut self,
+        msg: InboundDerivationMessage,
+        engine_l2_safe_head: &mut watch::Receiver<L2BlockInfo>,
+        el_sync_complete_rx: &oneshot::Receiver<()>,
+        derived_attributes_tx: &mpsc::Sender<Traced<OpAttributesWithParent>>,
         reset_request_tx: &mpsc::Sender<()>,
     ) -> Result<(), DerivationError> {
         // Only attempt derivation once the engine finishes syncing.
@@ -389,7 +430,7 @@ where
 
         // Send payload attributes out for processing.
         derived_attributes_tx
-            .send(payload_attrs)
+            .send(Traced::new(payload_attrs))
             .await
             .map_err(|e| DerivationError::Sender(Box::new(e)))?;
 
@@ -486,6 +527,12 @@ where
                         return Ok(());
                     }
 
+                    // Step derivation within the span of the new L1 head, so that any attributes
+                    // derived from it are attributed to its trace.
+                    if let Some(head) = self.l1_head_updates.borrow().as_ref() {
+                        state.l1_head_span = head.span().clone();
+                    }
+
                     state.process(InboundDerivationMessage::NewDataAvailable, &mut self.engine_l2_safe_head, &self.el_sync_complete_rx, &derived_attributes_tx, &reset_request_tx).await?;
                 }
                 _ = self.engine_l2_safe_head.changed() => {
@@ -530,3 +577,149 @@ pub enum DerivationError {
     #[error("Failed to receive L2 safe head")]
     L2SafeHeadReceiveFailed,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        EngineActor, EngineContext, NodeMode, actors::engine::test_utils::TestEngineBuilder,
+    };
+    use kona_derive::{OriginProvider, PipelineResult};
+    use kona_engine::test_utils::TestEngineApi;
+    use kona_genesis::SystemConfig;
+    use std::{sync::Mutex, time::Duration};
+    use tokio::time::timeout;
+
+    /// The time to wait for the actors to make progress before failing the test.
+    const TIMEOUT: Duration = Duration::from_secs(10);
+
+    /// A [`Pipeline`] deriving [`TestEngineApi::next_attributes`] on top of the safe head, until
+    /// the safe head reaches a target block number.
+    #[derive(Debug)]
+    struct TestPipeline {
+        cfg: Arc<RollupConfig>,
+        target: u64,
+        prepared: Option<OpAttributesWithParent>,
+        signals: Arc<Mutex<Vec<Signal>>>,
+    }
+
+    impl OriginProvider for TestPipeline {
+        fn origin(&self) -> Option<BlockInfo> {
+            None
+        }
+    }
+
+    impl Iterator for TestPipeline {
+        type Item = OpAttributesWithParent;
+
+        fn next(&mut self) -> Option<Self::Item> {
+            self.prepared.take()
+        }
+    }
+
+    #[async_trait]
+    impl SignalReceiver for TestPipeline {
+        async fn signal(&mut self, signal: Signal) -> PipelineResult<()> {
+            self.signals.lock().unwrap().push(signal);
+            Ok(())
+        }
+    }
+
+    #[async_trait]
+    impl Pipeline for TestPipeline {
+        fn peek(&self) -> Option<&OpAttributesWithParent> {
+            self.prepared.as_ref()
+        }
+
+        async fn step(&mut self, cursor: L2BlockInfo) -> StepResult {
+            if cursor.block_info.number >= self.target {
+                return StepResult::StepFailed(PipelineError::Eof.temp());
+            }
+            self.prepared = Some(TestEngineApi::next_attributes(cursor, &self.cfg));
+            StepResult::PreparedAttributes
+        }
+
+        fn rollup_config(&self) -> &RollupConfig {
+            &self.cfg
+        }
+
+        async fn system_config_by_number(
+            &mut self,
+            _: u64,
+        ) -> Result<SystemConfig, PipelineErrorKind> {
+            Ok(SystemConfig::default())
+        }
+    }
+
+    #[async_trait]
+    impl PipelineBuilder for TestPipeline {
+        type Pipeline = Self;
+
+        async fn build(self) -> DerivationState<Self> {
+            DerivationState::new(self)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_derive_onto_engine() {
+        const TARGET: u64 = 3;
+
+        let engine_builder = TestEngineBuilder::new(NodeMode::Validator);
+        let (cfg, client) = (engine_builder.cfg.clone(), engine_builder.client.clone());
+        let signals = Arc::new(Mutex::new(Vec::new()));
+        let pipeline =
+            TestPipeline { cfg, target: TARGET, prepared: None, signals: signals.clone() };
+
+        let (derivation_inbound, derivation) = DerivationActor::new(pipeline);
+        let (engine_inbound, engine) = EngineActor::new(engine_builder);
+        let DerivationInboundChannels {
+            l1_head_updates_tx: _l1_head_updates_tx,
+            engine_l2_safe_head_tx,
+            el_sync_complete_tx,
+            derivation_signal_tx,
+        } = derivation_inbound;
+        let mut safe_head_rx = engine_l2_safe_head_tx.subscribe();
+
+        let cancellation = CancellationToken::new();
+        let derivation_ctx = DerivationContext {
+            cancellation: cancellation.clone(),
+            derived_attributes_tx: engine_inbound.attributes_tx.clone(),
+            reset_request_tx: engine_inbound.reset_request_tx.clone(),
+        };
+        // The derivation error is not `Send`, so it is stringified within the task.
+        let derivation_handle = tokio::spawn(async move {
+            derivation.start(derivation_ctx).await.map_err(|err| err.to_string())
+        });
+        let engine_handle = tokio::spawn(engine.start(EngineContext {
+            cancellation: cancellation.clone(),
+            engine_unsafe_head_tx: None,
+            engine_l2_safe_head_tx,
+            sync_complete_tx: el_sync_complete_tx,
+            derivation_signal_tx,
+        }));
+
+        // The engine resets onto the genesis block, which the execution layer has already
+        // finalized, and starts derivation. The pipeline's attributes are then consolidated one
+        // by one until it runs out of data.
+        engine_inbound.reset_request_tx.send(()).await.unwrap();
+        let safe_head =
+            *timeout(TIMEOUT, safe_head_rx.wait_for(|head| head.block_info.number == TARGET))
+                .await
+                .unwrap()
+                .unwrap();
+        assert_eq!(client.canonical_hash(TARGET), Some(safe_head.block_info.hash));
+        assert_eq!(client.forkchoice().safe_block_hash, safe_head.block_info.hash);
+
+        // The pipeline was reset onto the genesis block before deriving.
+        let signals = signals.lock().unwrap().clone();
+        assert!(matches!(
+            signals.as_slice(),
+            [Signal::Reset(ResetSignal { l2_safe_head, .. })]
+                if l2_safe_head.block_info.hash == client.canonical_hash(0).unwrap()
+        ));
+
+        cancellation.cancel();
+        derivation_handle.await.unwrap().unwrap();
+        engine_handle.await.unwrap().unwrap();
+    }
+}
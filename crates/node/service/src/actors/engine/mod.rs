@@ -1,10 +1,15 @@
 //! The [`EngineActor`] and its components.
 
 mod actor;
-pub use actor::{EngineActor, EngineBuilder, EngineContext, EngineInboundData};
+pub use actor::{
+    EngineActor, EngineBuilder, EngineClientBuilder, EngineContext, EngineInboundData,
+};
 
 mod error;
 pub use error::EngineError;
 
 mod finalizer;
 pub use finalizer::L2Finalizer;
+
+#[cfg(test)]
+pub(crate) mod test_utils;
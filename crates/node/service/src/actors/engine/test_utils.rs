@@ -0,0 +1,45 @@
+//! Test utilities for the [`EngineActor`].
+//!
+//! [`EngineActor`]: super::EngineActor
+
+use super::EngineClientBuilder;
+use crate::NodeMode;
+use kona_engine::test_utils::TestEngineApi;
+use kona_genesis::RollupConfig;
+use std::sync::Arc;
+
+/// An [`EngineClientBuilder`] handing out a shared, in-memory [`TestEngineApi`].
+#[derive(Debug, Clone)]
+pub(crate) struct TestEngineBuilder {
+    /// The [`RollupConfig`] of the chain.
+    pub(crate) cfg: Arc<RollupConfig>,
+    /// The [`TestEngineApi`] driven by the engine actor.
+    pub(crate) client: Arc<TestEngineApi>,
+    /// The mode of operation for the node.
+    pub(crate) mode: NodeMode,
+}
+
+impl TestEngineBuilder {
+    /// Creates a new [`TestEngineBuilder`] whose L2 chain consists of the genesis block of
+    /// [`TestEngineApi::genesis`] only.
+    pub(crate) fn new(mode: NodeMode) -> Self {
+        let (cfg, client) = TestEngineApi::genesis();
+        Self { cfg, client: Arc::new(client), mode }
+    }
+}
+
+impl EngineClientBuilder for TestEngineBuilder {
+    type Client = TestEngineApi;
+
+    fn rollup_config(&self) -> Arc<RollupConfig> {
+        self.cfg.clone()
+    }
+
+    fn mode(&self) -> NodeMode {
+        self.mode
+    }
+
+    fn build(self) -> Arc<Self::Client> {
+        self.client
+    }
+}
@@ -0,0 +1,731 @@
+//! [`NodeActor`] implementation that tracks the safe and finalized heads of a trusted rollup node,
+//! instead of deriving them from L1.
+
+use crate::{NodeActor, Traced, actors::CancellableContext};
+use alloy_consensus::EMPTY_ROOT_HASH;
+use alloy_eips::BlockNumberOrTag;
+use alloy_primitives::B256;
+use alloy_provider::{Provider, RootProvider};
+use alloy_rpc_client::ReqwestClient;
+use alloy_rpc_types_engine::{ExecutionPayloadV1, ExecutionPayloadV2, ExecutionPayloadV3};
+use alloy_transport::{RpcError, TransportErrorKind};
+use async_trait::async_trait;
+use kona_engine::EngineQueries;
+use kona_protocol::{L2BlockInfo, OutputRoot, SyncStatus};
+use kona_rpc::OutputResponse;
+use op_alloy_consensus::OpBlock;
+use op_alloy_network::Optimism;
+use op_alloy_rpc_types_engine::{
+    OpExecutionPayload, OpExecutionPayloadEnvelope, OpExecutionPayloadV4,
+};
+use std::time::Duration;
+use tokio::sync::{mpsc, oneshot};
+use tokio_util::sync::{CancellationToken, WaitForCancellationFuture};
+use url::Url;
+
+/// The maximum number of blocks of the follow source inserted into the local execution layer per
+/// poll.
+const MAX_BACKFILL_BLOCKS: u64 = 128;
+
+/// A trusted rollup node followed by the [`FollowActor`].
+#[async_trait]
+pub trait FollowSource: Send + Sync {
+    /// Fetches the [`SyncStatus`] of the follow source.
+    async fn sync_status(&self) -> Result<SyncStatus, FollowError>;
+
+    /// Fetches the [`OutputResponse`] of the follow source at the given L2 block number.
+    async fn output_at_block(&self, number: u64) -> Result<OutputResponse, FollowError>;
+
+    /// Fetches the L2 block at the given number from the execution layer of the follow source, as
+    /// a payload that can be inserted into the local execution layer. Returns `None` if the block
+    /// is not known.
+    async fn payload_at_block(
+        &self,
+        number: u64,
+    ) -> Result<Option<OpExecutionPayloadEnvelope>, FollowError>;
+}
+
+/// A client for the rollup node and execution layer RPCs of the trusted follow source.
+#[derive(Debug, Clone)]
+pub struct FollowSourceClient {
+    /// The inner rollup node RPC client.
+    rpc: ReqwestClient,
+    /// The execution layer provider of the follow source.
+    l2_provider: RootProvider<Optimism>,
+}
+
+impl FollowSourceClient {
+    /// Creates a new follow source client using HTTP transport.
+    pub fn new_http(url: Url, l2_url: Url) -> Self {
+        Self { rpc: ReqwestClient::new_http(url), l2_provider: RootProvider::new_http(l2_url) }
+    }
+}
+
+#[async_trait]
+impl FollowSource for FollowSourceClient {
+    async fn sync_status(&self) -> Result<SyncStatus, FollowError> {
+        Ok(self.rpc.request("optimism_syncStatus", ()).await?)
+    }
+
+    async fn output_at_block(&self, number: u64) -> Result<OutputResponse, FollowError> {
+        Ok(self.rpc.request("optimism_outputAtBlock", (BlockNumberOrTag::Number(number),)).await?)
+    }
+
+    async fn payload_at_block(
+        &self,
+        number: u64,
+    ) -> Result<Option<OpExecutionPayloadEnvelope>, FollowError> {
+        let Some(block) = self.l2_provider.get_block_by_number(number.into()).full().await? else {
+            return Ok(None);
+        };
+        let hash = block.header.hash;
+        let block = block.into_consensus().map_transactions(|t| t.inner.inner.into_inner());
+        Ok(Some(payload_envelope(hash, &block)))
+    }
+}
+
+/// Converts a block into the payload envelope that inserts it, using the payload version of the
+/// hardfork that produced its header.
+fn payload_envelope(hash: B256, block: &OpBlock) -> OpExecutionPayloadEnvelope {
+    let header = &block.header;
+    let execution_payload = if header.requests_hash.is_some() {
+        OpExecutionPayload::V4(OpExecutionPayloadV4 {
+            payload_inner: ExecutionPayloadV3::from_block_unchecked(hash, block),
+            withdrawals_root: header.withdrawals_root.unwrap_or(EMPTY_ROOT_HASH),
+        })
+    } else if header.parent_beacon_block_root.is_some() {
+        OpExecutionPayload::V3(ExecutionPayloadV3::from_block_unchecked(hash, block))
+    } else if block.body.withdrawals.is_some() {
+        OpExecutionPayload::V2(ExecutionPayloadV2::from_block_unchecked(hash, block))
+    } else {
+        OpExecutionPayload::V1(ExecutionPayloadV1::from_block_unchecked(hash, block))
+    };
+
+    OpExecutionPayloadEnvelope {
+        parent_beacon_block_root: header.parent_beacon_block_root,
+        execution_payload,
+    }
+}
+
+/// An update of the safe and finalized heads, verified against the local execution layer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FollowUpdate {
+    /// The safe head of the follow source.
+    pub safe_head: L2BlockInfo,
+    /// The finalized head of the follow source, if it advanced.
+    pub finalized_head: Option<L2BlockInfo>,
+}
+
+/// The configuration for the [`FollowActor`].
+#[derive(Debug, Clone)]
+pub struct FollowBuilder {
+    /// The rollup node RPC URL of the trusted follow source.
+    pub source_url: Url,
+    /// The execution layer RPC URL of the trusted follow source, used to fetch the blocks that the
+    /// local execution layer is missing.
+    pub source_l2_url: Url,
+    /// The interval at which the sync status of the follow source is polled.
+    pub poll_interval: Duration,
+}
+
+impl FollowBuilder {
+    /// The default interval at which the follow source is polled.
+    pub const DEFAULT_POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+    /// Creates a new [`FollowBuilder`] for the given follow source, polled at the
+    /// [`Self::DEFAULT_POLL_INTERVAL`].
+    pub const fn new(source_url: Url, source_l2_url: Url) -> Self {
+        Self { source_url, source_l2_url, poll_interval: Self::DEFAULT_POLL_INTERVAL }
+    }
+}
+
+/// The [`FollowActor`] polls a trusted rollup node for its safe and finalized heads, and forwards
+/// them to the engine actor once the local execution layer agrees with the follow source on the
+/// block hash and output root at each head.
+///
+/// Unsafe blocks are still received over gossip. Blocks that the local execution layer is missing,
+/// or that were replaced on the follow source (e.g. a gossiped block reorged out by the derived
+/// one), are fetched from the execution layer of the follow source and inserted. An output root
+/// that differs for the same block is critical and halts the node.
+#[derive(Debug)]
+pub struct FollowActor {
+    /// The configuration of the actor.
+    builder: FollowBuilder,
+}
+
+/// The communication context used by the follow actor.
+#[derive(Debug)]
+pub struct FollowContext {
+    /// The engine query sender, used to compute output roots from the local execution layer.
+    pub engine_query_tx: mpsc::Sender<EngineQueries>,
+    /// The channel used to insert blocks of the follow source into the local execution layer.
+    pub unsafe_block_tx: mpsc::Sender<Traced<OpExecutionPayloadEnvelope>>,
+    /// The channel used to send verified head updates to the engine actor.
+    pub follow_update_tx: mpsc::Sender<FollowUpdate>,
+    /// The cancellation token, shared between all tasks.
+    pub cancellation: CancellationToken,
+}
+
+impl CancellableContext for FollowContext {
+    fn cancelled(&self) -> WaitForCancellationFuture<'_> {
+        self.cancellation.cancelled()
+    }
+}
+
+/// The outcome of verifying a local block against the follow source.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Verification {
+    /// The local block matches the block and output root of the follow source.
+    Verified(L2BlockInfo),
+    /// The local execution layer does not have the block.
+    Missing,
+    /// The local block differs from the block of the follow source.
+    Diverged,
+}
+
+impl FollowActor {
+    /// Constructs a new [`FollowActor`] from the [`FollowBuilder`].
+    pub const fn new(builder: FollowBuilder) -> Self {
+        Self { builder }
+    }
+
+    /// Polls the `source` every `poll_interval` and forwards verified head updates to the engine,
+    /// until cancelled or the local chain diverges from the follow source.
+    async fn follow<S: FollowSource>(
+        source: &S,
+        poll_interval: Duration,
+        FollowContext { engine_query_tx, unsafe_block_tx, follow_update_tx, cancellation }: FollowContext,
+    ) -> Result<(), FollowError> {
+        let mut interval = tokio::time::interval(poll_interval);
+        let mut last = None;
+
+        loop {
+            tokio::select! {
+                _ = cancellation.cancelled() => {
+                    info!(target: "follow", "Received shutdown signal. Exiting follow task.");
+                    return Ok(());
+                }
+                _ = interval.tick() => {}
+            }
+
+            let update = match Self::poll(source, &engine_query_tx, &unsafe_block_tx, last).await {
+                Ok(Some(update)) => update,
+                Ok(None) => continue,
+                Err(FollowError::Rpc(err)) => {
+                    warn!(target: "follow", ?err, "Failed to poll follow source");
+                    continue;
+                }
+                Err(err) => {
+                    error!(target: "follow", ?err, "Local chain diverged from follow source");
+                    cancellation.cancel();
+                    return Err(err);
+                }
+            };
+
+            debug!(
+                target: "follow",
+                safe = update.safe_head.block_info.number,
+                finalized = ?update.finalized_head.map(|head| head.block_info.number),
+                "Verified heads of follow source"
+            );
+            if follow_update_tx.send(update).await.is_err() {
+                error!(target: "follow", "Follow update receiver closed unexpectedly");
+                cancellation.cancel();
+                return Err(FollowError::ChannelClosed);
+            }
+            last = Some(update);
+        }
+    }
+
+    /// Polls the sync status of the follow source, and returns a [`FollowUpdate`] if its safe or
+    /// finalized head changed since the `last` update and could be verified.
+    ///
+    /// Returns `Ok(None)` if there is nothing to update, or if the local execution layer does not
+    /// agree with the new safe head yet. In the latter case, the blocks of the follow source are
+    /// inserted into the local execution layer.
+    async fn poll<S: FollowSource>(
+        source: &S,
+        engine_query_tx: &mpsc::Sender<EngineQueries>,
+        unsafe_block_tx: &mpsc::Sender<Traced<OpExecutionPayloadEnvelope>>,
+        last: Option<FollowUpdate>,
+    ) -> Result<Option<FollowUpdate>, FollowError> {
+        let status = source.sync_status().await?;
+        let last_safe = last.map(|update| update.safe_head);
+        let last_finalized = last.and_then(|update| update.finalized_head);
+
+        let safe_changed = last_safe.is_none_or(|head| head != status.safe_l2);
+        let finalized_changed = last_finalized.is_none_or(|head| head != status.finalized_l2);
+        if !safe_changed && !finalized_changed {
+            return Ok(None);
+        }
+
+        let safe_head = match last_safe {
+            Some(head) if !safe_changed => head,
+            _ => {
+                let number = status.safe_l2.block_info.number;
+                match Self::verify(source, engine_query_tx, number).await? {
+                    Verification::Verified(head) => head,
+                    verification => {
+                        Self::backfill(
+                            source,
+                            engine_query_tx,
+                            unsafe_block_tx,
+                            number,
+                            verification,
+                        )
+                        .await?;
+                        return Ok(None);
+                    }
+                }
+            }
+        };
+
+        // The finalized head is at or behind the verified safe head, so it is only promoted once it
+        // is verified as well.
+        let finalized_head = if !finalized_changed {
+            last_finalized
+        } else if status.finalized_l2 == safe_head {
+            Some(safe_head)
+        } else {
+            let number = status.finalized_l2.block_info.number;
+            match Self::verify(source, engine_query_tx, number).await? {
+                Verification::Verified(head) => Some(head),
+                _ => last_finalized,
+            }
+        };
+
+        let update = FollowUpdate { safe_head, finalized_head };
+        if Some(update) == last {
+            return Ok(None);
+        }
+        Ok(Some(update))
+    }
+
+    /// Verifies the local block at `number` against the output of the follow source.
+    async fn verify<S: FollowSource>(
+        source: &S,
+        engine_query_tx: &mpsc::Sender<EngineQueries>,
+        number: u64,
+    ) -> Result<Verification, FollowError> {
+        let upstream = source.output_at_block(number).await?;
+
+        let (sender, receiver) = oneshot::channel();
+        engine_query_tx
+            .send(EngineQueries::OutputAtBlock { block: number.into(), sender })
+            .await
+            .map_err(|_| FollowError::ChannelClosed)?;
+        // The engine drops the sender if it cannot compute the output, e.g. because the block has
+        // not been imported yet.
+        let Ok((local, output_root, _)) = receiver.await else {
+            return Ok(Verification::Missing);
+        };
+
+        check_output(number, &local, &output_root, &upstream.block_ref, upstream.output_root)
+    }
+
+    /// Inserts the blocks of the follow source leading up to its block at `number` into the local
+    /// execution layer, at most [`MAX_BACKFILL_BLOCKS`] per poll.
+    ///
+    /// Missing blocks are inserted from the local unsafe head on. If the local chain diverged, the
+    /// blocks are inserted from the local safe head on instead, which was verified against the
+    /// follow source, so that the unsafe chain is reorged onto the chain of the follow source.
+    async fn backfill<S: FollowSource>(
+        source: &S,
+        engine_query_tx: &mpsc::Sender<EngineQueries>,
+        unsafe_block_tx: &mpsc::Sender<Traced<OpExecutionPayloadEnvelope>>,
+        number: u64,
+        verification: Verification,
+    ) -> Result<(), FollowError> {
+        let (sender, receiver) = oneshot::channel();
+        engine_query_tx
+            .send(EngineQueries::State(sender))
+            .await
+            .map_err(|_| FollowError::ChannelClosed)?;
+        let state = receiver.await.map_err(|_| FollowError::ChannelClosed)?;
+
+        let head = match verification {
+            Verification::Diverged => state.sync_state.safe_head(),
+            _ => state.sync_state.unsafe_head(),
+        };
+        let from = (head.block_info.number + 1).min(number);
+        let to = number.min(from + MAX_BACKFILL_BLOCKS - 1);
+        info!(target: "follow", ?verification, from, to, "Inserting blocks from follow source");
+
+        for block_number in from..=to {
+            let Some(payload) = source.payload_at_block(block_number).await? else {
+                warn!(target: "follow", block_number, "Block not found on follow source");
+                return Ok(());
+            };
+            let span = info_span!(
+                target: "follow",
+                "unsafe_payload",
+                source = "follow",
+                number = block_number,
+                hash = %payload.execution_payload.block_hash(),
+            );
+            unsafe_block_tx
+                .send(Traced::with_span(payload, span))
+                .await
+                .map_err(|_| FollowError::ChannelClosed)?;
+        }
+        Ok(())
+    }
+}
+
+/// Checks the local block and output root against the block and output root reported by the
+/// follow source.
+///
+/// A different block hash is not an error, as the local unsafe block may have been reorged out on
+/// the follow source. A different output root for the same block is critical.
+fn check_output(
+    number: u64,
+    local: &L2BlockInfo,
+    output_root: &OutputRoot,
+    upstream: &L2BlockInfo,
+    upstream_root: B256,
+) -> Result<Verification, FollowError> {
+    if local.block_info.hash != upstream.block_info.hash {
+        warn!(
+            target: "follow",
+            number,
+            local = %local.block_info.hash,
+            upstream = %upstream.block_info.hash,
+            "Local block differs from follow source"
+        );
+        return Ok(Verification::Diverged);
+    }
+
+    let local_root = output_root.hash();
+    if local_root != upstream_root {
+        return Err(FollowError::OutputRootMismatch {
+            number,
+            local: local_root,
+            upstream: upstream_root,
+        });
+    }
+
+    Ok(Verification::Verified(*local))
+}
+
+#[async_trait]
+impl NodeActor for FollowActor {
+    type Error = FollowError;
+    type OutboundData = FollowContext;
+    type InboundData = ();
+    type Builder = FollowBuilder;
+
+    fn build(builder: Self::Builder) -> (Self::InboundData, Self) {
+        ((), Self::new(builder))
+    }
+
+    async fn start(self, context: Self::OutboundData) -> Result<(), Self::Error> {
+        let client = FollowSourceClient::new_http(
+            self.builder.source_url.clone(),
+            self.builder.source_l2_url.clone(),
+        );
+
+        info!(target: "follow", source = %self.builder.source_url, "Following trusted rollup node");
+        Self::follow(&client, self.builder.poll_interval, context).await
+    }
+}
+
+/// An error returned by the [`FollowActor`].
+#[derive(Debug, thiserror::Error)]
+pub enum FollowError {
+    /// The local output root differs from the output root of the follow source for the same block.
+    #[error("Output root mismatch at block {number}: local {local}, follow source {upstream}")]
+    OutputRootMismatch {
+        /// The L2 block number.
+        number: u64,
+        /// The local output root.
+        local: B256,
+        /// The output root of the follow source.
+        upstream: B256,
+    },
+    /// A channel to another actor was closed unexpectedly.
+    #[error("Channel closed unexpectedly")]
+    ChannelClosed,
+    /// An error occurred while making an RPC call to the follow source.
+    #[error("RPC error: {0}")]
+    Rpc(#[from] RpcError<TransportErrorKind>),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_consensus::Header;
+    use kona_engine::{EngineState, EngineSyncState, EngineSyncStateUpdate};
+    use kona_protocol::BlockInfo;
+    use std::{
+        collections::BTreeMap,
+        sync::{Arc, Mutex},
+    };
+    use tokio::task::JoinHandle;
+
+    /// Returns the block at `number` on the chain identified by `fork`.
+    fn block(fork: u8, number: u64) -> L2BlockInfo {
+        let mut hash = B256::repeat_byte(fork);
+        hash.0[..8].copy_from_slice(&number.to_be_bytes());
+        L2BlockInfo {
+            block_info: BlockInfo { hash, number, ..Default::default() },
+            ..Default::default()
+        }
+    }
+
+    fn output(block: &L2BlockInfo) -> OutputRoot {
+        OutputRoot::from_parts(B256::ZERO, B256::ZERO, block.block_info.hash)
+    }
+
+    fn status(safe: L2BlockInfo, finalized: L2BlockInfo) -> SyncStatus {
+        SyncStatus {
+            current_l1: BlockInfo::default(),
+            current_l1_finalized: BlockInfo::default(),
+            head_l1: BlockInfo::default(),
+            safe_l1: BlockInfo::default(),
+            finalized_l1: BlockInfo::default(),
+            unsafe_l2: safe,
+            safe_l2: safe,
+            finalized_l2: finalized,
+            cross_unsafe_l2: safe,
+            local_safe_l2: safe,
+        }
+    }
+
+    /// A follow source serving the chain identified by `fork`.
+    #[derive(Debug)]
+    struct TestSource {
+        fork: u8,
+        status: SyncStatus,
+    }
+
+    #[async_trait]
+    impl FollowSource for TestSource {
+        async fn sync_status(&self) -> Result<SyncStatus, FollowError> {
+            Ok(self.status.clone())
+        }
+
+        async fn output_at_block(&self, number: u64) -> Result<OutputResponse, FollowError> {
+            let block = block(self.fork, number);
+            Ok(OutputResponse::from_v0(output(&block), self.status.clone(), block))
+        }
+
+        async fn payload_at_block(
+            &self,
+            number: u64,
+        ) -> Result<Option<OpExecutionPayloadEnvelope>, FollowError> {
+            let op_block = OpBlock {
+                header: Header { number, ..Default::default() },
+                body: Default::default(),
+            };
+            Ok(Some(payload_envelope(block(self.fork, number).block_info.hash, &op_block)))
+        }
+    }
+
+    /// A local execution layer, which imports inserted blocks as its new unsafe head.
+    #[derive(Debug, Default)]
+    struct TestEngine {
+        blocks: BTreeMap<u64, (L2BlockInfo, OutputRoot)>,
+        state: EngineState,
+    }
+
+    impl TestEngine {
+        fn new(blocks: impl IntoIterator<Item = L2BlockInfo>, safe_head: L2BlockInfo) -> Self {
+            let blocks: BTreeMap<_, _> = blocks
+                .into_iter()
+                .map(|block| (block.block_info.number, (block, output(&block))))
+                .collect();
+            let unsafe_head = blocks.last_key_value().map(|(_, (block, _))| *block);
+            let sync_state = EngineSyncState::default().apply_update(EngineSyncStateUpdate {
+                unsafe_head,
+                safe_head: Some(safe_head),
+                ..Default::default()
+            });
+            Self { blocks, state: EngineState { sync_state, ..Default::default() } }
+        }
+
+        fn insert(&mut self, payload: &OpExecutionPayloadEnvelope) {
+            let block = L2BlockInfo {
+                block_info: BlockInfo {
+                    hash: payload.execution_payload.block_hash(),
+                    number: payload.execution_payload.block_number(),
+                    ..Default::default()
+                },
+                ..Default::default()
+            };
+            // The inserted block becomes the canonical head, reorging out any later blocks.
+            self.blocks.split_off(&block.block_info.number);
+            self.blocks.insert(block.block_info.number, (block, output(&block)));
+            self.state.sync_state = self.state.sync_state.apply_update(EngineSyncStateUpdate {
+                unsafe_head: Some(block),
+                ..Default::default()
+            });
+        }
+    }
+
+    struct Harness {
+        engine: Arc<Mutex<TestEngine>>,
+        follow_update_rx: mpsc::Receiver<FollowUpdate>,
+        cancellation: CancellationToken,
+        handle: JoinHandle<Result<(), FollowError>>,
+    }
+
+    /// Runs the follow loop against the `source` and a task serving the `engine`.
+    fn follow(source: TestSource, engine: TestEngine) -> Harness {
+        let engine = Arc::new(Mutex::new(engine));
+        let (engine_query_tx, mut engine_query_rx) = mpsc::channel(16);
+        let (unsafe_block_tx, mut unsafe_block_rx) =
+            mpsc::channel::<Traced<OpExecutionPayloadEnvelope>>(16);
+        let (follow_update_tx, follow_update_rx) = mpsc::channel(16);
+        let cancellation = CancellationToken::new();
+
+        let local = engine.clone();
+        tokio::spawn(async move {
+            loop {
+                tokio::select! {
+                    Some(query) = engine_query_rx.recv() => {
+                        let engine = local.lock().unwrap();
+                        match query {
+                            EngineQueries::OutputAtBlock { block: BlockNumberOrTag::Number(n), sender } => {
+                                if let Some((block, root)) = engine.blocks.get(&n) {
+                                    let _ = sender.send((*block, *root, engine.state));
+                                }
+                            }
+                            EngineQueries::State(sender) => {
+                                let _ = sender.send(engine.state);
+                            }
+                            query => panic!("unexpected engine query: {query:?}"),
+                        }
+                    }
+                    Some(payload) = unsafe_block_rx.recv() => local.lock().unwrap().insert(&payload),
+                    else => return,
+                }
+            }
+        });
+
+        let context = FollowContext {
+            engine_query_tx,
+            unsafe_block_tx,
+            follow_update_tx,
+            cancellation: cancellation.clone(),
+        };
+        let handle = tokio::spawn(async move {
+            FollowActor::follow(&source, Duration::from_millis(10), context).await
+        });
+
+        Harness { engine, follow_update_rx, cancellation, handle }
+    }
+
+    impl Harness {
+        async fn next_update(&mut self) -> FollowUpdate {
+            tokio::time::timeout(Duration::from_secs(5), self.follow_update_rx.recv())
+                .await
+                .expect("timed out waiting for a follow update")
+                .unwrap()
+        }
+
+        async fn stop(self) {
+            self.cancellation.cancel();
+            self.handle.await.unwrap().unwrap();
+        }
+    }
+
+    #[tokio::test]
+    async fn test_follow_promotes_verified_heads() {
+        let source = TestSource { fork: 1, status: status(block(1, 10), block(1, 5)) };
+        let engine = TestEngine::new((0..=10).map(|n| block(1, n)), block(1, 0));
+        let mut harness = follow(source, engine);
+
+        let update = harness.next_update().await;
+        assert_eq!(update.safe_head, block(1, 10));
+        assert_eq!(update.finalized_head, Some(block(1, 5)));
+
+        // Unchanged heads are not sent again.
+        assert!(
+            tokio::time::timeout(Duration::from_millis(100), harness.follow_update_rx.recv())
+                .await
+                .is_err()
+        );
+        harness.stop().await;
+    }
+
+    #[tokio::test]
+    async fn test_follow_backfills_missing_blocks() {
+        let source = TestSource { fork: 1, status: status(block(1, 6), block(1, 2)) };
+        let engine = TestEngine::new((0..=3).map(|n| block(1, n)), block(1, 3));
+        let mut harness = follow(source, engine);
+
+        let update = harness.next_update().await;
+        assert_eq!(update.safe_head, block(1, 6));
+        assert_eq!(update.finalized_head, Some(block(1, 2)));
+        assert_eq!(harness.engine.lock().unwrap().state.sync_state.unsafe_head(), block(1, 6));
+        harness.stop().await;
+    }
+
+    #[tokio::test]
+    async fn test_follow_reorgs_diverged_unsafe_blocks() {
+        // The local node gossiped blocks 6 to 8 of fork 2, which the follow source replaced.
+        let source = TestSource { fork: 1, status: status(block(1, 7), block(1, 5)) };
+        let engine = TestEngine::new(
+            (0..=5).map(|n| block(1, n)).chain((6..=8).map(|n| block(2, n))),
+            block(1, 5),
+        );
+        let mut harness = follow(source, engine);
+
+        let update = harness.next_update().await;
+        assert_eq!(update.safe_head, block(1, 7));
+        {
+            let engine = harness.engine.lock().unwrap();
+            assert_eq!(engine.state.sync_state.unsafe_head(), block(1, 7));
+            assert!(!engine.blocks.contains_key(&8));
+        }
+        harness.stop().await;
+    }
+
+    #[tokio::test]
+    async fn test_follow_halts_on_output_root_mismatch() {
+        let source = TestSource { fork: 1, status: status(block(1, 5), block(1, 5)) };
+        let mut engine = TestEngine::new((0..=5).map(|n| block(1, n)), block(1, 0));
+        let head = block(1, 5);
+        engine.blocks.insert(
+            5,
+            (
+                head,
+                OutputRoot::from_parts(B256::repeat_byte(0x09), B256::ZERO, head.block_info.hash),
+            ),
+        );
+        let harness = follow(source, engine);
+
+        let err = tokio::time::timeout(Duration::from_secs(5), harness.handle)
+            .await
+            .unwrap()
+            .unwrap()
+            .unwrap_err();
+        assert!(matches!(err, FollowError::OutputRootMismatch { number: 5, .. }));
+        assert!(harness.cancellation.is_cancelled());
+    }
+
+    #[test]
+    fn test_check_output() {
+        let local = block(1, 10);
+        let root = output(&local);
+
+        assert_eq!(
+            check_output(10, &local, &root, &local, root.hash()).unwrap(),
+            Verification::Verified(local)
+        );
+
+        // A different block is reorged onto rather than treated as critical.
+        assert_eq!(
+            check_output(10, &local, &root, &block(2, 10), root.hash()).unwrap(),
+            Verification::Diverged
+        );
+
+        let other_root = B256::repeat_byte(0x05);
+        let err = check_output(10, &local, &root, &local, other_root).unwrap_err();
+        assert!(matches!(
+            err,
+            FollowError::OutputRootMismatch { number: 10, local, upstream }
+                if local == root.hash() && upstream == other_root
+        ));
+    }
+}
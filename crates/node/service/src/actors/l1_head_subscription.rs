@@ -0,0 +1,341 @@
+//! A stream of L1 head blocks driven by a `newHeads` WebSocket subscription, with gap filling,
+//! reorg detection, and a fallback to HTTP polling.
+
+use super::l1_watcher_rpc::BlockStream;
+use alloy_eips::BlockNumberOrTag;
+use alloy_primitives::B256;
+use alloy_provider::{Provider, RootProvider};
+use async_stream::stream;
+use futures::{Stream, StreamExt};
+use kona_protocol::BlockInfo;
+use std::{collections::VecDeque, future::Future, time::Duration};
+use tokio::time::{Instant, sleep, timeout, timeout_at};
+use url::Url;
+
+/// The number of most recent canonical L1 blocks that are tracked to connect new heads to. This
+/// bounds both the size of a gap that is filled in and the depth of a reorg that is detected.
+const TRACKED_BLOCKS: usize = 64;
+
+/// The duration without a new head after which the subscription is considered stalled and is
+/// re-established.
+const STALL_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// The delay before re-subscribing after a subscription has been dropped.
+const RESUBSCRIBE_DELAY: Duration = Duration::from_secs(1);
+
+/// The duration to poll for L1 heads for before attempting to subscribe again, after the
+/// WebSocket endpoint could not be reached.
+const POLLING_FALLBACK_DURATION: Duration = Duration::from_secs(60);
+
+/// Observes the L1 head through a `newHeads` subscription on a WebSocket endpoint.
+///
+/// Unlike the polling [`BlockStream`], the resulting stream is contiguous: heads that arrive
+/// after a gap, such as after the subscription was dropped and re-established, are preceded by
+/// the blocks that were missed, and heads that reorg the tracked chain are preceded by the blocks
+/// of the new chain since the common ancestor. Missing blocks are fetched by hash over the HTTP
+/// provider.
+///
+/// If the WebSocket endpoint cannot be reached, the L1 head is polled over the HTTP provider for
+/// a while before subscribing is attempted again.
+#[derive(Debug)]
+pub(super) struct HeadSubscription {
+    /// The WebSocket URL of the L1 execution client.
+    ws_url: Url,
+    /// The HTTP L1 provider, used to fill gaps and to poll when the subscription is unavailable.
+    l1_provider: RootProvider,
+    /// The interval at which to poll for the L1 head when the subscription is unavailable.
+    poll_interval: Duration,
+}
+
+impl HeadSubscription {
+    /// Creates a new [`HeadSubscription`] instance.
+    pub(super) const fn new(
+        ws_url: Url,
+        l1_provider: RootProvider,
+        poll_interval: Duration,
+    ) -> Self {
+        Self { ws_url, l1_provider, poll_interval }
+    }
+
+    /// Transforms the subscription into a [`Stream`] of L1 heads.
+    pub(super) fn into_stream(self) -> impl Stream<Item = BlockInfo> + Unpin {
+        let Self { ws_url, l1_provider, poll_interval } = self;
+
+        Box::pin(stream! {
+            let mut heads = CanonicalHeads::default();
+            let fetch = |hash: B256| {
+                let l1_provider = l1_provider.clone();
+                async move {
+                    l1_provider.get_block_by_hash(hash).await.map(|block| block.map(BlockInfo::from))
+                }
+            };
+
+            loop {
+                let subscription = match RootProvider::connect(ws_url.as_str()).await {
+                    Ok(ws_provider) => ws_provider.subscribe_blocks().await.map(|sub| (ws_provider, sub)),
+                    Err(err) => Err(err),
+                };
+
+                match subscription {
+                    Ok((_ws_provider, subscription)) => {
+                        info!(target: "l1_watcher", "Subscribed to L1 heads over WebSocket");
+                        let mut subscription = subscription.into_stream();
+                        loop {
+                            match timeout(STALL_TIMEOUT, subscription.next()).await {
+                                Ok(Some(header)) => {
+                                    let head = BlockInfo::new(
+                                        header.hash,
+                                        header.number,
+                                        header.parent_hash,
+                                        header.timestamp,
+                                    );
+                                    for block in heads.advance_or_reset(head, &fetch).await {
+                                        yield block;
+                                    }
+                                }
+                                Ok(None) => {
+                                    warn!(target: "l1_watcher", "L1 head subscription closed, resubscribing");
+                                    break;
+                                }
+                                Err(_) => {
+                                    warn!(target: "l1_watcher", timeout = ?STALL_TIMEOUT, "L1 head subscription stalled, resubscribing");
+                                    break;
+                                }
+                            }
+                        }
+                        sleep(RESUBSCRIBE_DELAY).await;
+                    }
+                    Err(err) => {
+                        warn!(
+                            target: "l1_watcher",
+                            ?err,
+                            fallback = ?POLLING_FALLBACK_DURATION,
+                            "Failed to subscribe to L1 heads, falling back to polling"
+                        );
+                        let mut poll_stream =
+                            BlockStream::new(&l1_provider, BlockNumberOrTag::Latest, poll_interval)
+                                .into_stream();
+                        let deadline = Instant::now() + POLLING_FALLBACK_DURATION;
+                        while let Ok(Some(head)) = timeout_at(deadline, poll_stream.next()).await {
+                            for block in heads.advance_or_reset(head, &fetch).await {
+                                yield block;
+                            }
+                        }
+                    }
+                }
+            }
+        })
+    }
+}
+
+/// The most recent canonical L1 blocks, in ascending order, each the parent of the next.
+#[derive(Debug, Default)]
+struct CanonicalHeads {
+    /// The tracked blocks.
+    blocks: VecDeque<BlockInfo>,
+}
+
+impl CanonicalHeads {
+    /// Advances the tracked chain to `head` as in [`Self::advance`]. If a missing block cannot be
+    /// fetched, the tracked chain is reset to `head` alone, which is then the only block
+    /// returned.
+    async fn advance_or_reset<F, Fut, E>(&mut self, head: BlockInfo, fetch: F) -> Vec<BlockInfo>
+    where
+        F: Fn(B256) -> Fut,
+        Fut: Future<Output = Result<Option<BlockInfo>, E>>,
+        E: std::fmt::Debug,
+    {
+        match self.advance(head, fetch).await {
+            Ok(blocks) => blocks,
+            Err(err) => {
+                warn!(target: "l1_watcher", ?err, head = %head.hash, "Failed to fetch missing L1 blocks");
+                self.blocks.clear();
+                self.blocks.push_back(head);
+                vec![head]
+            }
+        }
+    }
+
+    /// Advances the tracked chain to `head`, walking back its parent hashes with `fetch` until
+    /// a tracked block is reached.
+    ///
+    /// Returns the new canonical blocks in ascending order, ending with `head`: the blocks
+    /// missed since the previous head, or on a reorg, the blocks of the new chain since the
+    /// common ancestor. Returns no blocks if `head` is already the tracked head. If `head` cannot
+    /// be connected to the tracked chain within [`TRACKED_BLOCKS`] blocks, the tracked chain is
+    /// replaced by the fetched blocks.
+    async fn advance<F, Fut, E>(&mut self, head: BlockInfo, fetch: F) -> Result<Vec<BlockInfo>, E>
+    where
+        F: Fn(B256) -> Fut,
+        Fut: Future<Output = Result<Option<BlockInfo>, E>>,
+    {
+        let Some(tip) = self.blocks.back().copied() else {
+            self.blocks.push_back(head);
+            return Ok(vec![head]);
+        };
+        if tip.hash == head.hash {
+            return Ok(Vec::new());
+        }
+        if let Some(index) = self.position(head.hash) {
+            // The new head is a tracked block, after a reorg to a shorter chain.
+            self.truncate(index + 1, tip, head);
+            return Ok(vec![head]);
+        }
+
+        // Walk back from the new head until its parent is tracked.
+        let mut new_blocks = vec![head];
+        let mut cursor = head;
+        loop {
+            if let Some(index) = self.position(cursor.parent_hash) {
+                self.truncate(index + 1, tip, head);
+                break;
+            }
+
+            let lowest = self.blocks.front().map_or(0, |block| block.number);
+            if new_blocks.len() >= TRACKED_BLOCKS || cursor.number <= lowest {
+                warn!(
+                    target: "l1_watcher",
+                    old_head = %tip.hash,
+                    new_head = %head.hash,
+                    "L1 head does not connect to the tracked chain, resetting"
+                );
+                self.blocks.clear();
+                break;
+            }
+            match fetch(cursor.parent_hash).await? {
+                Some(parent) => {
+                    new_blocks.push(parent);
+                    cursor = parent;
+                }
+                None => {
+                    warn!(
+                        target: "l1_watcher",
+                        parent = %cursor.parent_hash,
+                        "Parent of L1 block not found, resetting the tracked chain"
+                    );
+                    self.blocks.clear();
+                    break;
+                }
+            }
+        }
+
+        new_blocks.reverse();
+        for block in &new_blocks {
+            if self.blocks.len() == TRACKED_BLOCKS {
+                self.blocks.pop_front();
+            }
+            self.blocks.push_back(*block);
+        }
+        Ok(new_blocks)
+    }
+
+    /// Returns the index of the tracked block with the given hash.
+    fn position(&self, hash: B256) -> Option<usize> {
+        self.blocks.iter().rposition(|block| block.hash == hash)
+    }
+
+    /// Drops the tracked blocks from index `len` onwards, logging a reorg if any were dropped.
+    fn truncate(&mut self, len: usize, old_head: BlockInfo, new_head: BlockInfo) {
+        let depth = self.blocks.len() - len;
+        if depth > 0 {
+            warn!(
+                target: "l1_watcher",
+                depth,
+                old_head = %old_head.hash,
+                new_head = %new_head.hash,
+                "L1 reorg detected"
+            );
+        }
+        self.blocks.truncate(len);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::future::{Ready, ready};
+    use std::collections::HashMap;
+
+    /// Returns a chain of `len` blocks starting at block 1, forked from `parent` with `salt`.
+    fn chain(parent: BlockInfo, len: u64, salt: u8) -> Vec<BlockInfo> {
+        let mut blocks = Vec::new();
+        let mut parent = parent;
+        for _ in 0..len {
+            let number = parent.number + 1;
+            let mut hash = B256::with_last_byte(number as u8);
+            hash.0[0] = salt;
+            let block = BlockInfo::new(hash, number, parent.hash, number * 12);
+            blocks.push(block);
+            parent = block;
+        }
+        blocks
+    }
+
+    /// Returns a fetcher over the given blocks.
+    fn fetcher(blocks: &[BlockInfo]) -> impl Fn(B256) -> Ready<Result<Option<BlockInfo>, ()>> {
+        let blocks: HashMap<_, _> = blocks.iter().map(|block| (block.hash, *block)).collect();
+        move |hash| ready(Ok(blocks.get(&hash).copied()))
+    }
+
+    #[tokio::test]
+    async fn test_advance_contiguous_and_duplicate_heads() {
+        let blocks = chain(BlockInfo::default(), 3, 0);
+        let fetch = fetcher(&blocks);
+        let mut heads = CanonicalHeads::default();
+
+        assert_eq!(heads.advance(blocks[0], &fetch).await.unwrap(), vec![blocks[0]]);
+        assert_eq!(heads.advance(blocks[1], &fetch).await.unwrap(), vec![blocks[1]]);
+        assert!(heads.advance(blocks[1], &fetch).await.unwrap().is_empty());
+        assert_eq!(heads.advance(blocks[2], &fetch).await.unwrap(), vec![blocks[2]]);
+    }
+
+    #[tokio::test]
+    async fn test_advance_fills_gaps() {
+        let blocks = chain(BlockInfo::default(), 5, 0);
+        let fetch = fetcher(&blocks);
+        let mut heads = CanonicalHeads::default();
+
+        heads.advance(blocks[0], &fetch).await.unwrap();
+        assert_eq!(heads.advance(blocks[4], &fetch).await.unwrap(), blocks[1..].to_vec());
+        assert_eq!(heads.blocks, blocks);
+    }
+
+    #[tokio::test]
+    async fn test_advance_detects_reorgs() {
+        let canonical = chain(BlockInfo::default(), 3, 0);
+        let fork = chain(canonical[0], 3, 1);
+        let fetch = fetcher(&[canonical.as_slice(), fork.as_slice()].concat());
+        let mut heads = CanonicalHeads::default();
+
+        for block in &canonical {
+            heads.advance(*block, &fetch).await.unwrap();
+        }
+
+        // The new chain since the common ancestor is returned, and replaces the old one.
+        assert_eq!(heads.advance(fork[2], &fetch).await.unwrap(), fork);
+        assert_eq!(heads.blocks, [&canonical[..1], &fork[..]].concat());
+
+        // A reorg back to a tracked block returns that block alone.
+        assert_eq!(heads.advance(fork[0], &fetch).await.unwrap(), vec![fork[0]]);
+        assert_eq!(heads.blocks, vec![canonical[0], fork[0]]);
+    }
+
+    #[tokio::test]
+    async fn test_advance_resets_on_unconnected_heads() {
+        let blocks = chain(BlockInfo::default(), TRACKED_BLOCKS as u64 + 2, 0);
+        let fetch = fetcher(&blocks);
+        let mut heads = CanonicalHeads::default();
+
+        heads.advance(blocks[0], &fetch).await.unwrap();
+        let head = *blocks.last().unwrap();
+        let new_blocks = heads.advance(head, &fetch).await.unwrap();
+        assert_eq!(new_blocks.len(), TRACKED_BLOCKS);
+        assert_eq!(new_blocks.last(), Some(&head));
+        assert_eq!(heads.blocks.len(), TRACKED_BLOCKS);
+
+        // A head whose parent is unknown replaces the tracked chain.
+        let orphan = BlockInfo::new(B256::repeat_byte(0xff), 100, B256::repeat_byte(0xee), 1200);
+        assert_eq!(heads.advance(orphan, &fetch).await.unwrap(), vec![orphan]);
+        assert_eq!(heads.blocks, vec![orphan]);
+    }
+}
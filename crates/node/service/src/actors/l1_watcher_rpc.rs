@@ -1,7 +1,10 @@
 //! [`NodeActor`] implementation for an L1 chain watcher that polls for L1 block updates over HTTP
-//! RPC.
+//! RPC, or subscribes to them over WebSocket.
 
-use crate::{NodeActor, actors::CancellableContext};
+use crate::{
+    NodeActor, Traced,
+    actors::{CancellableContext, l1_head_subscription::HeadSubscription},
+};
 use alloy_eips::{BlockId, BlockNumberOrTag};
 use alloy_primitives::{Address, B256};
 use alloy_provider::{Provider, RootProvider};
@@ -25,6 +28,7 @@ use tokio::{
     task::JoinHandle,
 };
 use tokio_util::sync::{CancellationToken, WaitForCancellationFuture};
+use url::Url;
 
 /// An L1 chain watcher that checks for L1 block updates over RPC.
 #[derive(Debug)]
@@ -42,6 +46,9 @@ pub struct L1WatcherRpcState {
     pub rollup: Arc<RollupConfig>,
     /// The L1 provider.
     pub l1_provider: RootProvider,
+    /// The WebSocket URL of the L1 execution client. If set, the L1 head is observed through a
+    /// `newHeads` subscription instead of being polled over the L1 provider.
+    pub l1_ws_url: Option<Url>,
 }
 
 impl L1WatcherRpcState {
@@ -59,7 +66,7 @@ impl L1WatcherRpcState {
     fn start_query_processor(
         &self,
         mut inbound_queries: tokio::sync::mpsc::Receiver<L1WatcherQueries>,
-        head_updates_recv: watch::Receiver<Option<BlockInfo>>,
+        head_updates_recv: watch::Receiver<Option<Traced<BlockInfo>>>,
     ) -> JoinHandle<()> {
         // Start the inbound query processor in a separate task to avoid blocking the main task.
         // We can cheaply clone the l1 provider here because it is an Arc.
@@ -75,7 +82,7 @@ impl L1WatcherRpcState {
                         }
                     }
                     L1WatcherQueries::L1State(sender) => {
-                        let current_l1 = *head_updates_recv.borrow();
+                        let current_l1 = head_updates_recv.borrow().as_deref().copied();
 
                         let head_l1 = match l1_provider.get_block(BlockId::latest()).await {
                                 Ok(block) => block,
@@ -126,8 +133,8 @@ pub struct L1WatcherRpcInboundChannels {
 /// The communication context used by the L1 watcher actor.
 #[derive(Debug)]
 pub struct L1WatcherRpcContext {
-    /// The latest L1 head block.
-    pub latest_head: watch::Sender<Option<BlockInfo>>,
+    /// The latest L1 head block, traced in the `l1_head` span that follows it through derivation.
+    pub latest_head: watch::Sender<Option<Traced<BlockInfo>>>,
     /// The latest L1 finalized block.
     pub latest_finalized: watch::Sender<Option<BlockInfo>>,
     /// The block signer sender.
@@ -167,12 +174,22 @@ impl NodeActor for L1WatcherRpc {
         mut self,
         L1WatcherRpcContext { latest_head, latest_finalized, block_signer_sender, cancellation }: Self::OutboundData,
     ) -> Result<(), Self::Error> {
-        let mut head_stream = BlockStream::new(
-            &self.state.l1_provider,
-            BlockNumberOrTag::Latest,
-            Duration::from_secs(4),
-        )
-        .into_stream();
+        let mut head_stream = match self.state.l1_ws_url.clone() {
+            Some(ws_url) => HeadSubscription::new(
+                ws_url,
+                self.state.l1_provider.clone(),
+                Duration::from_secs(4),
+            )
+            .into_stream()
+            .left_stream(),
+            None => BlockStream::new(
+                &self.state.l1_provider,
+                BlockNumberOrTag::Latest,
+                Duration::from_secs(4),
+            )
+            .into_stream()
+            .right_stream(),
+        };
         let mut finalized_stream = BlockStream::new(
             &self.state.l1_provider,
             BlockNumberOrTag::Finalized,
@@ -204,7 +221,13 @@ impl NodeActor for L1WatcherRpc {
                     }
                     Some(head_block_info) => {
                         // Send the head update event to all consumers.
-                        latest_head.send_replace(Some(head_block_info));
+                        let span = info_span!(
+                            target: "l1_watcher",
+                            "l1_head",
+                            number = head_block_info.number,
+                            hash = %head_block_info.hash,
+                        );
+                        latest_head.send_replace(Some(Traced::with_span(head_block_info, span)));
 
                         // For each log, attempt to construct a `SystemConfigLog`.
                         // Build the `SystemConfigUpdate` from the log.
@@ -251,7 +274,7 @@ impl NodeActor for L1WatcherRpc {
 /// Note that this stream is not guaranteed to be contiguous. It may miss certain blocks, and
 /// yielded items should only be considered to be the latest block matching the given
 /// [`BlockNumberOrTag`].
-struct BlockStream<'a> {
+pub(super) struct BlockStream<'a> {
     /// The inner [`RootProvider`].
     l1_provider: &'a RootProvider,
     /// The block tag to poll for.
@@ -265,7 +288,11 @@ impl<'a> BlockStream<'a> {
     ///
     /// ## Panics
     /// Panics if the passed [`BlockNumberOrTag`] is of the [`BlockNumberOrTag::Number`] variant.
-    fn new(l1_provider: &'a RootProvider, tag: BlockNumberOrTag, poll_interval: Duration) -> Self {
+    pub(super) fn new(
+        l1_provider: &'a RootProvider,
+        tag: BlockNumberOrTag,
+        poll_interval: Duration,
+    ) -> Self {
         if matches!(tag, BlockNumberOrTag::Number(_)) {
             panic!("Invalid BlockNumberOrTag variant - Must be a tag");
         }
@@ -273,7 +300,7 @@ impl<'a> BlockStream<'a> {
     }
 
     /// Transforms the watcher into a [`Stream`].
-    fn into_stream(self) -> impl Stream<Item = BlockInfo> + Unpin {
+    pub(super) fn into_stream(self) -> impl Stream<Item = BlockInfo> + Unpin {
         let mut poll_stream = PollerBuilder::<_, Block>::new(
             self.l1_provider.weak_client(),
             "eth_getBlockByNumber",
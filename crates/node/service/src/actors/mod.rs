@@ -5,9 +5,13 @@
 mod traits;
 pub use traits::{CancellableContext, NodeActor};
 
+mod traced;
+pub use traced::Traced;
+
 mod engine;
 pub use engine::{
-    EngineActor, EngineBuilder, EngineContext, EngineError, EngineInboundData, L2Finalizer,
+    EngineActor, EngineBuilder, EngineClientBuilder, EngineContext, EngineError, EngineInboundData,
+    L2Finalizer,
 };
 
 mod rpc;
@@ -19,6 +23,14 @@ pub use derivation::{
     DerivationInboundChannels, DerivationState, InboundDerivationMessage, PipelineBuilder,
 };
 
+mod follow;
+pub use follow::{
+    FollowActor, FollowBuilder, FollowContext, FollowError, FollowSource, FollowSourceClient,
+    FollowUpdate,
+};
+
+mod l1_head_subscription;
+
 mod l1_watcher_rpc;
 pub use l1_watcher_rpc::{
     L1WatcherRpc, L1WatcherRpcContext, L1WatcherRpcError, L1WatcherRpcInboundChannels,
@@ -29,6 +41,7 @@ mod network;
 pub use network::{
     NetworkActor, NetworkActorError, NetworkBuilder, NetworkBuilderError, NetworkConfig,
     NetworkContext, NetworkDriver, NetworkDriverError, NetworkHandler, NetworkInboundData,
+    SignerRotationError,
 };
 
 mod sequencer;
@@ -10,7 +10,7 @@ use tokio::{self, select, sync::mpsc};
 use tokio_util::sync::{CancellationToken, WaitForCancellationFuture};
 
 use crate::{
-    CancellableContext, NodeActor,
+    CancellableContext, NodeActor, Traced,
     actors::network::{
         builder::NetworkBuilder, driver::NetworkDriverError, error::NetworkBuilderError,
     },
@@ -100,7 +100,9 @@ impl NetworkActor {
 #[derive(Debug)]
 pub struct NetworkContext {
     /// The channel used by the sequencer actor for sending unsafe blocks to the network.
-    pub blocks: mpsc::Sender<OpExecutionPayloadEnvelope>,
+    ///
+    /// Each block is traced in an `unsafe_payload` span created when it was received.
+    pub blocks: mpsc::Sender<Traced<OpExecutionPayloadEnvelope>>,
     /// Cancels the network actor.
     pub cancellation: CancellationToken,
 }
@@ -164,6 +166,9 @@ impl NodeActor for NetworkActor {
                         target: "network",
                         "Received shutdown signal. Exiting network task."
                     );
+                    if let Err(e) = handler.gossip.sync_peer_store() {
+                        warn!(target: "network", "Failed to sync peer store: {:?}", e);
+                    }
                     return Ok(());
                 }
                 block = unsafe_block_rx.recv() => {
@@ -185,7 +190,7 @@ impl NodeActor for NetworkActor {
                         );
                         return Err(NetworkActorError::ChannelClosed);
                     };
-                    if handler.unsafe_block_signer_sender.send(signer).is_err() {
+                    if handler.update_unsafe_block_signer(signer).is_err() {
                         warn!(
                             target: "network",
                             "Failed to send unsafe block signer to network handler",
@@ -228,7 +233,7 @@ impl NodeActor for NetworkActor {
                     };
 
                     if let Some(payload) = handler.gossip.handle_event(event) {
-                        if unsafe_block_tx.send(payload.into()).is_err() {
+                        if unsafe_block_tx.send(traced_unsafe_payload(payload.into(), "gossip")).is_err() {
                             warn!(target: "node::p2p", "Failed to send unsafe block to network handler");
                         }
                     }
@@ -243,10 +248,29 @@ impl NodeActor for NetworkActor {
                 _ = handler.peer_score_inspector.tick(), if handler.gossip.peer_monitoring.as_ref().is_some() => {
                     handler.handle_peer_monitoring().await;
                 },
-                Some(NetworkAdminQuery::PostUnsafePayload { payload }) = self.admin_rpc.recv(), if !self.admin_rpc.is_closed() => {
-                    debug!(target: "node::p2p", "Broadcasting unsafe payload from admin api");
-                    if unsafe_block_tx.send(payload).is_err() {
-                        warn!(target: "node::p2p", "Failed to send unsafe block to network handler");
+                _ = handler.static_peer_redialer.tick(), if !handler.gossip.static_peers.is_empty() => {
+                    handler.gossip.dial_static_peers();
+                },
+                _ = handler.peer_store_syncer.tick() => {
+                    if let Err(e) = handler.gossip.sync_peer_store() {
+                        warn!(target: "network", "Failed to sync peer store: {:?}", e);
+                    }
+                },
+                Some(query) = self.admin_rpc.recv(), if !self.admin_rpc.is_closed() => match query {
+                    NetworkAdminQuery::PostUnsafePayload { payload } => {
+                        debug!(target: "node::p2p", "Broadcasting unsafe payload from admin api");
+                        if unsafe_block_tx.send(traced_unsafe_payload(payload, "admin")).is_err() {
+                            warn!(target: "node::p2p", "Failed to send unsafe block to network handler");
+                        }
+                    }
+                    NetworkAdminQuery::RotateSequencerKey { signer, sender } => {
+                        let result = handler.rotate_signer(signer).map_err(|e| {
+                            warn!(target: "network", %e, "Failed to rotate the local block signer");
+                            e.to_string()
+                        });
+                        if sender.send(result).is_err() {
+                            warn!(target: "network", "Failed to send signer rotation response");
+                        }
                     }
                 },
                 Some(req) = self.p2p_rpc.recv(), if !self.p2p_rpc.is_closed() => {
@@ -257,6 +281,22 @@ impl NodeActor for NetworkActor {
     }
 }
 
+/// Wraps an unsafe payload received from the given source in a new `unsafe_payload` span, which
+/// follows it through the engine.
+fn traced_unsafe_payload(
+    payload: OpExecutionPayloadEnvelope,
+    source: &'static str,
+) -> Traced<OpExecutionPayloadEnvelope> {
+    let span = info_span!(
+        target: "node::p2p",
+        "unsafe_payload",
+        source,
+        number = payload.execution_payload.block_number(),
+        hash = %payload.execution_payload.block_hash(),
+    );
+    Traced::with_span(payload, span)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -5,7 +5,7 @@ use discv5::{Config as Discv5Config, Enr};
 use kona_disc::{Discv5Builder, LocalNode};
 use kona_genesis::RollupConfig;
 use kona_gossip::{GaterConfig, GossipDriverBuilder};
-use kona_peers::{BootStoreFile, PeerMonitoring, PeerScoreLevel};
+use kona_peers::{BootStoreFile, PeerMonitoring, PeerScoreLevel, PeerStoreFile};
 use kona_sources::BlockSigner;
 use libp2p::{Multiaddr, identity::Keypair};
 use std::time::Duration;
@@ -46,6 +46,8 @@ impl From<NetworkConfig> for NetworkBuilder {
         .with_discovery_randomize(config.discovery_randomize)
         .with_bootstore(config.bootstore)
         .with_bootnodes(config.bootnodes)
+        .with_static_peers(config.static_peers)
+        .with_peer_store(config.peer_store)
         .with_discovery_interval(config.discovery_interval)
         .with_gossip_config(config.gossip_config)
         .with_peer_scoring(config.scoring)
@@ -113,6 +115,16 @@ impl NetworkBuilder {
         Self { discovery: self.discovery.with_bootnodes(bootnodes), ..self }
     }
 
+    /// Sets the static peers for the [`GossipDriverBuilder`].
+    pub fn with_static_peers(self, static_peers: Vec<Multiaddr>) -> Self {
+        Self { gossip: self.gossip.with_static_peers(static_peers), ..self }
+    }
+
+    /// Sets the peerstore path for the [`GossipDriverBuilder`].
+    pub fn with_peer_store(self, peer_store: Option<PeerStoreFile>) -> Self {
+        Self { gossip: self.gossip.with_peer_store(peer_store), ..self }
+    }
+
     /// Sets the peer scoring based on the given [`PeerScoreLevel`].
     pub fn with_peer_scoring(self, level: PeerScoreLevel) -> Self {
         Self { gossip: self.gossip.with_peer_scoring(level), ..self }
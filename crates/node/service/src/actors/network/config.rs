@@ -5,7 +5,7 @@ use discv5::Enr;
 use kona_disc::LocalNode;
 use kona_genesis::RollupConfig;
 use kona_gossip::GaterConfig;
-use kona_peers::{BootStoreFile, PeerMonitoring, PeerScoreLevel};
+use kona_peers::{BootStoreFile, PeerMonitoring, PeerScoreLevel, PeerStoreFile};
 use kona_sources::BlockSigner;
 use libp2p::{Multiaddr, identity::Keypair};
 use tokio::time::Duration;
@@ -44,6 +44,10 @@ pub struct NetworkConfig {
     pub gater_config: GaterConfig,
     /// An optional list of bootnode ENRs to start the node with.
     pub bootnodes: Vec<Enr>,
+    /// Peers that are always redialed and protected from pruning.
+    pub static_peers: Vec<Multiaddr>,
+    /// An optional path to the peerstore.
+    pub peer_store: Option<PeerStoreFile>,
     /// The [`RollupConfig`].
     pub rollup_config: RollupConfig,
     /// A signer for gossip payloads.
@@ -89,6 +93,8 @@ impl NetworkConfig {
             enr_update: true,
             keypair: Keypair::generate_secp256k1(),
             bootnodes: Default::default(),
+            static_peers: Default::default(),
+            peer_store: Default::default(),
             bootstore: Default::default(),
             gater_config: Default::default(),
             gossip_config: Default::default(),
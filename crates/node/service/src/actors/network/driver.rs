@@ -1,10 +1,17 @@
-use std::net::{IpAddr, SocketAddr};
+use std::{
+    collections::HashSet,
+    net::{IpAddr, SocketAddr},
+    sync::Arc,
+};
 
 use alloy_primitives::Address;
 use discv5::multiaddr::Protocol;
 use futures::future::OptionFuture;
-use kona_disc::Discv5Driver;
-use kona_gossip::{ConnectionGater, GossipDriver, PEER_SCORE_INSPECT_FREQUENCY};
+use kona_disc::{Discv5Driver, HandlerRequest};
+use kona_gossip::{
+    ConnectionGater, GossipDriver, PEER_SCORE_INSPECT_FREQUENCY, PEER_STORE_SYNC_FREQUENCY,
+    STATIC_PEER_REDIAL_FREQUENCY,
+};
 use kona_sources::{BlockSigner, BlockSignerStartError};
 use libp2p::{Multiaddr, TransportError};
 use tokio::sync::watch;
@@ -78,9 +85,31 @@ impl NetworkDriver {
         // Start the discovery service.
         let (handler, enr_receiver) = self.discovery.start();
 
+        // Ban the peers that were banned before the last restart from the discovery service.
+        for (peer_id, ban_duration) in self.gossip.peer_store.active_bans() {
+            let Some(record) = self.gossip.peer_store.peers.get(&peer_id) else {
+                continue;
+            };
+            let addrs_to_ban = Arc::new(record.addrs.iter().cloned().collect::<HashSet<_>>());
+            if let Err(e) =
+                handler.sender.send(HandlerRequest::BanAddrs { addrs_to_ban, ban_duration }).await
+            {
+                warn!(target: "network", err = ?e, "Failed to restore peer ban");
+            }
+        }
+
         // We are checking the peer scores every [`PEER_SCORE_INSPECT_FREQUENCY`] seconds.
         let peer_score_inspector = tokio::time::interval(*PEER_SCORE_INSPECT_FREQUENCY);
 
+        // Static peers are dialed right away, and then redialed every
+        // [`STATIC_PEER_REDIAL_FREQUENCY`] seconds if they disconnected.
+        let static_peer_redialer = tokio::time::interval(*STATIC_PEER_REDIAL_FREQUENCY);
+
+        // The first tick of the interval completes immediately, skip it since there is nothing
+        // to persist yet.
+        let mut peer_store_syncer = tokio::time::interval(*PEER_STORE_SYNC_FREQUENCY);
+        peer_store_syncer.reset();
+
         // Start the block signer if it is configured.
         let signer =
             OptionFuture::from(self.signer.map(async |s| s.start().await)).await.transpose()?;
@@ -91,7 +120,10 @@ impl NetworkDriver {
             enr_receiver,
             unsafe_block_signer_sender: self.unsafe_block_signer_sender,
             peer_score_inspector,
+            static_peer_redialer,
+            peer_store_syncer,
             signer,
+            pending_signer: None,
         })
     }
 }
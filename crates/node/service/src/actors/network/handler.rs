@@ -1,10 +1,10 @@
-use std::collections::HashSet;
-
 use alloy_primitives::Address;
+use alloy_signer_local::PrivateKeySigner;
 use discv5::Enr;
 use kona_disc::{Discv5Handler, HandlerRequest};
 use kona_gossip::{ConnectionGater, GossipDriver};
 use kona_sources::BlockSignerHandler;
+use thiserror::Error;
 use tokio::sync::{mpsc, watch};
 
 /// A network handler used to communicate with the network once it is started.
@@ -20,83 +20,90 @@ pub struct NetworkHandler {
     pub unsafe_block_signer_sender: watch::Sender<Address>,
     /// The peer score inspector. Is used to ban peers that are below a given threshold.
     pub peer_score_inspector: tokio::time::Interval,
+    /// The static peer redialer. Is used to redial static peers that disconnected.
+    pub static_peer_redialer: tokio::time::Interval,
+    /// The peer store syncer. Is used to periodically write the peer store to disk.
+    pub peer_store_syncer: tokio::time::Interval,
     /// A handler for the block signer.
     pub signer: Option<BlockSignerHandler>,
+    /// A local block signer staged by a key rotation, which replaces [`Self::signer`] once the
+    /// unsafe block signer on L1 is updated to its address.
+    pub pending_signer: Option<PrivateKeySigner>,
+}
+
+/// An error from rotating the local block signer.
+#[derive(Debug, Error)]
+pub enum SignerRotationError {
+    /// Only a local block signer can be rotated.
+    #[error("No local block signer to rotate")]
+    NoLocalSigner,
 }
 
 impl NetworkHandler {
-    pub(super) async fn handle_peer_monitoring(&mut self) {
-        // Inspect peer scores and ban peers that are below the threshold.
-        let Some(ban_peers) = self.gossip.peer_monitoring.as_ref() else {
-            return;
-        };
+    /// Stages `signer` to replace the local block signer, and returns its address.
+    ///
+    /// If the unsafe block signer is already the new key's address, the signer is replaced right
+    /// away. Otherwise it is replaced by [`Self::update_unsafe_block_signer`], so that signing and
+    /// gossip validation switch to the new key at the same L1 block.
+    pub(super) fn rotate_signer(
+        &mut self,
+        signer: PrivateKeySigner,
+    ) -> Result<Address, SignerRotationError> {
+        if !matches!(self.signer, Some(BlockSignerHandler::Local(_))) {
+            return Err(SignerRotationError::NoLocalSigner);
+        }
 
-        // We iterate over all connected peers and check their scores.
-        // We collect a list of peers to remove
-        let peers_to_remove = self
-            .gossip
-            .swarm
-            .connected_peers()
-            .filter_map(|peer_id| {
-                // If the score is not available, we use a default value of 0.
-                let score =
-                    self.gossip.swarm.behaviour().gossipsub.peer_score(peer_id).unwrap_or_default();
+        // Sign for the node's chain, like the local block signer configured at startup.
+        let signer = signer.with_chain_id(Some(self.discovery.chain_id));
+        let address = signer.address();
 
-                // Record the peer score in the metrics.
-                kona_macros::record!(
-                    histogram,
-                    kona_gossip::Metrics::PEER_SCORES,
-                    "peer",
-                    peer_id.to_string(),
-                    score
-                );
+        if address == *self.unsafe_block_signer_sender.borrow() {
+            info!(target: "network", %address, "Rotated local block signer");
+            self.signer = Some(BlockSignerHandler::Local(signer));
+            self.pending_signer = None;
+        } else {
+            info!(
+                target: "network",
+                %address,
+                "Staged local block signer until it becomes the unsafe block signer on L1"
+            );
+            self.pending_signer = Some(signer);
+        }
 
-                if score < ban_peers.ban_threshold {
-                    return Some(*peer_id);
-                }
+        Ok(address)
+    }
 
-                None
-            })
-            .collect::<Vec<_>>();
+    /// Updates the unsafe block signer that gossiped blocks are validated against. If a staged
+    /// local block signer has the new address, it replaces the current one.
+    pub(super) fn update_unsafe_block_signer(
+        &mut self,
+        signer: Address,
+    ) -> Result<(), watch::error::SendError<Address>> {
+        self.unsafe_block_signer_sender.send(signer)?;
 
-        // We remove the addresses from the gossip layer.
-        let addrs_to_ban = peers_to_remove.into_iter().filter_map(|peer_to_remove| {
-                        // In that case, we ban the peer. This means...
-                        // 1. We remove the peer from the network gossip.
-                        // 2. We ban the peer from the discv5 service.
-                        if self.gossip.swarm.disconnect_peer_id(peer_to_remove).is_err() {
-                            warn!(peer = ?peer_to_remove, "Trying to disconnect a non-existing peer from the gossip driver.");
-                        }
+        if let Some(pending) = self.pending_signer.take_if(|pending| pending.address() == signer) {
+            info!(target: "network", address = %signer, "Rotated local block signer");
+            self.signer = Some(BlockSignerHandler::Local(pending));
+        }
 
-                        // Record the duration of the peer connection.
-                        if let Some(start_time) = self.gossip.peer_connection_start.remove(&peer_to_remove) {
-                            let peer_duration = start_time.elapsed();
-                            kona_macros::record!(
-                                histogram,
-                                kona_gossip::Metrics::GOSSIP_PEER_CONNECTION_DURATION_SECONDS,
-                                peer_duration.as_secs_f64()
-                            );
-                        }
+        Ok(())
+    }
 
-                        if let Some(info) = self.gossip.peerstore.remove(&peer_to_remove){
-                            use kona_gossip::ConnectionGate;
-                            self.gossip.connection_gate.remove_dial(&peer_to_remove);
-                            let score = self.gossip.swarm.behaviour().gossipsub.peer_score(&peer_to_remove).unwrap_or_default();
-                            kona_macros::inc!(gauge, kona_gossip::Metrics::BANNED_PEERS, "peer_id" => peer_to_remove.to_string(), "score" => score.to_string());
-                            return Some(info.listen_addrs);
-                        }
+    pub(super) async fn handle_peer_monitoring(&mut self) {
+        // Inspect peer scores and ban peers that are below the threshold.
+        let Some(ban_duration) = self.gossip.peer_monitoring.as_ref().map(|m| m.ban_duration)
+        else {
+            return;
+        };
 
-                        None
-                    }).collect::<Vec<_>>().into_iter().flatten().collect::<HashSet<_>>();
+        // The peers are banned from the gossip layer, and their addresses from discovery.
+        let addrs_to_ban = self.gossip.ban_peers_below_threshold();
 
         // We send a request to the discovery handler to ban the set of addresses.
         if let Err(send_err) = self
             .discovery
             .sender
-            .send(HandlerRequest::BanAddrs {
-                addrs_to_ban: addrs_to_ban.into(),
-                ban_duration: ban_peers.ban_duration,
-            })
+            .send(HandlerRequest::BanAddrs { addrs_to_ban: addrs_to_ban.into(), ban_duration })
             .await
         {
             warn!(err = ?send_err, "Impossible to send a request to the discovery handler. The channel connection is dropped.");
@@ -13,7 +13,7 @@ mod error;
 pub use error::NetworkBuilderError;
 
 mod handler;
-pub use handler::NetworkHandler;
+pub use handler::{NetworkHandler, SignerRotationError};
 
 mod config;
 pub use config::NetworkConfig;
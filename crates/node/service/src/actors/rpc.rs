@@ -4,8 +4,8 @@ use crate::{NodeActor, actors::CancellableContext};
 use async_trait::async_trait;
 use kona_gossip::P2pRpcRequest;
 use kona_rpc::{
-    AdminApiServer, AdminRpc, DevEngineApiServer, DevEngineRpc, HealthzResponse, NetworkAdminQuery,
-    OpP2PApiServer, RollupNodeApiServer, SequencerAdminQuery, WsRPC, WsServer,
+    AdminApiServer, AdminRpc, DevEngineApiServer, DevEngineRpc, HealthChecker, NetworkAdminQuery,
+    OpP2PApiServer, Probe, RollupNodeApiServer, SequencerAdminQuery, WsRPC, WsServer,
 };
 use std::time::Duration;
 
@@ -22,8 +22,8 @@ use tokio_util::sync::{CancellationToken, WaitForCancellationFuture};
 /// An error returned by the [`RpcActor`].
 #[derive(Debug, thiserror::Error)]
 pub enum RpcActorError {
-    /// Failed to register the healthz endpoint.
-    #[error("Failed to register the healthz endpoint")]
+    /// Failed to register the health endpoints.
+    #[error("Failed to register the health endpoints")]
     RegisterHealthz(#[from] RegisterMethodError),
     /// Failed to launch the RPC server.
     #[error(transparent)]
@@ -86,7 +86,7 @@ async fn launch(
 ) -> Result<ServerHandle, std::io::Error> {
     let middleware = tower::ServiceBuilder::new()
         .layer(
-            ProxyGetRequestLayer::new([("/healthz", "healthz")])
+            ProxyGetRequestLayer::new([("/healthz", "healthz"), ("/readyz", "readyz")])
                 .expect("Critical: Failed to build GET method proxy"),
         )
         .timeout(Duration::from_secs(2));
@@ -125,10 +125,20 @@ impl NodeActor for RpcActor {
     ) -> Result<(), Self::Error> {
         let mut modules = RpcModule::new(());
 
-        modules.register_method("healthz", |_, _, _| {
-            let response = HealthzResponse { version: std::env!("CARGO_PKG_VERSION").to_string() };
-            jsonrpsee::core::RpcResult::Ok(response)
+        // Build the health module, gating `/healthz` on liveness and `/readyz` on readiness.
+        let mut health = RpcModule::new(HealthChecker::new(
+            self.config.health(),
+            engine_query.clone(),
+            l1_watcher_queries.clone(),
+            p2p_network.clone(),
+        ));
+        health.register_async_method("healthz", |_, checker, _| async move {
+            checker.probe(Probe::Liveness).await
         })?;
+        health.register_async_method("readyz", |_, checker, _| async move {
+            checker.probe(Probe::Readiness).await
+        })?;
+        modules.merge(health)?;
 
         // Build the p2p rpc module.
         modules.merge(P2pRpc::new(p2p_network).into_rpc())?;
@@ -199,6 +209,7 @@ mod tests {
             admin_persistence: None,
             ws_enabled: false,
             dev_enabled: false,
+            health: Default::default(),
         };
         let result = launch(&launcher, RpcModule::new(())).await;
         assert!(result.is_ok());
@@ -213,6 +224,7 @@ mod tests {
             admin_persistence: None,
             ws_enabled: false,
             dev_enabled: false,
+            health: Default::default(),
         };
         let mut modules = RpcModule::new(());
 
@@ -1,9 +1,10 @@
 //! The [`SequencerActor`].
 
 use super::{
-    DelayedL1OriginSelectorProvider, L1OriginSelector, L1OriginSelectorError, SequencerConfig,
+    DelayedL1OriginSelectorProvider, L1OriginSelector, L1OriginSelectorError,
+    L1OriginSelectorProvider, SequencerConfig,
 };
-use crate::{CancellableContext, NodeActor, actors::sequencer::conductor::ConductorClient};
+use crate::{CancellableContext, NodeActor, Traced, actors::sequencer::conductor::ConductorClient};
 use alloy_provider::RootProvider;
 use async_trait::async_trait;
 use kona_derive::{AttributesBuilder, PipelineErrorKind, StatefulAttributesBuilder};
@@ -38,13 +39,16 @@ pub struct SequencerActor<AB: AttributesBuilderConfig> {
 
 /// The state of the [`SequencerActor`].
 #[derive(Debug)]
-pub(super) struct SequencerActorState<AB: AttributesBuilder> {
+pub(super) struct SequencerActorState<
+    AB: AttributesBuilder,
+    P: L1OriginSelectorProvider = DelayedL1OriginSelectorProvider,
+> {
     /// The [`RollupConfig`] for the chain being sequenced.
     pub cfg: Arc<RollupConfig>,
     /// The [`AttributesBuilder`].
     pub builder: AB,
     /// The [`L1OriginSelector`].
-    pub origin_selector: L1OriginSelector<DelayedL1OriginSelectorProvider>,
+    pub origin_selector: L1OriginSelector<P>,
     /// The ticker for building new blocks.
     pub build_ticker: tokio::time::Interval,
     /// The conductor RPC client.
@@ -74,7 +78,7 @@ pub trait AttributesBuilderConfig {
 impl SequencerActorState<StatefulAttributesBuilder<AlloyChainProvider, AlloyL2ChainProvider>> {
     fn new(
         seq_builder: SequencerBuilder,
-        l1_head_watcher: watch::Receiver<Option<BlockInfo>>,
+        l1_head_watcher: watch::Receiver<Option<Traced<BlockInfo>>>,
     ) -> Self {
         let SequencerConfig {
             sequencer_stopped,
@@ -169,7 +173,7 @@ pub struct SequencerContext {
     /// The cancellation token, shared between all tasks.
     pub cancellation: CancellationToken,
     /// Watch channel to observe the L1 head of the chain.
-    pub l1_head_rx: watch::Receiver<Option<BlockInfo>>,
+    pub l1_head_rx: watch::Receiver<Option<Traced<BlockInfo>>>,
     /// Sender to request the engine to reset.
     pub reset_request_tx: mpsc::Sender<()>,
     /// Sender to request the execution layer to build a payload attributes on top of the
@@ -212,7 +216,7 @@ impl<AB: AttributesBuilderConfig> SequencerActor<AB> {
     }
 }
 
-impl<AB: AttributesBuilder> SequencerActorState<AB> {
+impl<AB: AttributesBuilder, P: L1OriginSelectorProvider> SequencerActorState<AB, P> {
     /// Starts the build job for the next L2 block, on top of the current unsafe head.
     async fn build_block(
         &mut self,
@@ -533,3 +537,127 @@ impl NodeActor for SequencerActor<SequencerBuilder> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        EngineActor, EngineContext, NodeMode, actors::engine::test_utils::TestEngineBuilder,
+    };
+    use alloy_eips::BlockNumHash;
+    use alloy_primitives::B256;
+    use kona_derive::PipelineResult;
+    use kona_engine::test_utils::TestEngineApi;
+    use op_alloy_rpc_types_engine::OpPayloadAttributes;
+    use tokio::time::timeout;
+
+    /// The time to wait for the actors to make progress before failing the test.
+    const TIMEOUT: Duration = Duration::from_secs(10);
+
+    /// An [`AttributesBuilder`] preparing [`TestEngineApi::next_attributes`].
+    #[derive(Debug)]
+    struct TestAttributesBuilder {
+        cfg: Arc<RollupConfig>,
+    }
+
+    #[async_trait]
+    impl AttributesBuilder for TestAttributesBuilder {
+        async fn prepare_payload_attributes(
+            &mut self,
+            l2_parent: L2BlockInfo,
+            _: BlockNumHash,
+        ) -> PipelineResult<OpPayloadAttributes> {
+            Ok(TestEngineApi::next_attributes(l2_parent, &self.cfg).inner)
+        }
+    }
+
+    /// An [`L1OriginSelectorProvider`] serving the L1 genesis block of [`TestEngineApi::genesis`]
+    /// only.
+    #[derive(Debug)]
+    struct TestL1Provider;
+
+    #[async_trait]
+    impl L1OriginSelectorProvider for TestL1Provider {
+        async fn get_block_by_hash(
+            &self,
+            hash: B256,
+        ) -> Result<Option<BlockInfo>, L1OriginSelectorError> {
+            Ok((hash == TestEngineApi::L1_GENESIS_HASH).then(TestEngineApi::l1_genesis))
+        }
+
+        async fn get_block_by_number(
+            &self,
+            number: u64,
+        ) -> Result<Option<BlockInfo>, L1OriginSelectorError> {
+            Ok((number == 0).then(TestEngineApi::l1_genesis))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_sequence_onto_engine() {
+        let engine_builder = TestEngineBuilder::new(NodeMode::Sequencer);
+        let (cfg, client) = (engine_builder.cfg.clone(), engine_builder.client.clone());
+        let (engine_inbound, engine) = EngineActor::new(engine_builder);
+
+        let cancellation = CancellationToken::new();
+        let (unsafe_head_tx, mut unsafe_head_rx) = watch::channel(L2BlockInfo::default());
+        let (safe_head_tx, _safe_head_rx) = watch::channel(L2BlockInfo::default());
+        let (sync_complete_tx, _sync_complete_rx) = tokio::sync::oneshot::channel();
+        let (derivation_signal_tx, _derivation_signal_rx) = mpsc::channel(16);
+        let engine_handle = tokio::spawn(engine.start(EngineContext {
+            cancellation: cancellation.clone(),
+            engine_unsafe_head_tx: Some(unsafe_head_tx),
+            engine_l2_safe_head_tx: safe_head_tx,
+            sync_complete_tx,
+            derivation_signal_tx,
+        }));
+
+        let (gossip_payload_tx, mut gossip_payload_rx) = mpsc::channel(16);
+        let mut ctx = SequencerContext {
+            cancellation: cancellation.clone(),
+            l1_head_rx: watch::channel(None).1,
+            reset_request_tx: engine_inbound.reset_request_tx.clone(),
+            build_request_tx: engine_inbound.build_request_tx.clone().unwrap(),
+            gossip_payload_tx,
+        };
+        let mut state = SequencerActorState {
+            cfg: cfg.clone(),
+            builder: TestAttributesBuilder { cfg: cfg.clone() },
+            origin_selector: L1OriginSelector::new(cfg.clone(), TestL1Provider),
+            build_ticker: tokio::time::interval(Duration::from_secs(cfg.block_time)),
+            conductor: None,
+            is_active: true,
+            is_recovery_mode: false,
+        };
+
+        // The initial reset moves the unsafe head to the genesis block.
+        timeout(TIMEOUT, state.schedule_initial_reset(&mut ctx, &mut unsafe_head_rx))
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(unsafe_head_rx.borrow().block_info.hash, cfg.genesis.l2.hash);
+
+        // Every built block is canonicalized by the engine and scheduled for gossip.
+        for number in 1..=2 {
+            timeout(TIMEOUT, state.build_block(&mut ctx, &mut unsafe_head_rx, false))
+                .await
+                .unwrap()
+                .unwrap();
+            let payload = gossip_payload_rx.recv().await.unwrap();
+            assert_eq!(payload.execution_payload.block_number(), number);
+            assert_eq!(client.canonical_hash(number), Some(payload.execution_payload.block_hash()));
+
+            // Wait for the new unsafe head before building on top of it.
+            let unsafe_head =
+                *timeout(TIMEOUT, unsafe_head_rx.wait_for(|head| head.block_info.number == number))
+                    .await
+                    .unwrap()
+                    .unwrap();
+            assert_eq!(unsafe_head.block_info.hash, payload.execution_payload.block_hash());
+            assert_eq!(unsafe_head.l1_origin.hash, TestEngineApi::L1_GENESIS_HASH);
+        }
+
+        cancellation.cancel();
+        engine_handle.await.unwrap().unwrap();
+    }
+}
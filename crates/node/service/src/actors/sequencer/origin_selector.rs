@@ -1,5 +1,6 @@
 //! The [`L1OriginSelector`].
 
+use crate::Traced;
 use alloy_primitives::B256;
 use alloy_provider::{Provider, RootProvider};
 use alloy_transport::{RpcError, TransportErrorKind};
@@ -185,7 +186,7 @@ pub struct DelayedL1OriginSelectorProvider {
     /// The inner [`RootProvider`].
     inner: RootProvider,
     /// The L1 head watch channel.
-    l1_head: watch::Receiver<Option<BlockInfo>>,
+    l1_head: watch::Receiver<Option<Traced<BlockInfo>>>,
     /// The confirmation depth to delay the view of the L1 chain.
     confirmation_depth: u64,
 }
@@ -194,7 +195,7 @@ impl DelayedL1OriginSelectorProvider {
     /// Creates a new [`DelayedL1OriginSelectorProvider`].
     pub const fn new(
         inner: RootProvider,
-        l1_head: watch::Receiver<Option<BlockInfo>>,
+        l1_head: watch::Receiver<Option<Traced<BlockInfo>>>,
         confirmation_depth: u64,
     ) -> Self {
         Self { inner, l1_head, confirmation_depth }
@@ -215,7 +216,7 @@ impl L1OriginSelectorProvider for DelayedL1OriginSelectorProvider {
         &self,
         number: u64,
     ) -> Result<Option<BlockInfo>, L1OriginSelectorError> {
-        let Some(l1_head) = *self.l1_head.borrow() else {
+        let Some(l1_head) = self.l1_head.borrow().as_deref().copied() else {
             // If the L1 head is not available, do not enforce a confirmation delay.
             return Ok(Provider::get_block_by_number(&self.inner, number.into())
                 .await?
@@ -6,7 +6,7 @@ use kona_protocol::L2BlockInfo;
 use kona_rpc::SequencerAdminQuery;
 use tokio::sync::watch;
 
-use crate::actors::sequencer::actor::SequencerActorState;
+use crate::actors::sequencer::{L1OriginSelectorProvider, actor::SequencerActorState};
 
 /// Error type for sequencer RPC operations
 #[derive(Debug, thiserror::Error)]
@@ -18,7 +18,7 @@ pub(super) enum SequencerRpcError {
     SendResponse,
 }
 
-impl<AB: AttributesBuilder> SequencerActorState<AB> {
+impl<AB: AttributesBuilder, P: L1OriginSelectorProvider> SequencerActorState<AB, P> {
     pub(super) async fn handle_admin_query(
         &mut self,
         query: SequencerAdminQuery,
@@ -0,0 +1,67 @@
+//! A message that carries the span it was sent from across actor channels.
+
+use std::ops::Deref;
+use tracing::Span;
+
+/// A value sent between [`NodeActor`]s, along with the [`Span`] it was sent from.
+///
+/// Spans are thread-local context, and are lost when a message crosses a channel into another
+/// actor's task. The receiving actor enters [`Traced::span`] (or uses it as a parent) while
+/// handling the value, so that work done on its behalf is attributed to the same trace.
+///
+/// [`NodeActor`]: crate::NodeActor
+#[derive(Debug, Clone)]
+pub struct Traced<T> {
+    value: T,
+    span: Span,
+}
+
+impl<T> Traced<T> {
+    /// Wraps the value in the current span.
+    pub fn new(value: T) -> Self {
+        Self::with_span(value, Span::current())
+    }
+
+    /// Wraps the value in the given span.
+    pub const fn with_span(value: T, span: Span) -> Self {
+        Self { value, span }
+    }
+
+    /// Returns the span the value was sent from.
+    pub const fn span(&self) -> &Span {
+        &self.span
+    }
+
+    /// Consumes the [`Traced`], returning the value and the span it was sent from.
+    pub fn into_parts(self) -> (T, Span) {
+        (self.value, self.span)
+    }
+}
+
+impl<T> Deref for Traced<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.value
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::sync::mpsc;
+
+    #[test]
+    fn test_traced_carries_span_across_channel() {
+        let _guard = tracing::subscriber::set_default(tracing_subscriber::registry());
+        let (tx, mut rx) = mpsc::channel(1);
+
+        let span = info_span!("sender");
+        span.in_scope(|| tx.try_send(Traced::new(1u64))).unwrap();
+
+        let (value, received) = rx.try_recv().unwrap().into_parts();
+        assert_eq!(value, 1);
+        assert!(span.id().is_some());
+        assert_eq!(received.id(), span.id());
+    }
+}
@@ -17,13 +17,15 @@ pub use actors::{
     AttributesBuilderConfig, CancellableContext, ConductorClient, ConductorError,
     DelayedL1OriginSelectorProvider, DerivationActor, DerivationBuilder, DerivationContext,
     DerivationError, DerivationInboundChannels, DerivationState, EngineActor, EngineBuilder,
-    EngineContext, EngineError, EngineInboundData, InboundDerivationMessage, L1OriginSelector,
-    L1OriginSelectorError, L1OriginSelectorProvider, L1WatcherRpc, L1WatcherRpcContext,
-    L1WatcherRpcError, L1WatcherRpcInboundChannels, L1WatcherRpcState, L2Finalizer, NetworkActor,
-    NetworkActorError, NetworkBuilder, NetworkBuilderError, NetworkConfig, NetworkContext,
-    NetworkDriver, NetworkDriverError, NetworkHandler, NetworkInboundData, NodeActor,
-    PipelineBuilder, RpcActor, RpcActorError, RpcContext, SequencerActor, SequencerActorError,
-    SequencerBuilder, SequencerConfig, SequencerContext, SequencerInboundData,
+    EngineClientBuilder, EngineContext, EngineError, EngineInboundData, FollowActor, FollowBuilder,
+    FollowContext, FollowError, FollowSource, FollowSourceClient, FollowUpdate,
+    InboundDerivationMessage, L1OriginSelector, L1OriginSelectorError, L1OriginSelectorProvider,
+    L1WatcherRpc, L1WatcherRpcContext, L1WatcherRpcError, L1WatcherRpcInboundChannels,
+    L1WatcherRpcState, L2Finalizer, NetworkActor, NetworkActorError, NetworkBuilder,
+    NetworkBuilderError, NetworkConfig, NetworkContext, NetworkDriver, NetworkDriverError,
+    NetworkHandler, NetworkInboundData, NodeActor, PipelineBuilder, RpcActor, RpcActorError,
+    RpcContext, SequencerActor, SequencerActorError, SequencerBuilder, SequencerConfig,
+    SequencerContext, SequencerInboundData, SignerRotationError, Traced,
 };
 
 mod metrics;
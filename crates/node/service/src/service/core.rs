@@ -1,7 +1,7 @@
 //! The core [`RollupNodeService`] trait
 use crate::{
-    AttributesBuilderConfig, DerivationContext, EngineContext, L1WatcherRpcContext, NetworkContext,
-    NodeActor, NodeMode, RpcContext, SequencerContext, SequencerInboundData,
+    AttributesBuilderConfig, DerivationContext, EngineContext, FollowContext, L1WatcherRpcContext,
+    NetworkContext, NodeActor, NodeMode, RpcContext, SequencerContext, SequencerInboundData,
     actors::{
         DerivationInboundChannels, EngineInboundData, L1WatcherRpcInboundChannels,
         NetworkInboundData, PipelineBuilder,
@@ -34,6 +34,15 @@ use tokio_util::sync::CancellationToken;
 /// the network over p2p gossip. The node also listens for L1 finalized block updates and finalizes
 /// `safe` blocks that it has derived when L1 finalized block updates are received.
 ///
+/// ## Follow-Source Mode
+///
+/// In follow-source mode, the node does not run the derivation pipeline. Instead, it polls a
+/// trusted rollup node for its safe and finalized heads, verifies their block hashes and output
+/// roots against the local execution layer, and promotes them in the engine. Unsafe blocks are
+/// still imported from p2p gossip, and blocks that are missing or were reorged out are fetched from
+/// the follow source. The node halts if its output root differs from the follow source for the
+/// same block.
+///
 /// ## Types
 ///
 /// - `DataAvailabilityWatcher`: The type of [`NodeActor`] to use for the DA watcher service.
@@ -80,6 +89,9 @@ pub trait RollupNodeService {
     /// The type of rpc actor to use for the service.
     type RpcActor: NodeActor<Error: Display, OutboundData = RpcContext, InboundData = ()>;
 
+    /// The type of follow actor to use for the service.
+    type FollowActor: NodeActor<Error: Display, OutboundData = FollowContext, InboundData = ()>;
+
     /// The mode of operation for the node.
     fn mode(&self) -> NodeMode;
 
@@ -101,6 +113,9 @@ pub trait RollupNodeService {
     /// Returns the sequencer builder for the node.
     fn sequencer_builder(&self) -> <Self::SequencerActor as NodeActor>::Builder;
 
+    /// Returns the follow builder for the node, if a follow source is configured.
+    fn follow_builder(&self) -> Option<<Self::FollowActor as NodeActor>::Builder>;
+
     /// Starts the rollup node service.
     async fn start(&self) -> Result<(), String> {
         // Create a global cancellation token for graceful shutdown of tasks.
@@ -130,6 +145,7 @@ pub trait RollupNodeService {
                 reset_request_tx,
                 inbound_queries_tx: engine_rpc,
                 finalized_l1_block_tx,
+                follow_update_tx,
             },
             engine,
         ) = Self::EngineActor::build(self.engine_builder());
@@ -154,6 +170,19 @@ pub trait RollupNodeService {
             .then_some(Self::SequencerActor::build(self.sequencer_builder()))
             .unzip();
 
+        // Create the follow actor, which replaces the derivation actor in follow-source mode.
+        let follow = if self.mode().is_follow_source() {
+            let builder = self
+                .follow_builder()
+                .ok_or_else(|| "Follow source not set while in follow-source mode".to_string())?;
+            let (_, follow) = Self::FollowActor::build(builder);
+            Some(follow)
+        } else {
+            None
+        };
+        let follow_engine_query_tx = engine_rpc.clone();
+        let follow_unsafe_block_tx = unsafe_block_tx.clone();
+
         spawn_and_wait!(
             cancellation,
             actors = [
@@ -193,7 +222,18 @@ pub trait RollupNodeService {
                         cancellation: cancellation.clone(),
                     })
                 ),
-                Some((
+                follow.map(|f| (
+                    f,
+                    FollowContext {
+                        engine_query_tx: follow_engine_query_tx,
+                        unsafe_block_tx: follow_unsafe_block_tx,
+                        follow_update_tx: follow_update_tx.expect(
+                            "`follow_update_tx` not set while in follow-source mode. This should never happen.",
+                        ),
+                        cancellation: cancellation.clone(),
+                    })
+                ),
+                (!self.mode().is_follow_source()).then_some((
                     derivation,
                     DerivationContext {
                         reset_request_tx: reset_request_tx.clone(),
@@ -22,6 +22,10 @@ pub enum NodeMode {
     /// Sequencer mode.
     #[display("Sequencer")]
     Sequencer,
+    /// Follow-source mode. The node does not derive the L2 chain from L1, but tracks the safe and
+    /// finalized heads of a trusted rollup node.
+    #[display("FollowSource")]
+    FollowSource,
 }
 
 impl NodeMode {
@@ -34,6 +38,11 @@ impl NodeMode {
     pub const fn is_sequencer(&self) -> bool {
         matches!(self, Self::Sequencer)
     }
+
+    /// Returns `true` if [`Self`] is [`Self::FollowSource`].
+    pub const fn is_follow_source(&self) -> bool {
+        matches!(self, Self::FollowSource)
+    }
 }
 
 /// The [`InteropMode`] enum represents how the node works with interop.
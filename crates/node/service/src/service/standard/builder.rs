@@ -16,7 +16,7 @@ use tower::ServiceBuilder;
 use url::Url;
 
 use kona_genesis::{L1ChainConfig, RollupConfig};
-use kona_providers_alloy::OnlineBeaconClient;
+use kona_providers_alloy::{BlobArchive, OnlineBeaconClient};
 use kona_rpc::RpcBuilder;
 
 /// The [`RollupNodeBuilder`] is used to construct a [`RollupNode`] service.
@@ -28,12 +28,18 @@ pub struct RollupNodeBuilder {
     l1_config: L1ChainConfig,
     /// The L1 EL provider RPC URL.
     l1_provider_rpc_url: Option<Url>,
+    /// The L1 EL WebSocket RPC URL.
+    l1_ws_rpc_url: Option<Url>,
     /// Whether to trust the L1 RPC.
     l1_trust_rpc: bool,
     /// The L1 beacon API URL.
     l1_beacon_api_url: Option<Url>,
+    /// The archives to fetch blobs from when they have expired from the L1 beacon API.
+    l1_blob_archives: Vec<Arc<dyn BlobArchive>>,
     /// The L2 engine RPC URL.
     l2_engine_rpc_url: Option<Url>,
+    /// The engine RPC URLs of the shadow L2 execution layers.
+    l2_shadow_engine_rpc_urls: Vec<Url>,
     /// Whether to trust the L2 RPC.
     l2_trust_rpc: bool,
     /// The JWT secret.
@@ -44,6 +50,10 @@ pub struct RollupNodeBuilder {
     rpc_config: Option<RpcBuilder>,
     /// The [`SequencerConfig`].
     sequencer_config: Option<SequencerConfig>,
+    /// The rollup node RPC URL of the trusted node to follow in follow-source mode.
+    follow_source_url: Option<Url>,
+    /// The execution layer RPC URL of the trusted node to follow in follow-source mode.
+    follow_source_l2_url: Option<Url>,
     /// The mode to run the node in.
     mode: NodeMode,
     /// Whether to run the node in interop mode.
@@ -66,6 +76,11 @@ impl RollupNodeBuilder {
         Self { l1_provider_rpc_url: Some(l1_provider_rpc_url), ..self }
     }
 
+    /// Sets the L1 EL WebSocket RPC URL, used to subscribe to new L1 heads instead of polling.
+    pub fn with_l1_ws_rpc_url(self, l1_ws_rpc_url: Url) -> Self {
+        Self { l1_ws_rpc_url: Some(l1_ws_rpc_url), ..self }
+    }
+
     /// Sets whether to trust the L1 RPC.
     pub fn with_l1_trust_rpc(self, l1_trust_rpc: bool) -> Self {
         Self { l1_trust_rpc, ..self }
@@ -76,11 +91,23 @@ impl RollupNodeBuilder {
         Self { l1_beacon_api_url: Some(l1_beacon_api_url), ..self }
     }
 
+    /// Appends a [`BlobArchive`] to fall back to when blobs have expired from the L1 beacon API.
+    /// Archives are tried in the order they are added.
+    pub fn with_l1_blob_archive(mut self, archive: Arc<dyn BlobArchive>) -> Self {
+        self.l1_blob_archives.push(archive);
+        self
+    }
+
     /// Appends an L2 engine RPC URL to the builder.
     pub fn with_l2_engine_rpc_url(self, l2_engine_rpc_url: Url) -> Self {
         Self { l2_engine_rpc_url: Some(l2_engine_rpc_url), ..self }
     }
 
+    /// Sets the engine RPC URLs of the shadow L2 execution layers.
+    pub fn with_l2_shadow_engine_rpc_urls(self, l2_shadow_engine_rpc_urls: Vec<Url>) -> Self {
+        Self { l2_shadow_engine_rpc_urls, ..self }
+    }
+
     /// Sets whether to trust the L2 RPC.
     pub fn with_l2_trust_rpc(self, l2_trust_rpc: bool) -> Self {
         Self { l2_trust_rpc, ..self }
@@ -106,6 +133,16 @@ impl RollupNodeBuilder {
         Self { sequencer_config: Some(sequencer_config), ..self }
     }
 
+    /// Sets the rollup node RPC URL of the trusted node to follow in follow-source mode.
+    pub fn with_follow_source_url(self, follow_source_url: Url) -> Self {
+        Self { follow_source_url: Some(follow_source_url), ..self }
+    }
+
+    /// Sets the execution layer RPC URL of the trusted node to follow in follow-source mode.
+    pub fn with_follow_source_l2_url(self, follow_source_l2_url: Url) -> Self {
+        Self { follow_source_l2_url: Some(follow_source_l2_url), ..self }
+    }
+
     /// Assembles the [`RollupNode`] service.
     ///
     /// ## Panics
@@ -142,6 +179,7 @@ impl RollupNodeBuilder {
             config: Arc::clone(&rollup_config),
             l1_rpc_url,
             engine_url,
+            shadow_engine_urls: self.l2_shadow_engine_rpc_urls,
             jwt_secret,
             mode: self.mode,
         };
@@ -154,14 +192,18 @@ impl RollupNodeBuilder {
             l1_config,
             interop_mode: self.interop_mode,
             l1_provider,
+            l1_ws_rpc_url: self.l1_ws_rpc_url,
             l1_trust_rpc: self.l1_trust_rpc,
             l1_beacon,
+            l1_blob_archives: self.l1_blob_archives,
             l2_provider,
             l2_trust_rpc: self.l2_trust_rpc,
             engine_builder,
             rpc_builder: self.rpc_config,
             p2p_config,
             sequencer_config,
+            follow_source_url: self.follow_source_url,
+            follow_source_l2_url: self.follow_source_l2_url,
         }
     }
 }
@@ -1,8 +1,8 @@
 //! Contains the [`RollupNode`] implementation.
 use crate::{
-    DerivationActor, DerivationBuilder, EngineActor, EngineBuilder, InteropMode, L1WatcherRpc,
-    L1WatcherRpcState, NetworkActor, NetworkBuilder, NetworkConfig, NodeMode, RollupNodeBuilder,
-    RollupNodeService, RpcActor, SequencerConfig,
+    DerivationActor, DerivationBuilder, EngineActor, EngineBuilder, FollowActor, FollowBuilder,
+    InteropMode, L1WatcherRpc, L1WatcherRpcState, NetworkActor, NetworkBuilder, NetworkConfig,
+    NodeMode, RollupNodeBuilder, RollupNodeService, RpcActor, SequencerConfig,
     actors::{SequencerActor, SequencerBuilder},
 };
 use alloy_provider::RootProvider;
@@ -10,10 +10,11 @@ use async_trait::async_trait;
 use kona_derive::StatefulAttributesBuilder;
 use op_alloy_network::Optimism;
 use std::sync::Arc;
+use url::Url;
 
 use kona_genesis::{L1ChainConfig, RollupConfig};
 use kona_providers_alloy::{
-    AlloyChainProvider, AlloyL2ChainProvider, OnlineBeaconClient, OnlinePipeline,
+    AlloyChainProvider, AlloyL2ChainProvider, BlobArchive, OnlineBeaconClient, OnlinePipeline,
 };
 use kona_rpc::RpcBuilder;
 
@@ -29,10 +30,14 @@ pub struct RollupNode {
     pub(crate) interop_mode: InteropMode,
     /// The L1 EL provider.
     pub(crate) l1_provider: RootProvider,
+    /// The WebSocket URL of the L1 EL, used to subscribe to new L1 heads.
+    pub(crate) l1_ws_rpc_url: Option<Url>,
     /// Whether to trust the L1 RPC.
     pub(crate) l1_trust_rpc: bool,
     /// The L1 beacon API.
     pub(crate) l1_beacon: OnlineBeaconClient,
+    /// The archives to fetch blobs from when they have expired from the L1 beacon API.
+    pub(crate) l1_blob_archives: Vec<Arc<dyn BlobArchive>>,
     /// The L2 EL provider.
     pub(crate) l2_provider: RootProvider<Optimism>,
     /// Whether to trust the L2 RPC.
@@ -45,6 +50,10 @@ pub struct RollupNode {
     pub(crate) p2p_config: NetworkConfig,
     /// The [`SequencerConfig`] for the node.
     pub(crate) sequencer_config: SequencerConfig,
+    /// The rollup node RPC URL of the trusted node to follow in follow-source mode.
+    pub(crate) follow_source_url: Option<Url>,
+    /// The execution layer RPC URL of the trusted node to follow in follow-source mode.
+    pub(crate) follow_source_l2_url: Option<Url>,
 }
 
 impl RollupNode {
@@ -65,15 +74,20 @@ impl RollupNodeService for RollupNode {
     type DerivationActor = DerivationActor<DerivationBuilder>;
 
     type RpcActor = RpcActor;
-    type EngineActor = EngineActor;
+    type EngineActor = EngineActor<EngineBuilder>;
     type NetworkActor = NetworkActor;
+    type FollowActor = FollowActor;
 
     fn mode(&self) -> NodeMode {
         self.engine_builder.mode
     }
 
     fn da_watcher_builder(&self) -> L1WatcherRpcState {
-        L1WatcherRpcState { rollup: self.config.clone(), l1_provider: self.l1_provider.clone() }
+        L1WatcherRpcState {
+            rollup: self.config.clone(),
+            l1_provider: self.l1_provider.clone(),
+            l1_ws_url: self.l1_ws_rpc_url.clone(),
+        }
     }
 
     fn engine_builder(&self) -> EngineBuilder {
@@ -96,6 +110,12 @@ impl RollupNodeService for RollupNode {
         self.rpc_builder.clone()
     }
 
+    fn follow_builder(&self) -> Option<FollowBuilder> {
+        let source_url = self.follow_source_url.clone()?;
+        let source_l2_url = self.follow_source_l2_url.clone()?;
+        Some(FollowBuilder::new(source_url, source_l2_url))
+    }
+
     fn network_builder(&self) -> NetworkBuilder {
         NetworkBuilder::from(self.p2p_config.clone())
     }
@@ -105,6 +125,7 @@ impl RollupNodeService for RollupNode {
             l1_provider: self.l1_provider.clone(),
             l1_trust_rpc: self.l1_trust_rpc,
             l1_beacon: self.l1_beacon.clone(),
+            l1_blob_archives: self.l1_blob_archives.clone(),
             l2_provider: self.l2_provider.clone(),
             l2_trust_rpc: self.l2_trust_rpc,
             rollup_config: self.config.clone(),
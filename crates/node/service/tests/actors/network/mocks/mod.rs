@@ -5,7 +5,7 @@ use std::{str::FromStr, time::Duration};
 use backon::{ExponentialBuilder, Retryable};
 use discv5::Enr;
 use kona_gossip::{P2pRpcRequest, PeerDump, PeerInfo};
-use kona_node_service::{NetworkActorError, NetworkInboundData};
+use kona_node_service::{NetworkActorError, NetworkInboundData, Traced};
 use op_alloy_rpc_types_engine::OpExecutionPayloadEnvelope;
 use tokio::{
     sync::{mpsc, oneshot},
@@ -16,7 +16,7 @@ pub(crate) mod builder;
 
 pub(crate) struct TestNetwork {
     pub(super) inbound_data: NetworkInboundData,
-    pub(super) blocks_rx: mpsc::Receiver<OpExecutionPayloadEnvelope>,
+    pub(super) blocks_rx: mpsc::Receiver<Traced<OpExecutionPayloadEnvelope>>,
     #[allow(dead_code)]
     handle: JoinHandle<Result<(), NetworkActorError>>,
 }
@@ -1,3 +1,10 @@
+use std::{path::PathBuf, time::Duration};
+
+use alloy_primitives::address;
+use kona_rpc::NetworkAdminQuery;
+use kona_sources::LocalKeystore;
+use tokio::sync::oneshot;
+
 use crate::actors::{
     generator::{block_builder::PayloadVersion, seed::SEED_GENERATOR_BUILDER},
     network::mocks::builder::TestNetworkBuilder,
@@ -76,3 +83,73 @@ async fn test_sequencer_network_propagation() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+/// Test that a rotated local signer only starts signing blocks once the unsafe block signer on L1
+/// is updated to its address, at the same time as gossip validation.
+#[tokio::test(flavor = "multi_thread")]
+async fn test_sequencer_signer_rotation() -> anyhow::Result<()> {
+    // The address of the key in the `kona-sources` keystore fixture.
+    const ROTATED_SIGNER: alloy_primitives::Address =
+        address!("f39Fd6e51aad88F6F4ce6aB8827279cffFb92266");
+    const TIMEOUT: Duration = Duration::from_secs(60);
+    const RETRY_INTERVAL: Duration = Duration::from_millis(500);
+
+    let testdata = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../sources/testdata");
+
+    let mut builder = TestNetworkBuilder::new().set_sequencer();
+
+    let sequencer_network = builder.build(vec![]);
+    let enr = sequencer_network.peer_enr().await?;
+
+    let mut validator_network = builder.build(vec![enr]);
+
+    validator_network.is_connected_to_with_retries(&sequencer_network).await?;
+
+    // Stage the rotated key.
+    let signer =
+        LocalKeystore::new(testdata.join("keystore.json"), testdata.join("keystore.password"))
+            .decrypt()?;
+    let (tx, rx) = oneshot::channel();
+    sequencer_network
+        .inbound_data
+        .admin_rpc
+        .send(NetworkAdminQuery::RotateSequencerKey { signer, sender: tx })
+        .await?;
+    assert_eq!(rx.await?.map_err(anyhow::Error::msg)?, ROTATED_SIGNER);
+
+    let mut seed_generator = SEED_GENERATOR_BUILDER.next_generator();
+
+    // Until L1 designates the rotated key, blocks are still signed with the previous one.
+    let envelope = seed_generator.random_valid_payload(PayloadVersion::V1)?;
+    sequencer_network.inbound_data.gossip_payload_tx.send(envelope.clone()).await?;
+
+    let block = tokio::time::timeout(TIMEOUT, validator_network.blocks_rx.recv())
+        .await?
+        .ok_or(anyhow::anyhow!("No block received"))?;
+    assert_eq!(block.execution_payload, envelope.execution_payload);
+
+    // Both networks observe the unsafe block signer update from L1.
+    sequencer_network.inbound_data.signer.send(ROTATED_SIGNER).await?;
+    validator_network.inbound_data.signer.send(ROTATED_SIGNER).await?;
+
+    // Blocks are now signed with the rotated key, and validated against it. Until both networks
+    // have applied the update, blocks may be signed with a different key than the one they are
+    // validated against and dropped, so payloads are published until one is received.
+    tokio::time::timeout(TIMEOUT, async {
+        loop {
+            let envelope = seed_generator.random_valid_payload(PayloadVersion::V1)?;
+            sequencer_network.inbound_data.gossip_payload_tx.send(envelope.clone()).await?;
+
+            match tokio::time::timeout(RETRY_INTERVAL, validator_network.blocks_rx.recv()).await {
+                Ok(Some(block)) if block.execution_payload == envelope.execution_payload => {
+                    return anyhow::Ok(());
+                }
+                Ok(None) => anyhow::bail!("No block received"),
+                _ => {}
+            }
+        }
+    })
+    .await??;
+
+    Ok(())
+}
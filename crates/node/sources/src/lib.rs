@@ -13,5 +13,6 @@ pub use sync::{L2ForkchoiceState, SyncStartError, find_starting_forkchoice};
 mod signer;
 pub use signer::{
     BlockSigner, BlockSignerError, BlockSignerHandler, BlockSignerStartError, CertificateError,
-    ClientCert, RemoteSigner, RemoteSignerError, RemoteSignerHandler, RemoteSignerStartError,
+    ClientCert, KeystoreError, LocalKeystore, RemoteSigner, RemoteSignerError, RemoteSignerHandler,
+    RemoteSignerStartError,
 };
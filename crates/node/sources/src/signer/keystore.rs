@@ -0,0 +1,83 @@
+//! Encrypted JSON keystores for local block signers.
+
+use alloy_signer_local::{LocalSignerError, PrivateKeySigner};
+use std::path::PathBuf;
+use thiserror::Error;
+
+/// An encrypted JSON keystore ([Web3 Secret Storage]) holding the private key of a local block
+/// signer, unlocked with a password read from a file.
+///
+/// Neither the private key nor the password is ever passed on the command line or through the
+/// environment.
+///
+/// [Web3 Secret Storage]: https://ethereum.org/en/developers/docs/data-structures-and-encoding/web3-secret-storage/
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LocalKeystore {
+    /// The path to the encrypted JSON keystore.
+    pub path: PathBuf,
+    /// The path to the file holding the keystore password. A trailing newline is ignored.
+    pub password_file: PathBuf,
+}
+
+/// Errors that can occur when unlocking a [`LocalKeystore`].
+#[derive(Debug, Error)]
+pub enum KeystoreError {
+    /// The password file could not be read.
+    #[error("Failed to read keystore password file {0}: {1}")]
+    PasswordFile(PathBuf, std::io::Error),
+    /// The keystore could not be read or decrypted with the password.
+    #[error("Failed to decrypt keystore {0}: {1}")]
+    Decrypt(PathBuf, LocalSignerError),
+}
+
+impl LocalKeystore {
+    /// Creates a new [`LocalKeystore`].
+    pub const fn new(path: PathBuf, password_file: PathBuf) -> Self {
+        Self { path, password_file }
+    }
+
+    /// Reads the password and decrypts the keystore into a [`PrivateKeySigner`].
+    pub fn decrypt(&self) -> Result<PrivateKeySigner, KeystoreError> {
+        let password = std::fs::read_to_string(&self.password_file)
+            .map_err(|e| KeystoreError::PasswordFile(self.password_file.clone(), e))?;
+        let password = password.trim_end_matches(['\n', '\r']);
+
+        PrivateKeySigner::decrypt_keystore(&self.path, password)
+            .map_err(|e| KeystoreError::Decrypt(self.path.clone(), e))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::b256;
+    use std::path::Path;
+
+    fn testdata(name: &str) -> PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR")).join("testdata").join(name)
+    }
+
+    #[test]
+    fn test_decrypt_keystore() {
+        let keystore = LocalKeystore::new(testdata("keystore.json"), testdata("keystore.password"));
+        let signer = keystore.decrypt().unwrap();
+
+        let expected = PrivateKeySigner::from_bytes(&b256!(
+            "ac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80"
+        ))
+        .unwrap();
+        assert_eq!(signer.address(), expected.address());
+    }
+
+    #[test]
+    fn test_decrypt_keystore_wrong_password() {
+        let keystore = LocalKeystore::new(testdata("keystore.json"), testdata("keystore.json"));
+        assert!(matches!(keystore.decrypt(), Err(KeystoreError::Decrypt(..))));
+    }
+
+    #[test]
+    fn test_decrypt_keystore_missing_password_file() {
+        let keystore = LocalKeystore::new(testdata("keystore.json"), testdata("missing"));
+        assert!(matches!(keystore.decrypt(), Err(KeystoreError::PasswordFile(..))));
+    }
+}
@@ -2,7 +2,8 @@
 //!
 //! We currently support two types of block signers:
 //!
-//! 1. A local block signer that is used to sign blocks with a locally available private key.
+//! 1. A local block signer that is used to sign blocks with a locally available private key. The
+//!    key can be loaded from an encrypted [`LocalKeystore`].
 //! 2. A remote block signer that is used to sign blocks with a remote private key.
 
 use alloy_primitives::{Address, ChainId};
@@ -11,6 +12,9 @@ use derive_more::From;
 use op_alloy_rpc_types_engine::PayloadHash;
 use std::fmt::Debug;
 
+mod keystore;
+pub use keystore::{KeystoreError, LocalKeystore};
+
 mod remote;
 pub use remote::{
     CertificateError, ClientCert, RemoteSigner, RemoteSignerError, RemoteSignerHandler,
@@ -12,6 +12,7 @@ use kona_genesis::RollupConfig;
 use kona_protocol::L2BlockInfo;
 use op_alloy_consensus::{OpBlock, OpTxEnvelope, OpTxType};
 use spin::RwLock;
+use tracing::Instrument;
 
 /// The Rollup Driver entrypoint.
 ///
@@ -226,7 +227,11 @@ where
                 }
             }
 
-            let mut attributes = match self.pipeline.produce_payload(tip_cursor.l2_safe_head).await
+            let mut attributes = match self
+                .pipeline
+                .produce_payload(tip_cursor.l2_safe_head)
+                .instrument(info_span!(target: "client", "derive"))
+                .await
             {
                 Ok(attrs) => attrs.take_inner(),
                 Err(PipelineErrorKind::Critical(PipelineError::EndOfSource)) => {
@@ -253,7 +258,12 @@ where
             };
 
             self.executor.update_safe_head(tip_cursor.l2_safe_head_header.clone());
-            let outcome = match self.executor.execute_payload(attributes.clone()).await {
+            let outcome = match self
+                .executor
+                .execute_payload(attributes.clone())
+                .instrument(info_span!(target: "client", "execute"))
+                .await
+            {
                 Ok(outcome) => outcome,
                 Err(e) => {
                     error!(target: "client", "Failed to execute L2 block: {}", e);
@@ -277,7 +287,12 @@ where
 
                         // Retry the execution.
                         self.executor.update_safe_head(tip_cursor.l2_safe_head_header.clone());
-                        match self.executor.execute_payload(attributes.clone()).await {
+                        match self
+                            .executor
+                            .execute_payload(attributes.clone())
+                            .instrument(info_span!(target: "client", "execute"))
+                            .await
+                        {
                             Ok(header) => header,
                             Err(e) => {
                                 error!(
@@ -47,6 +47,9 @@ pub enum ChannelError {
     /// Unexpected EOF.
     #[error("Unexpected EOF in channel read operation.")]
     UnexpectedEOF,
+    /// A message does not fit in a single frame.
+    #[error("Message of {0} bytes does not fit in a single frame.")]
+    MessageTooLarge(usize),
 }
 
 /// A [Result] type for the [ChannelError] enum.
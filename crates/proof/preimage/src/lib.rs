@@ -33,3 +33,11 @@ pub use traits::{
 mod native_channel;
 #[cfg(feature = "std")]
 pub use native_channel::{BidirectionalChannel, NativeChannel};
+
+#[cfg(feature = "net")]
+mod socket_channel;
+#[cfg(feature = "net")]
+pub use socket_channel::{
+    ChannelAddr, ChannelAddrParseError, SessionChannel, SocketChannel, SocketChannels,
+    SocketListener,
+};
@@ -0,0 +1,549 @@
+//! Socket-backed implementation of the [Channel] trait.
+//!
+//! A single Unix-domain or TCP stream carries both the hint and preimage channels of one
+//! client session. Each write is sent as a frame of the form `tag (1 byte) ++ len (4 bytes, BE)
+//! ++ payload`, where `tag` identifies the logical channel. Reads are served from a per-channel
+//! byte buffer, so frame boundaries do not need to line up with the reader's buffer sizes. Writes
+//! larger than `MAX_FRAME_LEN` bytes are split across several frames, and a session is closed if
+//! the peer sends a larger frame. A third, message-oriented [SessionChannel] carries data
+//! exchanged out of band of the hint and preimage channels, such as the inputs a client session
+//! proves.
+
+use crate::{
+    Channel,
+    errors::{ChannelError, ChannelResult},
+};
+use async_channel::{Receiver, Sender, unbounded};
+use async_trait::async_trait;
+use core::{fmt, str::FromStr};
+use std::{
+    io::{self, ErrorKind},
+    path::PathBuf,
+    sync::Arc,
+};
+use tokio::{
+    io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt},
+    net::{TcpListener, TcpStream, UnixListener, UnixStream},
+    sync::Mutex,
+};
+
+/// The frame tag of the hint channel.
+const HINT_TAG: u8 = 0x00;
+
+/// The frame tag of the preimage channel.
+const PREIMAGE_TAG: u8 = 0x01;
+
+/// The frame tag of the session channel.
+const SESSION_TAG: u8 = 0x02;
+
+/// The length of a frame header, in bytes.
+const FRAME_HEADER_LEN: usize = 5;
+
+/// The maximum length of a frame payload, in bytes.
+const MAX_FRAME_LEN: usize = 1 << 20;
+
+/// The address of a preimage socket, in the form `unix:<path>` or `tcp:<host>:<port>`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ChannelAddr {
+    /// A Unix-domain socket at the given path.
+    Unix(PathBuf),
+    /// A TCP socket at the given `host:port`.
+    Tcp(String),
+}
+
+impl fmt::Display for ChannelAddr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Unix(path) => write!(f, "unix:{}", path.display()),
+            Self::Tcp(addr) => write!(f, "tcp:{addr}"),
+        }
+    }
+}
+
+/// An error returned when parsing a [ChannelAddr].
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("Invalid channel address \"{0}\"; expected `unix:<path>` or `tcp:<host>:<port>`")]
+pub struct ChannelAddrParseError(String);
+
+impl FromStr for ChannelAddr {
+    type Err = ChannelAddrParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.split_once(':') {
+            Some(("unix", path)) if !path.is_empty() => Ok(Self::Unix(PathBuf::from(path))),
+            Some(("tcp", addr)) if addr.contains(':') => Ok(Self::Tcp(addr.to_string())),
+            _ => Err(ChannelAddrParseError(s.to_string())),
+        }
+    }
+}
+
+/// The hint and preimage channels of a single socket session.
+#[derive(Debug, Clone)]
+pub struct SocketChannels {
+    /// The hint channel.
+    pub hint: SocketChannel,
+    /// The preimage channel.
+    pub preimage: SocketChannel,
+    /// The session channel.
+    pub session: SessionChannel,
+}
+
+impl SocketChannels {
+    /// Connects to the preimage server listening at the given [ChannelAddr].
+    pub async fn connect(addr: &ChannelAddr) -> io::Result<Self> {
+        match addr {
+            ChannelAddr::Unix(path) => Ok(Self::from_stream(UnixStream::connect(path).await?)),
+            ChannelAddr::Tcp(addr) => {
+                let stream = TcpStream::connect(addr).await?;
+                stream.set_nodelay(true)?;
+                Ok(Self::from_stream(stream))
+            }
+        }
+    }
+
+    /// Creates a [SocketChannels] session over an established stream.
+    ///
+    /// Spawns the tasks that multiplex the channels onto the stream, and must therefore be called
+    /// from within a tokio runtime. The stream is shut down once every handle to all three
+    /// channels has been dropped.
+    pub fn from_stream<S>(stream: S) -> Self
+    where
+        S: AsyncRead + AsyncWrite + Send + 'static,
+    {
+        let (reader, writer) = tokio::io::split(stream);
+        let (outbound_tx, outbound_rx) = unbounded();
+        let (hint_tx, hint_rx) = unbounded();
+        let (preimage_tx, preimage_rx) = unbounded();
+        let (session_tx, session_rx) = unbounded();
+
+        tokio::spawn(write_frames(writer, outbound_rx));
+        tokio::spawn(read_frames(reader, hint_tx, preimage_tx, session_tx));
+
+        Self {
+            hint: SocketChannel::new(HINT_TAG, hint_rx, outbound_tx.clone()),
+            preimage: SocketChannel::new(PREIMAGE_TAG, preimage_rx, outbound_tx.clone()),
+            session: SessionChannel { inbound: session_rx, outbound: outbound_tx },
+        }
+    }
+}
+
+/// A listener that accepts [SocketChannels] sessions from remote clients.
+#[derive(Debug)]
+pub enum SocketListener {
+    /// A Unix-domain socket listener.
+    Unix(UnixListener),
+    /// A TCP socket listener.
+    Tcp(TcpListener),
+}
+
+impl SocketListener {
+    /// Binds a listener to the given [ChannelAddr].
+    ///
+    /// A stale Unix-domain socket left behind at the target path is removed before binding.
+    pub async fn bind(addr: &ChannelAddr) -> io::Result<Self> {
+        match addr {
+            ChannelAddr::Unix(path) => {
+                if let Ok(meta) = std::fs::symlink_metadata(path) {
+                    use std::os::unix::fs::FileTypeExt;
+                    if meta.file_type().is_socket() {
+                        std::fs::remove_file(path)?;
+                    }
+                }
+                Ok(Self::Unix(UnixListener::bind(path)?))
+            }
+            ChannelAddr::Tcp(addr) => Ok(Self::Tcp(TcpListener::bind(addr).await?)),
+        }
+    }
+
+    /// Returns the local address of the listener.
+    pub fn local_addr(&self) -> io::Result<ChannelAddr> {
+        match self {
+            Self::Unix(listener) => {
+                let addr = listener.local_addr()?;
+                let path = addr.as_pathname().ok_or_else(|| {
+                    io::Error::new(ErrorKind::AddrNotAvailable, "unnamed unix socket")
+                })?;
+                Ok(ChannelAddr::Unix(path.to_path_buf()))
+            }
+            Self::Tcp(listener) => Ok(ChannelAddr::Tcp(listener.local_addr()?.to_string())),
+        }
+    }
+
+    /// Waits for the next client to connect, returning its [SocketChannels] session.
+    pub async fn accept(&self) -> io::Result<SocketChannels> {
+        match self {
+            Self::Unix(listener) => {
+                let (stream, _) = listener.accept().await?;
+                Ok(SocketChannels::from_stream(stream))
+            }
+            Self::Tcp(listener) => {
+                let (stream, _) = listener.accept().await?;
+                stream.set_nodelay(true)?;
+                Ok(SocketChannels::from_stream(stream))
+            }
+        }
+    }
+}
+
+/// One logical channel of a [SocketChannels] session.
+#[derive(Debug, Clone)]
+pub struct SocketChannel {
+    /// The frame tag of the channel.
+    tag: u8,
+    /// The inbound byte stream of the channel.
+    inbound: Arc<Mutex<InboundBuffer>>,
+    /// The sender of outbound frames.
+    outbound: Sender<(u8, Vec<u8>)>,
+}
+
+impl SocketChannel {
+    /// Creates a new [SocketChannel].
+    fn new(tag: u8, inbound: Receiver<Vec<u8>>, outbound: Sender<(u8, Vec<u8>)>) -> Self {
+        Self {
+            tag,
+            inbound: Arc::new(Mutex::new(InboundBuffer {
+                frames: inbound,
+                buf: Vec::new(),
+                pos: 0,
+            })),
+            outbound,
+        }
+    }
+}
+
+/// The session channel of a [SocketChannels] session.
+///
+/// Unlike the hint and preimage channels, the session channel carries whole messages, each sent
+/// as a single frame.
+#[derive(Debug, Clone)]
+pub struct SessionChannel {
+    /// The inbound messages of the channel.
+    inbound: Receiver<Vec<u8>>,
+    /// The sender of outbound frames.
+    outbound: Sender<(u8, Vec<u8>)>,
+}
+
+impl SessionChannel {
+    /// Sends a message to the peer. The message must fit in a single frame.
+    pub async fn send(&self, message: Vec<u8>) -> ChannelResult<()> {
+        if message.len() > MAX_FRAME_LEN {
+            return Err(ChannelError::MessageTooLarge(message.len()));
+        }
+        self.outbound.send((SESSION_TAG, message)).await.map_err(|_| ChannelError::Closed)
+    }
+
+    /// Waits for the next message from the peer.
+    pub async fn recv(&self) -> ChannelResult<Vec<u8>> {
+        self.inbound.recv().await.map_err(|_| ChannelError::Closed)
+    }
+}
+
+/// The buffered inbound half of a [SocketChannel].
+#[derive(Debug)]
+struct InboundBuffer {
+    /// The receiver of inbound frame payloads.
+    frames: Receiver<Vec<u8>>,
+    /// The payload currently being consumed.
+    buf: Vec<u8>,
+    /// The read position within `buf`.
+    pos: usize,
+}
+
+impl InboundBuffer {
+    /// Copies buffered bytes into `out`, waiting for the next frame if the buffer is drained.
+    async fn fill(&mut self, out: &mut [u8]) -> ChannelResult<usize> {
+        while self.pos == self.buf.len() {
+            self.buf = self.frames.recv().await.map_err(|_| ChannelError::Closed)?;
+            self.pos = 0;
+        }
+
+        let len = out.len().min(self.buf.len() - self.pos);
+        out[..len].copy_from_slice(&self.buf[self.pos..self.pos + len]);
+        self.pos += len;
+        Ok(len)
+    }
+}
+
+#[async_trait]
+impl Channel for SocketChannel {
+    async fn read(&self, buf: &mut [u8]) -> ChannelResult<usize> {
+        if buf.is_empty() {
+            return Ok(0);
+        }
+        self.inbound.lock().await.fill(buf).await
+    }
+
+    async fn read_exact(&self, buf: &mut [u8]) -> ChannelResult<usize> {
+        let mut inbound = self.inbound.lock().await;
+        let mut read = 0;
+        while read < buf.len() {
+            match inbound.fill(&mut buf[read..]).await {
+                Ok(n) => read += n,
+                Err(_) if read > 0 => return Err(ChannelError::UnexpectedEOF),
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(read)
+    }
+
+    async fn write(&self, buf: &[u8]) -> ChannelResult<usize> {
+        for chunk in buf.chunks(MAX_FRAME_LEN) {
+            self.outbound
+                .send((self.tag, chunk.to_vec()))
+                .await
+                .map_err(|_| ChannelError::Closed)?;
+        }
+        Ok(buf.len())
+    }
+}
+
+/// Writes outbound frames to the stream until every channel handle has been dropped, then shuts
+/// down the write half of the stream.
+async fn write_frames<W>(mut writer: W, frames: Receiver<(u8, Vec<u8>)>)
+where
+    W: AsyncWrite + Unpin,
+{
+    while let Ok((tag, payload)) = frames.recv().await {
+        let mut header = [0u8; FRAME_HEADER_LEN];
+        header[0] = tag;
+        header[1..].copy_from_slice(&(payload.len() as u32).to_be_bytes());
+
+        let result = async {
+            writer.write_all(&header).await?;
+            writer.write_all(&payload).await?;
+            writer.flush().await
+        }
+        .await;
+        if let Err(e) = result {
+            debug!(target: "socket_channel", "Failed to write frame: {e}");
+            return;
+        }
+    }
+
+    let _ = writer.shutdown().await;
+}
+
+/// Reads inbound frames from the stream and routes them to their channel, until the stream is
+/// closed. Dropping the senders on exit closes all three channels.
+async fn read_frames<R>(
+    mut reader: R,
+    hint: Sender<Vec<u8>>,
+    preimage: Sender<Vec<u8>>,
+    session: Sender<Vec<u8>>,
+) where
+    R: AsyncRead + Unpin,
+{
+    loop {
+        let mut header = [0u8; FRAME_HEADER_LEN];
+        if let Err(e) = reader.read_exact(&mut header).await {
+            if e.kind() != ErrorKind::UnexpectedEof {
+                debug!(target: "socket_channel", "Failed to read frame header: {e}");
+            }
+            return;
+        }
+
+        let len = u32::from_be_bytes([header[1], header[2], header[3], header[4]]) as usize;
+        if len > MAX_FRAME_LEN {
+            warn!(
+                target: "socket_channel",
+                "Received frame of {len} bytes, exceeding the maximum of {MAX_FRAME_LEN} bytes"
+            );
+            return;
+        }
+        let mut payload = vec![0u8; len];
+        if let Err(e) = reader.read_exact(&mut payload).await {
+            debug!(target: "socket_channel", "Failed to read frame payload: {e}");
+            return;
+        }
+
+        let channel = match header[0] {
+            HINT_TAG => &hint,
+            PREIMAGE_TAG => &preimage,
+            SESSION_TAG => &session,
+            tag => {
+                warn!(target: "socket_channel", "Received frame with unknown tag {tag:#04x}");
+                return;
+            }
+        };
+        if channel.send(payload).await.is_err() {
+            return;
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        HintReader, HintReaderServer, HintRouter, HintWriter, HintWriterClient, OracleReader,
+        OracleServer, PreimageFetcher, PreimageKey, PreimageOracleClient, PreimageOracleServer,
+        errors::PreimageOracleResult,
+    };
+    use alloy_primitives::keccak256;
+    use std::collections::HashMap;
+
+    struct TestBackend {
+        preimages: HashMap<PreimageKey, Vec<u8>>,
+        hints: Mutex<Vec<String>>,
+    }
+
+    #[async_trait]
+    impl PreimageFetcher for TestBackend {
+        async fn get_preimage(&self, key: PreimageKey) -> PreimageOracleResult<Vec<u8>> {
+            self.preimages.get(&key).cloned().ok_or(crate::errors::PreimageOracleError::KeyNotFound)
+        }
+    }
+
+    #[async_trait]
+    impl HintRouter for TestBackend {
+        async fn route_hint(&self, hint: String) -> PreimageOracleResult<()> {
+            self.hints.lock().await.push(hint);
+            Ok(())
+        }
+    }
+
+    async fn serve(channels: SocketChannels, backend: Arc<TestBackend>) {
+        let oracle = OracleServer::new(channels.preimage);
+        let hints = HintReader::new(channels.hint);
+        let hint_backend = backend.clone();
+        tokio::spawn(async move { while hints.next_hint(hint_backend.as_ref()).await.is_ok() {} });
+        while oracle.next_preimage_request(backend.as_ref()).await.is_ok() {}
+    }
+
+    async fn round_trip(addr: ChannelAddr) {
+        let preimage = b"hello, world".to_vec();
+        let key = PreimageKey::new_keccak256(*keccak256(&preimage));
+        let backend = Arc::new(TestBackend {
+            preimages: HashMap::from([(key, preimage.clone())]),
+            hints: Mutex::new(Vec::new()),
+        });
+
+        let listener = SocketListener::bind(&addr).await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let server_backend = backend.clone();
+        let server = tokio::spawn(async move {
+            let channels = listener.accept().await.unwrap();
+            serve(channels, server_backend).await;
+        });
+
+        let channels = SocketChannels::connect(&addr).await.unwrap();
+        let oracle = OracleReader::new(channels.preimage);
+        let hints = HintWriter::new(channels.hint);
+
+        hints.write("test-hint 0xdeadbeef").await.unwrap();
+        assert_eq!(oracle.get(key).await.unwrap(), preimage);
+
+        let mut exact = vec![0u8; preimage.len()];
+        oracle.get_exact(key, &mut exact).await.unwrap();
+        assert_eq!(exact, preimage);
+
+        // Dropping the client session closes the stream, which ends the server session.
+        drop((oracle, hints));
+        server.await.unwrap();
+
+        assert_eq!(*backend.hints.lock().await, vec!["test-hint 0xdeadbeef".to_string()]);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_round_trip_tcp() {
+        round_trip(ChannelAddr::Tcp("127.0.0.1:0".to_string())).await;
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_round_trip_unix() {
+        let path = std::env::temp_dir().join(format!("kona-preimage-{}.sock", std::process::id()));
+        round_trip(ChannelAddr::Unix(path.clone())).await;
+        let _ = std::fs::remove_file(path);
+    }
+
+    #[tokio::test]
+    async fn test_reads_span_frames() {
+        let (a, b) = tokio::io::duplex(64);
+        let (a, b) = (SocketChannels::from_stream(a), SocketChannels::from_stream(b));
+
+        a.preimage.write(&[1, 2]).await.unwrap();
+        a.preimage.write(&[3, 4, 5]).await.unwrap();
+        a.hint.write(&[9]).await.unwrap();
+
+        let mut buf = [0u8; 4];
+        assert_eq!(b.preimage.read_exact(&mut buf).await.unwrap(), 4);
+        assert_eq!(buf, [1, 2, 3, 4]);
+        assert_eq!(b.preimage.read(&mut buf).await.unwrap(), 1);
+        assert_eq!(buf[0], 5);
+        assert_eq!(b.hint.read(&mut buf).await.unwrap(), 1);
+        assert_eq!(buf[0], 9);
+
+        drop(a);
+        assert!(matches!(b.preimage.read(&mut buf).await, Err(ChannelError::Closed)));
+    }
+
+    #[tokio::test]
+    async fn test_session_messages() {
+        let (a, b) = tokio::io::duplex(64);
+        let (a, b) = (SocketChannels::from_stream(a), SocketChannels::from_stream(b));
+
+        a.session.send(b"hello".to_vec()).await.unwrap();
+        a.preimage.write(&[1]).await.unwrap();
+        a.session.send(Vec::new()).await.unwrap();
+
+        // Messages keep their boundaries, and do not interleave with the other channels.
+        assert_eq!(b.session.recv().await.unwrap(), b"hello");
+        assert_eq!(b.session.recv().await.unwrap(), b"");
+        let mut buf = [0u8; 1];
+        assert_eq!(b.preimage.read(&mut buf).await.unwrap(), 1);
+
+        let oversized = vec![0u8; MAX_FRAME_LEN + 1];
+        assert!(matches!(
+            a.session.send(oversized).await,
+            Err(ChannelError::MessageTooLarge(len)) if len == MAX_FRAME_LEN + 1
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_large_writes_span_frames() {
+        let (a, b) = tokio::io::duplex(1 << 16);
+        let (a, b) = (SocketChannels::from_stream(a), SocketChannels::from_stream(b));
+
+        let payload = (0..MAX_FRAME_LEN * 2 + 1).map(|i| i as u8).collect::<Vec<_>>();
+        assert_eq!(a.preimage.write(&payload).await.unwrap(), payload.len());
+
+        let mut buf = vec![0u8; payload.len()];
+        assert_eq!(b.preimage.read_exact(&mut buf).await.unwrap(), payload.len());
+        assert_eq!(buf, payload);
+    }
+
+    #[tokio::test]
+    async fn test_oversized_frame_closes_session() {
+        let (mut a, b) = tokio::io::duplex(64);
+        let b = SocketChannels::from_stream(b);
+
+        let mut header = [PREIMAGE_TAG; FRAME_HEADER_LEN];
+        header[1..].copy_from_slice(&(MAX_FRAME_LEN as u32 + 1).to_be_bytes());
+        a.write_all(&header).await.unwrap();
+
+        // The session is closed without waiting for the payload, while the stream is still open.
+        let mut buf = [0u8; 1];
+        assert!(matches!(b.preimage.read(&mut buf).await, Err(ChannelError::Closed)));
+        drop(a);
+    }
+
+    #[test]
+    fn test_parse_channel_addr() {
+        assert_eq!(
+            "unix:/tmp/kona.sock".parse::<ChannelAddr>().unwrap(),
+            ChannelAddr::Unix(PathBuf::from("/tmp/kona.sock"))
+        );
+        assert_eq!(
+            "tcp:127.0.0.1:7300".parse::<ChannelAddr>().unwrap(),
+            ChannelAddr::Tcp("127.0.0.1:7300".to_string())
+        );
+        assert!("127.0.0.1:7300".parse::<ChannelAddr>().is_err());
+        assert!("tcp:localhost".parse::<ChannelAddr>().is_err());
+        assert!("unix:".parse::<ChannelAddr>().is_err());
+
+        for addr in ["unix:/tmp/kona.sock", "tcp:127.0.0.1:7300"] {
+            assert_eq!(addr.parse::<ChannelAddr>().unwrap().to_string(), addr);
+        }
+    }
+}
@@ -5,9 +5,10 @@
 //! context about failures during proof generation and data retrieval.
 
 use alloc::string::{String, ToString};
+use alloy_primitives::Address;
 use kona_derive::{PipelineError, PipelineErrorKind};
 use kona_mpt::{OrderedListWalkerError, TrieNodeError};
-use kona_preimage::errors::PreimageOracleError;
+use kona_preimage::{PreimageKey, PreimageKeyType, errors::PreimageOracleError};
 use kona_protocol::{FromBlockError, OpBlockConversionError};
 use thiserror::Error;
 
@@ -135,3 +136,45 @@ impl From<OracleProviderError> for PipelineErrorKind {
 #[derive(Error, Debug)]
 #[error("Hint parsing error: {_0}")]
 pub struct HintParsingError(pub String);
+
+/// Error verifying a preimage served by the [`crate::WitnessOracle`].
+///
+/// [`WitnessOracleError`] occurs when a preimage requested from a witness is missing, or when its
+/// value can not be verified against its key. Only [`PreimageKeyType::Local`] preimages are
+/// trusted as-is; every other preimage must be proven by its key.
+#[derive(Error, Debug)]
+pub enum WitnessOracleError {
+    /// The preimage is not present in the witness.
+    #[error("Preimage for key {0} not found in witness")]
+    Missing(PreimageKey),
+    /// The preimage does not hash to its keccak256 or sha256 key.
+    #[error("Preimage for key {0} does not match its digest")]
+    DigestMismatch(PreimageKey),
+    /// The precompile result differs from the result of re-executing the precompile.
+    #[error("Precompile result for key {0} does not match re-execution")]
+    PrecompileMismatch(PreimageKey),
+    /// The precompile is not accelerated by the client program under any of the verified specs.
+    #[error("Precompile {1} for key {0} is not accelerated by the client program")]
+    NotAccelerated(PreimageKey, Address),
+    /// The blob reconstructed from the witness does not match its KZG commitment.
+    #[error("Blob element for key {0} does not match its KZG commitment")]
+    BlobMismatch(PreimageKey),
+    /// The preimage is malformed for its key type.
+    #[error("Malformed preimage for key {0}: {1}")]
+    Malformed(PreimageKey, &'static str),
+    /// Blob preimages can not be verified without KZG support.
+    #[error("Blob preimages can only be verified with the `kzg` feature enabled")]
+    KzgUnavailable,
+    /// The key type can not be verified.
+    #[error("Unsupported preimage key type: {0:?}")]
+    UnsupportedKeyType(PreimageKeyType),
+}
+
+impl From<WitnessOracleError> for PreimageOracleError {
+    fn from(err: WitnessOracleError) -> Self {
+        match err {
+            WitnessOracleError::Missing(_) => Self::KeyNotFound,
+            _ => Self::Other(err.to_string()),
+        }
+    }
+}
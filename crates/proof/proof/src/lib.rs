@@ -31,6 +31,9 @@ pub use boot::BootInfo;
 mod caching_oracle;
 pub use caching_oracle::{CachingOracle, FlushableCache};
 
+mod witness_oracle;
+pub use witness_oracle::WitnessOracle;
+
 mod blocking_runtime;
 pub use blocking_runtime::block_on;
 
@@ -0,0 +1,468 @@
+//! Contains the [WitnessOracle], a self-verifying [PreimageOracleClient] and [HintWriterClient]
+//! backed by an in-memory witness.
+
+use crate::errors::WitnessOracleError;
+use alloc::{
+    boxed::Box,
+    collections::{BTreeMap, BTreeSet},
+    sync::Arc,
+    vec,
+    vec::Vec,
+};
+use alloy_evm::revm::precompile::{Precompiles, bls12_381_const, bn254, u64_to_address};
+use alloy_primitives::{Address, Bytes, keccak256};
+use async_trait::async_trait;
+use kona_preimage::{
+    HintWriterClient, PreimageKey, PreimageKeyType, PreimageOracleClient,
+    errors::{PreimageOracleError, PreimageOracleResult},
+};
+use op_revm::{
+    OpSpecId,
+    precompiles::{fjord, granite, isthmus},
+};
+use sha2::{Digest, Sha256};
+use spin::Mutex;
+
+/// The precompiles accelerated by the client program from bedrock onwards.
+const ACCELERATED_BEDROCK: &[Address] = &[u64_to_address(0x01), bn254::pair::ADDRESS];
+
+/// The precompiles accelerated by the client program from ecotone onwards.
+const ACCELERATED_ECOTONE: &[Address] =
+    &[u64_to_address(0x01), bn254::pair::ADDRESS, u64_to_address(0x0A)];
+
+/// The precompiles accelerated by the client program from isthmus onwards.
+const ACCELERATED_ISTHMUS: &[Address] = &[
+    u64_to_address(0x01),
+    bn254::pair::ADDRESS,
+    u64_to_address(0x0A),
+    bls12_381_const::G1_ADD_ADDRESS,
+    bls12_381_const::G1_MSM_ADDRESS,
+    bls12_381_const::G2_ADD_ADDRESS,
+    bls12_381_const::G2_MSM_ADDRESS,
+    bls12_381_const::MAP_FP_TO_G1_ADDRESS,
+    bls12_381_const::MAP_FP2_TO_G2_ADDRESS,
+    bls12_381_const::PAIRING_ADDRESS,
+];
+
+/// Returns the addresses of the precompiles that the client program accelerates through the
+/// preimage oracle under the given spec, and the precompiles it executes with under that spec.
+///
+/// This mirrors the client program's `OpFpvmPrecompiles`.
+fn accelerated_precompiles(spec: OpSpecId) -> (&'static [Address], &'static Precompiles) {
+    let precompiles = match spec {
+        spec @ (OpSpecId::BEDROCK | OpSpecId::REGOLITH | OpSpecId::CANYON | OpSpecId::ECOTONE) => {
+            Precompiles::new(spec.into_eth_spec().into())
+        }
+        OpSpecId::FJORD => fjord(),
+        OpSpecId::GRANITE | OpSpecId::HOLOCENE => granite(),
+        OpSpecId::ISTHMUS |
+        OpSpecId::INTEROP |
+        OpSpecId::OSAKA |
+        OpSpecId::JOVIAN |
+        OpSpecId::ARSIA => isthmus(),
+    };
+    let accelerated = match spec {
+        OpSpecId::BEDROCK | OpSpecId::REGOLITH | OpSpecId::CANYON => ACCELERATED_BEDROCK,
+        OpSpecId::ECOTONE | OpSpecId::FJORD | OpSpecId::GRANITE | OpSpecId::HOLOCENE => {
+            ACCELERATED_ECOTONE
+        }
+        OpSpecId::ISTHMUS |
+        OpSpecId::INTEROP |
+        OpSpecId::OSAKA |
+        OpSpecId::JOVIAN |
+        OpSpecId::ARSIA => ACCELERATED_ISTHMUS,
+    };
+    (accelerated, precompiles)
+}
+
+/// A [PreimageOracleClient] and [HintWriterClient] that serves preimages from a witness supplied
+/// up front, such as inside of a zkVM or TEE where no host is available to answer hints.
+///
+/// Hints are ignored. Before a preimage is returned, it is verified against its key:
+/// - [PreimageKeyType::Keccak256] and [PreimageKeyType::Sha256] preimages must hash to their key.
+/// - [PreimageKeyType::Precompile] results must match the result of re-executing the precompile on
+///   the input committed to by the key. Only the precompiles that the client program accelerates
+///   under one of the specs set with [WitnessOracle::with_specs] are re-executed, with the
+///   precompiles of that spec.
+/// - [PreimageKeyType::Blob] field elements must belong to a blob whose KZG commitment matches the
+///   commitment committed to by the key. This requires the `kzg` feature.
+/// - [PreimageKeyType::Local] preimages are trusted as-is.
+///
+/// A preimage that can not be verified is never returned, so a client program driven by a
+/// [WitnessOracle] either runs against correct data or fails.
+#[derive(Debug, Clone, Default)]
+pub struct WitnessOracle {
+    /// The witness, mapping preimage keys to their preimages.
+    witness: Arc<BTreeMap<PreimageKey, Vec<u8>>>,
+    /// The keys whose preimages have already been verified.
+    verified: Arc<Mutex<BTreeSet<PreimageKey>>>,
+    /// The specs that the client program executes blocks with.
+    specs: Vec<OpSpecId>,
+}
+
+impl WitnessOracle {
+    /// Creates a new [WitnessOracle] serving preimages from the given witness.
+    pub fn new(witness: BTreeMap<PreimageKey, Vec<u8>>) -> Self {
+        Self { witness: Arc::new(witness), verified: Default::default(), specs: Vec::new() }
+    }
+
+    /// Sets the specs that the client program executes blocks with, which determine the
+    /// precompiles whose results can be verified.
+    pub fn with_specs(mut self, specs: impl IntoIterator<Item = OpSpecId>) -> Self {
+        self.specs = specs.into_iter().collect();
+        self
+    }
+
+    /// Returns the number of preimages in the witness.
+    pub fn len(&self) -> usize {
+        self.witness.len()
+    }
+
+    /// Returns `true` if the witness contains no preimages.
+    pub fn is_empty(&self) -> bool {
+        self.witness.is_empty()
+    }
+
+    /// Verifies every preimage in the witness against its key.
+    pub fn verify_all(&self) -> Result<(), WitnessOracleError> {
+        self.witness.keys().try_for_each(|key| self.verify(*key).map(|_| ()))
+    }
+
+    /// Returns the preimage for the given key, after verifying it against the key.
+    pub fn verify(&self, key: PreimageKey) -> Result<&[u8], WitnessOracleError> {
+        let value = self.lookup(key)?;
+        if self.verified.lock().contains(&key) {
+            return Ok(value);
+        }
+
+        match key.key_type() {
+            PreimageKeyType::Local => {}
+            PreimageKeyType::Keccak256 => {
+                if PreimageKey::new_keccak256(*keccak256(value)) != key {
+                    return Err(WitnessOracleError::DigestMismatch(key));
+                }
+            }
+            PreimageKeyType::Sha256 => {
+                if PreimageKey::new(Sha256::digest(value).into(), PreimageKeyType::Sha256) != key {
+                    return Err(WitnessOracleError::DigestMismatch(key));
+                }
+            }
+            PreimageKeyType::Precompile => self.verify_precompile(key, value)?,
+            PreimageKeyType::Blob => self.verify_blob(key)?,
+            key_type @ PreimageKeyType::GlobalGeneric => {
+                return Err(WitnessOracleError::UnsupportedKeyType(key_type));
+            }
+        }
+
+        self.verified.lock().insert(key);
+        Ok(value)
+    }
+
+    /// Returns the unverified preimage for the given key.
+    fn lookup(&self, key: PreimageKey) -> Result<&[u8], WitnessOracleError> {
+        self.witness.get(&key).map(Vec::as_slice).ok_or(WitnessOracleError::Missing(key))
+    }
+
+    /// Returns the verified keccak256 preimage of the digest that a precompile or blob key was
+    /// derived from.
+    fn committed_input(&self, key: PreimageKey) -> Result<&[u8], WitnessOracleError> {
+        let digest: [u8; 32] = key.into();
+        self.verify(PreimageKey::new_keccak256(digest))
+    }
+
+    /// Verifies a precompile result by re-executing the precompile on the input committed to by
+    /// the key, which is laid out as `address ++ gas ++ input`.
+    ///
+    /// The result is accepted if it matches the re-execution under any of the configured specs
+    /// that accelerate the precompile.
+    fn verify_precompile(&self, key: PreimageKey, result: &[u8]) -> Result<(), WitnessOracleError> {
+        let input = self.committed_input(key)?;
+        if input.len() < 28 {
+            return Err(WitnessOracleError::Malformed(key, "precompile input too short"));
+        }
+
+        let address = Address::from_slice(&input[..20]);
+        let gas = u64::from_be_bytes(input[20..28].try_into().expect("slice is 8 bytes"));
+        let precompile_input = Bytes::copy_from_slice(&input[28..]);
+
+        let mut accelerated = false;
+        for spec in &self.specs {
+            let (addresses, precompiles) = accelerated_precompiles(*spec);
+            if !addresses.contains(&address) {
+                continue;
+            }
+            accelerated = true;
+
+            let output = precompiles
+                .get(&address)
+                .and_then(|precompile| precompile.execute(&precompile_input, gas).ok());
+            // The result is prefixed with a status byte, which is `0` if the precompile failed.
+            let expected = output.map_or_else(
+                || vec![0u8; 1],
+                |output| [[0x01].as_slice(), output.bytes.as_ref()].concat(),
+            );
+            if result == expected.as_slice() {
+                return Ok(());
+            }
+        }
+
+        if !accelerated {
+            return Err(WitnessOracleError::NotAccelerated(key, address));
+        }
+        Err(WitnessOracleError::PrecompileMismatch(key))
+    }
+
+    /// Verifies a blob field element by reconstructing the whole blob from the witness and checking
+    /// it against the KZG commitment committed to by the key. All elements of the blob are marked
+    /// as verified at once.
+    #[cfg(feature = "kzg")]
+    fn verify_blob(&self, key: PreimageKey) -> Result<(), WitnessOracleError> {
+        use crate::l1::ROOTS_OF_UNITY;
+        use alloy_eips::eip4844::{
+            BYTES_PER_BLOB, FIELD_ELEMENTS_PER_BLOB, env_settings::EnvKzgSettings,
+        };
+        use ark_ff::{BigInteger, PrimeField};
+
+        let blob_key = self.committed_input(key)?;
+        if blob_key.len() != 80 {
+            return Err(WitnessOracleError::Malformed(key, "blob key must be 80 bytes"));
+        }
+
+        // Reconstruct the blob from its field elements, keyed by `commitment ++ root of unity`.
+        let mut blob = Vec::with_capacity(BYTES_PER_BLOB);
+        let mut element_keys = Vec::with_capacity(FIELD_ELEMENTS_PER_BLOB as usize + 1);
+        let mut field_element_key = [0u8; 80];
+        field_element_key[..48].copy_from_slice(&blob_key[..48]);
+        for root in ROOTS_OF_UNITY.iter() {
+            field_element_key[48..].copy_from_slice(root.into_bigint().to_bytes_be().as_ref());
+            let element_key =
+                PreimageKey::new(*keccak256(field_element_key), PreimageKeyType::Blob);
+            let element = self.lookup(element_key)?;
+            if element.len() != 32 {
+                return Err(WitnessOracleError::Malformed(
+                    element_key,
+                    "field element must be 32 bytes",
+                ));
+            }
+            blob.extend_from_slice(element);
+            element_keys.push(element_key);
+        }
+
+        let kzg_settings = EnvKzgSettings::Default;
+        let kzg_blob = c_kzg::Blob::from_bytes(&blob)
+            .map_err(|_| WitnessOracleError::Malformed(key, "invalid blob"))?;
+        let commitment = kzg_settings
+            .get()
+            .blob_to_kzg_commitment(&kzg_blob)
+            .map_err(|_| WitnessOracleError::Malformed(key, "invalid blob"))?;
+        if blob_key[..48] != *commitment.as_slice() {
+            return Err(WitnessOracleError::BlobMismatch(key));
+        }
+
+        // The blob's KZG proof is stored as an extra element, keyed by the index 4096 written over
+        // the tail of the last root of unity.
+        field_element_key[72..].copy_from_slice(FIELD_ELEMENTS_PER_BLOB.to_be_bytes().as_ref());
+        let proof_key = PreimageKey::new(*keccak256(field_element_key), PreimageKeyType::Blob);
+        if let Ok(proof) = self.lookup(proof_key) {
+            let proof = <[u8; 48]>::try_from(proof)
+                .map_err(|_| WitnessOracleError::Malformed(proof_key, "proof must be 48 bytes"))?;
+            let commitment = c_kzg::Bytes48::new(commitment.to_bytes().into_inner());
+            let valid = kzg_settings
+                .get()
+                .verify_blob_kzg_proof(&kzg_blob, &commitment, &c_kzg::Bytes48::new(proof))
+                .unwrap_or(false);
+            if !valid {
+                return Err(WitnessOracleError::BlobMismatch(proof_key));
+            }
+            element_keys.push(proof_key);
+        }
+
+        if !element_keys.contains(&key) {
+            return Err(WitnessOracleError::Malformed(key, "not a blob field element"));
+        }
+        self.verified.lock().extend(element_keys);
+        Ok(())
+    }
+
+    /// Blob field elements can not be verified without KZG support.
+    #[cfg(not(feature = "kzg"))]
+    const fn verify_blob(&self, _: PreimageKey) -> Result<(), WitnessOracleError> {
+        Err(WitnessOracleError::KzgUnavailable)
+    }
+}
+
+#[async_trait]
+impl PreimageOracleClient for WitnessOracle {
+    async fn get(&self, key: PreimageKey) -> PreimageOracleResult<Vec<u8>> {
+        Ok(self.verify(key)?.to_vec())
+    }
+
+    async fn get_exact(&self, key: PreimageKey, buf: &mut [u8]) -> PreimageOracleResult<()> {
+        let value = self.verify(key)?;
+        if value.len() != buf.len() {
+            return Err(PreimageOracleError::BufferLengthMismatch(value.len(), buf.len()));
+        }
+        buf.copy_from_slice(value);
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl HintWriterClient for WitnessOracle {
+    /// Hints are ignored, as the witness already contains every preimage.
+    async fn write(&self, _: &str) -> PreimageOracleResult<()> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use alloy_primitives::B256;
+
+    fn keccak_entry(value: &[u8]) -> (PreimageKey, Vec<u8>) {
+        (PreimageKey::new_keccak256(*keccak256(value)), value.to_vec())
+    }
+
+    #[tokio::test]
+    async fn test_verified_preimages() {
+        let local = (PreimageKey::new_local(1), B256::repeat_byte(0xaa).to_vec());
+        let keccak = keccak_entry(b"hello");
+        let sha = (
+            PreimageKey::new(Sha256::digest(b"world").into(), PreimageKeyType::Sha256),
+            b"world".to_vec(),
+        );
+        let oracle = WitnessOracle::new([local.clone(), keccak.clone(), sha.clone()].into());
+
+        for (key, value) in [local, keccak, sha] {
+            assert_eq!(oracle.get(key).await.unwrap(), value);
+            let mut buf = vec![0u8; value.len()];
+            oracle.get_exact(key, &mut buf).await.unwrap();
+            assert_eq!(buf, value);
+        }
+        oracle.verify_all().unwrap();
+
+        // Hints are accepted and ignored.
+        oracle.write("l1-block-header 0x00").await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_rejects_tampered_preimages() {
+        let (keccak_key, _) = keccak_entry(b"hello");
+        let sha_key = PreimageKey::new(Sha256::digest(b"world").into(), PreimageKeyType::Sha256);
+        let oracle = WitnessOracle::new(
+            [(keccak_key, b"jello".to_vec()), (sha_key, b"word".to_vec())].into(),
+        );
+
+        assert!(matches!(
+            oracle.verify(keccak_key),
+            Err(WitnessOracleError::DigestMismatch(key)) if key == keccak_key
+        ));
+        assert!(matches!(oracle.verify(sha_key), Err(WitnessOracleError::DigestMismatch(_))));
+        assert!(oracle.verify_all().is_err());
+        assert!(matches!(
+            oracle.get(PreimageKey::new_local(0)).await,
+            Err(PreimageOracleError::KeyNotFound)
+        ));
+    }
+
+    /// Returns the witness entry of the committed input of a precompile call, and its key.
+    fn precompile_call(address: Address, input: &[u8]) -> ((PreimageKey, Vec<u8>), PreimageKey) {
+        let mut call = address.to_vec();
+        call.extend_from_slice(&100_000u64.to_be_bytes());
+        call.extend_from_slice(input);
+        let entry = keccak_entry(&call);
+        let key = PreimageKey::new(entry.0.into(), PreimageKeyType::Precompile);
+        (entry, key)
+    }
+
+    #[tokio::test]
+    async fn test_verifies_precompile_results() {
+        // ecrecover returns no output for a signature that does not recover.
+        let (input, key) = precompile_call(u64_to_address(0x01), &[0u8; 128]);
+        let result = vec![0x01];
+        let oracle = WitnessOracle::new([input.clone(), (key, result.clone())].into())
+            .with_specs([OpSpecId::BEDROCK]);
+        assert_eq!(oracle.get(key).await.unwrap(), result);
+
+        let forged = [[0x01].as_slice(), B256::repeat_byte(0xaa).as_slice()].concat();
+        let oracle = WitnessOracle::new([input.clone(), (key, forged)].into())
+            .with_specs([OpSpecId::BEDROCK]);
+        assert!(matches!(oracle.verify(key), Err(WitnessOracleError::PrecompileMismatch(_))));
+
+        // Without the committed input, the result can not be verified.
+        let oracle = WitnessOracle::new([(key, result)].into()).with_specs([OpSpecId::BEDROCK]);
+        assert!(matches!(oracle.verify(key), Err(WitnessOracleError::Missing(k)) if k == input.0));
+    }
+
+    #[test]
+    fn test_rejects_unaccelerated_precompiles() {
+        // The identity precompile is never accelerated, so its results are never served.
+        let identity = u64_to_address(0x04);
+        let (input, key) = precompile_call(identity, b"identity");
+        let result = [[0x01].as_slice(), b"identity"].concat();
+        let oracle = WitnessOracle::new([input, (key, result)].into())
+            .with_specs([OpSpecId::BEDROCK, OpSpecId::ISTHMUS]);
+        assert!(matches!(
+            oracle.verify(key),
+            Err(WitnessOracleError::NotAccelerated(_, address)) if address == identity
+        ));
+
+        // BLS12-381 precompiles are only accelerated from isthmus onwards. Adding two points at
+        // infinity results in the point at infinity.
+        let (input, key) = precompile_call(bls12_381_const::G1_ADD_ADDRESS, &[0u8; 256]);
+        let witness: BTreeMap<_, _> =
+            [input, (key, [[0x01].as_slice(), [0u8; 128].as_slice()].concat())].into();
+        let oracle = WitnessOracle::new(witness.clone()).with_specs([OpSpecId::GRANITE]);
+        assert!(matches!(oracle.verify(key), Err(WitnessOracleError::NotAccelerated(..))));
+        let oracle = WitnessOracle::new(witness).with_specs([OpSpecId::GRANITE, OpSpecId::ISTHMUS]);
+        oracle.verify(key).unwrap();
+    }
+
+    #[cfg(not(feature = "kzg"))]
+    #[test]
+    fn test_blob_requires_kzg() {
+        let key = PreimageKey::new([0u8; 32], PreimageKeyType::Blob);
+        let oracle = WitnessOracle::new([(key, vec![0u8; 32])].into());
+        assert!(matches!(oracle.verify(key), Err(WitnessOracleError::KzgUnavailable)));
+    }
+
+    #[cfg(feature = "kzg")]
+    #[test]
+    fn test_verifies_blob_elements() {
+        use crate::l1::ROOTS_OF_UNITY;
+        use alloy_eips::eip4844::env_settings::EnvKzgSettings;
+        use ark_ff::{BigInteger, PrimeField};
+
+        // Construct a blob whose field elements are `i`, and its commitment.
+        let mut blob = vec![0u8; c_kzg::BYTES_PER_BLOB];
+        for (i, element) in blob.chunks_mut(32).enumerate() {
+            element[30..].copy_from_slice(&(i as u16).to_be_bytes());
+        }
+        let kzg_blob = c_kzg::Blob::from_bytes(&blob).unwrap();
+        let commitment = EnvKzgSettings::Default.get().blob_to_kzg_commitment(&kzg_blob).unwrap();
+
+        let mut witness = BTreeMap::new();
+        let mut element_key = [0u8; 80];
+        element_key[..48].copy_from_slice(commitment.as_slice());
+        for (i, root) in ROOTS_OF_UNITY.iter().enumerate() {
+            element_key[48..].copy_from_slice(root.into_bigint().to_bytes_be().as_ref());
+            let (keccak_key, preimage) = keccak_entry(&element_key);
+            witness.insert(keccak_key, preimage);
+            witness.insert(
+                PreimageKey::new(keccak_key.into(), PreimageKeyType::Blob),
+                blob[i * 32..(i + 1) * 32].to_vec(),
+            );
+        }
+        let last_key = PreimageKey::new(*keccak256(element_key), PreimageKeyType::Blob);
+
+        let oracle = WitnessOracle::new(witness.clone());
+        assert_eq!(oracle.verify(last_key).unwrap(), &blob[blob.len() - 32..]);
+        oracle.verify_all().unwrap();
+
+        // Tampering with any element invalidates the blob.
+        witness.insert(last_key, vec![0u8; 32]);
+        let oracle = WitnessOracle::new(witness);
+        assert!(matches!(oracle.verify(last_key), Err(WitnessOracleError::BlobMismatch(_))));
+    }
+}
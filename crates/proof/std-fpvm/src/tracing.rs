@@ -1,38 +1,105 @@
-//! This module contains
+//! This module contains the [FpvmTracingSubscriber], a [Subscriber] for use within the FPVM.
 
-use crate::io;
+use crate::{FileDescriptor, io};
 use alloc::{
     format,
     string::{String, ToString},
+    sync::Arc,
     vec::Vec,
 };
+use core::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use tracing::{
     Event, Level, Metadata, Subscriber,
     field::{Field, Visit},
     span::{Attributes, Id, Record},
 };
 
+/// The magic bytes written at the start of a span trace, followed by [SPAN_TRACE_VERSION].
+pub const SPAN_TRACE_MAGIC: [u8; 4] = *b"KSPN";
+
+/// The version of the span trace format.
+pub const SPAN_TRACE_VERSION: u8 = 1;
+
+/// The tags of the records in a span trace.
+///
+/// Every record starts with its tag, followed by the little-endian `u64` id of the span:
+/// - [SpanRecord::New] records are followed by a `u8` name length and the span's name.
+/// - [SpanRecord::Enter] and [SpanRecord::Exit] records carry no further data.
+///
+/// Trace tooling attributes the instructions executed between an enter and an exit record to the
+/// span, using the step at which each record was written.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum SpanRecord {
+    /// A span was created.
+    New = 0x01,
+    /// A span was entered.
+    Enter = 0x02,
+    /// A span was exited.
+    Exit = 0x03,
+}
+
 /// Custom [Subscriber] implementation that uses [crate::io] to write log entries to
 /// [crate::FileDescriptor::StdOut].
+///
+/// Spans are ignored unless [FpvmTracingSubscriber::with_span_trace] is used, in which case span
+/// creation, enter and exit records are written to [FileDescriptor::SpanTrace] in the binary format
+/// described by [SpanRecord].
 #[derive(Debug, Clone)]
 pub struct FpvmTracingSubscriber {
     min_level: Level,
+    span_trace: Option<Arc<SpanTrace>>,
 }
 
 impl FpvmTracingSubscriber {
     /// Create a new [FpvmTracingSubscriber] with the specified minimum log level.
     pub const fn new(min_level: Level) -> Self {
-        Self { min_level }
+        Self { min_level, span_trace: None }
+    }
+
+    /// Enables span tracing to [FileDescriptor::SpanTrace], if the file descriptor is open.
+    ///
+    /// The trace header is written eagerly; if the write fails, span tracing stays disabled and
+    /// spans are skipped entirely.
+    pub fn with_span_trace(mut self) -> Self {
+        let mut header = [0u8; 5];
+        header[..4].copy_from_slice(&SPAN_TRACE_MAGIC);
+        header[4] = SPAN_TRACE_VERSION;
+        if io::write(FileDescriptor::SpanTrace, &header).is_ok() {
+            self.span_trace = Some(Arc::new(SpanTrace::default()));
+        }
+        self
+    }
+
+    /// Returns `true` if span tracing is enabled.
+    pub fn span_trace_enabled(&self) -> bool {
+        self.span_trace.as_ref().is_some_and(|trace| trace.open.load(Ordering::Relaxed))
     }
 }
 
 impl Subscriber for FpvmTracingSubscriber {
-    fn enabled(&self, _metadata: &Metadata<'_>) -> bool {
-        true
+    fn enabled(&self, metadata: &Metadata<'_>) -> bool {
+        // Spans are only constructed when they are traced, so that they cost nothing otherwise.
+        !metadata.is_span() || (*metadata.level() <= self.min_level && self.span_trace_enabled())
     }
 
-    fn new_span(&self, _span: &Attributes<'_>) -> Id {
-        Id::from_u64(1)
+    fn new_span(&self, span: &Attributes<'_>) -> Id {
+        let Some(trace) = self.span_trace.as_ref() else {
+            return Id::from_u64(1);
+        };
+
+        let id = trace.next_id.fetch_add(1, Ordering::Relaxed);
+        let name = span.metadata().name().as_bytes();
+        let name = &name[..name.len().min(u8::MAX as usize)];
+
+        let mut record = Vec::with_capacity(10 + name.len());
+        record.push(SpanRecord::New as u8);
+        record.extend_from_slice(&id.to_le_bytes());
+        record.push(name.len() as u8);
+        record.extend_from_slice(name);
+        trace.write(&record);
+
+        Id::from_u64(id)
     }
 
     fn record(&self, _span: &Id, _values: &Record<'_>) {}
@@ -61,9 +128,51 @@ impl Subscriber for FpvmTracingSubscriber {
         io::print(&format!("[{}] {}: {}", metadata.level(), metadata.target(), formatted_message));
     }
 
-    fn enter(&self, _span: &Id) {}
+    fn enter(&self, span: &Id) {
+        if let Some(trace) = self.span_trace.as_ref() {
+            trace.write_record(SpanRecord::Enter, span);
+        }
+    }
+
+    fn exit(&self, span: &Id) {
+        if let Some(trace) = self.span_trace.as_ref() {
+            trace.write_record(SpanRecord::Exit, span);
+        }
+    }
+}
 
-    fn exit(&self, _span: &Id) {}
+/// The shared state of a span trace.
+#[derive(Debug)]
+struct SpanTrace {
+    /// The id of the next span. Span ids must be non-zero.
+    next_id: AtomicU64,
+    /// Whether [FileDescriptor::SpanTrace] is still accepting writes.
+    open: AtomicBool,
+}
+
+impl Default for SpanTrace {
+    fn default() -> Self {
+        Self { next_id: AtomicU64::new(1), open: AtomicBool::new(true) }
+    }
+}
+
+impl SpanTrace {
+    /// Writes an enter or exit record for the given span.
+    fn write_record(&self, tag: SpanRecord, span: &Id) {
+        let mut record = [0u8; 9];
+        record[0] = tag as u8;
+        record[1..].copy_from_slice(&span.into_u64().to_le_bytes());
+        self.write(&record);
+    }
+
+    /// Writes a record to the trace, disabling the trace if the write fails.
+    fn write(&self, record: &[u8]) {
+        if self.open.load(Ordering::Relaxed) &&
+            io::write(FileDescriptor::SpanTrace, record).is_err()
+        {
+            self.open.store(false, Ordering::Relaxed);
+        }
+    }
 }
 
 /// Custom [`Visit`] implementation to extract log field values.
@@ -17,6 +17,8 @@ pub enum FileDescriptor {
     PreimageRead,
     /// Write-only. Used to request pre-images.
     PreimageWrite,
+    /// Write-only. Used to emit binary span traces for profiling. Usually closed.
+    SpanTrace,
 }
 
 impl From<FileDescriptor> for usize {
@@ -29,6 +31,7 @@ impl From<FileDescriptor> for usize {
             FileDescriptor::HintWrite => 4,
             FileDescriptor::PreimageRead => 5,
             FileDescriptor::PreimageWrite => 6,
+            FileDescriptor::SpanTrace => 7,
         }
     }
 }
@@ -52,6 +55,7 @@ mod tests {
         assert_eq!(usize::from(FileDescriptor::HintWrite), 4);
         assert_eq!(usize::from(FileDescriptor::PreimageRead), 5);
         assert_eq!(usize::from(FileDescriptor::PreimageWrite), 6);
+        assert_eq!(usize::from(FileDescriptor::SpanTrace), 7);
     }
 
     #[test]
@@ -63,5 +67,6 @@ mod tests {
         assert_eq!(i32::from(FileDescriptor::HintWrite), 4);
         assert_eq!(i32::from(FileDescriptor::PreimageRead), 5);
         assert_eq!(i32::from(FileDescriptor::PreimageWrite), 6);
+        assert_eq!(i32::from(FileDescriptor::SpanTrace), 7);
     }
 }
@@ -3,6 +3,7 @@
 use crate::{
     AttributesBuilder, BuilderError, ChainProvider, L2ChainProvider, PipelineEncodingError,
     PipelineError, PipelineErrorKind, PipelineResult,
+    trace::{DerivationEvent, derive_trace},
 };
 use alloc::{boxed::Box, fmt::Debug, string::ToString, sync::Arc, vec, vec::Vec};
 use alloy_consensus::{Eip658Value, Receipt};
@@ -161,6 +162,16 @@ where
         let mut encoded_l1_info_tx = Vec::with_capacity(l1_info_tx_envelope.length());
         l1_info_tx_envelope.encode_2718(&mut encoded_l1_info_tx);
 
+        derive_trace!(DerivationEvent::AttributesBuilt {
+            parent: l2_parent.block_info.number,
+            parent_hash: l2_parent.block_info.hash,
+            epoch: epoch.number,
+            timestamp: next_l2_time,
+            sequence_number,
+            deposits: deposit_transactions.len(),
+            upgrades: upgrade_transactions.len(),
+        });
+
         let mut txs =
             Vec::with_capacity(1 + deposit_transactions.len() + upgrade_transactions.len());
         txs.push(encoded_l1_info_tx.into());
@@ -5,7 +5,7 @@
     issue_tracker_base_url = "https://github.com/op-rs/kona/issues/"
 )]
 #![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
-#![cfg_attr(not(feature = "metrics"), no_std)]
+#![cfg_attr(not(any(feature = "metrics", feature = "trace")), no_std)]
 
 extern crate alloc;
 
@@ -55,5 +55,7 @@ pub use types::{ActivationSignal, PipelineResult, ResetSignal, Signal, StepResul
 mod metrics;
 pub use metrics::Metrics;
 
+pub mod trace;
+
 #[cfg(any(test, feature = "test-utils"))]
 pub mod test_utils;
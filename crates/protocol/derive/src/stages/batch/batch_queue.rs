@@ -3,6 +3,7 @@
 use super::NextBatchProvider;
 use crate::{
     errors::{PipelineEncodingError, PipelineError, PipelineErrorKind, ResetError},
+    trace::{BatchAction, DerivationEvent, TraceStage, derive_trace},
     traits::{AttributesProvider, L2ChainProvider, OriginAdvancer, OriginProvider, SignalReceiver},
     types::{PipelineResult, ResetSignal, Signal},
 };
@@ -132,19 +133,33 @@ where
             let batch = &self.batches[i];
             let validity =
                 batch.check_batch(&self.cfg, &self.l1_blocks, parent, &mut self.fetcher).await;
+            let trace_verdict = |action| {
+                derive_trace!(DerivationEvent::batch_verdict(
+                    TraceStage::BatchQueue,
+                    origin.number,
+                    parent.block_info.number,
+                    batch.batch.timestamp(),
+                    validity,
+                    action
+                ))
+            };
             match validity {
                 BatchValidity::Future => {
                     // Drop Future batches post-holocene.
                     //
                     // See: <https://specs.optimism.io/protocol/holocene/derivation.html#batch_queue>
                     if !self.cfg.is_holocene_active(origin.timestamp) {
+                        trace_verdict(BatchAction::Retained);
                         remaining.push(batch.clone());
                     } else {
+                        trace_verdict(BatchAction::DroppedAndFlushed);
                         self.prev.flush();
                         warn!(target: "batch_queue", "[HOLOCENE] Dropping future batch with parent: {}", parent.block_info.number);
                     }
                 }
                 BatchValidity::Drop => {
+                    trace_verdict(BatchAction::DroppedAndFlushed);
+
                     // If we drop a batch, flush previous batches buffered in the BatchStream
                     // stage.
                     self.prev.flush();
@@ -152,6 +167,7 @@ where
                     continue;
                 }
                 BatchValidity::Accept => {
+                    trace_verdict(BatchAction::Forwarded);
                     next_batch = Some(batch.clone());
                     // Don't keep the current batch in the remaining items since we are processing
                     // it now, but retain every batch we didn't get to yet.
@@ -159,16 +175,19 @@ where
                     break;
                 }
                 BatchValidity::Undecided => {
+                    trace_verdict(BatchAction::Deferred);
                     remaining.extend_from_slice(&self.batches[i..]);
                     self.batches = remaining;
                     return Err(PipelineError::Eof.temp());
                 }
                 BatchValidity::Past => {
                     if !self.cfg.is_holocene_active(origin.timestamp) {
+                        trace_verdict(BatchAction::Rejected);
                         error!(target: "batch_queue", "BatchValidity::Past is not allowed pre-holocene");
                         return Err(PipelineError::InvalidBatchValidity.crit());
                     }
 
+                    trace_verdict(BatchAction::Dropped);
                     warn!(target: "batch_queue", "[HOLOCENE] Dropping outdated batch with parent: {}", parent.block_info.number);
                     continue;
                 }
@@ -3,6 +3,7 @@
 use crate::{
     L2ChainProvider, NextBatchProvider, OriginAdvancer, OriginProvider, PipelineEncodingError,
     PipelineError, PipelineResult, Signal, SignalReceiver,
+    trace::{BatchAction, DerivationEvent, TraceStage, derive_trace},
 };
 use alloc::{boxed::Box, collections::VecDeque, sync::Arc};
 use async_trait::async_trait;
@@ -169,9 +170,25 @@ where
                         "validity" => validity.to_string(),
                     );
 
+                    let trace_verdict = |action| {
+                        derive_trace!(DerivationEvent::batch_verdict(
+                            TraceStage::BatchStream,
+                            batch_with_inclusion.inclusion_block.number,
+                            parent.block_info.number,
+                            b.starting_timestamp(),
+                            validity,
+                            action
+                        ))
+                    };
+
                     match validity {
-                        BatchValidity::Accept => self.span = Some(b),
+                        BatchValidity::Accept => {
+                            trace_verdict(BatchAction::Forwarded);
+                            self.span = Some(b);
+                        }
                         BatchValidity::Drop => {
+                            trace_verdict(BatchAction::DroppedAndFlushed);
+
                             // Flush the stage.
                             self.flush();
 
@@ -179,13 +196,16 @@ where
                         }
                         BatchValidity::Past => {
                             if !self.is_active()? {
+                                trace_verdict(BatchAction::Rejected);
                                 error!(target: "batch_stream", "BatchValidity::Past is not allowed pre-holocene");
                                 return Err(PipelineError::InvalidBatchValidity.crit());
                             }
 
+                            trace_verdict(BatchAction::Dropped);
                             return Err(PipelineError::NotEnoughData.temp());
                         }
                         BatchValidity::Undecided | BatchValidity::Future => {
+                            trace_verdict(BatchAction::Deferred);
                             return Err(PipelineError::NotEnoughData.temp());
                         }
                     }
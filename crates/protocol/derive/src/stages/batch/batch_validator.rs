@@ -3,6 +3,7 @@
 use super::NextBatchProvider;
 use crate::{
     errors::{PipelineError, PipelineErrorKind, ResetError},
+    trace::{BatchAction, DerivationEvent, TraceStage, derive_trace},
     traits::{AttributesProvider, OriginAdvancer, OriginProvider, SignalReceiver},
     types::{PipelineResult, ResetSignal, Signal},
 };
@@ -248,27 +249,45 @@ where
         next_batch.parent_hash = parent.block_info.hash;
 
         // Check the validity of the single batch before forwarding it.
-        match next_batch.check_batch(
+        let validity = next_batch.check_batch(
             self.cfg.as_ref(),
             self.l1_blocks.as_ref(),
             parent,
             &stage_origin,
-        ) {
+        );
+        let trace_verdict = |action| {
+            derive_trace!(DerivationEvent::batch_verdict(
+                TraceStage::BatchValidator,
+                stage_origin.number,
+                parent.block_info.number,
+                next_batch.timestamp,
+                validity,
+                action
+            ))
+        };
+        match validity {
             BatchValidity::Accept => {
+                trace_verdict(BatchAction::Forwarded);
                 info!(target: "batch_validator", "Found next batch (epoch #{})", next_batch.epoch_num);
                 Ok(next_batch)
             }
             BatchValidity::Past => {
+                trace_verdict(BatchAction::Dropped);
                 warn!(target: "batch_validator", "Dropping old batch");
                 Err(PipelineError::NotEnoughData.temp())
             }
             BatchValidity::Drop => {
+                trace_verdict(BatchAction::DroppedAndFlushed);
                 warn!(target: "batch_validator", "Invalid singular batch, flushing current channel.");
                 self.prev.flush();
                 Err(PipelineError::NotEnoughData.temp())
             }
-            BatchValidity::Undecided => Err(PipelineError::NotEnoughData.temp()),
+            BatchValidity::Undecided => {
+                trace_verdict(BatchAction::Deferred);
+                Err(PipelineError::NotEnoughData.temp())
+            }
             BatchValidity::Future => {
+                trace_verdict(BatchAction::Rejected);
                 error!(target: "batch_validator", "Future batch detected in BatchValidator.");
                 Err(PipelineError::InvalidBatchValidity.crit())
             }
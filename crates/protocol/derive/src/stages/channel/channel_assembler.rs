@@ -3,11 +3,12 @@
 use super::{ChannelReaderProvider, NextFrameProvider};
 use crate::{
     errors::PipelineError,
+    trace::{DerivationEvent, FrameDropReason, TraceStage, derive_trace},
     traits::{OriginAdvancer, OriginProvider, SignalReceiver},
     types::{PipelineResult, Signal},
 };
 use alloc::{boxed::Box, sync::Arc};
-use alloy_primitives::{Bytes, hex};
+use alloy_primitives::{B128, Bytes, hex};
 use async_trait::async_trait;
 use core::fmt::Debug;
 use kona_genesis::{
@@ -76,6 +77,12 @@ where
                     origin.number,
                     channel.open_block_number()
                 );
+                derive_trace!(DerivationEvent::ChannelTimedOut {
+                    stage: TraceStage::ChannelAssembler,
+                    origin: origin.number,
+                    channel_id: B128::from(channel.id()),
+                    open_block: channel.open_block_number(),
+                });
                 self.channel = None;
             }
         }
@@ -97,6 +104,16 @@ where
         let count = if self.channel.is_some() { 1 } else { 0 };
         kona_macros::set!(gauge, crate::metrics::Metrics::PIPELINE_CHANNEL_BUFFER, count);
 
+        // Frames that do not open a channel are discarded if no channel is being assembled.
+        if self.channel.is_none() {
+            derive_trace!(DerivationEvent::frame_dropped(
+                TraceStage::ChannelAssembler,
+                origin.number,
+                &next_frame,
+                FrameDropReason::NoOpenChannel
+            ));
+        }
+
         if let Some(channel) = self.channel.as_mut() {
             // Track the number of blocks until the channel times out.
             let timeout = channel.open_block_number() + self.cfg.channel_timeout(origin.timestamp);
@@ -112,6 +129,7 @@ where
                 hex::encode(channel.id()),
                 origin.number
             );
+            let frame_number = next_frame.number;
             if channel.add_frame(next_frame, origin).is_err() {
                 error!(
                     target: "channel_assembler",
@@ -119,6 +137,13 @@ where
                     hex::encode(channel.id()),
                     origin.number
                 );
+                derive_trace!(DerivationEvent::FrameDropped {
+                    stage: TraceStage::ChannelAssembler,
+                    origin: origin.number,
+                    channel_id: B128::from(channel.id()),
+                    number: frame_number,
+                    reason: FrameDropReason::InvalidFrame,
+                });
                 return Err(PipelineError::NotEnoughData.temp());
             }
 
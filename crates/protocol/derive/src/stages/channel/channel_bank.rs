@@ -3,9 +3,10 @@
 use crate::{
     ChannelReaderProvider, NextFrameProvider, OriginAdvancer, OriginProvider, PipelineError,
     PipelineErrorKind, PipelineResult, Signal, SignalReceiver,
+    trace::{DerivationEvent, FrameDropReason, TraceStage, derive_trace},
 };
 use alloc::{boxed::Box, collections::VecDeque, sync::Arc};
-use alloy_primitives::{Bytes, hex, map::HashMap};
+use alloy_primitives::{B128, Bytes, hex, map::HashMap};
 use async_trait::async_trait;
 use core::fmt::Debug;
 use kona_genesis::RollupConfig;
@@ -99,13 +100,26 @@ where
                 target: "channel_bank",
                 "Channel (ID: {}) timed out", hex::encode(frame.id)
             );
+            derive_trace!(DerivationEvent::frame_dropped(
+                TraceStage::ChannelBank,
+                origin.number,
+                &frame,
+                FrameDropReason::ChannelTimedOut
+            ));
             return Ok(());
         }
 
         // Ingest the frame. If it fails, ignore the frame.
-        let frame_id = frame.id;
+        let (frame_id, frame_number) = (frame.id, frame.number);
         if current_channel.add_frame(frame, origin).is_err() {
             warn!(target: "channel_bank", "Failed to add frame to channel: {:?}", frame_id);
+            derive_trace!(DerivationEvent::FrameDropped {
+                stage: TraceStage::ChannelBank,
+                origin: origin.number,
+                channel_id: B128::from(frame_id),
+                number: frame_number,
+                reason: FrameDropReason::InvalidFrame,
+            });
             return Ok(());
         }
 
@@ -134,6 +148,12 @@ where
                 target: "channel_bank",
                 "Channel (ID: {}) timed out", hex::encode(first)
             );
+            derive_trace!(DerivationEvent::ChannelTimedOut {
+                stage: TraceStage::ChannelBank,
+                origin: origin.number,
+                channel_id: B128::from(first),
+                open_block: channel.open_block_number(),
+            });
             self.channels.remove(&first);
             self.channel_queue.pop_front();
             return Ok(None);
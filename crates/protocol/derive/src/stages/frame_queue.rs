@@ -3,6 +3,7 @@
 use crate::{
     NextFrameProvider, OriginAdvancer, OriginProvider, PipelineError, PipelineResult, Signal,
     SignalReceiver,
+    trace::{DerivationEvent, FrameDropReason, TraceStage, derive_trace},
 };
 use alloc::{boxed::Box, collections::VecDeque, sync::Arc};
 use alloy_primitives::Bytes;
@@ -71,18 +72,36 @@ where
             // If the frames are in the same channel, and the frame numbers are not sequential,
             // drop the next frame.
             if extends_channel && prev_frame.number + 1 != next_frame.number {
+                derive_trace!(DerivationEvent::frame_dropped(
+                    TraceStage::FrameQueue,
+                    origin.number,
+                    next_frame,
+                    FrameDropReason::NonSequential
+                ));
                 self.queue.remove(i + 1);
                 continue;
             }
 
             // If the frames are in the same channel, and the previous is last, drop the next frame.
             if extends_channel && prev_frame.is_last {
+                derive_trace!(DerivationEvent::frame_dropped(
+                    TraceStage::FrameQueue,
+                    origin.number,
+                    next_frame,
+                    FrameDropReason::AfterLastFrame
+                ));
                 self.queue.remove(i + 1);
                 continue;
             }
 
             // If the frames are in different channels, the next frame must be first.
             if !extends_channel && next_frame.number != 0 {
+                derive_trace!(DerivationEvent::frame_dropped(
+                    TraceStage::FrameQueue,
+                    origin.number,
+                    next_frame,
+                    FrameDropReason::NotFirstFrame
+                ));
                 self.queue.remove(i + 1);
                 continue;
             }
@@ -96,8 +115,15 @@ where
                     self.queue.iter().position(|f| f.id == prev_frame.id).expect("infallible");
 
                 // Drain all frames from the previous channel.
-                let drained = self.queue.drain(first_frame..=i);
-                i = i.saturating_sub(drained.len());
+                let drained = self.queue.drain(first_frame..=i).inspect(|frame| {
+                    derive_trace!(DerivationEvent::frame_dropped(
+                        TraceStage::FrameQueue,
+                        origin.number,
+                        frame,
+                        FrameDropReason::IncompleteChannel
+                    ));
+                });
+                i = i.saturating_sub(drained.count());
                 continue;
             }
 
@@ -121,12 +147,21 @@ where
             }
         };
 
-        let Ok(frames) = Frame::parse_frames(&data.into()) else {
+        let data = data.into();
+        let Ok(frames) = Frame::parse_frames(&data) else {
             // There may be more frames in the queue for the
             // pipeline to advance, so don't return an error here.
             error!(target: "frame_queue", "Failed to parse frames from data.");
+            derive_trace!(DerivationEvent::FrameParseFailed {
+                origin: self.origin().map(|o| o.number).unwrap_or_default(),
+                len: data.len()
+            });
             return Ok(());
         };
+        derive_trace!(DerivationEvent::FramesParsed {
+            origin: self.origin().map(|o| o.number).unwrap_or_default(),
+            count: frames.len()
+        });
 
         // Optimistically extend the queue with the new frames.
         self.queue.extend(frames);
@@ -172,7 +207,12 @@ where
             return Err(PipelineError::NotEnoughData.temp());
         }
 
-        Ok(self.queue.pop_front().expect("Frame queue impossibly empty"))
+        let frame = self.queue.pop_front().expect("Frame queue impossibly empty");
+        derive_trace!(DerivationEvent::frame_accepted(
+            self.origin().map(|o| o.number).unwrap_or_default(),
+            &frame
+        ));
+        Ok(frame)
     }
 }
 
@@ -545,4 +585,48 @@ pub(crate) mod tests {
         assert.holocene_active(true);
         assert.next_frames().await;
     }
+
+    #[cfg(feature = "trace")]
+    #[test]
+    fn test_holocene_prune_traces_dropped_frames() {
+        use crate::trace::{DerivationTraceLayer, read_trace};
+        use alloy_primitives::B128;
+        use tracing_subscriber::layer::SubscriberExt;
+
+        let frames = [
+            crate::frame!(0xEE, 0, vec![0xDD; 50], false),
+            crate::frame!(0xEE, 2, vec![0xDD; 50], true), // Dropped
+            crate::frame!(0xFF, 0, vec![0xDD; 50], true),
+        ];
+        let cfg = RollupConfig {
+            hardforks: HardForkConfig { holocene_time: Some(0), ..Default::default() },
+            ..Default::default()
+        };
+        let origin = BlockInfo { number: 10, ..Default::default() };
+        let mut frame_queue = FrameQueue::new(TestFrameQueueProvider::new(vec![]), Arc::new(cfg));
+        frame_queue.queue.extend(frames.iter().cloned());
+
+        let layer = Arc::new(DerivationTraceLayer::new(Vec::new()));
+        let subscriber = tracing_subscriber::Registry::default().with(layer.clone());
+        tracing::subscriber::with_default(subscriber, || frame_queue.prune(origin));
+
+        // The non-sequential frame is dropped, then the unclosed first channel is walked back.
+        let buf = Arc::into_inner(layer).unwrap().into_inner();
+        let events = read_trace(buf.as_slice()).unwrap();
+        let dropped = |id: u8, number, reason| DerivationEvent::FrameDropped {
+            stage: TraceStage::FrameQueue,
+            origin: 10,
+            channel_id: B128::from([id; 16]),
+            number,
+            reason,
+        };
+        assert_eq!(
+            events,
+            vec![
+                dropped(0xEE, 2, FrameDropReason::NonSequential),
+                dropped(0xEE, 0, FrameDropReason::IncompleteChannel),
+            ]
+        );
+        assert_eq!(frame_queue.queue, vec![frames[2].clone()]);
+    }
 }
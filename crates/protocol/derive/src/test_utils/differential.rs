@@ -0,0 +1,596 @@
+//! A differential test harness for the [`DerivationPipeline`].
+//!
+//! A [`DerivationFixture`] holds the L1 data a reference node (e.g. `op-node` or `mantle-node`)
+//! derived from, together with the [`OpAttributesWithParent`] and safe heads the reference node
+//! produced. The [`DifferentialHarness`] replays the L1 data through a [`DerivationPipeline`]
+//! built from the test providers and reports the first point at which its output diverges from
+//! the recorded output.
+
+use crate::{
+    ActivationSignal, DerivationPipeline, MantleEthereumDataSource, OriginProvider, Pipeline,
+    PipelineBuilder, PipelineError, PipelineErrorKind, PolledAttributesQueueStage, ResetError,
+    ResetSignal, SignalReceiver, StatefulAttributesBuilder, StepResult,
+    test_utils::{TestBlobProvider, TestChainProvider, TestL2ChainProvider},
+};
+use alloc::{boxed::Box, collections::BTreeMap, sync::Arc, vec::Vec};
+use alloy_consensus::{Header, Receipt, TxEnvelope};
+use alloy_eips::{eip2718::Decodable2718, eip4844::Blob};
+use alloy_primitives::B256;
+use kona_genesis::{L1ChainConfig, RollupConfig, SystemConfig};
+use kona_protocol::{BlockInfo, L1BlockInfoTx, L2BlockInfo, OpAttributesWithParent};
+use op_alloy_consensus::OpTxEnvelope;
+use thiserror::Error;
+
+/// The default maximum number of pipeline steps the [`DifferentialHarness`] takes.
+pub const DEFAULT_MAX_STEPS: usize = 100_000;
+
+/// An L1 block recorded in a [`DerivationFixture`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FixtureL1Block {
+    /// The block header.
+    pub header: Header,
+    /// The transactions in the block.
+    pub transactions: Vec<TxEnvelope>,
+    /// The receipts of the transactions in the block.
+    pub receipts: Vec<Receipt>,
+}
+
+impl FixtureL1Block {
+    /// Returns the [`BlockInfo`] of the block.
+    pub fn block_info(&self) -> BlockInfo {
+        BlockInfo::new(
+            self.header.hash_slow(),
+            self.header.number,
+            self.header.parent_hash,
+            self.header.timestamp,
+        )
+    }
+}
+
+/// A single output of the reference node.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FixtureStep {
+    /// The attributes produced by the reference node.
+    pub attributes: OpAttributesWithParent,
+    /// The safe head after the attributes were executed by the reference node.
+    pub safe_head: L2BlockInfo,
+}
+
+/// A recorded derivation fixture.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DerivationFixture {
+    /// The rollup config of the chain.
+    pub rollup_config: RollupConfig,
+    /// The L1 chain config.
+    pub l1_config: L1ChainConfig,
+    /// The L1 origin the pipeline starts deriving from.
+    pub l1_origin: BlockInfo,
+    /// The L2 safe head the pipeline starts deriving from.
+    pub safe_head: L2BlockInfo,
+    /// The L1 blocks available to the pipeline.
+    pub l1_blocks: Vec<FixtureL1Block>,
+    /// The blobs available to the pipeline, keyed by versioned hash.
+    pub blobs: BTreeMap<B256, Blob>,
+    /// The L2 blocks known to the reference node, used for batch validation.
+    pub l2_blocks: Vec<L2BlockInfo>,
+    /// The system configs of the L2 blocks, keyed by L2 block number.
+    pub system_configs: BTreeMap<u64, SystemConfig>,
+    /// The outputs of the reference node, in order.
+    pub steps: Vec<FixtureStep>,
+}
+
+/// The [`DerivationPipeline`] driven by the [`DifferentialHarness`].
+pub type DifferentialPipeline = DerivationPipeline<
+    PolledAttributesQueueStage<
+        MantleEthereumDataSource<TestChainProvider, TestBlobProvider>,
+        TestChainProvider,
+        TestL2ChainProvider,
+        StatefulAttributesBuilder<TestChainProvider, TestL2ChainProvider>,
+    >,
+    TestL2ChainProvider,
+>;
+
+/// The first point at which the pipeline diverged from a [`DerivationFixture`].
+#[derive(Error, Debug, PartialEq)]
+pub enum Divergence {
+    /// The pipeline produced different attributes than the reference node.
+    #[error("Attributes diverged at step {index}")]
+    Attributes {
+        /// The index of the diverging step.
+        index: usize,
+        /// The attributes produced by the reference node.
+        expected: Box<OpAttributesWithParent>,
+        /// The attributes produced by the pipeline.
+        actual: Box<OpAttributesWithParent>,
+    },
+    /// The recorded safe head is inconsistent with the attributes that produced it, or is not
+    /// the reference node's block at that height.
+    #[error("Safe head #{} is inconsistent with the attributes at step {index}", .safe_head.block_info.number)]
+    SafeHead {
+        /// The index of the diverging step.
+        index: usize,
+        /// The recorded safe head.
+        safe_head: L2BlockInfo,
+        /// The attributes produced by the pipeline.
+        actual: Box<OpAttributesWithParent>,
+    },
+    /// The pipeline ran out of L1 data before producing every recorded step.
+    #[error("L1 data exhausted at step {index} (origin: {origin:?}): {error}")]
+    Exhausted {
+        /// The index of the step the pipeline could not produce.
+        index: usize,
+        /// The L1 origin of the pipeline.
+        origin: Option<BlockInfo>,
+        /// The error returned when advancing the origin.
+        error: PipelineErrorKind,
+    },
+    /// The pipeline failed with an error the harness cannot recover from.
+    #[error("Pipeline failed at step {index}: {error}")]
+    Failed {
+        /// The index of the step the pipeline could not produce.
+        index: usize,
+        /// The pipeline error.
+        error: PipelineErrorKind,
+    },
+    /// The pipeline exceeded the maximum number of steps.
+    #[error("Step limit reached at step {index}")]
+    StepLimit {
+        /// The index of the step the pipeline could not produce.
+        index: usize,
+    },
+}
+
+/// A harness that runs a [`DerivationFixture`] through a [`DerivationPipeline`].
+#[derive(Debug, Clone)]
+pub struct DifferentialHarness {
+    /// The fixture being replayed.
+    pub fixture: DerivationFixture,
+    /// The maximum number of pipeline steps to take.
+    pub max_steps: usize,
+}
+
+impl DifferentialHarness {
+    /// Creates a new [`DifferentialHarness`] for the given fixture.
+    pub const fn new(fixture: DerivationFixture) -> Self {
+        Self { fixture, max_steps: DEFAULT_MAX_STEPS }
+    }
+
+    /// Sets the maximum number of pipeline steps to take.
+    pub const fn with_max_steps(mut self, max_steps: usize) -> Self {
+        self.max_steps = max_steps;
+        self
+    }
+
+    /// Returns the L1 chain provider for the fixture.
+    pub fn chain_provider(&self) -> TestChainProvider {
+        let mut provider = TestChainProvider::default();
+        for block in &self.fixture.l1_blocks {
+            let info = block.block_info();
+            provider.insert_block_with_transactions(info.number, info, block.transactions.clone());
+            provider.insert_header(info.hash, block.header.clone());
+            provider.insert_receipts(info.hash, block.receipts.clone());
+        }
+        provider
+    }
+
+    /// Returns the blob provider for the fixture.
+    pub fn blob_provider(&self) -> TestBlobProvider {
+        let mut provider = TestBlobProvider::default();
+        for (hash, blob) in &self.fixture.blobs {
+            provider.insert_blob(*hash, *blob);
+        }
+        provider
+    }
+
+    /// Returns the L2 chain provider for the fixture.
+    pub fn l2_chain_provider(&self) -> TestL2ChainProvider {
+        TestL2ChainProvider::new(
+            self.fixture.l2_blocks.clone(),
+            Vec::new(),
+            self.fixture.system_configs.iter().map(|(n, c)| (*n, *c)).collect(),
+        )
+    }
+
+    /// Builds the [`DifferentialPipeline`] and resets it to the fixture's starting point.
+    pub async fn pipeline(&self) -> Result<DifferentialPipeline, PipelineErrorKind> {
+        let cfg = Arc::new(self.fixture.rollup_config.clone());
+        let l1_cfg = Arc::new(self.fixture.l1_config.clone());
+        let chain_provider = self.chain_provider();
+        let l2_chain_provider = self.l2_chain_provider();
+
+        let dap = MantleEthereumDataSource::new_from_parts(
+            chain_provider.clone(),
+            self.blob_provider(),
+            &cfg,
+        );
+        let builder = StatefulAttributesBuilder::new(
+            cfg.clone(),
+            l1_cfg,
+            l2_chain_provider.clone(),
+            chain_provider.clone(),
+        );
+        let mut pipeline = PipelineBuilder::new()
+            .rollup_config(cfg)
+            .dap_source(dap)
+            .l2_chain_provider(l2_chain_provider)
+            .chain_provider(chain_provider)
+            .builder(builder)
+            .origin(self.fixture.l1_origin)
+            .build_polled();
+
+        let system_config = self.system_config(&self.fixture.safe_head);
+        pipeline
+            .signal(
+                ResetSignal {
+                    l2_safe_head: self.fixture.safe_head,
+                    l1_origin: self.fixture.l1_origin,
+                    system_config,
+                }
+                .signal(),
+            )
+            .await?;
+
+        Ok(pipeline)
+    }
+
+    /// Runs the fixture through the pipeline, comparing every produced
+    /// [`OpAttributesWithParent`] against the recorded steps.
+    ///
+    /// Returns the number of steps that matched, or the first [`Divergence`].
+    pub async fn run(&self) -> Result<usize, Divergence> {
+        let expected = &self.fixture.steps;
+        let mut index = 0;
+        self.drive(expected.len(), |actual| {
+            let step = &expected[index];
+            if *actual != step.attributes {
+                return Err(Divergence::Attributes {
+                    index,
+                    expected: Box::new(step.attributes.clone()),
+                    actual: Box::new(actual.clone()),
+                });
+            }
+            if !self.is_consistent(actual, &step.safe_head) {
+                return Err(Divergence::SafeHead {
+                    index,
+                    safe_head: step.safe_head,
+                    actual: Box::new(actual.clone()),
+                });
+            }
+            index += 1;
+            Ok(step.safe_head)
+        })
+        .await
+    }
+
+    /// Steps the pipeline until `count` attributes were produced. Every produced attributes
+    /// is handed to `on_attributes`, which returns the safe head to continue deriving from.
+    async fn drive(
+        &self,
+        count: usize,
+        mut on_attributes: impl FnMut(&OpAttributesWithParent) -> Result<L2BlockInfo, Divergence>,
+    ) -> Result<usize, Divergence> {
+        let mut pipeline =
+            self.pipeline().await.map_err(|error| Divergence::Failed { index: 0, error })?;
+        let mut cursor = self.fixture.safe_head;
+        let mut produced = 0;
+        let mut steps = 0;
+
+        while produced < count {
+            if steps >= self.max_steps {
+                return Err(Divergence::StepLimit { index: produced });
+            }
+            steps += 1;
+
+            match pipeline.step(cursor).await {
+                StepResult::PreparedAttributes => {
+                    let Some(attributes) = pipeline.next() else {
+                        continue;
+                    };
+                    cursor = on_attributes(&attributes)?;
+                    produced += 1;
+                }
+                StepResult::AdvancedOrigin => {}
+                // All L1 data is local, so a temporary error while advancing the origin means
+                // that the fixture has no more L1 blocks.
+                StepResult::OriginAdvanceErr(error @ PipelineErrorKind::Temporary(_)) => {
+                    return Err(Divergence::Exhausted {
+                        index: produced,
+                        origin: pipeline.origin(),
+                        error,
+                    });
+                }
+                StepResult::StepFailed(PipelineErrorKind::Temporary(_)) => {}
+                StepResult::OriginAdvanceErr(PipelineErrorKind::Reset(e)) |
+                StepResult::StepFailed(PipelineErrorKind::Reset(e)) => {
+                    let failed = |error| Divergence::Failed { index: produced, error };
+                    let l1_origin = pipeline
+                        .origin()
+                        .ok_or_else(|| failed(PipelineError::MissingOrigin.crit()))?;
+                    let system_config = self.system_config(&cursor);
+                    let signal = if matches!(e, ResetError::HoloceneActivation) {
+                        ActivationSignal { l2_safe_head: cursor, l1_origin, system_config }.signal()
+                    } else {
+                        ResetSignal { l2_safe_head: cursor, l1_origin, system_config }.signal()
+                    };
+                    pipeline.signal(signal).await.map_err(failed)?;
+                }
+                StepResult::OriginAdvanceErr(error) | StepResult::StepFailed(error) => {
+                    return Err(Divergence::Failed { index: produced, error });
+                }
+            }
+        }
+
+        Ok(produced)
+    }
+
+    /// Returns the system config of the given L2 block, if the fixture has one.
+    fn system_config(&self, block: &L2BlockInfo) -> Option<SystemConfig> {
+        self.fixture.system_configs.get(&block.block_info.number).copied()
+    }
+
+    /// Returns the reference node's L2 block at the given height, if the fixture has it.
+    fn reference_block(&self, number: u64) -> Option<L2BlockInfo> {
+        self.fixture.l2_blocks.iter().find(|block| block.block_info.number == number).copied()
+    }
+
+    /// Returns whether the safe head is the block built from the given attributes.
+    ///
+    /// The safe head must extend the parent of the attributes, sit on the L1 origin and sequence
+    /// number of their L1 info deposit, and be the reference node's block at that height. Its
+    /// hash is the parent of the next attributes, and is checked by the pipeline against the
+    /// parent hash of the next batch.
+    fn is_consistent(&self, attributes: &OpAttributesWithParent, safe_head: &L2BlockInfo) -> bool {
+        let parent = &attributes.parent.block_info;
+        let Some(l1_info) = Self::l1_info(attributes) else {
+            return false;
+        };
+        safe_head.block_info.number == parent.number + 1 &&
+            safe_head.block_info.parent_hash == parent.hash &&
+            safe_head.block_info.timestamp == attributes.inner.payload_attributes.timestamp &&
+            safe_head.l1_origin == l1_info.id() &&
+            safe_head.seq_num == l1_info.sequence_number() &&
+            self.reference_block(safe_head.block_info.number) == Some(*safe_head)
+    }
+
+    /// Decodes the [`L1BlockInfoTx`] from the first transaction of the attributes.
+    fn l1_info(attributes: &OpAttributesWithParent) -> Option<L1BlockInfoTx> {
+        let tx = attributes.inner.transactions.as_ref()?.first()?;
+        let OpTxEnvelope::Deposit(deposit) = OpTxEnvelope::decode_2718(&mut tx.as_ref()).ok()?
+        else {
+            return None;
+        };
+        L1BlockInfoTx::decode_calldata(deposit.input.as_ref()).ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+    use alloy_eips::{BlockNumHash, eip2718::Encodable2718};
+    use alloy_primitives::keccak256;
+    use alloy_rpc_types_engine::PayloadAttributes;
+    use kona_genesis::ChainGenesis;
+    use kona_protocol::Predeploys;
+    use op_alloy_rpc_types_engine::OpPayloadAttributes;
+
+    /// Builds a chain of empty L1 blocks, 12 seconds apart.
+    fn l1_chain(count: u64) -> Vec<FixtureL1Block> {
+        let mut parent_hash = B256::ZERO;
+        (0..count)
+            .map(|number| {
+                let header = Header {
+                    number,
+                    parent_hash,
+                    timestamp: 1_000 + number * 12,
+                    base_fee_per_gas: Some(7),
+                    ..Default::default()
+                };
+                parent_hash = header.hash_slow();
+                FixtureL1Block { header, ..Default::default() }
+            })
+            .collect()
+    }
+
+    /// Builds the reference L2 chain of empty blocks on top of `genesis`, 2 seconds apart, with
+    /// six blocks per L1 epoch.
+    fn l2_chain(l1_blocks: &[FixtureL1Block], genesis: L2BlockInfo) -> Vec<L2BlockInfo> {
+        let mut parent = genesis;
+        let mut blocks = vec![genesis];
+        for number in 1..6 * l1_blocks.len() as u64 {
+            let block = L2BlockInfo::new(
+                BlockInfo::new(
+                    keccak256(number.to_be_bytes()),
+                    number,
+                    parent.block_info.hash,
+                    genesis.block_info.timestamp + number * 2,
+                ),
+                l1_blocks[number as usize / 6].block_info().id(),
+                number % 6,
+            );
+            blocks.push(block);
+            parent = block;
+        }
+        blocks
+    }
+
+    /// A fixture without any batches, where the pipeline derives empty batches once the
+    /// sequencing window of each epoch expires.
+    fn empty_batches_fixture() -> DerivationFixture {
+        let l1_blocks = l1_chain(8);
+        let l1_origin = l1_blocks[0].block_info();
+        let safe_head = L2BlockInfo::new(
+            BlockInfo::new(B256::repeat_byte(0x11), 0, B256::ZERO, l1_origin.timestamp),
+            l1_origin.id(),
+            0,
+        );
+        let l2_blocks = l2_chain(&l1_blocks, safe_head);
+        let system_config = SystemConfig { gas_limit: 30_000_000, ..Default::default() };
+        let rollup_config = RollupConfig {
+            block_time: 2,
+            seq_window_size: 2,
+            channel_timeout: 2,
+            genesis: ChainGenesis {
+                l1: l1_origin.id(),
+                l2: BlockNumHash { number: 0, hash: safe_head.block_info.hash },
+                l2_time: l1_origin.timestamp,
+                system_config: Some(system_config),
+            },
+            ..Default::default()
+        };
+
+        DerivationFixture {
+            rollup_config,
+            l1_config: L1ChainConfig::default(),
+            l1_origin,
+            safe_head,
+            l1_blocks,
+            blobs: BTreeMap::new(),
+            l2_blocks,
+            system_configs: (0..64).map(|n| (n, system_config)).collect(),
+            steps: Vec::new(),
+        }
+    }
+
+    /// Builds the attributes the reference node derives for the first `count` blocks of an
+    /// [`empty_batches_fixture`], following the derivation spec.
+    ///
+    /// Every block is built from an empty batch: its only transaction is the L1 info deposit, and
+    /// it is derived once the pipeline's origin reaches the end of its epoch's sequencing window.
+    fn spec_steps(fixture: &DerivationFixture, count: usize) -> Vec<FixtureStep> {
+        let cfg = &fixture.rollup_config;
+        fixture.l2_blocks[..=count]
+            .windows(2)
+            .map(|blocks| {
+                let (parent, safe_head) = (blocks[0], blocks[1]);
+                let epoch = safe_head.l1_origin.number as usize;
+                let l1_header = &fixture.l1_blocks[epoch].header;
+                let system_config = fixture.system_configs[&parent.block_info.number];
+                let (_, l1_info) = L1BlockInfoTx::try_new_with_deposit_tx(
+                    cfg,
+                    &fixture.l1_config,
+                    &system_config,
+                    safe_head.seq_num,
+                    l1_header,
+                    safe_head.block_info.timestamp,
+                )
+                .unwrap();
+
+                let inner = OpPayloadAttributes {
+                    payload_attributes: PayloadAttributes {
+                        timestamp: safe_head.block_info.timestamp,
+                        prev_randao: l1_header.mix_hash,
+                        suggested_fee_recipient: Predeploys::SEQUENCER_FEE_VAULT,
+                        parent_beacon_block_root: None,
+                        withdrawals: None,
+                    },
+                    transactions: Some(vec![l1_info.encoded_2718().into()]),
+                    no_tx_pool: Some(true),
+                    gas_limit: Some(system_config.gas_limit),
+                    eip_1559_params: None,
+                    min_base_fee: None,
+                };
+                let derived_from =
+                    fixture.l1_blocks[epoch + cfg.seq_window_size as usize].block_info();
+                let attributes =
+                    OpAttributesWithParent::new(inner, parent, Some(derived_from), true);
+                FixtureStep { attributes, safe_head }
+            })
+            .collect()
+    }
+
+    fn spec_harness(count: usize) -> DifferentialHarness {
+        let mut fixture = empty_batches_fixture();
+        fixture.steps = spec_steps(&fixture, count);
+        DifferentialHarness::new(fixture).with_max_steps(1_000)
+    }
+
+    #[tokio::test]
+    async fn test_replay_spec_steps() {
+        let harness = spec_harness(8);
+        assert_eq!(harness.fixture.steps.len(), 8);
+        assert_eq!(harness.run().await, Ok(8));
+    }
+
+    #[tokio::test]
+    async fn test_attributes_divergence() {
+        let mut harness = spec_harness(8);
+        let expected = &mut harness.fixture.steps[3].attributes;
+        expected.inner.gas_limit = expected.inner.gas_limit.map(|g| g + 1);
+
+        let Err(Divergence::Attributes { index, expected, actual }) = harness.run().await else {
+            panic!("expected attributes divergence");
+        };
+        assert_eq!(index, 3);
+        assert_eq!(expected.inner.gas_limit, actual.inner.gas_limit.map(|g| g + 1));
+    }
+
+    #[tokio::test]
+    async fn test_safe_head_divergence() {
+        let mut harness = spec_harness(4);
+        harness.fixture.steps[2].safe_head.block_info.timestamp += 1;
+
+        let Err(Divergence::SafeHead { index, .. }) = harness.run().await else {
+            panic!("expected safe head divergence");
+        };
+        assert_eq!(index, 2);
+    }
+
+    #[tokio::test]
+    async fn test_safe_head_hash_divergence() {
+        let mut harness = spec_harness(4);
+        harness.fixture.steps[2].safe_head.block_info.hash = B256::repeat_byte(0xff);
+
+        let Err(Divergence::SafeHead { index, .. }) = harness.run().await else {
+            panic!("expected safe head divergence");
+        };
+        assert_eq!(index, 2);
+    }
+
+    #[tokio::test]
+    async fn test_missing_reference_block() {
+        let mut harness = spec_harness(8);
+        harness.fixture.l2_blocks.truncate(4);
+
+        let Err(Divergence::SafeHead { index, .. }) = harness.run().await else {
+            panic!("expected safe head divergence");
+        };
+        assert_eq!(index, 3);
+    }
+
+    #[tokio::test]
+    async fn test_exhausted_l1_data() {
+        let mut harness = spec_harness(8);
+        harness.fixture.l1_blocks.truncate(2);
+
+        let Err(Divergence::Exhausted { origin, .. }) = harness.run().await else {
+            panic!("expected exhausted L1 data");
+        };
+        assert_eq!(origin.map(|o| o.number), Some(1));
+    }
+
+    #[cfg(feature = "serde")]
+    #[tokio::test]
+    async fn test_recorded_fixtures() {
+        let dir = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("testdata/differential");
+        let mut paths = std::fs::read_dir(&dir)
+            .unwrap_or_else(|e| panic!("failed to read {}: {e}", dir.display()))
+            .map(|e| e.unwrap().path())
+            .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+            .collect::<Vec<_>>();
+        paths.sort();
+        assert!(!paths.is_empty(), "no fixtures in {}", dir.display());
+
+        for path in paths {
+            let fixture: DerivationFixture =
+                serde_json::from_slice(&std::fs::read(&path).unwrap()).unwrap();
+            let steps = fixture.steps.len();
+            assert!(steps > 0, "fixture {} has no steps", path.display());
+            let res = DifferentialHarness::new(fixture).run().await;
+            assert_eq!(res, Ok(steps), "fixture {} diverged", path.display());
+        }
+    }
+}
@@ -49,4 +49,10 @@ pub use sys_config_fetcher::{TestSystemConfigL2Fetcher, TestSystemConfigL2Fetche
 mod frames;
 pub use frames::{FrameQueueAsserter, FrameQueueBuilder};
 
+mod differential;
+pub use differential::{
+    DEFAULT_MAX_STEPS, DerivationFixture, DifferentialHarness, DifferentialPipeline, Divergence,
+    FixtureL1Block, FixtureStep,
+};
+
 mod macros;
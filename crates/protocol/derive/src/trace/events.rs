@@ -0,0 +1,194 @@
+//! Contains the [`DerivationEvent`] type and its components.
+
+use alloy_primitives::{B128, B256};
+use kona_protocol::{BatchValidity, Frame};
+
+/// A pipeline stage that emits [`DerivationEvent`]s.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum TraceStage {
+    /// The [`FrameQueue`](crate::FrameQueue) stage.
+    FrameQueue,
+    /// The [`ChannelBank`](crate::ChannelBank) stage.
+    ChannelBank,
+    /// The [`ChannelAssembler`](crate::ChannelAssembler) stage.
+    ChannelAssembler,
+    /// The [`BatchStream`](crate::BatchStream) stage.
+    BatchStream,
+    /// The [`BatchQueue`](crate::BatchQueue) stage.
+    BatchQueue,
+    /// The [`BatchValidator`](crate::BatchValidator) stage.
+    BatchValidator,
+}
+
+/// The reason a frame was dropped by the pipeline.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum FrameDropReason {
+    /// Holocene: the frame number does not follow the previous frame of the same channel.
+    NonSequential,
+    /// Holocene: the frame follows the last frame of its channel.
+    AfterLastFrame,
+    /// Holocene: the frame opens a new channel but is not the first frame.
+    NotFirstFrame,
+    /// Holocene: the frame's channel was superseded by a new channel before it was closed.
+    IncompleteChannel,
+    /// The frame belongs to a channel that has timed out.
+    ChannelTimedOut,
+    /// The frame could not be added to its channel.
+    InvalidFrame,
+    /// The frame does not open a channel and no channel is being assembled.
+    NoOpenChannel,
+}
+
+/// The action a stage took after receiving a [`BatchValidity`] verdict.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum BatchAction {
+    /// The batch was forwarded to the next stage.
+    Forwarded,
+    /// The batch was dropped and the previous stages were flushed.
+    DroppedAndFlushed,
+    /// The batch was dropped without flushing the previous stages.
+    Dropped,
+    /// The batch was kept to be checked again later.
+    Retained,
+    /// The stage is waiting on more L1 data before it can decide.
+    Deferred,
+    /// The verdict is not allowed in this stage and caused a critical error.
+    Rejected,
+}
+
+/// A structured event emitted by a stage of the derivation pipeline.
+///
+/// Block numbers in `origin` fields refer to the L1 origin of the emitting stage at the time
+/// the event was emitted.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "event", rename_all = "snake_case"))]
+pub enum DerivationEvent {
+    /// Frames were parsed from a piece of L1 data.
+    FramesParsed {
+        /// The L1 origin block number.
+        origin: u64,
+        /// The number of frames parsed.
+        count: usize,
+    },
+    /// A piece of L1 data could not be parsed into frames.
+    FrameParseFailed {
+        /// The L1 origin block number.
+        origin: u64,
+        /// The length of the data in bytes.
+        len: usize,
+    },
+    /// A frame was forwarded by the frame queue.
+    FrameAccepted {
+        /// The L1 origin block number.
+        origin: u64,
+        /// The frame's channel identifier.
+        channel_id: B128,
+        /// The frame number.
+        number: u16,
+        /// Whether the frame is the last frame of its channel.
+        is_last: bool,
+    },
+    /// A frame was dropped.
+    FrameDropped {
+        /// The stage that dropped the frame.
+        stage: TraceStage,
+        /// The L1 origin block number.
+        origin: u64,
+        /// The frame's channel identifier.
+        channel_id: B128,
+        /// The frame number.
+        number: u16,
+        /// The reason the frame was dropped.
+        reason: FrameDropReason,
+    },
+    /// A channel timed out and was discarded.
+    ChannelTimedOut {
+        /// The stage that discarded the channel.
+        stage: TraceStage,
+        /// The L1 origin block number.
+        origin: u64,
+        /// The channel identifier.
+        channel_id: B128,
+        /// The L1 block number the channel was opened at.
+        open_block: u64,
+    },
+    /// A batch was checked for validity.
+    BatchVerdict {
+        /// The stage that checked the batch.
+        stage: TraceStage,
+        /// The L1 origin block number.
+        origin: u64,
+        /// The L2 parent block number the batch was checked against.
+        parent: u64,
+        /// The timestamp of the batch.
+        timestamp: u64,
+        /// The validity of the batch.
+        validity: BatchValidity,
+        /// The action the stage took.
+        action: BatchAction,
+    },
+    /// Payload attributes were built on top of an L2 parent block.
+    AttributesBuilt {
+        /// The L2 parent block number.
+        parent: u64,
+        /// The L2 parent block hash.
+        parent_hash: B256,
+        /// The L1 epoch block number.
+        epoch: u64,
+        /// The timestamp of the attributes.
+        timestamp: u64,
+        /// The sequence number within the epoch.
+        sequence_number: u64,
+        /// The number of deposit transactions, excluding the L1 info deposit.
+        deposits: usize,
+        /// The number of upgrade transactions.
+        upgrades: usize,
+    },
+}
+
+impl DerivationEvent {
+    /// Creates a [`DerivationEvent::FrameAccepted`] event for the given frame.
+    pub fn frame_accepted(origin: u64, frame: &Frame) -> Self {
+        Self::FrameAccepted {
+            origin,
+            channel_id: B128::from(frame.id),
+            number: frame.number,
+            is_last: frame.is_last,
+        }
+    }
+
+    /// Creates a [`DerivationEvent::FrameDropped`] event for the given frame.
+    pub fn frame_dropped(
+        stage: TraceStage,
+        origin: u64,
+        frame: &Frame,
+        reason: FrameDropReason,
+    ) -> Self {
+        Self::FrameDropped {
+            stage,
+            origin,
+            channel_id: B128::from(frame.id),
+            number: frame.number,
+            reason,
+        }
+    }
+
+    /// Creates a [`DerivationEvent::BatchVerdict`] event.
+    pub const fn batch_verdict(
+        stage: TraceStage,
+        origin: u64,
+        parent: u64,
+        timestamp: u64,
+        validity: BatchValidity,
+        action: BatchAction,
+    ) -> Self {
+        Self::BatchVerdict { stage, origin, parent, timestamp, validity, action }
+    }
+}
@@ -0,0 +1,183 @@
+//! Contains the [`DerivationTraceLayer`] and utilities for reading recorded traces.
+
+use super::{DerivationEvent, TRACE_FIELD, TRACE_TARGET};
+use std::{
+    io::{BufRead, Write},
+    sync::Mutex,
+};
+use tracing::{
+    Event, Subscriber,
+    field::{Field, Visit},
+};
+use tracing_subscriber::{Layer, layer::Context};
+
+/// A [`Layer`] that writes [`DerivationEvent`]s to a writer as JSON lines.
+///
+/// Only events emitted under the [`TRACE_TARGET`] are recorded. All other events are ignored,
+/// so the layer can be stacked next to a regular formatting layer.
+#[derive(Debug)]
+pub struct DerivationTraceLayer<W> {
+    /// The writer that events are written to.
+    writer: Mutex<W>,
+}
+
+impl<W: Write> DerivationTraceLayer<W> {
+    /// Creates a new [`DerivationTraceLayer`] that writes to the given writer.
+    pub const fn new(writer: W) -> Self {
+        Self { writer: Mutex::new(writer) }
+    }
+
+    /// Consumes the layer and returns the inner writer.
+    pub fn into_inner(self) -> W {
+        self.writer.into_inner().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl<S, W> Layer<S> for DerivationTraceLayer<W>
+where
+    S: Subscriber,
+    W: Write + 'static,
+{
+    fn on_event(&self, event: &Event<'_>, _: Context<'_, S>) {
+        if event.metadata().target() != TRACE_TARGET {
+            return;
+        }
+
+        let mut visitor = EventVisitor::default();
+        event.record(&mut visitor);
+        let Some(line) = visitor.0 else {
+            return;
+        };
+
+        let mut writer = self.writer.lock().unwrap_or_else(|e| e.into_inner());
+        if let Err(e) = writeln!(writer, "{line}") {
+            // Logging here would recurse into this layer's subscriber, so the failure is
+            // written to stderr instead.
+            std::eprintln!("Failed to write derivation event: {e}");
+        }
+    }
+}
+
+/// A [`Visit`] implementation that extracts the [`TRACE_FIELD`] from an event.
+#[derive(Debug, Default)]
+struct EventVisitor(Option<String>);
+
+impl Visit for EventVisitor {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        if field.name() == TRACE_FIELD {
+            self.0 = Some(value.into());
+        }
+    }
+
+    fn record_debug(&mut self, _: &Field, _: &dyn core::fmt::Debug) {}
+}
+
+/// Reads a JSON lines trace written by the [`DerivationTraceLayer`].
+///
+/// Empty lines are skipped.
+pub fn read_trace<R: BufRead>(reader: R) -> std::io::Result<Vec<DerivationEvent>> {
+    let mut events = Vec::new();
+    for line in reader.lines() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        events.push(serde_json::from_str(&line)?);
+    }
+    Ok(events)
+}
+
+/// The first point at which two derivation traces diverge.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TraceDivergence {
+    /// The index of the first diverging event.
+    pub index: usize,
+    /// The event in the expected trace, if any.
+    pub expected: Option<DerivationEvent>,
+    /// The event in the actual trace, if any.
+    pub actual: Option<DerivationEvent>,
+}
+
+/// Returns the first point at which the `actual` trace diverges from the `expected` trace, or
+/// `None` if both traces are identical.
+pub fn first_divergence(
+    expected: &[DerivationEvent],
+    actual: &[DerivationEvent],
+) -> Option<TraceDivergence> {
+    let len = expected.len().max(actual.len());
+    (0..len).find_map(|index| {
+        let (e, a) = (expected.get(index), actual.get(index));
+        (e != a).then(|| TraceDivergence { index, expected: e.cloned(), actual: a.cloned() })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::trace::{FrameDropReason, TraceStage};
+    use alloy_primitives::B128;
+    use std::sync::Arc;
+    use tracing_subscriber::layer::SubscriberExt;
+
+    #[derive(Debug, Default, Clone)]
+    struct SharedBuf(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for SharedBuf {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().write(buf)
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    fn dropped(number: u16) -> DerivationEvent {
+        DerivationEvent::FrameDropped {
+            stage: TraceStage::FrameQueue,
+            origin: 1,
+            channel_id: B128::ZERO,
+            number,
+            reason: FrameDropReason::NonSequential,
+        }
+    }
+
+    #[test]
+    fn test_layer_records_events_roundtrip() {
+        let buf = SharedBuf::default();
+        let subscriber =
+            tracing_subscriber::Registry::default().with(DerivationTraceLayer::new(buf.clone()));
+
+        tracing::subscriber::with_default(subscriber, || {
+            crate::trace::emit(|| dropped(1));
+            tracing::info!(target: "frame_queue", "unrelated event");
+            crate::trace::emit(|| dropped(2));
+        });
+
+        let data = buf.0.lock().unwrap().clone();
+        let events = read_trace(data.as_slice()).unwrap();
+        assert_eq!(events, vec![dropped(1), dropped(2)]);
+    }
+
+    #[test]
+    fn test_first_divergence() {
+        let expected = vec![dropped(1), dropped(2)];
+        assert_eq!(first_divergence(&expected, &expected), None);
+
+        let actual = vec![dropped(1), dropped(3)];
+        assert_eq!(
+            first_divergence(&expected, &actual),
+            Some(TraceDivergence {
+                index: 1,
+                expected: Some(dropped(2)),
+                actual: Some(dropped(3))
+            })
+        );
+
+        let actual = vec![dropped(1)];
+        assert_eq!(
+            first_divergence(&expected, &actual),
+            Some(TraceDivergence { index: 1, expected: Some(dropped(2)), actual: None })
+        );
+    }
+}
@@ -0,0 +1,69 @@
+//! Structured derivation tracing.
+//!
+//! When the `trace` feature is enabled, every stage of the derivation pipeline emits a
+//! [`DerivationEvent`] describing the decisions it makes: frames accepted or dropped by the
+//! [`FrameQueue`], channels timed out by the [`ChannelBank`] and [`ChannelAssembler`], batch
+//! verdicts in the [`BatchStream`], [`BatchQueue`] and [`BatchValidator`] stages, and payload
+//! attributes built by the [`StatefulAttributesBuilder`].
+//!
+//! Events are dispatched as [`tracing`] events under the [`TRACE_TARGET`] target, so they follow
+//! the active subscriber. The [`DerivationTraceLayer`] collects them and writes them out as JSON
+//! lines, which can be read back with [`read_trace`] and compared against a trace recorded by a
+//! second implementation with [`first_divergence`].
+//!
+//! If no subscriber is interested in the [`TRACE_TARGET`], events are never constructed.
+//!
+//! [`FrameQueue`]: crate::FrameQueue
+//! [`ChannelBank`]: crate::ChannelBank
+//! [`ChannelAssembler`]: crate::ChannelAssembler
+//! [`BatchStream`]: crate::BatchStream
+//! [`BatchQueue`]: crate::BatchQueue
+//! [`BatchValidator`]: crate::BatchValidator
+//! [`StatefulAttributesBuilder`]: crate::StatefulAttributesBuilder
+
+mod events;
+pub use events::{BatchAction, DerivationEvent, FrameDropReason, TraceStage};
+
+#[cfg(feature = "trace")]
+mod layer;
+#[cfg(feature = "trace")]
+pub use layer::{DerivationTraceLayer, TraceDivergence, first_divergence, read_trace};
+
+/// The [`tracing`] target that derivation events are emitted under.
+pub const TRACE_TARGET: &str = "kona_derive::trace";
+
+/// The name of the field that holds the JSON encoded [`DerivationEvent`].
+pub const TRACE_FIELD: &str = "event";
+
+/// Emits the [`DerivationEvent`] produced by `f`, if a subscriber is listening on the
+/// [`TRACE_TARGET`].
+#[cfg(feature = "trace")]
+#[inline]
+pub(crate) fn emit(f: impl FnOnce() -> DerivationEvent) {
+    if !tracing::enabled!(target: TRACE_TARGET, tracing::Level::TRACE) {
+        return;
+    }
+
+    match serde_json::to_string(&f()) {
+        Ok(event) => {
+            tracing::event!(target: TRACE_TARGET, tracing::Level::TRACE, event = event.as_str())
+        }
+        Err(e) => warn!(target: "derive_trace", "Failed to serialize derivation event: {e}"),
+    }
+}
+
+/// Without the `trace` feature, events are discarded without being constructed.
+#[cfg(not(feature = "trace"))]
+#[inline(always)]
+pub(crate) fn emit(_: impl FnOnce() -> DerivationEvent) {}
+
+/// Emits a [`DerivationEvent`].
+///
+/// The event expression is only evaluated if the `trace` feature is enabled and a subscriber is
+/// listening on the [`TRACE_TARGET`].
+macro_rules! derive_trace {
+    ($event:expr) => {
+        $crate::trace::emit(|| $event)
+    };
+}
+pub(crate) use derive_trace;
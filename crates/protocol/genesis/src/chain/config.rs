@@ -92,6 +92,12 @@ pub struct ChainConfig {
     /// Hardfork Config. These values may override the superchain-wide defaults.
     #[cfg_attr(feature = "serde", serde(rename = "hardfork_configuration", alias = "hardforks"))]
     pub hardfork_config: HardForkConfig,
+    /// Mantle-specific hardfork config. Empty for chains that do not use Mantle hardforks.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "MantleHardForkConfig::is_empty")
+    )]
+    pub mantle_hardforks: MantleHardForkConfig,
     /// Optimism configuration
     #[cfg_attr(feature = "serde", serde(rename = "optimism"))]
     pub optimism: Option<BaseFeeConfig>,
@@ -177,7 +183,7 @@ impl ChainConfig {
             channel_timeout: 300,
             granite_channel_timeout: GRANITE_CHANNEL_TIMEOUT,
             interop_message_expiry_window: DEFAULT_INTEROP_MESSAGE_EXPIRY_WINDOW,
-            mantle_hardforks: MantleHardForkConfig::default(),
+            mantle_hardforks: self.mantle_hardforks,
             chain_op_config: self.base_fee_config(),
             alt_da_config: self.alt_da.clone(),
         }
@@ -355,4 +361,58 @@ mod tests {
         let err = serde_json::from_str::<ChainConfig>(raw).unwrap_err();
         assert_eq!(err.classify(), serde_json::error::Category::Data);
     }
+
+    #[test]
+    fn test_chain_config_mantle_hardforks_toml() {
+        let raw: &str = r#"
+        name = "Mantle"
+        public_rpc = "https://rpc.mantle.xyz"
+        sequencer_rpc = "https://rpc.mantle.xyz"
+        explorer = "https://mantlescan.xyz"
+        superchain_level = 0
+        superchain_time = 0
+        data_availability_type = "eth-da"
+        chain_id = 5000
+        batch_inbox_addr = "0xFFEEDDCCBBAA0000000000000000000000000000"
+        block_time = 2
+        seq_window_size = 3600
+        max_sequencer_drift = 600
+
+        [hardforks]
+
+        [mantle_hardforks]
+        mantle_skadi_time = 1000
+        mantle_limb_time = 2000
+
+        [genesis]
+        l2_time = 0
+        [genesis.l1]
+        hash = "0x0000000000000000000000000000000000000000000000000000000000000000"
+        number = 0
+        [genesis.l2]
+        hash = "0x0000000000000000000000000000000000000000000000000000000000000000"
+        number = 0
+        "#;
+
+        let config: ChainConfig = toml::from_str(raw).unwrap();
+        let expected = MantleHardForkConfig {
+            mantle_skadi_time: Some(1000),
+            mantle_limb_time: Some(2000),
+            ..Default::default()
+        };
+        assert_eq!(config.mantle_hardforks, expected);
+
+        let rollup = config.as_rollup_config();
+        assert!(rollup.is_mantle());
+        assert_eq!(rollup.mantle_hardforks, expected);
+        assert_eq!(rollup.chain_op_config, crate::MANTLE_BASE_FEE_CONFIG);
+    }
+
+    #[test]
+    fn test_chain_config_skips_empty_mantle_hardforks() {
+        let config = ChainConfig::default();
+        let json = serde_json::to_value(&config).unwrap();
+        assert!(json.get("mantle_hardforks").is_none());
+        assert!(!config.as_rollup_config().is_mantle());
+    }
 }
@@ -65,6 +65,11 @@ impl MantleHardForkConfig {
             || self.mantle_limb_time.is_some()
             || self.mantle_arsia_time.is_some()
     }
+
+    /// Returns true if no Mantle hardfork is configured.
+    pub const fn is_empty(&self) -> bool {
+        !self.has_any_hardfork()
+    }
 }
 
 #[cfg(test)]
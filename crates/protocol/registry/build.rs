@@ -1,4 +1,4 @@
-//! Build script that generates a `configs.json` file from the configs.
+//! Build script that generates the `configs.json` and `mantle.json` files from the configs.
 
 use kona_genesis::{ChainConfig, Superchain, SuperchainConfig, Superchains};
 
@@ -29,6 +29,30 @@ fn main() {
 
     // Get the `superchain-registry/superchain/configs` directory`
     let configs_dir = format!("{src_dir}/superchain-registry/superchain/configs");
+    let superchains = read_superchains(&configs_dir);
+    let output_path = std::path::Path::new("etc/configs.json");
+    std::fs::write(output_path, serde_json::to_string_pretty(&superchains).unwrap()).unwrap();
+
+    // The Mantle chains are not part of the `superchain-registry`, their configs are checked in
+    // under `etc/mantle` using the same layout.
+    let mantle_dir = format!("{src_dir}/etc/mantle");
+    let mantle = read_superchains(&mantle_dir);
+    for chain in mantle.superchains.iter().flat_map(|superchain| &superchain.chains) {
+        let genesis = &chain.genesis;
+        assert!(
+            !genesis.l1.hash.is_zero() &&
+                !genesis.l2.hash.is_zero() &&
+                genesis.system_config.is_some(),
+            "Mantle chain {} is missing its genesis anchors or system config",
+            chain.chain_id
+        );
+    }
+    let output_path = std::path::Path::new("etc/mantle.json");
+    std::fs::write(output_path, serde_json::to_string_pretty(&mantle).unwrap()).unwrap();
+}
+
+/// Reads all superchain directories in `configs_dir` into [`Superchains`].
+fn read_superchains(configs_dir: &str) -> Superchains {
     let configs = std::fs::read_dir(configs_dir).unwrap();
 
     // Get all the directories in the `configs` directory
@@ -71,6 +95,5 @@ fn main() {
         superchain.chains.sort_by(|a, b| a.chain_id.cmp(&b.chain_id));
     }
 
-    let output_path = std::path::Path::new("etc/configs.json");
-    std::fs::write(output_path, serde_json::to_string_pretty(&superchains).unwrap()).unwrap();
+    superchains
 }
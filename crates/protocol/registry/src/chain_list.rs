@@ -1,7 +1,8 @@
 //! List of OP Stack chains.
 
-use alloc::{string::String, vec::Vec};
+use alloc::{format, string::String, vec, vec::Vec};
 use alloy_chains::Chain as AlloyChain;
+use kona_genesis::ChainConfig;
 
 /// List of Chains.
 #[derive(Debug, Clone, Default, Hash, Eq, PartialEq, serde::Serialize, serde::Deserialize)]
@@ -69,6 +70,27 @@ pub struct Chain {
     pub parent: SuperchainParent,
 }
 
+impl Chain {
+    /// Creates a [Chain] entry for a chain config that is not listed in the chain list.
+    ///
+    /// The identifier is `<superchain>/<name>`, following the chain list convention.
+    pub fn from_chain_config(superchain: &str, name: &str, config: &ChainConfig) -> Self {
+        Self {
+            name: config.name.clone(),
+            identifier: format!("{superchain}/{name}"),
+            chain_id: config.chain_id,
+            rpc: vec![config.public_rpc.clone()],
+            explorers: vec![config.explorer.clone()],
+            superchain_level: config.superchain_level as u64,
+            data_availability_type: config.data_availability_type.clone(),
+            parent: SuperchainParent {
+                r#type: String::from("L2"),
+                chain: String::from(superchain),
+            },
+        }
+    }
+}
+
 /// A Chain Parent
 #[derive(Debug, Clone, Default, Hash, Eq, PartialEq, serde::Serialize, serde::Deserialize)]
 #[serde(rename_all = "camelCase")]
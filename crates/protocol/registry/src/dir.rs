@@ -0,0 +1,273 @@
+//! Loads a [Registry] from a directory at runtime.
+
+use crate::{Chain, Registry, RegistryError};
+use kona_genesis::{ChainConfig, L1ChainConfig, Superchain, SuperchainConfig};
+use serde::de::DeserializeOwned;
+use std::path::{Path, PathBuf};
+
+/// The name of the directory holding l1 chain configurations.
+const L1_DIR: &str = "l1";
+
+/// The file stem of a superchain configuration.
+const SUPERCHAIN_CONFIG: &str = "superchain";
+
+impl Registry {
+    /// Loads a [Registry] from a directory in the `superchain-registry` layout.
+    ///
+    /// Every subdirectory is a superchain, holding a `superchain.toml` and one config per chain.
+    /// Configs may be written in TOML or JSON, and chain configs may set `mantle_hardforks`.
+    /// Chain list entries are derived from the chain configs, using `<superchain>/<file stem>`
+    /// as the identifier.
+    ///
+    /// An optional `l1` subdirectory holds l1 chain configurations as JSON, either as a bare
+    /// chain config or as a full genesis file.
+    ///
+    /// The returned registry only contains the configs found in the directory. Use
+    /// [Registry::merge] to merge it over the built-in registry.
+    pub fn from_dir(dir: impl AsRef<Path>) -> Result<Self, RegistryError> {
+        let mut registry = Self::default();
+
+        for path in read_dir(dir.as_ref())? {
+            if !path.is_dir() {
+                continue;
+            }
+
+            let name = file_stem(&path);
+            if name == L1_DIR {
+                for path in read_dir(&path)? {
+                    if path.extension().is_some_and(|ext| ext == "json") {
+                        let config = read_l1_config(&path)?;
+                        registry.l1_configs.insert(config.chain_id, config);
+                    }
+                }
+                continue;
+            }
+
+            let mut config = None;
+            let mut chains = Vec::new();
+            for path in read_dir(&path)? {
+                if file_stem(&path) == SUPERCHAIN_CONFIG {
+                    config = read_config::<SuperchainConfig>(&path)?.or(config);
+                } else if let Some(chain) = read_config::<ChainConfig>(&path)? {
+                    chains.push((file_stem(&path), chain));
+                }
+            }
+            let config =
+                config.ok_or_else(|| RegistryError::MissingSuperchainConfig(path.clone()))?;
+            if config.protocol_versions_addr.is_none() {
+                return Err(RegistryError::MissingProtocolVersionsAddress(path));
+            }
+
+            chains.sort_by_key(|(_, chain)| chain.chain_id);
+            for (chain_name, chain) in &chains {
+                registry.chain_list.chains.push(Chain::from_chain_config(&name, chain_name, chain));
+            }
+            registry.insert_superchain(Superchain {
+                name,
+                config,
+                chains: chains.into_iter().map(|(_, chain)| chain).collect(),
+            });
+        }
+
+        Ok(registry)
+    }
+}
+
+/// Returns the sorted paths of the entries in `dir`.
+fn read_dir(dir: &Path) -> Result<Vec<PathBuf>, RegistryError> {
+    let io_err = |source| RegistryError::Io { path: dir.to_path_buf(), source };
+    let mut paths = std::fs::read_dir(dir)
+        .map_err(io_err)?
+        .map(|entry| entry.map(|e| e.path()))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(io_err)?;
+    paths.sort();
+    Ok(paths)
+}
+
+/// Returns the file stem of `path` as a string.
+fn file_stem(path: &Path) -> String {
+    path.file_stem().unwrap_or_default().to_string_lossy().into_owned()
+}
+
+/// Reads a TOML or JSON config. Files with other extensions are skipped.
+fn read_config<T: DeserializeOwned>(path: &Path) -> Result<Option<T>, RegistryError> {
+    let Some(ext) = path.extension() else {
+        return Ok(None);
+    };
+    if ext != "toml" && ext != "json" {
+        return Ok(None);
+    }
+
+    let raw = std::fs::read_to_string(path)
+        .map_err(|source| RegistryError::Io { path: path.to_path_buf(), source })?;
+    let config = if ext == "toml" {
+        toml::from_str(&raw)
+            .map_err(|source| RegistryError::Toml { path: path.to_path_buf(), source })?
+    } else {
+        serde_json::from_str(&raw)
+            .map_err(|source| RegistryError::Json { path: path.to_path_buf(), source })?
+    };
+    Ok(Some(config))
+}
+
+/// Reads an l1 chain config from a bare chain config or a genesis file.
+fn read_l1_config(path: &Path) -> Result<L1ChainConfig, RegistryError> {
+    let json_err = |source| RegistryError::Json { path: path.to_path_buf(), source };
+    let raw = std::fs::read_to_string(path)
+        .map_err(|source| RegistryError::Io { path: path.to_path_buf(), source })?;
+    let mut value: serde_json::Value = serde_json::from_str(&raw).map_err(json_err)?;
+    if let Some(config) = value.get_mut("config") {
+        value = config.take();
+    }
+    serde_json::from_value(value).map_err(json_err)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::address;
+
+    const SUPERCHAIN_TOML: &str = r#"
+name = "Devnet"
+protocol_versions_addr = "0x0000000000000000000000000000000000000006"
+[l1]
+chain_id = 900
+public_rpc = "http://localhost:8545"
+explorer = ""
+[hardforks]
+"#;
+
+    const CHAIN_TOML: &str = r#"
+name = "Mantle Devnet"
+public_rpc = "http://localhost:9545"
+sequencer_rpc = "http://localhost:9545"
+explorer = ""
+superchain_level = 0
+data_availability_type = "eth-da"
+chain_id = 5001
+batch_inbox_addr = "0xFFEEDDCCBBAA0000000000000000000000000000"
+block_time = 2
+seq_window_size = 3600
+max_sequencer_drift = 600
+
+[hardforks]
+
+[mantle_hardforks]
+mantle_skadi_time = 0
+
+[genesis]
+l2_time = 0
+[genesis.l1]
+hash = "0x0000000000000000000000000000000000000000000000000000000000000001"
+number = 0
+[genesis.l2]
+hash = "0x0000000000000000000000000000000000000000000000000000000000000002"
+number = 0
+
+[addresses]
+SystemConfigProxy = "0x0000000000000000000000000000000000000005"
+"#;
+
+    fn write_devnet(dir: &Path) {
+        let devnet = dir.join("devnet");
+        std::fs::create_dir_all(&devnet).unwrap();
+        std::fs::write(devnet.join("superchain.toml"), SUPERCHAIN_TOML).unwrap();
+        std::fs::write(devnet.join("mantle.toml"), CHAIN_TOML).unwrap();
+        std::fs::write(devnet.join("README.md"), "ignored").unwrap();
+
+        let l1 = dir.join(L1_DIR);
+        std::fs::create_dir_all(&l1).unwrap();
+        std::fs::write(l1.join("genesis.json"), r#"{"config":{"chainId":900},"alloc":{}}"#)
+            .unwrap();
+    }
+
+    #[test]
+    fn test_from_dir() {
+        let dir = tempfile::tempdir().unwrap();
+        write_devnet(dir.path());
+
+        let registry = Registry::from_dir(dir.path()).unwrap();
+        let chain = registry.chain_list.get_chain_by_ident("devnet/mantle").unwrap();
+        assert_eq!(chain.chain_id, 5001);
+
+        let rollup = registry.rollup_configs.get(&5001).unwrap();
+        assert!(rollup.is_mantle());
+        assert_eq!(rollup.l1_chain_id, 900);
+        assert_eq!(
+            rollup.protocol_versions_address,
+            address!("0000000000000000000000000000000000000006")
+        );
+        assert_eq!(
+            rollup.l1_system_config_address,
+            address!("0000000000000000000000000000000000000005")
+        );
+        assert!(registry.op_chains.contains_key(&5001));
+        assert!(registry.l1_configs.contains_key(&900));
+    }
+
+    #[test]
+    fn test_from_dir_merges_over_builtins() {
+        let dir = tempfile::tempdir().unwrap();
+        write_devnet(dir.path());
+        let devnet = dir.path().join("devnet");
+        std::fs::write(devnet.join("mantle.toml"), CHAIN_TOML.replace("5001", "5000")).unwrap();
+
+        let mut registry = Registry::from_chain_list();
+        let chains = registry.chain_list.len();
+        registry.merge(Registry::from_dir(dir.path()).unwrap());
+
+        assert_eq!(registry.chain_list.len(), chains);
+        assert_eq!(registry.chain_list.get_chain_by_id(5000).unwrap().identifier, "devnet/mantle");
+        assert_eq!(registry.rollup_configs.get(&5000).unwrap().l1_chain_id, 900);
+        assert!(registry.rollup_configs.contains_key(&10));
+    }
+
+    #[test]
+    fn test_merge_replaces_identifiers() {
+        let dir = tempfile::tempdir().unwrap();
+        write_devnet(dir.path());
+        let mut registry = Registry::from_dir(dir.path()).unwrap();
+
+        // The same identifier now names another chain.
+        let devnet = dir.path().join("devnet");
+        std::fs::write(devnet.join("mantle.toml"), CHAIN_TOML.replace("5001", "5002")).unwrap();
+        registry.merge(Registry::from_dir(dir.path()).unwrap());
+
+        assert_eq!(registry.chain_list.len(), 1);
+        assert_eq!(registry.chain_list.get_chain_by_ident("devnet/mantle").unwrap().chain_id, 5002);
+        assert!(registry.chain_list.get_chain_by_id(5001).is_none());
+    }
+
+    #[test]
+    fn test_from_dir_missing_superchain_config() {
+        let dir = tempfile::tempdir().unwrap();
+        write_devnet(dir.path());
+        std::fs::remove_file(dir.path().join("devnet/superchain.toml")).unwrap();
+
+        let err = Registry::from_dir(dir.path()).unwrap_err();
+        assert!(matches!(err, RegistryError::MissingSuperchainConfig(_)));
+    }
+
+    #[test]
+    fn test_from_dir_missing_protocol_versions_address() {
+        let dir = tempfile::tempdir().unwrap();
+        write_devnet(dir.path());
+        let superchain =
+            SUPERCHAIN_TOML.replace("protocol_versions_addr", "# protocol_versions_addr");
+        std::fs::write(dir.path().join("devnet/superchain.toml"), superchain).unwrap();
+
+        let err = Registry::from_dir(dir.path()).unwrap_err();
+        assert!(matches!(err, RegistryError::MissingProtocolVersionsAddress(_)));
+    }
+
+    #[test]
+    fn test_from_dir_invalid_config() {
+        let dir = tempfile::tempdir().unwrap();
+        write_devnet(dir.path());
+        std::fs::write(dir.path().join("devnet/mantle.toml"), "chain_id = ").unwrap();
+
+        let err = Registry::from_dir(dir.path()).unwrap_err();
+        assert!(matches!(err, RegistryError::Toml { .. }));
+    }
+}
@@ -0,0 +1,41 @@
+//! Error types for the registry.
+
+use std::path::PathBuf;
+
+/// An error loading a [Registry](crate::Registry) at runtime.
+#[derive(Debug, thiserror::Error)]
+pub enum RegistryError {
+    /// A file or directory could not be read.
+    #[error("Failed to read {}: {source}", path.display())]
+    Io {
+        /// The path that could not be read.
+        path: PathBuf,
+        /// The underlying error.
+        source: std::io::Error,
+    },
+    /// A TOML config could not be parsed.
+    #[error("Failed to parse {}: {source}", path.display())]
+    Toml {
+        /// The path of the config.
+        path: PathBuf,
+        /// The underlying error.
+        source: toml::de::Error,
+    },
+    /// A JSON config could not be parsed.
+    #[error("Failed to parse {}: {source}", path.display())]
+    Json {
+        /// The path of the config.
+        path: PathBuf,
+        /// The underlying error.
+        source: serde_json::Error,
+    },
+    /// A superchain directory does not contain a `superchain.toml` or `superchain.json`.
+    #[error("Missing superchain config in {}", .0.display())]
+    MissingSuperchainConfig(PathBuf),
+    /// A superchain config does not set `protocol_versions_addr`.
+    #[error("Missing protocol versions address in the superchain config of {}", .0.display())]
+    MissingProtocolVersionsAddress(PathBuf),
+    /// The registry statics were accessed before the runtime registry was installed.
+    #[error("The registry has already been initialized")]
+    AlreadyInitialized,
+}
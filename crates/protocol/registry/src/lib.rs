@@ -19,6 +19,14 @@ pub use chain_list::{Chain, ChainList};
 pub mod superchain;
 pub use superchain::Registry;
 
+#[cfg(feature = "std")]
+mod dir;
+
+#[cfg(feature = "std")]
+mod errors;
+#[cfg(feature = "std")]
+pub use errors::RegistryError;
+
 /// L1 chain configurations.
 pub mod l1;
 pub use l1::L1Config;
@@ -26,9 +34,24 @@ pub use l1::L1Config;
 #[cfg(test)]
 pub mod test_utils;
 
+/// The registry installed with [load_registry_dir], merged over the built-in registry.
+///
+/// Initializing the registry statics seals this with `None` if no registry was installed yet, so
+/// that a registry can not be installed after the statics were accessed.
+#[cfg(feature = "std")]
+static RUNTIME_REGISTRY: std::sync::OnceLock<Option<Registry>> = std::sync::OnceLock::new();
+
 lazy_static::lazy_static! {
     /// Private initializer that loads the superchain configurations.
-    static ref _INIT: Registry = Registry::from_chain_list();
+    static ref _INIT: Registry = {
+        #[allow(unused_mut)]
+        let mut registry = Registry::from_chain_list();
+        #[cfg(feature = "std")]
+        if let Some(runtime) = RUNTIME_REGISTRY.get_or_init(|| None) {
+            registry.merge(runtime.clone());
+        }
+        registry
+    };
 
     /// Chain configurations exported from the registry
     pub static ref CHAINS: ChainList = _INIT.chain_list.clone();
@@ -44,6 +67,16 @@ lazy_static::lazy_static! {
     pub static ref L1_CONFIGS: HashMap<u64, L1ChainConfig> = _INIT.l1_configs.clone();
 }
 
+/// Loads the chain configurations in `dir` and merges them over the built-in registry.
+///
+/// See [Registry::from_dir] for the expected directory layout. This must be called before any of
+/// the registry statics are accessed, otherwise [RegistryError::AlreadyInitialized] is returned.
+#[cfg(feature = "std")]
+pub fn load_registry_dir(dir: impl AsRef<std::path::Path>) -> Result<(), RegistryError> {
+    let registry = Registry::from_dir(dir)?;
+    RUNTIME_REGISTRY.set(Some(registry)).map_err(|_| RegistryError::AlreadyInitialized)
+}
+
 /// Returns a [RollupConfig] by its identifier.
 pub fn scr_rollup_config_by_ident(ident: &str) -> Option<&RollupConfig> {
     let chain_id = CHAINS.get_chain_by_ident(ident)?.chain_id;
@@ -108,6 +141,42 @@ mod tests {
         assert_eq!(rollup_config_by_alloy_ident, rollup_config_by_id);
     }
 
+    // TODO: sync the Mantle configs from the Mantle networks repository, with their genesis
+    // anchors, and assert the anchors' known values.
+    #[ignore]
+    #[test]
+    fn test_mantle_rollup_configs() {
+        use alloy_primitives::address;
+        use kona_genesis::{
+            MANTLE_BASE_FEE_CONFIG, MANTLE_MAINNET_CHAIN_ID, MANTLE_SEPOLIA_CHAIN_ID,
+        };
+
+        let test_cases = [
+            (MANTLE_MAINNET_CHAIN_ID, 1, "mainnet/mantle"),
+            (MANTLE_SEPOLIA_CHAIN_ID, 11155111, "sepolia/mantle"),
+        ];
+
+        for (chain_id, l1_chain_id, ident) in test_cases {
+            let rollup_config =
+                scr_rollup_config_by_alloy_ident(&AlloyChain::from_id(chain_id)).unwrap();
+            assert_eq!(scr_rollup_config_by_ident(ident).unwrap(), rollup_config);
+            assert_eq!(CHAINS.get_chain_by_id(chain_id).unwrap().identifier, ident);
+            assert!(OPCHAINS.contains_key(&chain_id));
+
+            assert!(rollup_config.is_mantle());
+            assert_eq!(rollup_config.l1_chain_id, l1_chain_id);
+            assert_eq!(rollup_config.chain_op_config, MANTLE_BASE_FEE_CONFIG);
+            assert_eq!(
+                rollup_config.batch_inbox_address,
+                address!("FFEEDDCCBBAA0000000000000000000000000000")
+            );
+            assert!(!rollup_config.l1_system_config_address.is_zero());
+            assert!(!rollup_config.genesis.l1.hash.is_zero());
+            assert!(!rollup_config.genesis.l2.hash.is_zero());
+            assert!(rollup_config.genesis.system_config.is_some());
+        }
+    }
+
     #[test]
     fn test_jovian_timestamps() {
         let base_mainnet_config_by_ident = scr_rollup_config_by_ident("mainnet/base").unwrap();
@@ -2,9 +2,9 @@
 
 use crate::L1Config;
 
-use super::ChainList;
+use super::{Chain, ChainList};
 use alloy_primitives::map::HashMap;
-use kona_genesis::{ChainConfig, L1ChainConfig, RollupConfig, Superchains};
+use kona_genesis::{ChainConfig, L1ChainConfig, RollupConfig, Superchain, Superchains};
 
 /// The registry containing all the superchain configurations.
 #[derive(Debug, Clone, Default, Eq, PartialEq)]
@@ -32,31 +32,77 @@ impl Registry {
         serde_json::from_str(superchain_configs).expect("Failed to read superchain configs")
     }
 
+    /// Read the Mantle chain configs.
+    ///
+    /// Mantle chains are not part of the `superchain-registry`. Their configs are checked in
+    /// under `etc/mantle` and use the same layout as the superchain configs.
+    pub fn read_mantle_configs() -> Superchains {
+        let mantle_configs = include_str!("../etc/mantle.json");
+        serde_json::from_str(mantle_configs).expect("Failed to read mantle configs")
+    }
+
     /// Initialize the superchain configurations from the chain list.
     pub fn from_chain_list() -> Self {
-        let chain_list = Self::read_chain_list();
-        let superchains = Self::read_superchain_configs();
-        let mut op_chains = HashMap::default();
-        let mut rollup_configs = HashMap::default();
-
-        for superchain in superchains.superchains {
-            for mut chain_config in superchain.chains {
-                chain_config.l1_chain_id = superchain.config.l1.chain_id;
-                if let Some(a) = &mut chain_config.addresses {
-                    a.zero_proof_addresses();
-                }
-                let mut rollup = chain_config.as_rollup_config();
-                rollup.protocol_versions_address = superchain
-                    .config
-                    .protocol_versions_addr
-                    .expect("Missing protocol versions address");
-                rollup.superchain_config_address = superchain.config.superchain_config_addr;
-                rollup_configs.insert(chain_config.chain_id, rollup);
-                op_chains.insert(chain_config.chain_id, chain_config);
+        let mut registry = Self {
+            chain_list: Self::read_chain_list(),
+            l1_configs: L1Config::build_l1_configs(),
+            ..Default::default()
+        };
+
+        for superchain in Self::read_superchain_configs().superchains {
+            registry.insert_superchain(superchain);
+        }
+
+        // The Mantle chains are not listed in the chain list, so their entries are derived from
+        // their chain configs.
+        for superchain in Self::read_mantle_configs().superchains {
+            for chain_config in &superchain.chains {
+                registry.chain_list.chains.push(Chain::from_chain_config(
+                    &superchain.name,
+                    "mantle",
+                    chain_config,
+                ));
             }
+            registry.insert_superchain(superchain);
         }
 
-        Self { chain_list, op_chains, rollup_configs, l1_configs: L1Config::build_l1_configs() }
+        registry
+    }
+
+    /// Inserts the chain and rollup configs of all chains in the [Superchain].
+    ///
+    /// Configs of chains that are already present are replaced. The chain list is not updated.
+    pub fn insert_superchain(&mut self, superchain: Superchain) {
+        for mut chain_config in superchain.chains {
+            chain_config.l1_chain_id = superchain.config.l1.chain_id;
+            if let Some(a) = &mut chain_config.addresses {
+                a.zero_proof_addresses();
+            }
+            let mut rollup = chain_config.as_rollup_config();
+            rollup.protocol_versions_address = superchain
+                .config
+                .protocol_versions_addr
+                .expect("Missing protocol versions address");
+            rollup.superchain_config_address = superchain.config.superchain_config_addr;
+            self.rollup_configs.insert(chain_config.chain_id, rollup);
+            self.op_chains.insert(chain_config.chain_id, chain_config);
+        }
+    }
+
+    /// Merges `other` over this registry.
+    ///
+    /// Chains and l1 configurations in `other` replace the ones with the same chain id. Chain list
+    /// entries in `other` also replace the ones with the same identifier, so that identifiers stay
+    /// unique.
+    pub fn merge(&mut self, other: Self) {
+        self.chain_list.chains.retain(|c| {
+            other.chain_list.get_chain_by_id(c.chain_id).is_none() &&
+                other.chain_list.get_chain_by_ident(&c.identifier).is_none()
+        });
+        self.chain_list.chains.extend(other.chain_list.chains);
+        self.op_chains.extend(other.op_chains);
+        self.rollup_configs.extend(other.rollup_configs);
+        self.l1_configs.extend(other.l1_configs);
     }
 }
 
@@ -89,6 +135,7 @@ mod tests {
             superchain_time: Some(0),
             batch_inbox_addr: address!("ff00000000000000000000000000000000008453"),
             hardfork_config: crate::test_utils::BASE_MAINNET_CONFIG.hardforks,
+            mantle_hardforks: Default::default(),
             block_time: 2,
             seq_window_size: 3600,
             max_sequencer_drift: 600,
@@ -0,0 +1,165 @@
+//! Contains the [BlobArchive] trait and its implementations, used by the [OnlineBlobProvider] to
+//! fetch blobs that have expired from the beacon node.
+//!
+//! [OnlineBlobProvider]: crate::OnlineBlobProvider
+
+use crate::{BeaconClient, BoxedBlobWithIndex, OnlineBeaconClient};
+use alloy_eips::eip4844::{BYTES_PER_BLOB, Blob, IndexedBlobHash};
+use async_trait::async_trait;
+use core::fmt::Debug;
+use kona_derive::BlobProviderError;
+use std::{
+    boxed::Box,
+    format,
+    path::{Path, PathBuf},
+    string::ToString,
+    vec::Vec,
+};
+
+/// A [BlobArchive] serves blobs that are no longer available from the beacon node, such as blobs
+/// that have fallen out of the beacon node's retention window.
+///
+/// Blobs returned by an archive are untrusted: the [OnlineBlobProvider] verifies their KZG
+/// commitments against the requested versioned hashes.
+///
+/// [OnlineBlobProvider]: crate::OnlineBlobProvider
+#[async_trait]
+pub trait BlobArchive: Debug + Send + Sync {
+    /// Fetches the blobs with the given hashes that were confirmed in the beacon block at `slot`.
+    async fn archived_blobs(
+        &self,
+        slot: u64,
+        blob_hashes: &[IndexedBlobHash],
+    ) -> Result<Vec<BoxedBlobWithIndex>, BlobProviderError>;
+}
+
+/// A [BlobArchive] backed by a blob-archiver service, which serves the beacon API's blob sidecar
+/// endpoints for historical slots.
+#[derive(Debug, Clone)]
+pub struct OnlineBlobArchiver {
+    /// The beacon API client pointed at the archiver.
+    pub client: OnlineBeaconClient,
+}
+
+impl OnlineBlobArchiver {
+    /// Creates a new [OnlineBlobArchiver] from the base URL of the archiver API.
+    pub fn new_http(base: String) -> Self {
+        Self { client: OnlineBeaconClient::new_http(base) }
+    }
+}
+
+#[async_trait]
+impl BlobArchive for OnlineBlobArchiver {
+    async fn archived_blobs(
+        &self,
+        slot: u64,
+        blob_hashes: &[IndexedBlobHash],
+    ) -> Result<Vec<BoxedBlobWithIndex>, BlobProviderError> {
+        BeaconClient::filtered_beacon_blobs(&self.client, slot, blob_hashes)
+            .await
+            .map_err(|e| BlobProviderError::Backend(format!("Blob archiver error: {e}")))
+    }
+}
+
+/// A [BlobArchive] backed by a local directory of blob sidecars.
+///
+/// Each blob is stored in a file named after its versioned hash (e.g. `0x01ab…`), containing
+/// either the raw blob bytes or a beacon API blob sidecar JSON object.
+#[derive(Debug, Clone)]
+pub struct LocalBlobArchive {
+    /// The directory containing the blob sidecars.
+    pub dir: PathBuf,
+}
+
+/// The subset of a beacon API blob sidecar read from a [LocalBlobArchive].
+#[derive(serde::Deserialize)]
+struct ArchivedSidecar {
+    /// The blob data.
+    blob: Box<Blob>,
+}
+
+impl LocalBlobArchive {
+    /// Creates a new [LocalBlobArchive] reading blob sidecars from `dir`.
+    pub fn new(dir: impl AsRef<Path>) -> Self {
+        Self { dir: dir.as_ref().to_path_buf() }
+    }
+
+    /// Returns the path of the file holding the blob with the given versioned hash.
+    pub fn blob_path(&self, blob_hash: &IndexedBlobHash) -> PathBuf {
+        self.dir.join(blob_hash.hash.to_string())
+    }
+
+    /// Decodes a blob from the contents of a sidecar file.
+    fn decode_blob(data: &[u8]) -> Result<Box<Blob>, BlobProviderError> {
+        if data.len() == BYTES_PER_BLOB {
+            return Ok(Box::new(Blob::from_slice(data)));
+        }
+        serde_json::from_slice::<ArchivedSidecar>(data)
+            .map(|sidecar| sidecar.blob)
+            .map_err(|e| BlobProviderError::Backend(format!("Invalid blob sidecar: {e}")))
+    }
+}
+
+#[async_trait]
+impl BlobArchive for LocalBlobArchive {
+    async fn archived_blobs(
+        &self,
+        _: u64,
+        blob_hashes: &[IndexedBlobHash],
+    ) -> Result<Vec<BoxedBlobWithIndex>, BlobProviderError> {
+        let mut blobs = Vec::with_capacity(blob_hashes.len());
+        for blob_hash in blob_hashes {
+            let path = self.blob_path(blob_hash);
+            let data = tokio::fs::read(&path).await.map_err(|e| {
+                BlobProviderError::Backend(format!(
+                    "Failed to read blob sidecar {}: {e}",
+                    path.display()
+                ))
+            })?;
+            blobs.push(BoxedBlobWithIndex {
+                index: blob_hash.index,
+                blob: Self::decode_blob(&data)?,
+            });
+        }
+        Ok(blobs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::B256;
+
+    #[tokio::test]
+    async fn test_local_archive_formats() {
+        let dir = tempfile::tempdir().unwrap();
+        let archive = LocalBlobArchive::new(dir.path());
+
+        let raw = IndexedBlobHash { index: 0, hash: B256::repeat_byte(0x01) };
+        let mut blob = Blob::default();
+        blob[0] = 0xaa;
+        std::fs::write(archive.blob_path(&raw), blob.as_slice()).unwrap();
+
+        let json = IndexedBlobHash { index: 3, hash: B256::repeat_byte(0x02) };
+        let sidecar = serde_json::json!({ "index": "3", "blob": blob, "kzg_commitment": "0x00" });
+        std::fs::write(archive.blob_path(&json), sidecar.to_string()).unwrap();
+
+        let blobs = archive.archived_blobs(0, &[raw, json]).await.unwrap();
+        assert_eq!(blobs.len(), 2);
+        assert_eq!((blobs[0].index, blobs[1].index), (0, 3));
+        assert!(blobs.iter().all(|b| *b.blob == blob));
+    }
+
+    #[tokio::test]
+    async fn test_local_archive_missing_blob() {
+        let dir = tempfile::tempdir().unwrap();
+        let archive = LocalBlobArchive::new(dir.path());
+
+        let missing = [IndexedBlobHash { index: 0, hash: B256::ZERO }];
+        assert!(archive.archived_blobs(0, &missing).await.is_err());
+
+        // Files that are neither a raw blob nor a sidecar are rejected.
+        std::fs::write(archive.blob_path(&missing[0]), [0u8; 32]).unwrap();
+        assert!(archive.archived_blobs(0, &missing).await.is_err());
+    }
+}
@@ -1,8 +1,8 @@
 //! Contains an online implementation of the `BlobProvider` trait.
 
-use crate::BeaconClient;
 #[cfg(feature = "metrics")]
 use crate::Metrics;
+use crate::{BeaconClient, BlobArchive};
 use alloy_eips::eip4844::{
     Blob, BlobTransactionSidecarItem, IndexedBlobHash, env_settings::EnvKzgSettings,
 };
@@ -10,7 +10,7 @@ use alloy_primitives::FixedBytes;
 use async_trait::async_trait;
 use kona_derive::{BlobProvider, BlobProviderError};
 use kona_protocol::BlockInfo;
-use std::{boxed::Box, string::ToString, vec::Vec};
+use std::{boxed::Box, format, string::ToString, sync::Arc, vec::Vec};
 
 /// A boxed blob with index.
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -30,6 +30,9 @@ pub struct OnlineBlobProvider<B: BeaconClient> {
     pub genesis_time: u64,
     /// Slot interval used for the time to slot conversion.
     pub slot_interval: u64,
+    /// The archives to fall back to, in order, when blobs can not be fetched from the beacon
+    /// client.
+    pub archives: Vec<Arc<dyn BlobArchive>>,
 }
 
 impl<B: BeaconClient> OnlineBlobProvider<B> {
@@ -54,7 +57,14 @@ impl<B: BeaconClient> OnlineBlobProvider<B> {
             .map(|r| r.data.seconds_per_slot)
             .map_err(|e| BlobProviderError::Backend(e.to_string()))
             .expect("Failed to load slot interval from beacon client");
-        Self { beacon_client, genesis_time, slot_interval }
+        Self { beacon_client, genesis_time, slot_interval, archives: Vec::new() }
+    }
+
+    /// Adds a [BlobArchive] to fall back to when blobs can not be fetched from the beacon client,
+    /// e.g. because they have expired. Archives are tried in the order they are added.
+    pub fn with_archive(mut self, archive: Arc<dyn BlobArchive>) -> Self {
+        self.archives.push(archive);
+        self
     }
 
     /// Computes the slot for the given timestamp.
@@ -130,8 +140,33 @@ impl<B: BeaconClient> OnlineBlobProvider<B> {
             .collect()
     }
 
-    /// Fetches blob sidecars for the given block reference and blob hashes.
-    /// Does not validate the blobs. Recomputes the kzg proofs associated with the blobs.
+    /// Converts the fetched blobs to sidecars and verifies that their KZG commitments match the
+    /// requested versioned hashes.
+    fn verified_sidecars(
+        blobs: Vec<BoxedBlobWithIndex>,
+        blob_hashes: &[IndexedBlobHash],
+    ) -> Result<Vec<BlobTransactionSidecarItem>, BlobProviderError> {
+        if blobs.len() != blob_hashes.len() {
+            return Err(BlobProviderError::Backend(format!(
+                "Expected {} blobs, got {}",
+                blob_hashes.len(),
+                blobs.len()
+            )));
+        }
+
+        let sidecars = Self::sidecar_from_blobs(blobs)
+            .map_err(|e| BlobProviderError::Backend(format!("KZG commitment error: {e}")))?;
+        for (sidecar, blob_hash) in sidecars.iter().zip(blob_hashes) {
+            if sidecar.to_kzg_versioned_hash() != blob_hash.hash.as_slice() {
+                return Err(BlobProviderError::Backend("KZG commitment mismatch".to_string()));
+            }
+        }
+        Ok(sidecars)
+    }
+
+    /// Fetches blob sidecars for the given block reference and blob hashes, falling back to the
+    /// configured [BlobArchive]s if the beacon client fails. Recomputes the kzg proofs associated
+    /// with the blobs, and verifies their commitments against the versioned hashes.
     ///
     /// Use [`Self::beacon_client`] to fetch the blobs without recomputing the kzg
     /// proofs/commitments.
@@ -147,11 +182,21 @@ impl<B: BeaconClient> OnlineBlobProvider<B> {
         // Calculate the slot for the given timestamp.
         let slot = Self::slot(self.genesis_time, self.slot_interval, block_ref.timestamp)?;
 
-        // Fetch blobs for the slot using.
-        let blobs = self.fetch_filtered_blobs(slot, blob_hashes).await?;
-
-        Self::sidecar_from_blobs(blobs)
-            .map_err(|e| BlobProviderError::Backend(format!("KZG commitment error: {e}")))
+        // Fetch blobs for the slot from the beacon client, then from the archives in order.
+        let mut result = self
+            .fetch_filtered_blobs(slot, blob_hashes)
+            .await
+            .and_then(|blobs| Self::verified_sidecars(blobs, blob_hashes));
+        for archive in &self.archives {
+            if result.is_ok() {
+                break;
+            }
+            result = archive
+                .archived_blobs(slot, blob_hashes)
+                .await
+                .and_then(|blobs| Self::verified_sidecars(blobs, blob_hashes));
+        }
+        result
     }
 }
 
@@ -170,28 +215,92 @@ where
         block_ref: &BlockInfo,
         blob_hashes: &[IndexedBlobHash],
     ) -> Result<Vec<Box<Blob>>, Self::Error> {
-        // Fetch the blob sidecars for the given block reference and blob hashes.
-        let blobs = self.fetch_filtered_blob_sidecars(block_ref, blob_hashes).await?;
+        // Fetch the blob sidecars for the given block reference and blob hashes. The sidecars are
+        // verified against the hashes as they are fetched.
+        let sidecars = self.fetch_filtered_blob_sidecars(block_ref, blob_hashes).await?;
+        Ok(sidecars.into_iter().map(|sidecar| sidecar.blob).collect())
+    }
+}
 
-        // Validate the blob sidecars straight away with the num hashes.
-        let blobs = blobs
-            .into_iter()
-            .enumerate()
-            .map(|(i, sidecar)| {
-                let hash = blob_hashes
-                    .get(i)
-                    .ok_or(BlobProviderError::Backend("Missing blob hash".to_string()))?
-                    .hash
-                    .as_slice();
-
-                if sidecar.to_kzg_versioned_hash() != hash {
-                    return Err(BlobProviderError::Backend("KZG commitment mismatch".to_string()));
-                }
-
-                Ok(sidecar.blob)
-            })
-            .collect::<Result<Vec<Box<Blob>>, BlobProviderError>>()
-            .map_err(|e| BlobProviderError::Backend(e.to_string()))?;
-        Ok(blobs)
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{APIConfigResponse, APIGenesisResponse, LocalBlobArchive};
+    use alloy_primitives::B256;
+
+    /// A [BeaconClient] whose blobs have all expired.
+    #[derive(Debug)]
+    struct ExpiredBeaconClient;
+
+    #[async_trait]
+    impl BeaconClient for ExpiredBeaconClient {
+        type Error = &'static str;
+
+        async fn config_spec(&self) -> Result<APIConfigResponse, Self::Error> {
+            Ok(APIConfigResponse::new(12))
+        }
+
+        async fn beacon_genesis(&self) -> Result<APIGenesisResponse, Self::Error> {
+            Ok(APIGenesisResponse::new(0))
+        }
+
+        async fn filtered_beacon_blobs(
+            &self,
+            _: u64,
+            _: &[IndexedBlobHash],
+        ) -> Result<Vec<BoxedBlobWithIndex>, Self::Error> {
+            Err("blobs expired")
+        }
+    }
+
+    #[tokio::test]
+    async fn test_archive_fallback() {
+        let dir = tempfile::tempdir().unwrap();
+        let archive = LocalBlobArchive::new(dir.path());
+
+        let mut blob = Box::new(Blob::default());
+        blob[31] = 0x01;
+        let sidecars = OnlineBlobProvider::<ExpiredBeaconClient>::sidecar_from_blobs(vec![
+            BoxedBlobWithIndex { index: 0, blob: blob.clone() },
+        ])
+        .unwrap();
+        let hashes =
+            [IndexedBlobHash { index: 0, hash: B256::from(sidecars[0].to_kzg_versioned_hash()) }];
+        std::fs::write(archive.blob_path(&hashes[0]), blob.as_slice()).unwrap();
+
+        let mut provider = OnlineBlobProvider {
+            beacon_client: ExpiredBeaconClient,
+            genesis_time: 0,
+            slot_interval: 12,
+            archives: Vec::new(),
+        };
+        let block_ref = BlockInfo::default();
+        assert!(provider.get_and_validate_blobs(&block_ref, &hashes).await.is_err());
+
+        let mut provider = provider.with_archive(Arc::new(archive));
+        let blobs = provider.get_and_validate_blobs(&block_ref, &hashes).await.unwrap();
+        assert_eq!(blobs, [blob]);
+    }
+
+    #[tokio::test]
+    async fn test_archive_commitment_mismatch() {
+        let dir = tempfile::tempdir().unwrap();
+        let archive = LocalBlobArchive::new(dir.path());
+
+        // The archived blob does not commit to the requested versioned hash.
+        let hashes = [IndexedBlobHash { index: 0, hash: B256::repeat_byte(0x01) }];
+        std::fs::write(archive.blob_path(&hashes[0]), Blob::default().as_slice()).unwrap();
+
+        let mut provider = OnlineBlobProvider {
+            beacon_client: ExpiredBeaconClient,
+            genesis_time: 0,
+            slot_interval: 12,
+            archives: Vec::new(),
+        }
+        .with_archive(Arc::new(archive));
+        let err = provider.get_and_validate_blobs(&BlockInfo::default(), &hashes).await;
+        assert!(
+            matches!(err, Err(BlobProviderError::Backend(e)) if e == "KZG commitment mismatch")
+        );
     }
 }
@@ -15,6 +15,9 @@ pub use beacon_client::{
     ReducedGenesisData,
 };
 
+mod blob_archive;
+pub use blob_archive::{BlobArchive, LocalBlobArchive, OnlineBlobArchiver};
+
 mod blobs;
 pub use blobs::{BoxedBlobWithIndex, OnlineBlobProvider};
 
@@ -17,6 +17,10 @@ pub enum CliError {
     #[error("No unsafe block signer found for chain ID: {0}")]
     UnsafeBlockSignerNotFound(u64),
 
+    /// Error loading the registry directory.
+    #[error("Failed to load registry directory: {0}")]
+    Registry(#[from] kona_registry::RegistryError),
+
     /// Error initializing metrics.
     #[error("Failed to initialize metrics")]
     MetricsInitialization(#[from] metrics_exporter_prometheus::BuildError),
@@ -0,0 +1,20 @@
+//! Arguments for loading a TOML configuration file.
+
+use clap::Args;
+use std::path::PathBuf;
+
+/// Configuration file arguments.
+///
+/// The file itself is read before clap parses the command line, see
+/// [`parse_with_config`](crate::parse_with_config). This argument only exists so that `--config`
+/// is accepted and documented in the help output.
+#[derive(Args, Debug, Default, Clone, PartialEq, Eq)]
+pub struct ConfigArgs {
+    /// Path to a TOML configuration file.
+    ///
+    /// Keys are the long names of the command line flags, either written out in full or split
+    /// into tables on `.`, e.g. `"p2p.listen.tcp" = 9222` or `[p2p.listen] tcp = 9222`. Values in
+    /// the file are overridden by environment variables and command line flags.
+    #[arg(long = "config", global = true)]
+    pub config: Option<PathBuf>,
+}
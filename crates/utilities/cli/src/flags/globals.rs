@@ -6,7 +6,7 @@ use clap::Parser;
 use kona_genesis::RollupConfig;
 use kona_registry::OPCHAINS;
 
-use crate::{CliError, CliResult, LogArgs, MetricsArgs, OverrideArgs};
+use crate::{CliError, CliResult, LogArgs, MetricsArgs, OverrideArgs, RegistryArgs};
 
 /// Global arguments for the CLI.
 #[derive(Parser, Default, Clone, Debug)]
@@ -25,6 +25,9 @@ pub struct GlobalArgs {
         help = "The L2 chain ID to use"
     )]
     pub l2_chain_id: Chain,
+    /// Chain registry arguments.
+    #[command(flatten)]
+    pub registry: RegistryArgs,
     /// Embed the override flags globally to provide override values adjacent to the configs.
     #[command(flatten)]
     pub override_args: OverrideArgs,
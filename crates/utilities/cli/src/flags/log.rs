@@ -55,6 +55,20 @@ pub struct LogArgs {
     /// If set, new log files will be created every interval.
     #[arg(long = "logs.file.rotation", default_value = "never", env = "KONA_LOG_FILE_ROTATION")]
     pub file_rotation: LogRotation,
+    /// The OTLP/HTTP endpoint to export trace spans to, e.g. `http://localhost:4318/v1/traces`.
+    /// If not set, spans are not exported.
+    ///
+    /// Requires the `otlp` feature to be enabled.
+    #[arg(long = "otlp.endpoint", global = true, env = "KONA_OTLP_ENDPOINT")]
+    pub otlp_endpoint: Option<String>,
+    /// The service name reported with exported trace spans.
+    #[arg(
+        long = "otlp.service-name",
+        global = true,
+        default_value = "kona",
+        env = "KONA_OTLP_SERVICE_NAME"
+    )]
+    pub otlp_service_name: String,
 }
 
 #[cfg(test)]
@@ -89,4 +103,21 @@ mod tests {
         let cli_v5 = TestCli::parse_from(["test_app", "-vvvvv"]);
         assert_eq!(cli_v5.global.level, 5, "Verbosity with -vvvvv should be 5.");
     }
+
+    #[test]
+    fn test_otlp_args() {
+        let cli = TestCli::parse_from(["test_app"]);
+        assert_eq!(cli.global.otlp_endpoint, None);
+        assert_eq!(cli.global.otlp_service_name, "kona");
+
+        let cli = TestCli::parse_from([
+            "test_app",
+            "--otlp.endpoint",
+            "http://localhost:4318/v1/traces",
+            "--otlp.service-name",
+            "kona-node",
+        ]);
+        assert_eq!(cli.global.otlp_endpoint.as_deref(), Some("http://localhost:4318/v1/traces"));
+        assert_eq!(cli.global.otlp_service_name, "kona-node");
+    }
 }
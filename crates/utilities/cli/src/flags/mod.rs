@@ -2,6 +2,9 @@
 //!
 //! These are cli flags that are shared across binaries to standardize kona's services CLI UX.
 
+mod config;
+pub use config::ConfigArgs;
+
 mod globals;
 pub use globals::GlobalArgs;
 
@@ -13,3 +16,6 @@ pub use log::LogArgs;
 
 mod metrics;
 pub use metrics::MetricsArgs;
+
+mod registry;
+pub use registry::RegistryArgs;
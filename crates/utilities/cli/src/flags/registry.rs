@@ -0,0 +1,51 @@
+//! Arguments for the chain registry.
+
+use crate::CliResult;
+use clap::Args;
+use std::path::PathBuf;
+
+/// Chain registry arguments.
+#[derive(Args, Debug, Default, Clone, PartialEq, Eq)]
+pub struct RegistryArgs {
+    /// A directory of chain configs to merge over the built-in registry.
+    #[arg(
+        long = "registry-dir",
+        global = true,
+        env = "KONA_REGISTRY_DIR",
+        help = "Directory of chain configs to merge over the built-in registry"
+    )]
+    pub registry_dir: Option<PathBuf>,
+}
+
+impl RegistryArgs {
+    /// Loads the chain configs in the `--registry-dir`, if set, over the built-in registry.
+    ///
+    /// This must be called before the registry is first accessed.
+    pub fn load(&self) -> CliResult<()> {
+        if let Some(dir) = &self.registry_dir {
+            kona_registry::load_registry_dir(dir)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+
+    #[derive(Parser, Debug)]
+    struct TestCli {
+        #[command(flatten)]
+        registry: RegistryArgs,
+    }
+
+    #[test]
+    fn test_registry_dir() {
+        let cli = TestCli::try_parse_from(["test"]).unwrap();
+        assert_eq!(cli.registry.registry_dir, None);
+
+        let cli = TestCli::try_parse_from(["test", "--registry-dir", "/tmp/registry"]).unwrap();
+        assert_eq!(cli.registry.registry_dir, Some(PathBuf::from("/tmp/registry")));
+    }
+}
@@ -9,10 +9,15 @@ mod error;
 pub use error::{CliError, CliResult};
 
 mod flags;
-pub use flags::{GlobalArgs, LogArgs, MetricsArgs, OverrideArgs};
+pub use flags::{ConfigArgs, GlobalArgs, LogArgs, MetricsArgs, OverrideArgs, RegistryArgs};
+
+mod config;
+pub use config::{
+    ConfigFile, ConfigFileError, dump_config, parse_with_config, resolve_config_args,
+};
 
 mod logs;
-pub use logs::{FileLogConfig, LogConfig, LogRotation, StdoutLogConfig};
+pub use logs::{FileLogConfig, LogConfig, LogRotation, OtlpConfig, StdoutLogConfig};
 
 mod clap;
 pub use clap::cli_styles;
@@ -25,7 +30,10 @@ pub use secrets::{KeypairError, ParseKeyError, SecretKeyLoader};
 pub mod backtrace;
 
 mod tracing;
-pub use tracing::{LogFormat, init_test_tracing};
+pub use tracing::{LogFormat, init_test_tracing, shutdown_tracing};
+
+#[cfg(feature = "otlp")]
+mod otlp;
 
 mod prometheus;
 pub use prometheus::init_prometheus_server;
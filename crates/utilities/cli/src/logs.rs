@@ -41,6 +41,15 @@ pub struct StdoutLogConfig {
     pub format: LogFormat,
 }
 
+/// Configuration for exporting trace spans over OTLP.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OtlpConfig {
+    /// The OTLP/HTTP endpoint that spans are exported to.
+    pub endpoint: String,
+    /// The service name reported with exported spans.
+    pub service_name: String,
+}
+
 /// Global configuration for logging.
 /// Default is to only print logs to stdout in full format.
 #[derive(Debug, Clone)]
@@ -51,6 +60,8 @@ pub struct LogConfig {
     pub stdout_logs: Option<StdoutLogConfig>,
     /// The configuration for file logging.
     pub file_logs: Option<FileLogConfig>,
+    /// The configuration for OTLP trace export.
+    pub otlp: Option<OtlpConfig>,
 }
 
 impl Default for LogConfig {
@@ -59,6 +70,7 @@ impl Default for LogConfig {
             global_level: LevelFilter::INFO,
             stdout_logs: Some(StdoutLogConfig { format: LogFormat::Full }),
             file_logs: None,
+            otlp: None,
         }
     }
 }
@@ -92,6 +104,10 @@ impl LogConfig {
             rotation: args.file_rotation,
         });
 
-        Self { global_level: level, stdout_logs, file_logs }
+        let otlp = args
+            .otlp_endpoint
+            .map(|endpoint| OtlpConfig { endpoint, service_name: args.otlp_service_name });
+
+        Self { global_level: level, stdout_logs, file_logs, otlp }
     }
 }
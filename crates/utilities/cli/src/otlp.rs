@@ -0,0 +1,118 @@
+//! OTLP trace export.
+
+use crate::OtlpConfig;
+use opentelemetry::trace::TracerProvider as _;
+use opentelemetry_otlp::{ExporterBuildError, SpanExporter, WithExportConfig};
+use opentelemetry_sdk::{
+    Resource,
+    trace::{SdkTracer, SdkTracerProvider},
+};
+use std::sync::OnceLock;
+use tracing::Subscriber;
+use tracing_opentelemetry::OpenTelemetryLayer;
+use tracing_subscriber::registry::LookupSpan;
+
+/// The instrumentation scope of the spans exported by kona.
+const TRACER_NAME: &str = "kona";
+
+/// The installed tracer provider, kept so that buffered spans can be flushed on shutdown.
+static PROVIDER: OnceLock<SdkTracerProvider> = OnceLock::new();
+
+/// Builds a tracer provider that batches spans and exports them to the configured endpoint.
+fn provider(config: &OtlpConfig) -> Result<SdkTracerProvider, ExporterBuildError> {
+    let exporter = SpanExporter::builder().with_http().with_endpoint(&config.endpoint).build()?;
+    let resource = Resource::builder().with_service_name(config.service_name.clone()).build();
+
+    Ok(SdkTracerProvider::builder().with_batch_exporter(exporter).with_resource(resource).build())
+}
+
+/// Builds a [`tracing_subscriber::Layer`] that exports spans over OTLP, and installs its tracer
+/// provider so that it is flushed by [`shutdown`].
+pub(crate) fn layer<S>(
+    config: &OtlpConfig,
+) -> Result<OpenTelemetryLayer<S, SdkTracer>, ExporterBuildError>
+where
+    S: Subscriber + for<'span> LookupSpan<'span>,
+{
+    let provider = provider(config)?;
+    let tracer = provider.tracer(TRACER_NAME);
+    let _ = PROVIDER.set(provider);
+
+    Ok(tracing_opentelemetry::layer().with_tracer(tracer))
+}
+
+/// Flushes and shuts down the installed tracer provider, if any.
+pub(crate) fn shutdown() {
+    if let Some(Err(err)) = PROVIDER.get().map(SdkTracerProvider::shutdown) {
+        eprintln!("Failed to shut down the OTLP exporter: {err}");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{
+        io::{BufRead, BufReader, Read, Write},
+        net::TcpListener,
+        thread,
+    };
+    use tracing_subscriber::{Registry, layer::SubscriberExt};
+
+    /// Accepts a single OTLP/HTTP export request, acknowledges it, and returns its request line
+    /// and body.
+    fn collect_one(listener: TcpListener) -> (String, Vec<u8>) {
+        let (stream, _) = listener.accept().unwrap();
+        let mut reader = BufReader::new(stream);
+
+        let mut request_line = String::new();
+        reader.read_line(&mut request_line).unwrap();
+
+        let mut content_length = 0;
+        loop {
+            let mut header = String::new();
+            reader.read_line(&mut header).unwrap();
+            let header = header.trim_end();
+            if header.is_empty() {
+                break;
+            }
+            if let Some((_, value)) = header
+                .split_once(':')
+                .filter(|(name, _)| name.eq_ignore_ascii_case("content-length"))
+            {
+                content_length = value.trim().parse().unwrap();
+            }
+        }
+
+        let mut body = vec![0; content_length];
+        reader.read_exact(&mut body).unwrap();
+        reader.get_mut().write_all(b"HTTP/1.1 200 OK\r\ncontent-length: 0\r\n\r\n").unwrap();
+
+        (request_line.trim_end().to_string(), body)
+    }
+
+    #[test]
+    fn test_exports_spans_to_collector() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let endpoint = format!("http://{}/v1/traces", listener.local_addr().unwrap());
+        let collector = thread::spawn(move || collect_one(listener));
+
+        let provider =
+            provider(&OtlpConfig { endpoint, service_name: "kona-test".to_string() }).unwrap();
+        let layer = tracing_opentelemetry::layer().with_tracer(provider.tracer(TRACER_NAME));
+        let subscriber = Registry::default().with(layer);
+
+        tracing::subscriber::with_default(subscriber, || {
+            let parent = tracing::info_span!("l1_head", number = 1);
+            let _guard = parent.enter();
+            tracing::info_span!("derivation_step").in_scope(|| {});
+        });
+        provider.shutdown().unwrap();
+
+        let (request_line, body) = collector.join().unwrap();
+        assert_eq!(request_line, "POST /v1/traces HTTP/1.1");
+        let body = String::from_utf8_lossy(&body);
+        assert!(body.contains("kona-test"));
+        assert!(body.contains("l1_head"));
+        assert!(body.contains("derivation_step"));
+    }
+}
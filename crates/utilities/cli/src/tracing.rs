@@ -32,6 +32,9 @@ pub enum LogFormat {
 impl LogConfig {
     /// Initializes the tracing subscriber
     ///
+    /// If [`LogConfig::otlp`] is set and the `otlp` feature is enabled, spans are also exported
+    /// over OTLP. Failing to set up the exporter is logged, but does not fail initialization.
+    ///
     /// # Arguments
     /// * `verbosity_level` - The verbosity level (0-5). If `0`, no logs are printed.
     /// * `env_filter` - Optional environment filter for the subscriber.
@@ -77,6 +80,15 @@ impl LogConfig {
             LogFormat::Compact => tracing_subscriber::fmt::layer().compact().boxed(),
         });
 
+        #[cfg(feature = "otlp")]
+        let (otlp_layer, otlp_error) = match self.otlp.as_ref().map(crate::otlp::layer).transpose()
+        {
+            Ok(layer) => (layer, None),
+            Err(err) => (None, Some(err)),
+        };
+        #[cfg(not(feature = "otlp"))]
+        let otlp_layer = None::<tracing_subscriber::layer::Identity>;
+
         let env_filter = env_filter
             .unwrap_or(EnvFilter::from_default_env())
             .add_directive(self.global_level.into());
@@ -85,12 +97,29 @@ impl LogConfig {
             .with(env_filter)
             .with(file_layer)
             .with(stdout_layer)
+            .with(otlp_layer)
             .try_init()?;
 
+        #[cfg(feature = "otlp")]
+        if let Some(err) = otlp_error {
+            tracing::warn!(target: "cli", %err, "Failed to set up the OTLP exporter, spans will not be exported");
+        }
+        #[cfg(not(feature = "otlp"))]
+        if self.otlp.is_some() {
+            tracing::warn!(target: "cli", "OTLP export requires the `otlp` feature, spans will not be exported");
+        }
+
         Ok(())
     }
 }
 
+/// Flushes and shuts down the OTLP exporter installed by [`LogConfig::init_tracing_subscriber`],
+/// if any. Spans that are still buffered when the process exits are lost unless this is called.
+pub fn shutdown_tracing() {
+    #[cfg(feature = "otlp")]
+    crate::otlp::shutdown();
+}
+
 /// This provides function for init tracing in testing
 ///
 /// # Functions
@@ -101,6 +101,8 @@ impl GossipCommand {
                 bootstore: None,
                 gater_config: Default::default(),
                 bootnodes: Default::default(),
+                static_peers: Default::default(),
+                peer_store: None,
                 rollup_config: rollup_config.clone(),
                 gossip_signer: None,
                 enr_update: true,